@@ -0,0 +1,64 @@
+-- Create projects table
+CREATE TABLE projects (
+    id SERIAL PRIMARY KEY,
+    name VARCHAR(100) NOT NULL,
+    repository_path VARCHAR(500) NOT NULL UNIQUE,
+    description TEXT,
+    is_public BOOLEAN DEFAULT FALSE,
+    last_scanned_at TIMESTAMP,
+    created_at TIMESTAMP DEFAULT NOW(),
+    updated_at TIMESTAMP DEFAULT NOW()
+);
+
+-- Indexes for projects
+CREATE INDEX idx_projects_path ON projects(repository_path);
+CREATE INDEX idx_projects_public ON projects(is_public) WHERE is_public = TRUE;
+CREATE INDEX idx_projects_name ON projects(name);
+
+-- Comments
+COMMENT ON TABLE projects IS 'Git repositories detected and tracked by the system';
+COMMENT ON COLUMN projects.repository_path IS 'Absolute path to the .git directory';
+COMMENT ON COLUMN projects.is_public IS 'If true, all users can view (no need to be a member)';
+COMMENT ON COLUMN projects.last_scanned_at IS 'Last time repository was scanned for commits';
+
+-- Trigger for updated_at on projects table
+CREATE TRIGGER update_projects_updated_at BEFORE UPDATE ON projects
+    FOR EACH ROW EXECUTE FUNCTION update_updated_at_column();
+
+-- Project members (many-to-many: users <-> projects)
+CREATE TABLE project_members (
+    project_id INTEGER REFERENCES projects(id) ON DELETE CASCADE,
+    user_id INTEGER REFERENCES users(id) ON DELETE CASCADE,
+    role VARCHAR(20) NOT NULL DEFAULT 'viewer',
+    created_at TIMESTAMP DEFAULT NOW(),
+    PRIMARY KEY (project_id, user_id)
+);
+
+-- Indexes for project_members
+CREATE INDEX idx_project_members_user ON project_members(user_id);
+CREATE INDEX idx_project_members_project ON project_members(project_id);
+
+COMMENT ON TABLE project_members IS 'User access control per project';
+COMMENT ON COLUMN project_members.role IS 'viewer: read-only, contributor: can comment (Phase 2), admin: can manage members';
+
+-- Commits cache (performance optimization)
+CREATE TABLE commits (
+    id SERIAL PRIMARY KEY,
+    project_id INTEGER REFERENCES projects(id) ON DELETE CASCADE,
+    commit_hash VARCHAR(40) NOT NULL,
+    author_name VARCHAR(255) NOT NULL,
+    author_email VARCHAR(255),
+    message TEXT NOT NULL,
+    committed_at TIMESTAMP NOT NULL,
+    created_at TIMESTAMP DEFAULT NOW(),
+    UNIQUE (project_id, commit_hash)
+);
+
+-- Indexes for commits
+CREATE INDEX idx_commits_project_date ON commits(project_id, committed_at DESC);
+CREATE INDEX idx_commits_hash ON commits(commit_hash);
+CREATE INDEX idx_commits_author ON commits(author_email) WHERE author_email IS NOT NULL;
+
+COMMENT ON TABLE commits IS 'Cached Git commits for faster queries and historical tracking';
+COMMENT ON COLUMN commits.commit_hash IS 'Git SHA-1 hash (40 hex characters), unique per project (forks share history)';
+COMMENT ON COLUMN commits.committed_at IS 'Commit timestamp from Git metadata';
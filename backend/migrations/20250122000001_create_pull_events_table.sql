@@ -0,0 +1,23 @@
+-- Last detected pull per project
+ALTER TABLE projects ADD COLUMN last_pull_at TIMESTAMP;
+
+COMMENT ON COLUMN projects.last_pull_at IS 'Last time a branch tip moved in the repository (pull detected by the watcher)';
+
+-- Pull events (audit log of branch updates detected by the watcher)
+CREATE TABLE pull_events (
+    id SERIAL PRIMARY KEY,
+    project_id INTEGER NOT NULL REFERENCES projects(id) ON DELETE CASCADE,
+    ref_name VARCHAR(255) NOT NULL,
+    old_commit VARCHAR(40),
+    new_commit VARCHAR(40) NOT NULL,
+    commit_count INTEGER NOT NULL DEFAULT 0,
+    detected_at TIMESTAMP DEFAULT NOW()
+);
+
+-- Indexes for pull_events
+CREATE INDEX idx_pull_events_project_date ON pull_events(project_id, detected_at DESC);
+
+COMMENT ON TABLE pull_events IS 'Branch updates detected in monitored repositories (RF-009)';
+COMMENT ON COLUMN pull_events.ref_name IS 'Full reference name, e.g. refs/heads/main';
+COMMENT ON COLUMN pull_events.old_commit IS 'Previous tip; NULL if the branch was created';
+COMMENT ON COLUMN pull_events.commit_count IS 'Number of new commits (capped by the watcher)';
@@ -0,0 +1,49 @@
+-- Lock state on projects (RF-010)
+ALTER TABLE projects
+    ADD COLUMN is_blocked BOOLEAN NOT NULL DEFAULT FALSE,
+    ADD COLUMN lock_reason TEXT,
+    ADD COLUMN locked_by INTEGER REFERENCES users(id) ON DELETE SET NULL,
+    ADD COLUMN locked_at TIMESTAMP;
+
+COMMENT ON COLUMN projects.is_blocked IS 'If true, pulls into the repository are refused by the installed Git hook';
+COMMENT ON COLUMN projects.locked_by IS 'Admin who locked the repository (or approved the lock request)';
+
+-- Lock requests from regular users, pending admin approval
+CREATE TABLE project_lock_requests (
+    id SERIAL PRIMARY KEY,
+    project_id INTEGER NOT NULL REFERENCES projects(id) ON DELETE CASCADE,
+    requested_by INTEGER REFERENCES users(id) ON DELETE SET NULL,
+    reason TEXT NOT NULL,
+    status VARCHAR(20) NOT NULL DEFAULT 'pending',
+    reviewed_by INTEGER REFERENCES users(id) ON DELETE SET NULL,
+    review_comment TEXT,
+    created_at TIMESTAMP DEFAULT NOW(),
+    reviewed_at TIMESTAMP,
+    CONSTRAINT chk_lock_request_status CHECK (status IN ('pending', 'approved', 'rejected'))
+);
+
+-- Indexes for project_lock_requests
+CREATE INDEX idx_lock_requests_project ON project_lock_requests(project_id, created_at DESC);
+CREATE UNIQUE INDEX idx_lock_requests_pending ON project_lock_requests(project_id, requested_by)
+    WHERE status = 'pending';
+
+COMMENT ON TABLE project_lock_requests IS 'User requests to lock a repository, approved or rejected by an admin';
+COMMENT ON COLUMN project_lock_requests.status IS 'pending | approved | rejected';
+
+-- Lock history (every lock/unlock)
+CREATE TABLE project_lock_history (
+    id SERIAL PRIMARY KEY,
+    project_id INTEGER NOT NULL REFERENCES projects(id) ON DELETE CASCADE,
+    action VARCHAR(20) NOT NULL,
+    reason TEXT,
+    actor_id INTEGER REFERENCES users(id) ON DELETE SET NULL,
+    request_id INTEGER REFERENCES project_lock_requests(id) ON DELETE SET NULL,
+    created_at TIMESTAMP DEFAULT NOW(),
+    CONSTRAINT chk_lock_history_action CHECK (action IN ('locked', 'unlocked'))
+);
+
+-- Indexes for project_lock_history
+CREATE INDEX idx_lock_history_project ON project_lock_history(project_id, created_at DESC);
+
+COMMENT ON TABLE project_lock_history IS 'Audit log of repository lock state changes';
+COMMENT ON COLUMN project_lock_history.request_id IS 'Lock request that originated the change, if any';
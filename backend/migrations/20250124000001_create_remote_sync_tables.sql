@@ -0,0 +1,39 @@
+-- Remote credentials per project, encrypted at rest with CREDENTIALS_KEY
+CREATE TABLE project_credentials (
+    project_id INTEGER PRIMARY KEY REFERENCES projects(id) ON DELETE CASCADE,
+    kind VARCHAR(20) NOT NULL,
+    username VARCHAR(255),
+    secret_encrypted BYTEA NOT NULL,
+    created_by INTEGER REFERENCES users(id) ON DELETE SET NULL,
+    created_at TIMESTAMP DEFAULT NOW(),
+    updated_at TIMESTAMP DEFAULT NOW(),
+    CONSTRAINT chk_credential_kind CHECK (kind IN ('ssh_key', 'token'))
+);
+
+CREATE TRIGGER update_project_credentials_updated_at BEFORE UPDATE ON project_credentials
+    FOR EACH ROW EXECUTE FUNCTION update_updated_at_column();
+
+COMMENT ON TABLE project_credentials IS 'Credentials used to fetch from the project remote';
+COMMENT ON COLUMN project_credentials.secret_encrypted IS 'AES-256-GCM: nonce || ciphertext, bound to the project id';
+
+-- Server-initiated fetch + fast-forward operations
+CREATE TABLE sync_operations (
+    id SERIAL PRIMARY KEY,
+    project_id INTEGER NOT NULL REFERENCES projects(id) ON DELETE CASCADE,
+    triggered_by INTEGER REFERENCES users(id) ON DELETE SET NULL,
+    remote VARCHAR(255),
+    branch VARCHAR(255),
+    status VARCHAR(20) NOT NULL,
+    old_commit VARCHAR(40),
+    new_commit VARCHAR(40),
+    message TEXT,
+    duration_ms INTEGER NOT NULL DEFAULT 0,
+    created_at TIMESTAMP DEFAULT NOW(),
+    CONSTRAINT chk_sync_status CHECK (status IN ('up_to_date', 'fast_forwarded', 'failed'))
+);
+
+-- Indexes for sync_operations
+CREATE INDEX idx_sync_operations_project ON sync_operations(project_id, created_at DESC);
+
+COMMENT ON TABLE sync_operations IS 'Outcome of fetch + fast-forward operations triggered from the API';
+COMMENT ON COLUMN sync_operations.message IS 'Error message when status = failed';
@@ -0,0 +1,20 @@
+-- Full-text search over project metadata and cached commits
+-- 'simple' configuration: content mixes Spanish and English, so no stemming
+ALTER TABLE projects
+    ADD COLUMN search_vector tsvector GENERATED ALWAYS AS (
+        setweight(to_tsvector('simple', coalesce(name, '')), 'A') ||
+        setweight(to_tsvector('simple', coalesce(description, '')), 'B')
+    ) STORED;
+
+CREATE INDEX idx_projects_search ON projects USING GIN (search_vector);
+
+ALTER TABLE commits
+    ADD COLUMN search_vector tsvector GENERATED ALWAYS AS (
+        setweight(to_tsvector('simple', coalesce(message, '')), 'A') ||
+        setweight(to_tsvector('simple', coalesce(author_name, '') || ' ' || coalesce(author_email, '')), 'B')
+    ) STORED;
+
+CREATE INDEX idx_commits_search ON commits USING GIN (search_vector);
+
+COMMENT ON COLUMN projects.search_vector IS 'Full-text index: name (A) + description (B)';
+COMMENT ON COLUMN commits.search_vector IS 'Full-text index: message (A) + author name/email (B)';
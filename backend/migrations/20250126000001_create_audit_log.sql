@@ -0,0 +1,17 @@
+-- General audit log of user actions on projects
+CREATE TABLE audit_log (
+    id SERIAL PRIMARY KEY,
+    project_id INTEGER REFERENCES projects(id) ON DELETE CASCADE,
+    user_id INTEGER REFERENCES users(id) ON DELETE SET NULL,
+    action VARCHAR(50) NOT NULL,
+    details JSONB NOT NULL DEFAULT '{}',
+    created_at TIMESTAMP DEFAULT NOW()
+);
+
+-- Indexes for audit_log
+CREATE INDEX idx_audit_log_project ON audit_log(project_id, created_at DESC);
+CREATE INDEX idx_audit_log_user ON audit_log(user_id, created_at DESC);
+
+COMMENT ON TABLE audit_log IS 'Audit log of user actions (downloads, ...); the action set grows with the API';
+COMMENT ON COLUMN audit_log.user_id IS 'NULL for anonymous access to public projects or deleted users';
+COMMENT ON COLUMN audit_log.details IS 'Action-specific data (ref, commit, path, format, size...)';
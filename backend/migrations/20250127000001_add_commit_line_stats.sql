@@ -0,0 +1,6 @@
+-- Lines added/removed per cached commit (churn statistics)
+ALTER TABLE commits ADD COLUMN additions INTEGER;
+ALTER TABLE commits ADD COLUMN deletions INTEGER;
+
+COMMENT ON COLUMN commits.additions IS 'Lines added vs. the first parent; NULL for merges and commits cached before churn tracking';
+COMMENT ON COLUMN commits.deletions IS 'Lines removed vs. the first parent; NULL for merges and commits cached before churn tracking';
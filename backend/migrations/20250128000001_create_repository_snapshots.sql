@@ -0,0 +1,35 @@
+-- Repository composition (languages, size, packs) at the default branch tip
+CREATE TABLE repository_snapshots (
+    id SERIAL PRIMARY KEY,
+    project_id INTEGER NOT NULL REFERENCES projects(id) ON DELETE CASCADE,
+    commit_hash VARCHAR(40) NOT NULL,
+    branch VARCHAR(255) NOT NULL,
+    committed_at TIMESTAMP NOT NULL,
+    total_files INTEGER NOT NULL,
+    total_bytes BIGINT NOT NULL,
+    excluded_files INTEGER NOT NULL,
+    disk_size BIGINT NOT NULL,
+    pack_count INTEGER NOT NULL,
+    pack_size BIGINT NOT NULL,
+    loose_objects INTEGER NOT NULL,
+    loose_size BIGINT NOT NULL,
+    analyzed_at TIMESTAMP DEFAULT NOW(),
+    UNIQUE (project_id, commit_hash)
+);
+
+CREATE TABLE repository_languages (
+    snapshot_id INTEGER NOT NULL REFERENCES repository_snapshots(id) ON DELETE CASCADE,
+    language VARCHAR(50) NOT NULL,
+    files INTEGER NOT NULL,
+    bytes BIGINT NOT NULL,
+    PRIMARY KEY (snapshot_id, language)
+);
+
+-- Indexes for repository_snapshots
+CREATE INDEX idx_repository_snapshots_project ON repository_snapshots(project_id, committed_at DESC);
+
+COMMENT ON TABLE repository_snapshots IS 'Composition of the default branch tip, one row per analyzed commit (charted over time)';
+COMMENT ON COLUMN repository_snapshots.total_bytes IS 'Sum of blob sizes in the tree, excluding vendored/generated/documentation paths';
+COMMENT ON COLUMN repository_snapshots.excluded_files IS 'Files skipped as vendored, generated or documentation (linguist-style rules)';
+COMMENT ON COLUMN repository_snapshots.disk_size IS 'Size of the Git directory on disk when analyzed, in bytes';
+COMMENT ON TABLE repository_languages IS 'Bytes and files per detected language for a repository snapshot';
@@ -0,0 +1,26 @@
+-- Conventional Commits metadata parsed from each cached commit message
+ALTER TABLE commits ADD COLUMN commit_type VARCHAR(50);
+ALTER TABLE commits ADD COLUMN commit_scope VARCHAR(100);
+ALTER TABLE commits ADD COLUMN is_breaking BOOLEAN NOT NULL DEFAULT FALSE;
+ALTER TABLE commits ADD COLUMN trailers JSONB NOT NULL DEFAULT '[]';
+ALTER TABLE commits ADD COLUMN issue_refs TEXT[] NOT NULL DEFAULT '{}';
+
+-- Backfill type/scope/breaking for commits already cached; trailers and
+-- issue references are only extracted for commits cached from now on
+UPDATE commits c
+SET commit_type = lower(h.m[1]),
+    commit_scope = NULLIF(btrim(h.m[3]), ''),
+    is_breaking = h.m[4] = '!' OR c.message ~ '\nBREAKING[ -]CHANGE: '
+FROM (
+    SELECT id, regexp_match(split_part(message, E'\n', 1), '^([A-Za-z]+)(\(([^()\r\n]*)\))?(!?): +\S') AS m
+    FROM commits
+) h
+WHERE h.id = c.id AND h.m IS NOT NULL AND length(h.m[1]) <= 50 AND length(coalesce(h.m[3], '')) <= 100;
+
+CREATE INDEX idx_commits_type ON commits(project_id, commit_type) WHERE commit_type IS NOT NULL;
+
+COMMENT ON COLUMN commits.commit_type IS 'Conventional Commits type in lowercase (feat, fix...); NULL if the header does not follow the format';
+COMMENT ON COLUMN commits.commit_scope IS 'Conventional Commits scope, if any';
+COMMENT ON COLUMN commits.is_breaking IS 'True if the header has "!" or the footer a BREAKING CHANGE trailer';
+COMMENT ON COLUMN commits.trailers IS 'Footer trailers as [{"key", "value"}]; empty for commits cached before parsing was added';
+COMMENT ON COLUMN commits.issue_refs IS 'Issue references (#12, org/repo#12, PROJ-12); empty for commits cached before parsing was added';
@@ -0,0 +1,18 @@
+-- Trusted keys used to verify commit signatures (SSH allowed signers and GPG keyring)
+CREATE TABLE signing_keys (
+    id SERIAL PRIMARY KEY,
+    kind VARCHAR(10) NOT NULL CHECK (kind IN ('ssh', 'gpg')),
+    fingerprint VARCHAR(100) NOT NULL UNIQUE,
+    principals TEXT[] NOT NULL DEFAULT '{}',
+    public_key TEXT NOT NULL,
+    comment VARCHAR(255),
+    created_by INTEGER REFERENCES users(id) ON DELETE SET NULL,
+    created_at TIMESTAMP NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_signing_keys_kind ON signing_keys(kind);
+
+COMMENT ON TABLE signing_keys IS 'Admin-managed keys trusted to sign commits';
+COMMENT ON COLUMN signing_keys.fingerprint IS 'SSH: SHA256:<base64> of the public key; GPG: primary key fingerprint (40 hex, uppercase)';
+COMMENT ON COLUMN signing_keys.principals IS 'SSH: allowed-signers principals (emails, * and ? wildcards); GPG: emails of the key user IDs';
+COMMENT ON COLUMN signing_keys.public_key IS 'SSH: public key in OpenSSH format; GPG: ASCII-armored public key';
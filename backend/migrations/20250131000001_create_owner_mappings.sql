@@ -0,0 +1,20 @@
+-- Map CODEOWNERS entries (@user, @org/team, email) to LumaStack users and Telegram handles
+CREATE TABLE owner_mappings (
+    id SERIAL PRIMARY KEY,
+    owner VARCHAR(255) NOT NULL,
+    user_id INTEGER REFERENCES users(id) ON DELETE CASCADE,
+    telegram_handle VARCHAR(64),
+    created_by INTEGER REFERENCES users(id) ON DELETE SET NULL,
+    created_at TIMESTAMP NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMP NOT NULL DEFAULT NOW(),
+    CONSTRAINT owner_mappings_target CHECK (user_id IS NOT NULL OR telegram_handle IS NOT NULL)
+);
+
+CREATE UNIQUE INDEX idx_owner_mappings_owner ON owner_mappings(LOWER(owner));
+
+CREATE TRIGGER update_owner_mappings_updated_at BEFORE UPDATE ON owner_mappings
+    FOR EACH ROW EXECUTE FUNCTION update_updated_at_column();
+
+COMMENT ON TABLE owner_mappings IS 'Admin-managed mapping from CODEOWNERS entries to users and Telegram handles';
+COMMENT ON COLUMN owner_mappings.owner IS 'Owner as written in CODEOWNERS: @username, @org/team or email (case-insensitive)';
+COMMENT ON COLUMN owner_mappings.telegram_handle IS 'Telegram username without the leading @';
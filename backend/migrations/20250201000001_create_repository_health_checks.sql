@@ -0,0 +1,32 @@
+-- Periodic and on-demand health checks of monitored repositories
+CREATE TABLE repository_health_checks (
+    id SERIAL PRIMARY KEY,
+    project_id INTEGER NOT NULL REFERENCES projects(id) ON DELETE CASCADE,
+    status VARCHAR(20) NOT NULL,
+    integrity_ok BOOLEAN NOT NULL,
+    integrity_complete BOOLEAN NOT NULL,
+    objects_checked INTEGER NOT NULL,
+    unreachable_objects INTEGER,
+    loose_objects INTEGER NOT NULL,
+    loose_size BIGINT NOT NULL,
+    pack_count INTEGER NOT NULL,
+    pack_size BIGINT NOT NULL,
+    is_bare BOOLEAN NOT NULL,
+    dirty_files INTEGER NOT NULL,
+    detached_head BOOLEAN NOT NULL,
+    unmerged_files TEXT[] NOT NULL DEFAULT '{}',
+    issues JSONB NOT NULL DEFAULT '[]',
+    triggered_by INTEGER REFERENCES users(id) ON DELETE SET NULL,
+    duration_ms INTEGER NOT NULL DEFAULT 0,
+    checked_at TIMESTAMP NOT NULL DEFAULT NOW(),
+    CONSTRAINT chk_health_status CHECK (status IN ('healthy', 'warning', 'failing'))
+);
+
+-- Indexes for repository_health_checks
+CREATE INDEX idx_repository_health_checks_project ON repository_health_checks(project_id, checked_at DESC);
+
+COMMENT ON TABLE repository_health_checks IS 'Integrity, storage and working-copy checks of each repository (latest rows kept per project)';
+COMMENT ON COLUMN repository_health_checks.integrity_complete IS 'False when the reachability walk stopped at the configured object limit';
+COMMENT ON COLUMN repository_health_checks.unreachable_objects IS 'Stored objects no ref, reflog or index entry reaches (dangling); NULL if the walk was incomplete';
+COMMENT ON COLUMN repository_health_checks.issues IS 'JSON array of {kind, severity, message} found by the check';
+COMMENT ON COLUMN repository_health_checks.triggered_by IS 'User who requested the check; NULL for scheduled checks';
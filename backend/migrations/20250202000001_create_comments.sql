@@ -0,0 +1,25 @@
+-- Project- and commit-level comments (RF-016)
+CREATE TABLE comments (
+    id SERIAL PRIMARY KEY,
+    project_id INTEGER NOT NULL REFERENCES projects(id) ON DELETE CASCADE,
+    user_id INTEGER REFERENCES users(id) ON DELETE SET NULL,
+    commit_hash VARCHAR(40),
+    content TEXT NOT NULL,
+    parent_comment_id INTEGER REFERENCES comments(id) ON DELETE CASCADE,
+    created_at TIMESTAMP NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMP NOT NULL DEFAULT NOW(),
+    deleted_at TIMESTAMP
+);
+
+-- Indexes for comments
+CREATE INDEX idx_comments_project ON comments(project_id, created_at DESC);
+CREATE INDEX idx_comments_parent ON comments(parent_comment_id) WHERE parent_comment_id IS NOT NULL;
+CREATE INDEX idx_comments_commit ON comments(project_id, commit_hash) WHERE commit_hash IS NOT NULL;
+
+CREATE TRIGGER update_comments_updated_at BEFORE UPDATE ON comments
+    FOR EACH ROW EXECUTE FUNCTION update_updated_at_column();
+
+COMMENT ON TABLE comments IS 'User comments on projects or specific commits';
+COMMENT ON COLUMN comments.commit_hash IS 'If set, comment is specific to this commit';
+COMMENT ON COLUMN comments.parent_comment_id IS 'For threaded comments (1 level deep)';
+COMMENT ON COLUMN comments.deleted_at IS 'Soft delete: content is hidden but replies stay in the thread';
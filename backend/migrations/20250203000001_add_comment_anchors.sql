@@ -0,0 +1,25 @@
+-- File- and line-anchored comments (RF-016)
+-- The original anchor is (commit_hash, file_path, line_start..line_end); anchor_* is the position
+-- in anchor_commit, re-mapped as the default branch moves.
+ALTER TABLE comments
+    ADD COLUMN file_path TEXT,
+    ADD COLUMN line_start INTEGER,
+    ADD COLUMN line_end INTEGER,
+    ADD COLUMN anchor_commit VARCHAR(40),
+    ADD COLUMN anchor_path TEXT,
+    ADD COLUMN anchor_line_start INTEGER,
+    ADD COLUMN anchor_line_end INTEGER,
+    ADD COLUMN is_outdated BOOLEAN NOT NULL DEFAULT FALSE,
+    ADD CONSTRAINT chk_comments_file CHECK (file_path IS NULL OR commit_hash IS NOT NULL),
+    ADD CONSTRAINT chk_comments_lines CHECK (
+        (line_start IS NULL AND line_end IS NULL)
+        OR (file_path IS NOT NULL AND line_start >= 1 AND line_end >= line_start)
+    );
+
+CREATE INDEX idx_comments_anchor ON comments(project_id, anchor_path) WHERE anchor_path IS NOT NULL;
+CREATE INDEX idx_comments_tracked ON comments(project_id)
+    WHERE anchor_commit IS NOT NULL AND is_outdated = FALSE AND deleted_at IS NULL;
+
+COMMENT ON COLUMN comments.file_path IS 'If set, comment is on this file (and lines) as of commit_hash';
+COMMENT ON COLUMN comments.anchor_commit IS 'Commit where anchor_path/anchor_line_* were last computed';
+COMMENT ON COLUMN comments.is_outdated IS 'True once the commented lines changed or the file was deleted; anchor_* keep the last known position';
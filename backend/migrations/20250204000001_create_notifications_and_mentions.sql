@@ -0,0 +1,33 @@
+-- In-app notifications (RF-012)
+CREATE TABLE notifications (
+    id SERIAL PRIMARY KEY,
+    user_id INTEGER NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    project_id INTEGER REFERENCES projects(id) ON DELETE CASCADE,
+    type VARCHAR(50) NOT NULL,
+    title VARCHAR(255) NOT NULL,
+    message TEXT NOT NULL,
+    link VARCHAR(500),
+    is_read BOOLEAN NOT NULL DEFAULT FALSE,
+    created_at TIMESTAMP NOT NULL DEFAULT NOW(),
+    CONSTRAINT chk_notifications_type CHECK (type IN ('commit', 'comment', 'mention'))
+);
+
+-- Indexes for notifications
+CREATE INDEX idx_notifications_user_unread ON notifications(user_id, is_read, created_at DESC);
+CREATE INDEX idx_notifications_type ON notifications(type);
+
+COMMENT ON TABLE notifications IS 'In-app notifications for users';
+COMMENT ON COLUMN notifications.type IS 'Notification category for filtering and icons';
+COMMENT ON COLUMN notifications.link IS 'Deep link to relevant content in the app';
+
+-- Users mentioned with @username in a comment
+CREATE TABLE comment_mentions (
+    comment_id INTEGER NOT NULL REFERENCES comments(id) ON DELETE CASCADE,
+    user_id INTEGER NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    created_at TIMESTAMP NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (comment_id, user_id)
+);
+
+CREATE INDEX idx_comment_mentions_user ON comment_mentions(user_id);
+
+COMMENT ON TABLE comment_mentions IS 'Resolved @mentions; only users who can see the project are recorded';
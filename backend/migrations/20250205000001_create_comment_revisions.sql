@@ -0,0 +1,14 @@
+-- Edit history of comments: one row per replaced version
+CREATE TABLE comment_revisions (
+    id SERIAL PRIMARY KEY,
+    comment_id INTEGER NOT NULL REFERENCES comments(id) ON DELETE CASCADE,
+    content TEXT NOT NULL,
+    edited_by INTEGER REFERENCES users(id) ON DELETE SET NULL,
+    edited_at TIMESTAMP NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_comment_revisions_comment ON comment_revisions(comment_id, edited_at);
+
+COMMENT ON TABLE comment_revisions IS 'Previous versions of edited comments, for auditing';
+COMMENT ON COLUMN comment_revisions.content IS 'Content before the edit';
+COMMENT ON COLUMN comment_revisions.edited_at IS 'When this version was replaced';
@@ -0,0 +1,23 @@
+-- Emoji reactions on comments: one row per user and emoji
+CREATE TABLE comment_reactions (
+    comment_id INTEGER NOT NULL REFERENCES comments(id) ON DELETE CASCADE,
+    user_id INTEGER NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    emoji VARCHAR(32) NOT NULL,
+    created_at TIMESTAMP NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (comment_id, user_id, emoji)
+);
+
+CREATE INDEX idx_comment_reactions_comment ON comment_reactions(comment_id, emoji);
+
+COMMENT ON TABLE comment_reactions IS 'Emoji reactions; counts are aggregated per comment and emoji';
+
+-- Resolved state of top-level threads
+ALTER TABLE comments
+    ADD COLUMN resolved_at TIMESTAMP,
+    ADD COLUMN resolved_by INTEGER REFERENCES users(id) ON DELETE SET NULL,
+    ADD CONSTRAINT chk_comments_resolved CHECK (resolved_at IS NULL OR parent_comment_id IS NULL);
+
+CREATE INDEX idx_comments_resolved ON comments(project_id, resolved_at) WHERE parent_comment_id IS NULL;
+
+COMMENT ON COLUMN comments.resolved_at IS 'When the thread was resolved; NULL while open (top-level comments only)';
+COMMENT ON COLUMN comments.resolved_by IS 'User who resolved the thread';
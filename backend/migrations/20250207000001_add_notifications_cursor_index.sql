@@ -0,0 +1,2 @@
+-- Keyset pagination of a user's notifications (newest first)
+CREATE INDEX idx_notifications_user_id ON notifications(user_id, id DESC);
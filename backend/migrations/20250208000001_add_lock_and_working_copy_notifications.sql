@@ -0,0 +1,5 @@
+-- Notifications for lock workflow events and dirty working copies
+ALTER TABLE notifications
+    DROP CONSTRAINT chk_notifications_type,
+    ADD CONSTRAINT chk_notifications_type
+        CHECK (type IN ('commit', 'comment', 'mention', 'lock', 'working_copy'));
@@ -1,8 +1,8 @@
 use sqlx::PgPool;
 use std::io::{self, Write};
 
-use crate::db::users::{create_user, find_user_by_email};
-use crate::models::user::{CreateUser, UserRole};
+use lumastack_backend::db::users::{create_user, find_user_by_email};
+use lumastack_backend::models::user::{CreateUser, UserRole};
 
 /// Crea un usuario administrador
 ///
@@ -38,7 +38,7 @@ pub async fn create_admin(
             println!("❌ Error: Ya existe un usuario con el email '{}'", email);
             return Err("Usuario ya existe".into());
         }
-        Err(crate::db::users::UserError::NotFound) => {
+        Err(lumastack_backend::db::users::UserError::NotFound) => {
             // Bien, no existe, podemos continuar
         }
         Err(e) => {
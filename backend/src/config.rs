@@ -0,0 +1,513 @@
+use std::path::PathBuf;
+use std::time::Duration;
+
+/// Configuración de lectura de repositorios
+///
+/// # Configuración (vía variables de entorno)
+/// - `GIT_MAX_BLOB_SIZE`: Bytes máximos de contenido devuelto en `/blob`; el resto se trunca (default: 1 MiB)
+/// - `GIT_MAX_RAW_SIZE`: Bytes máximos servidos por `/raw` y por archivo de una descarga `.zip`; archivos mayores se rechazan (default: 50 MiB)
+/// - `GIT_MAX_HIGHLIGHT_SIZE`: Bytes máximos para resaltar sintaxis o renderizar Markdown (default: 512 KiB)
+/// - `GIT_MAX_DIFF_FILES`: Archivos con detalle en las respuestas de diff (default: 300)
+/// - `GIT_MAX_DIFF_LINES`: Líneas de diff totales en una respuesta (default: 20000)
+/// - `GIT_BLAME_CACHE_SIZE`: Resultados de blame guardados en memoria; 0 desactiva la caché (default: 256)
+/// - `GIT_GREP_TIME_BUDGET_MS`: Tiempo máximo de una búsqueda en el contenido de un repositorio (default: 5000)
+/// - `GIT_GREP_MAX_MATCHES`: Coincidencias máximas devueltas por búsqueda (default: 1000)
+/// - `GIT_GREP_MAX_FILE_SIZE`: Archivos mayores se omiten en la búsqueda (default: 1 MiB)
+/// - `GIT_MAX_ARCHIVE_SIZE`: Bytes sin comprimir máximos de una descarga `/archive` (default: 512 MiB)
+#[derive(Debug, Clone)]
+pub struct GitConfig {
+    pub max_blob_size: u64,
+    pub max_raw_size: u64,
+    pub max_highlight_size: u64,
+    pub max_diff_files: usize,
+    pub max_diff_lines: usize,
+    pub blame_cache_size: usize,
+    pub grep_time_budget: Duration,
+    pub grep_max_matches: usize,
+    pub grep_max_file_size: u64,
+    pub max_archive_size: u64,
+}
+
+impl Default for GitConfig {
+    fn default() -> Self {
+        Self {
+            max_blob_size: 1024 * 1024,
+            max_raw_size: 50 * 1024 * 1024,
+            max_highlight_size: 512 * 1024,
+            max_diff_files: 300,
+            max_diff_lines: 20_000,
+            blame_cache_size: 256,
+            grep_time_budget: Duration::from_secs(5),
+            grep_max_matches: 1000,
+            grep_max_file_size: 1024 * 1024,
+            max_archive_size: 512 * 1024 * 1024,
+        }
+    }
+}
+
+impl GitConfig {
+    /// Lee la configuración desde variables de entorno con valores por defecto
+    pub fn from_env() -> Self {
+        let defaults = Self::default();
+
+        let max_blob_size = std::env::var("GIT_MAX_BLOB_SIZE")
+            .ok()
+            .and_then(|s| s.parse::<u64>().ok())
+            .unwrap_or(defaults.max_blob_size);
+
+        let max_raw_size = std::env::var("GIT_MAX_RAW_SIZE")
+            .ok()
+            .and_then(|s| s.parse::<u64>().ok())
+            .unwrap_or(defaults.max_raw_size);
+
+        let max_highlight_size = std::env::var("GIT_MAX_HIGHLIGHT_SIZE")
+            .ok()
+            .and_then(|s| s.parse::<u64>().ok())
+            .unwrap_or(defaults.max_highlight_size);
+
+        let max_diff_files = std::env::var("GIT_MAX_DIFF_FILES")
+            .ok()
+            .and_then(|s| s.parse::<usize>().ok())
+            .unwrap_or(defaults.max_diff_files);
+
+        let max_diff_lines = std::env::var("GIT_MAX_DIFF_LINES")
+            .ok()
+            .and_then(|s| s.parse::<usize>().ok())
+            .unwrap_or(defaults.max_diff_lines);
+
+        let blame_cache_size = std::env::var("GIT_BLAME_CACHE_SIZE")
+            .ok()
+            .and_then(|s| s.parse::<usize>().ok())
+            .unwrap_or(defaults.blame_cache_size);
+
+        let grep_time_budget = std::env::var("GIT_GREP_TIME_BUDGET_MS")
+            .ok()
+            .and_then(|s| s.parse::<u64>().ok())
+            .filter(|ms| *ms > 0)
+            .map(Duration::from_millis)
+            .unwrap_or(defaults.grep_time_budget);
+
+        let grep_max_matches = std::env::var("GIT_GREP_MAX_MATCHES")
+            .ok()
+            .and_then(|s| s.parse::<usize>().ok())
+            .filter(|n| *n > 0)
+            .unwrap_or(defaults.grep_max_matches);
+
+        let grep_max_file_size = std::env::var("GIT_GREP_MAX_FILE_SIZE")
+            .ok()
+            .and_then(|s| s.parse::<u64>().ok())
+            .unwrap_or(defaults.grep_max_file_size);
+
+        let max_archive_size = std::env::var("GIT_MAX_ARCHIVE_SIZE")
+            .ok()
+            .and_then(|s| s.parse::<u64>().ok())
+            .unwrap_or(defaults.max_archive_size);
+
+        tracing::info!(
+            "Configuración de Git: max_blob_size={}, max_raw_size={}, max_highlight_size={}, max_diff_files={}, max_diff_lines={}, blame_cache_size={}, grep_time_budget={:?}, grep_max_matches={}, grep_max_file_size={}, max_archive_size={}",
+            max_blob_size,
+            max_raw_size,
+            max_highlight_size,
+            max_diff_files,
+            max_diff_lines,
+            blame_cache_size,
+            grep_time_budget,
+            grep_max_matches,
+            grep_max_file_size,
+            max_archive_size
+        );
+
+        Self {
+            max_blob_size,
+            max_raw_size,
+            max_highlight_size,
+            max_diff_files,
+            max_diff_lines,
+            blame_cache_size,
+            grep_time_budget,
+            grep_max_matches,
+            grep_max_file_size,
+            max_archive_size,
+        }
+    }
+}
+
+/// Estrategia para detectar cambios en las referencias de los repositorios
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WatchMode {
+    /// Eventos del sistema de archivos (inotify/FSEvents/...) con polling
+    /// como respaldo para los repositorios que no se puedan observar
+    Auto,
+    /// Solo polling; necesario en sistemas de archivos de red (NFS, SMB),
+    /// donde los eventos de otros clientes no llegan al kernel local
+    Poll,
+}
+
+/// Configuración del watcher de pulls
+///
+/// # Configuración (vía variables de entorno)
+/// - `WATCHER_ENABLED`: Activa la detección de pulls (default: true)
+/// - `WATCHER_MODE`: `auto` o `poll` (default: auto)
+/// - `WATCHER_DEBOUNCE_MS`: Espera tras el último cambio antes de revisar las ramas (default: 1000)
+/// - `WATCHER_POLL_INTERVAL_SECS`: Intervalo del polling de respaldo (default: 30)
+#[derive(Debug, Clone)]
+pub struct WatcherConfig {
+    pub enabled: bool,
+    pub mode: WatchMode,
+    pub debounce: Duration,
+    pub poll_interval: Duration,
+}
+
+impl Default for WatcherConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            mode: WatchMode::Auto,
+            debounce: Duration::from_millis(1000),
+            poll_interval: Duration::from_secs(30),
+        }
+    }
+}
+
+impl WatcherConfig {
+    /// Lee la configuración desde variables de entorno con valores por defecto
+    pub fn from_env() -> Self {
+        let defaults = Self::default();
+
+        let enabled = std::env::var("WATCHER_ENABLED")
+            .ok()
+            .and_then(|s| s.parse::<bool>().ok())
+            .unwrap_or(defaults.enabled);
+
+        let mode = match std::env::var("WATCHER_MODE").ok().as_deref() {
+            Some("poll") => WatchMode::Poll,
+            Some("auto") | None => WatchMode::Auto,
+            Some(other) => {
+                tracing::warn!("WATCHER_MODE desconocido '{}', se usa 'auto'", other);
+                WatchMode::Auto
+            }
+        };
+
+        let debounce = std::env::var("WATCHER_DEBOUNCE_MS")
+            .ok()
+            .and_then(|s| s.parse::<u64>().ok())
+            .map(Duration::from_millis)
+            .unwrap_or(defaults.debounce);
+
+        let poll_interval = std::env::var("WATCHER_POLL_INTERVAL_SECS")
+            .ok()
+            .and_then(|s| s.parse::<u64>().ok())
+            .filter(|secs| *secs > 0)
+            .map(Duration::from_secs)
+            .unwrap_or(defaults.poll_interval);
+
+        tracing::info!(
+            "Configuración del watcher: enabled={}, mode={:?}, debounce={:?}, poll_interval={:?}",
+            enabled,
+            mode,
+            debounce,
+            poll_interval
+        );
+
+        Self {
+            enabled,
+            mode,
+            debounce,
+            poll_interval,
+        }
+    }
+}
+
+/// Configuración del análisis de composición de los repositorios
+///
+/// # Configuración (vía variables de entorno)
+/// - `ANALYZER_ENABLED`: Activa el análisis en segundo plano (default: true)
+/// - `ANALYZER_INTERVAL_SECS`: Intervalo entre revisiones de todos los proyectos (default: 3600)
+#[derive(Debug, Clone)]
+pub struct AnalyzerConfig {
+    pub enabled: bool,
+    pub interval: Duration,
+}
+
+impl Default for AnalyzerConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            interval: Duration::from_secs(3600),
+        }
+    }
+}
+
+impl AnalyzerConfig {
+    /// Lee la configuración desde variables de entorno con valores por defecto
+    pub fn from_env() -> Self {
+        let defaults = Self::default();
+
+        let enabled = std::env::var("ANALYZER_ENABLED")
+            .ok()
+            .and_then(|s| s.parse::<bool>().ok())
+            .unwrap_or(defaults.enabled);
+
+        let interval = std::env::var("ANALYZER_INTERVAL_SECS")
+            .ok()
+            .and_then(|s| s.parse::<u64>().ok())
+            .filter(|secs| *secs > 0)
+            .map(Duration::from_secs)
+            .unwrap_or(defaults.interval);
+
+        tracing::info!(
+            "Configuración del analizador: enabled={}, interval={:?}",
+            enabled,
+            interval
+        );
+
+        Self { enabled, interval }
+    }
+}
+
+/// Configuración de las revisiones de salud de los repositorios
+///
+/// Los umbrales por defecto de objetos sueltos y packs son los de
+/// `gc.auto` y `gc.autoPackLimit` de Git.
+///
+/// # Configuración (vía variables de entorno)
+/// - `HEALTH_ENABLED`: Activa las revisiones programadas (default: true)
+/// - `HEALTH_WORKING_COPY_ENABLED`: Vigila los working copies y avisa cuando uno pasa a
+///   tener cambios sin commitear, aunque las revisiones programadas estén desactivadas
+///   (default: true)
+/// - `HEALTH_INTERVAL_SECS`: Intervalo entre revisiones de todos los proyectos (default: 21600)
+/// - `HEALTH_WORKING_COPY_INTERVAL_SECS`: Intervalo entre comprobaciones de cambios sin
+///   commitear en los working copies, que avisan cuando uno deja de estar limpio (default: 300)
+/// - `HEALTH_MAX_OBJECTS`: Objetos alcanzables que se revisan como máximo (default: 1000000)
+/// - `HEALTH_MAX_LOOSE_OBJECTS`: Objetos sueltos a partir de los que se avisa (default: 6700)
+/// - `HEALTH_MAX_PACKS`: Packs a partir de los que se avisa (default: 50)
+/// - `HEALTH_MAX_PACK_SIZE_MB`: Tamaño total de los packs a partir del que se avisa (default: 2048)
+/// - `HEALTH_MAX_UNREACHABLE_OBJECTS`: Objetos colgando a partir de los que se avisa (default: 10000)
+#[derive(Debug, Clone)]
+pub struct HealthConfig {
+    pub enabled: bool,
+    pub working_copy_enabled: bool,
+    pub interval: Duration,
+    pub working_copy_interval: Duration,
+    pub max_objects: u64,
+    pub max_loose_objects: u64,
+    pub max_packs: u64,
+    /// En bytes
+    pub max_pack_size: u64,
+    pub max_unreachable_objects: u64,
+}
+
+impl Default for HealthConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            working_copy_enabled: true,
+            interval: Duration::from_secs(6 * 3600),
+            working_copy_interval: Duration::from_secs(300),
+            max_objects: 1_000_000,
+            max_loose_objects: 6700,
+            max_packs: 50,
+            max_pack_size: 2048 * 1024 * 1024,
+            max_unreachable_objects: 10_000,
+        }
+    }
+}
+
+impl HealthConfig {
+    /// Lee la configuración desde variables de entorno con valores por defecto
+    pub fn from_env() -> Self {
+        let defaults = Self::default();
+        let positive = |name: &str| {
+            std::env::var(name)
+                .ok()
+                .and_then(|s| s.parse::<u64>().ok())
+                .filter(|n| *n > 0)
+        };
+
+        let flag = |name: &str, default: bool| {
+            std::env::var(name)
+                .ok()
+                .and_then(|s| s.parse::<bool>().ok())
+                .unwrap_or(default)
+        };
+
+        let config = Self {
+            enabled: flag("HEALTH_ENABLED", defaults.enabled),
+            working_copy_enabled: flag(
+                "HEALTH_WORKING_COPY_ENABLED",
+                defaults.working_copy_enabled,
+            ),
+            interval: positive("HEALTH_INTERVAL_SECS")
+                .map(Duration::from_secs)
+                .unwrap_or(defaults.interval),
+            working_copy_interval: positive("HEALTH_WORKING_COPY_INTERVAL_SECS")
+                .map(Duration::from_secs)
+                .unwrap_or(defaults.working_copy_interval),
+            max_objects: positive("HEALTH_MAX_OBJECTS").unwrap_or(defaults.max_objects),
+            max_loose_objects: positive("HEALTH_MAX_LOOSE_OBJECTS")
+                .unwrap_or(defaults.max_loose_objects),
+            max_packs: positive("HEALTH_MAX_PACKS").unwrap_or(defaults.max_packs),
+            max_pack_size: positive("HEALTH_MAX_PACK_SIZE_MB")
+                .map(|mb| mb.saturating_mul(1024 * 1024))
+                .unwrap_or(defaults.max_pack_size),
+            max_unreachable_objects: positive("HEALTH_MAX_UNREACHABLE_OBJECTS")
+                .unwrap_or(defaults.max_unreachable_objects),
+        };
+
+        tracing::info!(
+            "Configuración de las revisiones de salud: enabled={}, working_copy_enabled={}, interval={:?}, working_copy_interval={:?}, max_objects={}",
+            config.enabled,
+            config.working_copy_enabled,
+            config.interval,
+            config.working_copy_interval,
+            config.max_objects
+        );
+
+        config
+    }
+}
+
+/// Configuración de autenticación
+///
+/// # Configuración (vía variables de entorno)
+/// - `JWT_SECRET`: Clave para firmar los tokens (HS256). Si falta se genera una
+///   aleatoria y los tokens dejan de ser válidos al reiniciar el servidor
+/// - `JWT_EXPIRATION`: Validez de los tokens en segundos (default: 86400)
+#[derive(Debug, Clone)]
+pub struct AuthConfig {
+    pub jwt_secret: String,
+    pub jwt_expiration: Duration,
+}
+
+impl AuthConfig {
+    /// Lee la configuración desde variables de entorno con valores por defecto
+    pub fn from_env() -> Self {
+        let jwt_secret = match std::env::var("JWT_SECRET") {
+            Ok(secret) if !secret.trim().is_empty() => secret,
+            _ => {
+                tracing::warn!("JWT_SECRET no configurado: se usará una clave aleatoria");
+                format!("{}{}", uuid::Uuid::new_v4().simple(), uuid::Uuid::new_v4().simple())
+            }
+        };
+
+        let jwt_expiration = std::env::var("JWT_EXPIRATION")
+            .ok()
+            .and_then(|s| s.parse::<u64>().ok())
+            .filter(|secs| *secs > 0)
+            .map(Duration::from_secs)
+            .unwrap_or(Duration::from_secs(86400));
+
+        tracing::info!("Configuración de autenticación: jwt_expiration={:?}", jwt_expiration);
+
+        Self {
+            jwt_secret,
+            jwt_expiration,
+        }
+    }
+}
+
+/// Configuración de la verificación de firmas de commits
+///
+/// # Configuración (vía variables de entorno)
+/// - `SIGNATURE_GPG_PROGRAM`: Programa para verificar firmas GPG (default: `gpg`)
+/// - `SIGNATURE_GPG_HOME`: Directorio del keyring con las claves GPG de confianza;
+///   lo gestiona el servidor y se reconstruye al cambiar las claves
+///   (default: `lumastack-gnupg` en el directorio temporal del sistema)
+/// - `SIGNATURE_CACHE_SIZE`: Resultados de verificación guardados en memoria; 0 desactiva la caché (default: 4096)
+#[derive(Debug, Clone)]
+pub struct SignatureConfig {
+    pub gpg_program: String,
+    pub gpg_home: PathBuf,
+    pub cache_size: usize,
+}
+
+impl Default for SignatureConfig {
+    fn default() -> Self {
+        Self {
+            gpg_program: "gpg".to_string(),
+            gpg_home: std::env::temp_dir().join("lumastack-gnupg"),
+            cache_size: 4096,
+        }
+    }
+}
+
+impl SignatureConfig {
+    /// Lee la configuración desde variables de entorno con valores por defecto
+    pub fn from_env() -> Self {
+        let defaults = Self::default();
+
+        let gpg_program = std::env::var("SIGNATURE_GPG_PROGRAM")
+            .ok()
+            .filter(|s| !s.trim().is_empty())
+            .unwrap_or(defaults.gpg_program);
+
+        let gpg_home = std::env::var("SIGNATURE_GPG_HOME")
+            .ok()
+            .filter(|s| !s.trim().is_empty())
+            .map(PathBuf::from)
+            .unwrap_or(defaults.gpg_home);
+
+        let cache_size = std::env::var("SIGNATURE_CACHE_SIZE")
+            .ok()
+            .and_then(|s| s.parse::<usize>().ok())
+            .unwrap_or(defaults.cache_size);
+
+        Self {
+            gpg_program,
+            gpg_home,
+            cache_size,
+        }
+    }
+}
+
+/// Configuración del almacén de credenciales de remotos
+///
+/// # Configuración (vía variables de entorno)
+/// - `CREDENTIALS_KEY`: Clave AES-256 en base64 (32 bytes) para cifrar las
+///   credenciales de los remotos. Si falta o es inválida no se pueden guardar
+///   ni usar credenciales; los remotos públicos o locales siguen funcionando.
+///   Se genera con `openssl rand -base64 32`
+#[derive(Clone, Default)]
+pub struct VaultConfig {
+    pub key: Option<[u8; 32]>,
+}
+
+impl VaultConfig {
+    /// Lee la configuración desde variables de entorno
+    pub fn from_env() -> Self {
+        use base64::Engine;
+
+        let key = match std::env::var("CREDENTIALS_KEY") {
+            Ok(encoded) if !encoded.trim().is_empty() => {
+                match base64::engine::general_purpose::STANDARD
+                    .decode(encoded.trim())
+                    .ok()
+                    .and_then(|bytes| <[u8; 32]>::try_from(bytes).ok())
+                {
+                    Some(key) => Some(key),
+                    None => {
+                        tracing::error!(
+                            "CREDENTIALS_KEY inválida: debe ser una clave de 32 bytes en base64"
+                        );
+                        None
+                    }
+                }
+            }
+            _ => {
+                tracing::warn!("CREDENTIALS_KEY no configurada: no se podrán usar credenciales de remotos");
+                None
+            }
+        };
+
+        Self { key }
+    }
+}
+
+// La clave nunca debe acabar en los logs
+impl std::fmt::Debug for VaultConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("VaultConfig")
+            .field("configured", &self.key.is_some())
+            .finish()
+    }
+}
@@ -0,0 +1,58 @@
+use crate::models::{AuditAction, AuditEntry, CreateAuditEntry};
+use sqlx::PgPool;
+
+/// Registra una entrada en el log de auditoría
+///
+/// # Errors
+/// Retorna error si falla la inserción
+pub async fn record_audit(pool: &PgPool, entry: CreateAuditEntry) -> Result<i32, sqlx::Error> {
+    sqlx::query_scalar!(
+        r#"
+        INSERT INTO audit_log (project_id, user_id, action, details)
+        VALUES ($1, $2, $3, $4)
+        RETURNING id
+        "#,
+        entry.project_id,
+        entry.user_id,
+        entry.action as AuditAction,
+        entry.details
+    )
+    .fetch_one(pool)
+    .await
+}
+
+/// Lista las últimas entradas de auditoría de un proyecto, opcionalmente de una acción
+///
+/// # Errors
+/// Retorna error si falla la consulta
+pub async fn list_project_audit(
+    pool: &PgPool,
+    project_id: i32,
+    action: Option<AuditAction>,
+    limit: i64,
+) -> Result<Vec<AuditEntry>, sqlx::Error> {
+    sqlx::query_as!(
+        AuditEntry,
+        r#"
+        SELECT
+            a.id,
+            a.project_id,
+            a.user_id,
+            u.username as "username?",
+            a.action as "action: AuditAction",
+            a.details,
+            a.created_at as "created_at!"
+        FROM audit_log a
+        LEFT JOIN users u ON u.id = a.user_id
+        WHERE a.project_id = $1
+          AND ($2::varchar IS NULL OR a.action = $2)
+        ORDER BY a.created_at DESC, a.id DESC
+        LIMIT $3
+        "#,
+        project_id,
+        action as Option<AuditAction>,
+        limit
+    )
+    .fetch_all(pool)
+    .await
+}
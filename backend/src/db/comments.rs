@@ -0,0 +1,936 @@
+use crate::models::{
+    Comment, CommentPosition, CommentRevision, ReactionCount, UserResponse, UserRole,
+};
+use chrono::NaiveDateTime;
+use sqlx::types::Json;
+use sqlx::PgPool;
+
+/// Fila de `comments` con los datos del autor
+struct CommentRow {
+    id: i32,
+    project_id: i32,
+    user_id: Option<i32>,
+    commit_hash: Option<String>,
+    content: String,
+    file_path: Option<String>,
+    line_start: Option<i32>,
+    line_end: Option<i32>,
+    anchor_commit: Option<String>,
+    anchor_path: Option<String>,
+    anchor_line_start: Option<i32>,
+    anchor_line_end: Option<i32>,
+    is_outdated: bool,
+    mentions: Vec<String>,
+    parent_comment_id: Option<i32>,
+    revision_count: i64,
+    reactions: Json<Vec<ReactionCount>>,
+    resolved_at: Option<NaiveDateTime>,
+    resolved_by: Option<i32>,
+    resolved_by_username: Option<String>,
+    created_at: NaiveDateTime,
+    updated_at: NaiveDateTime,
+    deleted_at: Option<NaiveDateTime>,
+    author_username: Option<String>,
+    author_email: Option<String>,
+    author_role: Option<UserRole>,
+    author_is_active: Option<bool>,
+    author_created_at: Option<NaiveDateTime>,
+    author_updated_at: Option<NaiveDateTime>,
+}
+
+impl From<CommentRow> for Comment {
+    fn from(row: CommentRow) -> Self {
+        let user = match (
+            row.user_id,
+            row.author_username,
+            row.author_email,
+            row.author_role,
+            row.author_created_at,
+            row.author_updated_at,
+        ) {
+            (Some(id), Some(username), Some(email), Some(role), Some(created), Some(updated)) => {
+                Some(UserResponse {
+                    id,
+                    username,
+                    email,
+                    role,
+                    is_active: row.author_is_active.unwrap_or(true),
+                    created_at: created,
+                    updated_at: updated,
+                })
+            }
+            _ => None,
+        };
+        let position = match (row.anchor_commit, row.anchor_path) {
+            (Some(commit), Some(path)) => Some(CommentPosition {
+                commit,
+                path,
+                line_start: row.anchor_line_start,
+                line_end: row.anchor_line_end,
+            }),
+            _ => None,
+        };
+
+        Self {
+            id: row.id,
+            project_id: row.project_id,
+            user_id: row.user_id,
+            commit_hash: row.commit_hash,
+            content: row.content,
+            // Lo rellena `services::comments::render_comments`
+            content_html: String::new(),
+            file_path: row.file_path,
+            line_start: row.line_start,
+            line_end: row.line_end,
+            position,
+            is_outdated: row.is_outdated,
+            mentions: row.mentions,
+            parent_comment_id: row.parent_comment_id,
+            revision_count: row.revision_count,
+            reactions: row.reactions.0,
+            resolved_at: row.resolved_at,
+            resolved_by: row.resolved_by,
+            resolved_by_username: row.resolved_by_username,
+            created_at: row.created_at,
+            updated_at: row.updated_at,
+            deleted_at: row.deleted_at,
+            user,
+        }
+    }
+}
+
+/// Comentarios que se listan juntos
+#[derive(Debug, Clone, Copy)]
+pub enum CommentScope<'a> {
+    /// Comentarios del proyecto, sin commit
+    Project,
+    /// Comentarios de un commit (hash completo), incluidos los de sus archivos
+    Commit(&'a str),
+    /// Comentarios cuya posición actual está en un archivo
+    File(&'a str),
+}
+
+/// Datos de un comentario nuevo
+#[derive(Debug, Clone, Copy)]
+pub struct NewComment<'a> {
+    pub project_id: i32,
+    pub user_id: i32,
+    pub commit_hash: Option<&'a str>,
+    pub file_path: Option<&'a str>,
+    pub lines: Option<(i32, i32)>,
+    pub parent_comment_id: Option<i32>,
+    pub content: &'a str,
+}
+
+/// Posición de un comentario de archivo que todavía se sigue
+#[derive(Debug, Clone)]
+pub struct TrackedAnchor {
+    pub id: i32,
+    pub commit: String,
+    pub path: String,
+    pub line_start: Option<i32>,
+    pub line_end: Option<i32>,
+}
+
+/// Usuario mencionado en un comentario
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MentionedUser {
+    pub id: i32,
+    pub username: String,
+}
+
+/// Dónde se muestra un comentario: el commit y el archivo de su hilo
+#[derive(Debug, Clone)]
+pub struct CommentLocation {
+    pub id: i32,
+    pub commit_hash: Option<String>,
+    /// Posición actual del archivo, o la original si no se sigue
+    pub path: Option<String>,
+}
+
+/// Nueva posición de un comentario; `None` si quedó desactualizado
+pub type AnchorUpdate = (i32, Option<(String, Option<(i32, i32)>)>);
+
+/// Comentarios de un proyecto, del más antiguo al más reciente
+///
+/// Las respuestas se incluyen con su comentario raíz. Los comentarios
+/// eliminados solo se incluyen si conservan alguna respuesta. Con
+/// `resolved` solo se incluyen los hilos resueltos (`true`) o abiertos
+/// (`false`).
+///
+/// # Errors
+/// Retorna error si falla la consulta
+pub async fn list_comments(
+    pool: &PgPool,
+    project_id: i32,
+    scope: CommentScope<'_>,
+    resolved: Option<bool>,
+) -> Result<Vec<Comment>, sqlx::Error> {
+    find_comments(pool, project_id, None, scope, resolved).await
+}
+
+/// Busca un comentario de un proyecto, aunque esté eliminado
+///
+/// # Errors
+/// Retorna error si falla la consulta
+pub async fn find_comment(
+    pool: &PgPool,
+    project_id: i32,
+    id: i32,
+) -> Result<Option<Comment>, sqlx::Error> {
+    Ok(
+        find_comments(pool, project_id, Some(id), CommentScope::Project, None)
+            .await?
+            .into_iter()
+            .next(),
+    )
+}
+
+/// Crea un comentario
+///
+/// La validación del anidamiento corresponde al llamador.
+///
+/// # Errors
+/// Retorna error si falla la inserción (p. ej. el padre no existe)
+pub async fn insert_comment(
+    pool: &PgPool,
+    comment: NewComment<'_>,
+) -> Result<Comment, sqlx::Error> {
+    let (line_start, line_end) = comment.lines.unzip();
+    // La posición de un comentario de archivo empieza siendo la original
+    let id = sqlx::query_scalar!(
+        r#"
+        INSERT INTO comments
+            (project_id, user_id, commit_hash, parent_comment_id, content, file_path, line_start,
+             line_end, anchor_commit, anchor_path, anchor_line_start, anchor_line_end)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $6, $7, $8)
+        RETURNING id
+        "#,
+        comment.project_id,
+        comment.user_id,
+        comment.commit_hash,
+        comment.parent_comment_id,
+        comment.content,
+        comment.file_path,
+        line_start,
+        line_end,
+        comment.file_path.and(comment.commit_hash)
+    )
+    .fetch_one(pool)
+    .await?;
+
+    find_comment(pool, comment.project_id, id)
+        .await?
+        .ok_or(sqlx::Error::RowNotFound)
+}
+
+/// Cambia el contenido de un comentario no eliminado
+///
+/// El contenido anterior se guarda como revisión a nombre de `edited_by`.
+/// Si el contenido no cambia no se guarda nada. Retorna `None` si el
+/// comentario no existe o está eliminado.
+///
+/// # Errors
+/// Retorna error si falla la actualización
+pub async fn update_comment(
+    pool: &PgPool,
+    project_id: i32,
+    id: i32,
+    content: &str,
+    edited_by: i32,
+) -> Result<Option<Comment>, sqlx::Error> {
+    let mut tx = pool.begin().await?;
+
+    let previous = sqlx::query_scalar!(
+        r#"
+        SELECT content FROM comments
+        WHERE project_id = $1 AND id = $2 AND deleted_at IS NULL
+        FOR UPDATE
+        "#,
+        project_id,
+        id
+    )
+    .fetch_optional(&mut *tx)
+    .await?;
+
+    let Some(previous) = previous else {
+        return Ok(None);
+    };
+    if previous != content {
+        sqlx::query!(
+            "INSERT INTO comment_revisions (comment_id, content, edited_by) VALUES ($1, $2, $3)",
+            id,
+            previous,
+            edited_by
+        )
+        .execute(&mut *tx)
+        .await?;
+        sqlx::query!("UPDATE comments SET content = $2 WHERE id = $1", id, content)
+            .execute(&mut *tx)
+            .await?;
+    }
+
+    tx.commit().await?;
+    find_comment(pool, project_id, id).await
+}
+
+/// Marca un hilo como resuelto por `user_id`
+///
+/// Retorna `false` si el comentario no existe, está eliminado, es una
+/// respuesta o ya estaba resuelto.
+///
+/// # Errors
+/// Retorna error si falla la actualización
+pub async fn resolve_comment(
+    pool: &PgPool,
+    project_id: i32,
+    id: i32,
+    user_id: i32,
+) -> Result<bool, sqlx::Error> {
+    let resolved = sqlx::query!(
+        r#"
+        UPDATE comments SET resolved_at = NOW(), resolved_by = $3
+        WHERE project_id = $1 AND id = $2 AND parent_comment_id IS NULL
+          AND deleted_at IS NULL AND resolved_at IS NULL
+        "#,
+        project_id,
+        id,
+        user_id
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(resolved.rows_affected() > 0)
+}
+
+/// Vuelve a abrir un hilo resuelto
+///
+/// Retorna `false` si el comentario no existe, está eliminado o no estaba resuelto.
+///
+/// # Errors
+/// Retorna error si falla la actualización
+pub async fn unresolve_comment(
+    pool: &PgPool,
+    project_id: i32,
+    id: i32,
+) -> Result<bool, sqlx::Error> {
+    let reopened = sqlx::query!(
+        r#"
+        UPDATE comments SET resolved_at = NULL, resolved_by = NULL
+        WHERE project_id = $1 AND id = $2 AND deleted_at IS NULL AND resolved_at IS NOT NULL
+        "#,
+        project_id,
+        id
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(reopened.rows_affected() > 0)
+}
+
+/// Añade la reacción de un usuario a un comentario
+///
+/// Retorna `false` si el usuario ya había reaccionado con ese emoji.
+///
+/// # Errors
+/// Retorna error si falla la inserción
+pub async fn add_reaction(
+    pool: &PgPool,
+    comment_id: i32,
+    user_id: i32,
+    emoji: &str,
+) -> Result<bool, sqlx::Error> {
+    let added = sqlx::query!(
+        r#"
+        INSERT INTO comment_reactions (comment_id, user_id, emoji)
+        VALUES ($1, $2, $3)
+        ON CONFLICT DO NOTHING
+        "#,
+        comment_id,
+        user_id,
+        emoji
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(added.rows_affected() > 0)
+}
+
+/// Quita la reacción de un usuario a un comentario
+///
+/// Retorna `false` si el usuario no había reaccionado con ese emoji.
+///
+/// # Errors
+/// Retorna error si falla el borrado
+pub async fn remove_reaction(
+    pool: &PgPool,
+    comment_id: i32,
+    user_id: i32,
+    emoji: &str,
+) -> Result<bool, sqlx::Error> {
+    let removed = sqlx::query!(
+        "DELETE FROM comment_reactions WHERE comment_id = $1 AND user_id = $2 AND emoji = $3",
+        comment_id,
+        user_id,
+        emoji
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(removed.rows_affected() > 0)
+}
+
+/// Versiones anteriores de un comentario, de la más antigua a la más reciente
+///
+/// # Errors
+/// Retorna error si falla la consulta
+pub async fn list_comment_revisions(
+    pool: &PgPool,
+    comment_id: i32,
+) -> Result<Vec<CommentRevision>, sqlx::Error> {
+    sqlx::query_as!(
+        CommentRevision,
+        r#"
+        SELECT
+            r.id,
+            r.comment_id,
+            r.content,
+            '' as "content_html!",
+            r.edited_by,
+            u.username as "edited_by_username?",
+            r.edited_at
+        FROM comment_revisions r
+        LEFT JOIN users u ON u.id = r.edited_by
+        WHERE r.comment_id = $1
+        ORDER BY r.edited_at, r.id
+        "#,
+        comment_id
+    )
+    .fetch_all(pool)
+    .await
+}
+
+/// Dónde se muestran los comentarios no eliminados de un proyecto entre los indicados
+///
+/// Las respuestas se ubican en el hilo de su comentario raíz.
+///
+/// # Errors
+/// Retorna error si falla la consulta
+pub async fn find_comment_locations(
+    pool: &PgPool,
+    project_id: i32,
+    ids: &[i32],
+) -> Result<Vec<CommentLocation>, sqlx::Error> {
+    sqlx::query_as!(
+        CommentLocation,
+        r#"
+        SELECT
+            c.id,
+            COALESCE(parent.commit_hash, c.commit_hash) as commit_hash,
+            COALESCE(parent.anchor_path, parent.file_path, c.anchor_path, c.file_path) as path
+        FROM comments c
+        LEFT JOIN comments parent ON parent.id = c.parent_comment_id
+        WHERE c.project_id = $1 AND c.id = ANY($2) AND c.deleted_at IS NULL
+        "#,
+        project_id,
+        ids
+    )
+    .fetch_all(pool)
+    .await
+}
+
+/// Marca un comentario como eliminado
+///
+/// Retorna `false` si no existe o ya estaba eliminado.
+///
+/// # Errors
+/// Retorna error si falla la actualización
+pub async fn soft_delete_comment(
+    pool: &PgPool,
+    project_id: i32,
+    id: i32,
+) -> Result<bool, sqlx::Error> {
+    let deleted = sqlx::query!(
+        r#"
+        UPDATE comments SET deleted_at = NOW()
+        WHERE project_id = $1 AND id = $2 AND deleted_at IS NULL
+        "#,
+        project_id,
+        id
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(deleted.rows_affected() > 0)
+}
+
+/// Usuarios activos que pueden ver el proyecto entre los nombres indicados
+///
+/// Los nombres no distinguen mayúsculas.
+///
+/// # Errors
+/// Retorna error si falla la consulta
+pub async fn find_mentionable_users(
+    pool: &PgPool,
+    project_id: i32,
+    usernames: &[String],
+) -> Result<Vec<MentionedUser>, sqlx::Error> {
+    let usernames: Vec<String> = usernames.iter().map(|u| u.to_lowercase()).collect();
+
+    sqlx::query_as!(
+        MentionedUser,
+        r#"
+        SELECT u.id, u.username
+        FROM users u
+        JOIN projects p ON p.id = $1
+        WHERE LOWER(u.username) = ANY($2)
+          AND COALESCE(u.is_active, TRUE)
+          AND (
+              COALESCE(p.is_public, FALSE)
+              OR u.role = 'admin'
+              OR EXISTS (
+                  SELECT 1 FROM project_members m
+                  WHERE m.project_id = p.id AND m.user_id = u.id
+              )
+          )
+        ORDER BY u.username
+        "#,
+        project_id,
+        &usernames
+    )
+    .fetch_all(pool)
+    .await
+}
+
+/// Reemplaza las menciones de un comentario
+///
+/// Retorna los usuarios que no estaban ya mencionados.
+///
+/// # Errors
+/// Retorna error si falla alguna consulta
+pub async fn replace_mentions(
+    pool: &PgPool,
+    comment_id: i32,
+    user_ids: &[i32],
+) -> Result<Vec<i32>, sqlx::Error> {
+    let mut tx = pool.begin().await?;
+
+    sqlx::query!(
+        "DELETE FROM comment_mentions WHERE comment_id = $1 AND user_id <> ALL($2)",
+        comment_id,
+        user_ids
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    let added = sqlx::query_scalar!(
+        r#"
+        INSERT INTO comment_mentions (comment_id, user_id)
+        SELECT $1, unnest($2::int4[])
+        ON CONFLICT DO NOTHING
+        RETURNING user_id
+        "#,
+        comment_id,
+        user_ids
+    )
+    .fetch_all(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+    Ok(added)
+}
+
+/// Posiciones de archivo de un proyecto que aún no están desactualizadas
+///
+/// # Errors
+/// Retorna error si falla la consulta
+pub async fn list_tracked_anchors(
+    pool: &PgPool,
+    project_id: i32,
+) -> Result<Vec<TrackedAnchor>, sqlx::Error> {
+    sqlx::query_as!(
+        TrackedAnchor,
+        r#"
+        SELECT
+            id,
+            anchor_commit as "commit!",
+            anchor_path as "path!",
+            anchor_line_start as line_start,
+            anchor_line_end as line_end
+        FROM comments
+        WHERE project_id = $1
+          AND anchor_commit IS NOT NULL
+          AND anchor_path IS NOT NULL
+          AND is_outdated = FALSE
+          AND deleted_at IS NULL
+        ORDER BY id
+        "#,
+        project_id
+    )
+    .fetch_all(pool)
+    .await
+}
+
+/// Guarda las posiciones trasladadas a `commit`
+///
+/// Las desactualizadas conservan su última posición.
+///
+/// # Errors
+/// Retorna error si falla alguna actualización
+pub async fn update_anchors(
+    pool: &PgPool,
+    commit: &str,
+    updates: &[AnchorUpdate],
+) -> Result<(), sqlx::Error> {
+    let mut tx = pool.begin().await?;
+
+    for (id, anchor) in updates {
+        match anchor {
+            Some((path, lines)) => {
+                let (line_start, line_end) = lines.unzip();
+                sqlx::query!(
+                    r#"
+                    UPDATE comments
+                    SET anchor_commit = $2, anchor_path = $3, anchor_line_start = $4,
+                        anchor_line_end = $5
+                    WHERE id = $1
+                    "#,
+                    id,
+                    commit,
+                    path,
+                    line_start,
+                    line_end
+                )
+                .execute(&mut *tx)
+                .await?;
+            }
+            None => {
+                sqlx::query!("UPDATE comments SET is_outdated = TRUE WHERE id = $1", id)
+                    .execute(&mut *tx)
+                    .await?;
+            }
+        }
+    }
+
+    tx.commit().await
+}
+
+/// Comentarios de un proyecto: solo el indicado si hay `id`, o los de
+/// `scope` en otro caso, filtrando por estado del hilo si hay `resolved`
+///
+/// El contenido y las reacciones de los eliminados se vacían.
+async fn find_comments(
+    pool: &PgPool,
+    project_id: i32,
+    id: Option<i32>,
+    scope: CommentScope<'_>,
+    resolved: Option<bool>,
+) -> Result<Vec<Comment>, sqlx::Error> {
+    let (commit_hash, path) = match scope {
+        CommentScope::Project => (None, None),
+        CommentScope::Commit(hash) => (Some(hash), None),
+        CommentScope::File(path) => (None, Some(path)),
+    };
+
+    let rows = sqlx::query_as!(
+        CommentRow,
+        r#"
+        SELECT
+            c.id,
+            c.project_id as "project_id!",
+            c.user_id,
+            c.commit_hash,
+            CASE WHEN c.deleted_at IS NULL THEN c.content ELSE '' END as "content!",
+            c.file_path,
+            c.line_start,
+            c.line_end,
+            c.anchor_commit,
+            c.anchor_path,
+            c.anchor_line_start,
+            c.anchor_line_end,
+            c.is_outdated,
+            ARRAY(
+                SELECT mu.username FROM comment_mentions cm
+                JOIN users mu ON mu.id = cm.user_id
+                WHERE cm.comment_id = c.id
+                ORDER BY mu.username
+            ) as "mentions!",
+            c.parent_comment_id,
+            (SELECT COUNT(*) FROM comment_revisions r WHERE r.comment_id = c.id) as "revision_count!",
+            COALESCE(
+                (
+                    SELECT json_agg(
+                        json_build_object('emoji', g.emoji, 'count', g.count, 'users', g.users)
+                        ORDER BY g.first_at, g.emoji
+                    )
+                    FROM (
+                        SELECT
+                            cr.emoji,
+                            COUNT(*) as count,
+                            array_agg(ru.username ORDER BY cr.created_at) as users,
+                            MIN(cr.created_at) as first_at
+                        FROM comment_reactions cr
+                        JOIN users ru ON ru.id = cr.user_id
+                        WHERE cr.comment_id = c.id AND c.deleted_at IS NULL
+                        GROUP BY cr.emoji
+                    ) g
+                ),
+                '[]'
+            ) as "reactions!: Json<Vec<ReactionCount>>",
+            c.resolved_at,
+            c.resolved_by,
+            rv.username as "resolved_by_username?",
+            c.created_at,
+            c.updated_at,
+            c.deleted_at,
+            u.username as "author_username?",
+            u.email as "author_email?",
+            u.role as "author_role?: UserRole",
+            u.is_active as "author_is_active?",
+            u.created_at as "author_created_at?",
+            u.updated_at as "author_updated_at?"
+        FROM comments c
+        LEFT JOIN comments parent ON parent.id = c.parent_comment_id
+        LEFT JOIN users u ON u.id = c.user_id
+        LEFT JOIN users rv ON rv.id = c.resolved_by
+        WHERE c.project_id = $1
+          AND (
+              c.id = $2
+              OR (
+                  $2::int4 IS NULL
+                  AND CASE
+                      WHEN $4::text IS NOT NULL THEN COALESCE(parent.anchor_path, c.anchor_path) = $4
+                      ELSE c.commit_hash IS NOT DISTINCT FROM $3
+                  END
+                  AND (
+                      $5::bool IS NULL
+                      OR (COALESCE(parent.resolved_at, c.resolved_at) IS NOT NULL) = $5
+                  )
+                  AND (
+                      c.deleted_at IS NULL
+                      OR EXISTS (
+                          SELECT 1 FROM comments r
+                          WHERE r.parent_comment_id = c.id AND r.deleted_at IS NULL
+                      )
+                  )
+              )
+          )
+        ORDER BY c.created_at, c.id
+        "#,
+        project_id,
+        id,
+        commit_hash,
+        path,
+        resolved
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(rows.into_iter().map(Comment::from).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Requiere `DATABASE_URL` con las migraciones aplicadas
+    #[tokio::test]
+    #[ignore]
+    async fn test_reactions_and_resolution() {
+        let pool = PgPool::connect(&std::env::var("DATABASE_URL").unwrap())
+            .await
+            .unwrap();
+        let project_id: i32 = sqlx::query_scalar(
+            "INSERT INTO projects (name, repository_path) VALUES ('Reactions', '/tmp/reactions') RETURNING id",
+        )
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+        let mut users = Vec::new();
+        for name in ["reactions_ana", "reactions_bob"] {
+            let id: i32 = sqlx::query_scalar(
+                "INSERT INTO users (username, email, password_hash) VALUES ($1, $1 || '@example.com', 'x') RETURNING id",
+            )
+            .bind(name)
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+            users.push(id);
+        }
+        let (ana, bob) = (users[0], users[1]);
+
+        let comment = |user_id, parent_comment_id, content| NewComment {
+            project_id,
+            user_id,
+            commit_hash: None,
+            file_path: None,
+            lines: None,
+            parent_comment_id,
+            content,
+        };
+        let root = insert_comment(&pool, comment(ana, None, "¿Lo revisamos?"))
+            .await
+            .unwrap();
+        let reply = insert_comment(&pool, comment(bob, Some(root.id), "Hecho"))
+            .await
+            .unwrap();
+        let open = insert_comment(&pool, comment(bob, None, "Otro hilo"))
+            .await
+            .unwrap();
+
+        let first = add_reaction(&pool, root.id, ana, "👍").await.unwrap();
+        let duplicate = add_reaction(&pool, root.id, ana, "👍").await.unwrap();
+        add_reaction(&pool, root.id, bob, "🎉").await.unwrap();
+        add_reaction(&pool, root.id, bob, "👍").await.unwrap();
+        let removed = remove_reaction(&pool, root.id, bob, "🎉").await.unwrap();
+        let missing = remove_reaction(&pool, root.id, bob, "🎉").await.unwrap();
+        let reactions = find_comment(&pool, project_id, root.id)
+            .await
+            .unwrap()
+            .unwrap()
+            .reactions;
+
+        let reply_resolved = resolve_comment(&pool, project_id, reply.id, ana)
+            .await
+            .unwrap();
+        let resolved = resolve_comment(&pool, project_id, root.id, ana)
+            .await
+            .unwrap();
+        let again = resolve_comment(&pool, project_id, root.id, ana)
+            .await
+            .unwrap();
+        let closed = list_comments(&pool, project_id, CommentScope::Project, Some(true))
+            .await
+            .unwrap();
+        let opened = list_comments(&pool, project_id, CommentScope::Project, Some(false))
+            .await
+            .unwrap();
+        let reopened = unresolve_comment(&pool, project_id, root.id).await.unwrap();
+        let reopened_again = unresolve_comment(&pool, project_id, root.id).await.unwrap();
+        let after = list_comments(&pool, project_id, CommentScope::Project, Some(false))
+            .await
+            .unwrap();
+
+        sqlx::query("DELETE FROM projects WHERE id = $1")
+            .bind(project_id)
+            .execute(&pool)
+            .await
+            .unwrap();
+        sqlx::query("DELETE FROM users WHERE id = ANY($1)")
+            .bind(&users)
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        assert!(first);
+        assert!(!duplicate);
+        assert!(removed);
+        assert!(!missing);
+        assert_eq!(reactions.len(), 1);
+        assert_eq!(reactions[0].emoji, "👍");
+        assert_eq!(reactions[0].count, 2);
+        assert_eq!(reactions[0].users, vec!["reactions_ana", "reactions_bob"]);
+
+        // Solo se resuelve el comentario raíz, y una sola vez
+        assert!(!reply_resolved);
+        assert!(resolved);
+        assert!(!again);
+        // Las respuestas siguen el estado de su hilo
+        let ids = |comments: &[Comment]| comments.iter().map(|c| c.id).collect::<Vec<_>>();
+        assert_eq!(ids(&closed), vec![root.id, reply.id]);
+        assert_eq!(
+            closed[0].resolved_by_username.as_deref(),
+            Some("reactions_ana")
+        );
+        assert_eq!(ids(&opened), vec![open.id]);
+
+        assert!(reopened);
+        assert!(!reopened_again);
+        assert_eq!(ids(&after), vec![root.id, reply.id, open.id]);
+    }
+
+    /// Requiere `DATABASE_URL` con las migraciones aplicadas
+    #[tokio::test]
+    #[ignore]
+    async fn test_soft_delete_keeps_thread() {
+        let pool = PgPool::connect(&std::env::var("DATABASE_URL").unwrap())
+            .await
+            .unwrap();
+        let project_id: i32 = sqlx::query_scalar(
+            "INSERT INTO projects (name, repository_path) VALUES ('Soft delete', '/tmp/soft-delete') RETURNING id",
+        )
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+        let user_id: i32 = sqlx::query_scalar(
+            "INSERT INTO users (username, email, password_hash) VALUES ('soft_delete_ana', 'soft_delete_ana@example.com', 'x') RETURNING id",
+        )
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+
+        let comment = |parent_comment_id, content| NewComment {
+            project_id,
+            user_id,
+            commit_hash: None,
+            file_path: None,
+            lines: None,
+            parent_comment_id,
+            content,
+        };
+        let root = insert_comment(&pool, comment(None, "Texto original"))
+            .await
+            .unwrap();
+        let reply = insert_comment(&pool, comment(Some(root.id), "Respuesta"))
+            .await
+            .unwrap();
+        let lonely = insert_comment(&pool, comment(None, "Sin respuestas"))
+            .await
+            .unwrap();
+
+        let deleted = soft_delete_comment(&pool, project_id, root.id)
+            .await
+            .unwrap();
+        let again = soft_delete_comment(&pool, project_id, root.id)
+            .await
+            .unwrap();
+        soft_delete_comment(&pool, project_id, lonely.id)
+            .await
+            .unwrap();
+        let thread = list_comments(&pool, project_id, CommentScope::Project, None)
+            .await
+            .unwrap();
+        soft_delete_comment(&pool, project_id, reply.id)
+            .await
+            .unwrap();
+        let emptied = list_comments(&pool, project_id, CommentScope::Project, None)
+            .await
+            .unwrap();
+        let stored = find_comment(&pool, project_id, root.id).await.unwrap();
+
+        sqlx::query("DELETE FROM projects WHERE id = $1")
+            .bind(project_id)
+            .execute(&pool)
+            .await
+            .unwrap();
+        sqlx::query("DELETE FROM users WHERE id = $1")
+            .bind(user_id)
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        assert!(deleted);
+        assert!(!again);
+        // El comentario eliminado sigue en el hilo, sin contenido, mientras
+        // tenga respuestas; el que no tiene respuestas desaparece
+        assert_eq!(thread.len(), 2);
+        assert_eq!(thread[0].id, root.id);
+        assert!(thread[0].deleted_at.is_some());
+        assert_eq!(thread[0].content, "");
+        assert_eq!(thread[1].id, reply.id);
+        assert_eq!(thread[1].content, "Respuesta");
+        // Sin respuestas vivas el hilo deja de listarse, pero la fila se conserva
+        assert!(emptied.is_empty());
+        assert!(stored.unwrap().deleted_at.is_some());
+    }
+}
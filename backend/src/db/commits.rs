@@ -0,0 +1,162 @@
+use sqlx::PgPool;
+use crate::models::CommitRecord;
+
+/// Longitud máxima de las columnas `VARCHAR(255)` de la tabla `commits`
+const MAX_FIELD_CHARS: usize = 255;
+
+/// Longitud máxima de `commits.commit_type`
+const MAX_TYPE_CHARS: usize = 50;
+
+/// Longitud máxima de `commits.commit_scope`
+const MAX_SCOPE_CHARS: usize = 100;
+
+/// Guarda commits en la caché de un proyecto
+///
+/// Los commits que ya estaban en la caché se ignoran. Retorna cuántos se
+/// insertaron.
+///
+/// # Errors
+/// Retorna error si falla la inserción
+pub async fn insert_commits(pool: &PgPool, project_id: i32, commits: &[CommitRecord]) -> Result<u64, sqlx::Error> {
+    if commits.is_empty() {
+        return Ok(0);
+    }
+
+    let hashes: Vec<String> = commits.iter().map(|c| c.hash.clone()).collect();
+    let names: Vec<String> = commits.iter().map(|c| truncate_chars(&c.author_name)).collect();
+    let emails: Vec<String> = commits.iter().map(|c| truncate_chars(&c.author_email)).collect();
+    let messages: Vec<String> = commits.iter().map(|c| c.message.clone()).collect();
+    let dates: Vec<chrono::NaiveDateTime> = commits.iter().map(|c| c.committed_at.naive_utc()).collect();
+    let additions: Vec<Option<i32>> = commits.iter().map(|c| c.additions).collect();
+    let deletions: Vec<Option<i32>> = commits.iter().map(|c| c.deletions).collect();
+    let types: Vec<Option<String>> = commits
+        .iter()
+        .map(|c| c.conventional.commit_type.as_deref().map(|t| truncate_to(t, MAX_TYPE_CHARS)))
+        .collect();
+    let scopes: Vec<Option<String>> = commits
+        .iter()
+        .map(|c| c.conventional.scope.as_deref().map(|s| truncate_to(s, MAX_SCOPE_CHARS)))
+        .collect();
+    let breaking: Vec<bool> = commits.iter().map(|c| c.conventional.breaking).collect();
+    let trailers: Vec<serde_json::Value> = commits
+        .iter()
+        .map(|c| serde_json::to_value(&c.conventional.trailers).unwrap_or_default())
+        .collect();
+    let issues: Vec<serde_json::Value> = commits
+        .iter()
+        .map(|c| serde_json::to_value(&c.conventional.issues).unwrap_or_default())
+        .collect();
+
+    // Los arrays de TEXT[] no pueden anidarse en UNNEST, así que las
+    // referencias a issues viajan como JSONB y se convierten en la consulta
+    let result = sqlx::query!(
+        r#"
+        INSERT INTO commits
+            (project_id, commit_hash, author_name, author_email, message, committed_at, additions, deletions,
+             commit_type, commit_scope, is_breaking, trailers, issue_refs)
+        SELECT $1, u.hash, u.name, u.email, u.message, u.committed_at, u.additions, u.deletions,
+               u.commit_type, u.commit_scope, u.is_breaking, u.trailers,
+               ARRAY(SELECT jsonb_array_elements_text(u.issues))
+        FROM UNNEST(
+            $2::varchar[], $3::varchar[], $4::varchar[], $5::text[], $6::timestamp[], $7::int4[], $8::int4[],
+            $9::varchar[], $10::varchar[], $11::bool[], $12::jsonb[], $13::jsonb[]
+        ) AS u(hash, name, email, message, committed_at, additions, deletions,
+               commit_type, commit_scope, is_breaking, trailers, issues)
+        ON CONFLICT (project_id, commit_hash) DO NOTHING
+        "#,
+        project_id,
+        &hashes,
+        &names,
+        &emails,
+        &messages,
+        &dates,
+        &additions as &[Option<i32>],
+        &deletions as &[Option<i32>],
+        &types as &[Option<String>],
+        &scopes as &[Option<String>],
+        &breaking,
+        &trailers,
+        &issues
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(result.rows_affected())
+}
+
+fn truncate_chars(value: &str) -> String {
+    truncate_to(value, MAX_FIELD_CHARS)
+}
+
+fn truncate_to(value: &str, max: usize) -> String {
+    value.chars().take(max).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::conventional::parse_commit_message;
+    use chrono::Utc;
+
+    /// Requiere `DATABASE_URL` con las migraciones aplicadas
+    #[tokio::test]
+    #[ignore]
+    async fn test_insert_conventional_commits() {
+        let pool = PgPool::connect(&std::env::var("DATABASE_URL").unwrap())
+            .await
+            .unwrap();
+        let project_id: i32 = sqlx::query_scalar(
+            "INSERT INTO projects (name, repository_path) VALUES ('Conventional', '/tmp/conv') RETURNING id",
+        )
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+
+        let record = |hash: &str, message: &str| CommitRecord {
+            hash: hash.to_string(),
+            author_name: "Ada".to_string(),
+            author_email: "ada@example.com".to_string(),
+            message: message.to_string(),
+            committed_at: Utc::now(),
+            additions: Some(1),
+            deletions: None,
+            conventional: parse_commit_message(message),
+        };
+        let commits = vec![
+            record(
+                "c1",
+                "feat(api)!: drop v1\n\nCo-authored-by: Bob <bob@example.com>\nCloses #7",
+            ),
+            record("c2", "Update readme"),
+        ];
+
+        assert_eq!(insert_commits(&pool, project_id, &commits).await.unwrap(), 2);
+        assert_eq!(insert_commits(&pool, project_id, &commits).await.unwrap(), 0);
+
+        type Row = (String, Option<String>, Option<String>, bool, serde_json::Value, Vec<String>);
+        let rows: Vec<Row> =
+            sqlx::query_as(
+                "SELECT commit_hash, commit_type, commit_scope, is_breaking, trailers, issue_refs
+                 FROM commits WHERE project_id = $1 ORDER BY commit_hash",
+            )
+            .bind(project_id)
+            .fetch_all(&pool)
+            .await
+            .unwrap();
+
+        sqlx::query("DELETE FROM projects WHERE id = $1")
+            .bind(project_id)
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        assert_eq!(rows[0].1.as_deref(), Some("feat"));
+        assert_eq!(rows[0].2.as_deref(), Some("api"));
+        assert!(rows[0].3);
+        assert_eq!(rows[0].4[0]["key"], "Co-authored-by");
+        assert_eq!(rows[0].5, vec!["#7"]);
+        assert_eq!(rows[1].1, None);
+        assert!(!rows[1].3);
+        assert!(rows[1].5.is_empty());
+    }
+}
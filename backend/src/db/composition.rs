@@ -0,0 +1,260 @@
+use crate::models::{CreateRepositorySnapshot, LanguageBytes, RepositorySnapshot};
+use sqlx::PgPool;
+
+/// Guarda la composición de un commit con sus lenguajes
+///
+/// Si el commit ya estaba analizado no hace nada y retorna `None`.
+///
+/// # Errors
+/// Retorna error si falla la inserción
+pub async fn insert_snapshot(
+    pool: &PgPool,
+    snapshot: &CreateRepositorySnapshot,
+) -> Result<Option<i32>, sqlx::Error> {
+    let mut tx = pool.begin().await?;
+
+    let id = sqlx::query_scalar!(
+        r#"
+        INSERT INTO repository_snapshots
+            (project_id, commit_hash, branch, committed_at, total_files, total_bytes, excluded_files,
+             disk_size, pack_count, pack_size, loose_objects, loose_size)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12)
+        ON CONFLICT (project_id, commit_hash) DO NOTHING
+        RETURNING id
+        "#,
+        snapshot.project_id,
+        snapshot.commit_hash,
+        snapshot.branch,
+        snapshot.committed_at,
+        snapshot.total_files,
+        snapshot.total_bytes,
+        snapshot.excluded_files,
+        snapshot.disk_size,
+        snapshot.pack_count,
+        snapshot.pack_size,
+        snapshot.loose_objects,
+        snapshot.loose_size
+    )
+    .fetch_optional(&mut *tx)
+    .await?;
+
+    let Some(id) = id else {
+        return Ok(None);
+    };
+
+    let languages: Vec<String> = snapshot
+        .languages
+        .iter()
+        .map(|l| l.language.clone())
+        .collect();
+    let files: Vec<i32> = snapshot.languages.iter().map(|l| l.files).collect();
+    let bytes: Vec<i64> = snapshot.languages.iter().map(|l| l.bytes).collect();
+    sqlx::query!(
+        r#"
+        INSERT INTO repository_languages (snapshot_id, language, files, bytes)
+        SELECT $1, * FROM UNNEST($2::varchar[], $3::int4[], $4::int8[])
+        "#,
+        id,
+        &languages,
+        &files,
+        &bytes
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+    Ok(Some(id))
+}
+
+/// Indica si un commit de un proyecto ya está analizado
+///
+/// # Errors
+/// Retorna error si falla la consulta
+pub async fn snapshot_exists(
+    pool: &PgPool,
+    project_id: i32,
+    commit_hash: &str,
+) -> Result<bool, sqlx::Error> {
+    sqlx::query_scalar!(
+        r#"
+        SELECT EXISTS (
+            SELECT 1 FROM repository_snapshots WHERE project_id = $1 AND commit_hash = $2
+        ) as "exists!"
+        "#,
+        project_id,
+        commit_hash
+    )
+    .fetch_one(pool)
+    .await
+}
+
+/// Última composición analizada de un proyecto (por fecha del commit)
+///
+/// # Errors
+/// Retorna error si falla la consulta
+pub async fn latest_snapshot(
+    pool: &PgPool,
+    project_id: i32,
+) -> Result<Option<RepositorySnapshot>, sqlx::Error> {
+    sqlx::query_as!(
+        RepositorySnapshot,
+        r#"
+        SELECT id, project_id, commit_hash, branch, committed_at, total_files, total_bytes,
+               excluded_files, disk_size, pack_count, pack_size, loose_objects, loose_size,
+               analyzed_at
+        FROM repository_snapshots
+        WHERE project_id = $1
+        ORDER BY committed_at DESC, analyzed_at DESC
+        LIMIT 1
+        "#,
+        project_id
+    )
+    .fetch_optional(pool)
+    .await
+}
+
+/// Últimas `limit` composiciones de un proyecto, de la más antigua a la más reciente
+///
+/// # Errors
+/// Retorna error si falla la consulta
+pub async fn list_snapshots(
+    pool: &PgPool,
+    project_id: i32,
+    limit: i64,
+) -> Result<Vec<RepositorySnapshot>, sqlx::Error> {
+    sqlx::query_as!(
+        RepositorySnapshot,
+        r#"
+        SELECT * FROM (
+            SELECT id, project_id, commit_hash, branch, committed_at, total_files, total_bytes,
+                   excluded_files, disk_size, pack_count, pack_size, loose_objects, loose_size,
+                   analyzed_at
+            FROM repository_snapshots
+            WHERE project_id = $1
+            ORDER BY committed_at DESC, analyzed_at DESC
+            LIMIT $2
+        ) s
+        ORDER BY committed_at, analyzed_at
+        "#,
+        project_id,
+        limit
+    )
+    .fetch_all(pool)
+    .await
+}
+
+/// Lenguajes de varias composiciones, con el id de la composición
+///
+/// # Errors
+/// Retorna error si falla la consulta
+pub async fn snapshot_languages(
+    pool: &PgPool,
+    snapshot_ids: &[i32],
+) -> Result<Vec<(i32, LanguageBytes)>, sqlx::Error> {
+    let rows = sqlx::query!(
+        r#"
+        SELECT snapshot_id, language, files, bytes
+        FROM repository_languages
+        WHERE snapshot_id = ANY($1)
+        ORDER BY snapshot_id, bytes DESC, language
+        "#,
+        snapshot_ids
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(rows
+        .into_iter()
+        .map(|r| {
+            (
+                r.snapshot_id,
+                LanguageBytes {
+                    language: r.language,
+                    files: r.files,
+                    bytes: r.bytes,
+                },
+            )
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+
+    /// Requiere `DATABASE_URL` con las migraciones aplicadas
+    #[tokio::test]
+    #[ignore]
+    async fn test_snapshots() {
+        let pool = PgPool::connect(&std::env::var("DATABASE_URL").unwrap())
+            .await
+            .unwrap();
+        let project_id: i32 = sqlx::query_scalar(
+            "INSERT INTO projects (name, repository_path) VALUES ('Snapshots', '/tmp/snapshots') RETURNING id",
+        )
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+
+        let snapshot = |hash: &str, day: u32, rust: i64| CreateRepositorySnapshot {
+            project_id,
+            commit_hash: hash.to_string(),
+            branch: "main".to_string(),
+            committed_at: NaiveDate::from_ymd_opt(2025, 1, day)
+                .unwrap()
+                .and_hms_opt(0, 0, 0)
+                .unwrap(),
+            total_files: 2,
+            total_bytes: rust + 10,
+            excluded_files: 0,
+            disk_size: 4096,
+            pack_count: 1,
+            pack_size: 2048,
+            loose_objects: 0,
+            loose_size: 0,
+            languages: vec![
+                LanguageBytes {
+                    language: "Rust".to_string(),
+                    files: 1,
+                    bytes: rust,
+                },
+                LanguageBytes {
+                    language: "TOML".to_string(),
+                    files: 1,
+                    bytes: 10,
+                },
+            ],
+        };
+
+        let first = insert_snapshot(&pool, &snapshot("a1", 2, 100))
+            .await
+            .unwrap();
+        let duplicate = insert_snapshot(&pool, &snapshot("a1", 2, 100))
+            .await
+            .unwrap();
+        insert_snapshot(&pool, &snapshot("b2", 3, 150))
+            .await
+            .unwrap();
+        let exists = snapshot_exists(&pool, project_id, "a1").await.unwrap();
+        let latest = latest_snapshot(&pool, project_id).await.unwrap().unwrap();
+        let history = list_snapshots(&pool, project_id, 10).await.unwrap();
+        let ids: Vec<i32> = history.iter().map(|s| s.id).collect();
+        let languages = snapshot_languages(&pool, &ids).await.unwrap();
+
+        sqlx::query("DELETE FROM projects WHERE id = $1")
+            .bind(project_id)
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        assert!(first.is_some());
+        assert!(duplicate.is_none());
+        assert!(exists);
+        assert_eq!(latest.commit_hash, "b2");
+        assert_eq!(ids.len(), 2);
+        assert_eq!(history[0].commit_hash, "a1");
+        assert_eq!(languages.len(), 4);
+        assert_eq!(languages[0].1.language, "Rust");
+    }
+}
@@ -0,0 +1,90 @@
+use crate::models::{CredentialKind, ProjectCredential};
+use sqlx::PgPool;
+
+/// Busca la credencial de un proyecto
+///
+/// # Errors
+/// Retorna error si falla la consulta
+pub async fn find_credential(
+    pool: &PgPool,
+    project_id: i32,
+) -> Result<Option<ProjectCredential>, sqlx::Error> {
+    sqlx::query_as!(
+        ProjectCredential,
+        r#"
+        SELECT
+            project_id,
+            kind as "kind: CredentialKind",
+            username,
+            secret_encrypted,
+            created_by,
+            created_at as "created_at!",
+            updated_at as "updated_at!"
+        FROM project_credentials
+        WHERE project_id = $1
+        "#,
+        project_id
+    )
+    .fetch_optional(pool)
+    .await
+}
+
+/// Crea o reemplaza la credencial de un proyecto
+///
+/// `secret_encrypted` debe venir ya cifrado por el almacén de credenciales.
+///
+/// # Errors
+/// Retorna error si falla la inserción (p. ej. el proyecto no existe)
+pub async fn upsert_credential(
+    pool: &PgPool,
+    project_id: i32,
+    kind: CredentialKind,
+    username: Option<&str>,
+    secret_encrypted: &[u8],
+    created_by: i32,
+) -> Result<ProjectCredential, sqlx::Error> {
+    sqlx::query_as!(
+        ProjectCredential,
+        r#"
+        INSERT INTO project_credentials (project_id, kind, username, secret_encrypted, created_by)
+        VALUES ($1, $2, $3, $4, $5)
+        ON CONFLICT (project_id) DO UPDATE
+        SET kind = EXCLUDED.kind,
+            username = EXCLUDED.username,
+            secret_encrypted = EXCLUDED.secret_encrypted,
+            created_by = EXCLUDED.created_by
+        RETURNING
+            project_id,
+            kind as "kind: CredentialKind",
+            username,
+            secret_encrypted,
+            created_by,
+            created_at as "created_at!",
+            updated_at as "updated_at!"
+        "#,
+        project_id,
+        kind as CredentialKind,
+        username,
+        secret_encrypted,
+        created_by
+    )
+    .fetch_one(pool)
+    .await
+}
+
+/// Elimina la credencial de un proyecto
+///
+/// Retorna `false` si el proyecto no tenía credencial.
+///
+/// # Errors
+/// Retorna error si falla la consulta
+pub async fn delete_credential(pool: &PgPool, project_id: i32) -> Result<bool, sqlx::Error> {
+    let result = sqlx::query!(
+        "DELETE FROM project_credentials WHERE project_id = $1",
+        project_id
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(result.rows_affected() > 0)
+}
@@ -0,0 +1,181 @@
+use crate::models::{
+    CreateRepositoryHealthCheck, HealthIssue, HealthStatus, RepositoryHealthCheck,
+    RepositoryHealthSummary,
+};
+use sqlx::types::Json;
+use sqlx::PgPool;
+
+/// Revisiones que se conservan por proyecto; las más antiguas se borran al guardar
+const KEEP_CHECKS_PER_PROJECT: i64 = 200;
+
+/// Guarda una revisión de salud y descarta las más antiguas del proyecto
+///
+/// # Errors
+/// Retorna error si falla la inserción
+pub async fn insert_health_check(
+    pool: &PgPool,
+    check: CreateRepositoryHealthCheck,
+) -> Result<RepositoryHealthCheck, sqlx::Error> {
+    let mut tx = pool.begin().await?;
+
+    let id = sqlx::query_scalar!(
+        r#"
+        INSERT INTO repository_health_checks
+            (project_id, status, integrity_ok, integrity_complete, objects_checked,
+             unreachable_objects, loose_objects, loose_size, pack_count, pack_size, is_bare,
+             dirty_files, detached_head, unmerged_files, issues, triggered_by, duration_ms)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, $17)
+        RETURNING id
+        "#,
+        check.project_id,
+        check.status as HealthStatus,
+        check.integrity_ok,
+        check.integrity_complete,
+        check.objects_checked,
+        check.unreachable_objects,
+        check.loose_objects,
+        check.loose_size,
+        check.pack_count,
+        check.pack_size,
+        check.is_bare,
+        check.dirty_files,
+        check.detached_head,
+        &check.unmerged_files,
+        Json(&check.issues) as _,
+        check.triggered_by,
+        check.duration_ms
+    )
+    .fetch_one(&mut *tx)
+    .await?;
+
+    sqlx::query!(
+        r#"
+        DELETE FROM repository_health_checks
+        WHERE project_id = $1
+          AND id NOT IN (
+              SELECT id FROM repository_health_checks
+              WHERE project_id = $1
+              ORDER BY checked_at DESC, id DESC
+              LIMIT $2
+          )
+        "#,
+        check.project_id,
+        KEEP_CHECKS_PER_PROJECT
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+
+    Ok(find_health_checks(pool, check.project_id, Some(id), 1)
+        .await?
+        .remove(0))
+}
+
+/// Últimas `limit` revisiones de un proyecto, de la más reciente a la más antigua
+///
+/// # Errors
+/// Retorna error si falla la consulta
+pub async fn list_health_checks(
+    pool: &PgPool,
+    project_id: i32,
+    limit: i64,
+) -> Result<Vec<RepositoryHealthCheck>, sqlx::Error> {
+    find_health_checks(pool, project_id, None, limit).await
+}
+
+/// Última revisión de un proyecto
+///
+/// # Errors
+/// Retorna error si falla la consulta
+pub async fn latest_health_check(
+    pool: &PgPool,
+    project_id: i32,
+) -> Result<Option<RepositoryHealthCheck>, sqlx::Error> {
+    Ok(find_health_checks(pool, project_id, None, 1)
+        .await?
+        .into_iter()
+        .next())
+}
+
+/// Cuenta los proyectos visibles para un usuario según el estado de su última revisión
+///
+/// Mismo criterio de visibilidad que [`crate::db::projects::list_visible_projects`].
+///
+/// # Errors
+/// Retorna error si falla la consulta
+pub async fn health_summary(
+    pool: &PgPool,
+    user_id: Option<i32>,
+    is_admin: bool,
+) -> Result<RepositoryHealthSummary, sqlx::Error> {
+    sqlx::query_as!(
+        RepositoryHealthSummary,
+        r#"
+        SELECT
+            COUNT(*) FILTER (WHERE h.status = 'healthy') as "healthy!",
+            COUNT(*) FILTER (WHERE h.status = 'warning') as "warning!",
+            COUNT(*) FILTER (WHERE h.status = 'failing') as "failing!",
+            COUNT(*) FILTER (WHERE h.status IS NULL) as "unchecked!"
+        FROM projects p
+        LEFT JOIN LATERAL (
+            SELECT status FROM repository_health_checks
+            WHERE project_id = p.id
+            ORDER BY checked_at DESC, id DESC
+            LIMIT 1
+        ) h ON true
+        WHERE COALESCE(p.is_public, FALSE)
+            OR $2
+            OR EXISTS (SELECT 1 FROM project_members m WHERE m.project_id = p.id AND m.user_id = $1)
+        "#,
+        user_id,
+        is_admin
+    )
+    .fetch_one(pool)
+    .await
+}
+
+/// Revisiones de un proyecto, o solo la indicada si hay `id`
+async fn find_health_checks(
+    pool: &PgPool,
+    project_id: i32,
+    id: Option<i32>,
+    limit: i64,
+) -> Result<Vec<RepositoryHealthCheck>, sqlx::Error> {
+    sqlx::query_as!(
+        RepositoryHealthCheck,
+        r#"
+        SELECT
+            h.id,
+            h.project_id,
+            h.status as "status: HealthStatus",
+            h.integrity_ok,
+            h.integrity_complete,
+            h.objects_checked,
+            h.unreachable_objects,
+            h.loose_objects,
+            h.loose_size,
+            h.pack_count,
+            h.pack_size,
+            h.is_bare,
+            h.dirty_files,
+            h.detached_head,
+            h.unmerged_files,
+            h.issues as "issues: Json<Vec<HealthIssue>>",
+            h.triggered_by,
+            u.username as "triggered_by_username?",
+            h.duration_ms,
+            h.checked_at
+        FROM repository_health_checks h
+        LEFT JOIN users u ON u.id = h.triggered_by
+        WHERE h.project_id = $1 AND ($2::int4 IS NULL OR h.id = $2)
+        ORDER BY h.checked_at DESC, h.id DESC
+        LIMIT $3
+        "#,
+        project_id,
+        id,
+        limit
+    )
+    .fetch_all(pool)
+    .await
+}
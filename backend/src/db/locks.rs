@@ -0,0 +1,355 @@
+use crate::models::{LockAction, LockHistoryEntry, LockRequest, LockRequestStatus, LockStatus};
+use sqlx::{PgConnection, PgExecutor, PgPool};
+
+/// Errores relacionados con el bloqueo de proyectos
+#[derive(Debug, thiserror::Error)]
+pub enum LockError {
+    #[error("Proyecto no encontrado")]
+    ProjectNotFound,
+
+    #[error("Solicitud de bloqueo no encontrada")]
+    RequestNotFound,
+
+    #[error("El proyecto ya está bloqueado")]
+    AlreadyLocked,
+
+    #[error("El proyecto no está bloqueado")]
+    NotLocked,
+
+    #[error("Ya tienes una solicitud de bloqueo pendiente para este proyecto")]
+    RequestAlreadyPending,
+
+    #[error("La solicitud ya fue revisada")]
+    RequestAlreadyReviewed,
+
+    #[error("Error de base de datos: {0}")]
+    Database(#[from] sqlx::Error),
+}
+
+pub type Result<T> = std::result::Result<T, LockError>;
+
+/// Obtiene el estado de bloqueo de un proyecto
+///
+/// # Errors
+/// - `LockError::ProjectNotFound` si el proyecto no existe
+/// - `LockError::Database` si hay un error de base de datos
+pub async fn find_lock_status<'e>(
+    executor: impl PgExecutor<'e>,
+    project_id: i32,
+) -> Result<LockStatus> {
+    sqlx::query_as!(
+        LockStatus,
+        r#"
+        SELECT
+            p.id as project_id,
+            p.is_blocked,
+            p.lock_reason as reason,
+            p.locked_by,
+            u.username as "locked_by_username?",
+            p.locked_at
+        FROM projects p
+        LEFT JOIN users u ON u.id = p.locked_by
+        WHERE p.id = $1
+        "#,
+        project_id
+    )
+    .fetch_optional(executor)
+    .await?
+    .ok_or(LockError::ProjectNotFound)
+}
+
+/// Bloquea un proyecto y lo registra en el historial
+///
+/// Pensada para ejecutarse dentro de una transacción junto con la
+/// actualización del marcador en el repositorio.
+///
+/// # Errors
+/// - `LockError::ProjectNotFound` si el proyecto no existe
+/// - `LockError::AlreadyLocked` si el proyecto ya estaba bloqueado
+/// - `LockError::Database` si hay un error de base de datos
+pub async fn lock_project(
+    conn: &mut PgConnection,
+    project_id: i32,
+    actor_id: i32,
+    reason: &str,
+    request_id: Option<i32>,
+) -> Result<LockStatus> {
+    let updated = sqlx::query!(
+        r#"
+        UPDATE projects
+        SET is_blocked = TRUE, lock_reason = $2, locked_by = $3, locked_at = NOW()
+        WHERE id = $1 AND is_blocked = FALSE
+        "#,
+        project_id,
+        reason,
+        actor_id
+    )
+    .execute(&mut *conn)
+    .await?;
+
+    if updated.rows_affected() == 0 {
+        find_lock_status(&mut *conn, project_id).await?;
+        return Err(LockError::AlreadyLocked);
+    }
+
+    insert_history(
+        &mut *conn,
+        project_id,
+        LockAction::Locked,
+        Some(reason),
+        actor_id,
+        request_id,
+    )
+    .await?;
+    find_lock_status(&mut *conn, project_id).await
+}
+
+/// Desbloquea un proyecto y lo registra en el historial
+///
+/// # Errors
+/// - `LockError::ProjectNotFound` si el proyecto no existe
+/// - `LockError::NotLocked` si el proyecto no estaba bloqueado
+/// - `LockError::Database` si hay un error de base de datos
+pub async fn unlock_project(
+    conn: &mut PgConnection,
+    project_id: i32,
+    actor_id: i32,
+    reason: Option<&str>,
+) -> Result<LockStatus> {
+    let updated = sqlx::query!(
+        r#"
+        UPDATE projects
+        SET is_blocked = FALSE, lock_reason = NULL, locked_by = NULL, locked_at = NULL
+        WHERE id = $1 AND is_blocked = TRUE
+        "#,
+        project_id
+    )
+    .execute(&mut *conn)
+    .await?;
+
+    if updated.rows_affected() == 0 {
+        find_lock_status(&mut *conn, project_id).await?;
+        return Err(LockError::NotLocked);
+    }
+
+    insert_history(
+        &mut *conn,
+        project_id,
+        LockAction::Unlocked,
+        reason,
+        actor_id,
+        None,
+    )
+    .await?;
+    find_lock_status(&mut *conn, project_id).await
+}
+
+async fn insert_history(
+    conn: &mut PgConnection,
+    project_id: i32,
+    action: LockAction,
+    reason: Option<&str>,
+    actor_id: i32,
+    request_id: Option<i32>,
+) -> Result<()> {
+    sqlx::query!(
+        r#"
+        INSERT INTO project_lock_history (project_id, action, reason, actor_id, request_id)
+        VALUES ($1, $2, $3, $4, $5)
+        "#,
+        project_id,
+        action as LockAction,
+        reason,
+        actor_id,
+        request_id
+    )
+    .execute(conn)
+    .await?;
+
+    Ok(())
+}
+
+/// Lista el historial de bloqueos de un proyecto, del más reciente al más antiguo
+///
+/// # Errors
+/// - `LockError::Database` si hay un error de base de datos
+pub async fn list_lock_history(
+    pool: &PgPool,
+    project_id: i32,
+    limit: i64,
+) -> Result<Vec<LockHistoryEntry>> {
+    let history = sqlx::query_as!(
+        LockHistoryEntry,
+        r#"
+        SELECT
+            h.id,
+            h.project_id,
+            h.action as "action: LockAction",
+            h.reason,
+            h.actor_id,
+            u.username as "actor_username?",
+            h.request_id,
+            h.created_at as "created_at!"
+        FROM project_lock_history h
+        LEFT JOIN users u ON u.id = h.actor_id
+        WHERE h.project_id = $1
+        ORDER BY h.created_at DESC, h.id DESC
+        LIMIT $2
+        "#,
+        project_id,
+        limit
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(history)
+}
+
+/// Crea una solicitud de bloqueo pendiente de aprobación
+///
+/// # Errors
+/// - `LockError::ProjectNotFound` si el proyecto no existe
+/// - `LockError::AlreadyLocked` si el proyecto ya está bloqueado
+/// - `LockError::RequestAlreadyPending` si el usuario ya tiene una solicitud pendiente
+/// - `LockError::Database` si hay un error de base de datos
+pub async fn create_lock_request(
+    pool: &PgPool,
+    project_id: i32,
+    user_id: i32,
+    reason: &str,
+) -> Result<LockRequest> {
+    if find_lock_status(pool, project_id).await?.is_blocked {
+        return Err(LockError::AlreadyLocked);
+    }
+
+    let result = sqlx::query_scalar!(
+        r#"
+        INSERT INTO project_lock_requests (project_id, requested_by, reason)
+        VALUES ($1, $2, $3)
+        RETURNING id
+        "#,
+        project_id,
+        user_id,
+        reason
+    )
+    .fetch_one(pool)
+    .await;
+
+    match result {
+        Ok(id) => find_lock_request(pool, project_id, id).await,
+        Err(sqlx::Error::Database(db_err)) if db_err.is_unique_violation() => {
+            Err(LockError::RequestAlreadyPending)
+        }
+        Err(e) => Err(LockError::Database(e)),
+    }
+}
+
+/// Busca una solicitud de bloqueo de un proyecto
+///
+/// # Errors
+/// - `LockError::RequestNotFound` si la solicitud no existe en ese proyecto
+/// - `LockError::Database` si hay un error de base de datos
+pub async fn find_lock_request<'e>(
+    executor: impl PgExecutor<'e>,
+    project_id: i32,
+    id: i32,
+) -> Result<LockRequest> {
+    sqlx::query_as!(
+        LockRequest,
+        r#"
+        SELECT
+            r.id,
+            r.project_id,
+            r.requested_by,
+            u.username as "requested_by_username?",
+            r.reason,
+            r.status as "status: LockRequestStatus",
+            r.reviewed_by,
+            r.review_comment,
+            r.created_at as "created_at!",
+            r.reviewed_at
+        FROM project_lock_requests r
+        LEFT JOIN users u ON u.id = r.requested_by
+        WHERE r.project_id = $1 AND r.id = $2
+        "#,
+        project_id,
+        id
+    )
+    .fetch_optional(executor)
+    .await?
+    .ok_or(LockError::RequestNotFound)
+}
+
+/// Lista las solicitudes de bloqueo de un proyecto, opcionalmente filtradas por estado
+///
+/// # Errors
+/// - `LockError::Database` si hay un error de base de datos
+pub async fn list_lock_requests(
+    pool: &PgPool,
+    project_id: i32,
+    status: Option<LockRequestStatus>,
+) -> Result<Vec<LockRequest>> {
+    let requests = sqlx::query_as!(
+        LockRequest,
+        r#"
+        SELECT
+            r.id,
+            r.project_id,
+            r.requested_by,
+            u.username as "requested_by_username?",
+            r.reason,
+            r.status as "status: LockRequestStatus",
+            r.reviewed_by,
+            r.review_comment,
+            r.created_at as "created_at!",
+            r.reviewed_at
+        FROM project_lock_requests r
+        LEFT JOIN users u ON u.id = r.requested_by
+        WHERE r.project_id = $1 AND ($2::varchar IS NULL OR r.status = $2)
+        ORDER BY r.created_at DESC, r.id DESC
+        "#,
+        project_id,
+        status as Option<LockRequestStatus>
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(requests)
+}
+
+/// Marca una solicitud pendiente como aprobada o rechazada
+///
+/// # Errors
+/// - `LockError::RequestNotFound` si la solicitud no existe en ese proyecto
+/// - `LockError::RequestAlreadyReviewed` si la solicitud no estaba pendiente
+/// - `LockError::Database` si hay un error de base de datos
+pub async fn review_lock_request(
+    conn: &mut PgConnection,
+    project_id: i32,
+    id: i32,
+    reviewer_id: i32,
+    status: LockRequestStatus,
+    comment: Option<&str>,
+) -> Result<LockRequest> {
+    let updated = sqlx::query!(
+        r#"
+        UPDATE project_lock_requests
+        SET status = $3, reviewed_by = $4, review_comment = $5, reviewed_at = NOW()
+        WHERE project_id = $1 AND id = $2 AND status = 'pending'
+        "#,
+        project_id,
+        id,
+        status as LockRequestStatus,
+        reviewer_id,
+        comment
+    )
+    .execute(&mut *conn)
+    .await?;
+
+    if updated.rows_affected() == 0 {
+        find_lock_request(&mut *conn, project_id, id).await?;
+        return Err(LockError::RequestAlreadyReviewed);
+    }
+
+    find_lock_request(&mut *conn, project_id, id).await
+}
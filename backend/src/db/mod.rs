@@ -1,3 +1,18 @@
+pub mod audit;
+pub mod comments;
+pub mod commits;
+pub mod composition;
+pub mod credentials;
+pub mod health;
+pub mod locks;
+pub mod notifications;
+pub mod owners;
+pub mod projects;
+pub mod pulls;
+pub mod search;
+pub mod signing_keys;
+pub mod stats;
+pub mod sync;
 pub mod users;
 
 use sqlx::{postgres::PgPoolOptions, PgPool};
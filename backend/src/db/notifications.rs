@@ -0,0 +1,344 @@
+use crate::models::{CreateNotification, Notification, NotificationType};
+use sqlx::{PgExecutor, PgPool};
+
+/// Filtros del listado de notificaciones
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NotificationFilter {
+    pub kind: Option<NotificationType>,
+    pub project_id: Option<i32>,
+    /// Solo las no leídas
+    pub unread_only: bool,
+}
+
+/// Crea la misma notificación para varios usuarios
+///
+/// # Errors
+/// Retorna error si falla la inserción
+pub async fn insert_notifications<'e>(
+    executor: impl PgExecutor<'e>,
+    user_ids: &[i32],
+    notification: &CreateNotification,
+) -> Result<Vec<Notification>, sqlx::Error> {
+    sqlx::query_as!(
+        Notification,
+        r#"
+        INSERT INTO notifications (user_id, project_id, type, title, message, link)
+        SELECT user_id, $2, $3, $4, $5, $6 FROM unnest($1::int4[]) as user_id
+        RETURNING
+            id,
+            user_id,
+            project_id,
+            type as "kind: NotificationType",
+            title,
+            message,
+            link,
+            is_read,
+            created_at
+        "#,
+        user_ids,
+        notification.project_id,
+        notification.kind as NotificationType,
+        notification.title,
+        notification.message,
+        notification.link
+    )
+    .fetch_all(executor)
+    .await
+}
+
+/// Notificaciones de un usuario, de la más reciente a la más antigua
+///
+/// Con `before` se listan solo las anteriores a esa notificación.
+///
+/// # Errors
+/// Retorna error si falla la consulta
+pub async fn list_notifications(
+    pool: &PgPool,
+    user_id: i32,
+    filter: NotificationFilter,
+    before: Option<i32>,
+    limit: i64,
+) -> Result<Vec<Notification>, sqlx::Error> {
+    sqlx::query_as!(
+        Notification,
+        r#"
+        SELECT
+            id,
+            user_id,
+            project_id,
+            type as "kind: NotificationType",
+            title,
+            message,
+            link,
+            is_read,
+            created_at
+        FROM notifications
+        WHERE user_id = $1
+          AND ($2::int4 IS NULL OR id < $2)
+          AND ($3::varchar IS NULL OR type = $3)
+          AND ($4::int4 IS NULL OR project_id = $4)
+          AND (NOT $5 OR is_read = FALSE)
+        ORDER BY id DESC
+        LIMIT $6
+        "#,
+        user_id,
+        before,
+        filter.kind as Option<NotificationType>,
+        filter.project_id,
+        filter.unread_only,
+        limit
+    )
+    .fetch_all(pool)
+    .await
+}
+
+/// Número de notificaciones sin leer de un usuario
+///
+/// # Errors
+/// Retorna error si falla la consulta
+pub async fn count_unread_notifications(pool: &PgPool, user_id: i32) -> Result<i64, sqlx::Error> {
+    sqlx::query_scalar!(
+        r#"
+        SELECT COUNT(*) as "count!"
+        FROM notifications
+        WHERE user_id = $1 AND is_read = FALSE
+        "#,
+        user_id
+    )
+    .fetch_one(pool)
+    .await
+}
+
+/// Marca como leída una notificación del usuario
+///
+/// Retorna `None` si no existe o es de otro usuario.
+///
+/// # Errors
+/// Retorna error si falla la actualización
+pub async fn mark_notification_read(
+    pool: &PgPool,
+    user_id: i32,
+    id: i32,
+) -> Result<Option<Notification>, sqlx::Error> {
+    sqlx::query_as!(
+        Notification,
+        r#"
+        UPDATE notifications SET is_read = TRUE
+        WHERE id = $1 AND user_id = $2
+        RETURNING
+            id,
+            user_id,
+            project_id,
+            type as "kind: NotificationType",
+            title,
+            message,
+            link,
+            is_read,
+            created_at
+        "#,
+        id,
+        user_id
+    )
+    .fetch_optional(pool)
+    .await
+}
+
+/// Marca como leídas todas las notificaciones del usuario
+///
+/// Retorna cuántas estaban sin leer.
+///
+/// # Errors
+/// Retorna error si falla la actualización
+pub async fn mark_all_notifications_read(pool: &PgPool, user_id: i32) -> Result<u64, sqlx::Error> {
+    let updated = sqlx::query!(
+        "UPDATE notifications SET is_read = TRUE WHERE user_id = $1 AND is_read = FALSE",
+        user_id
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(updated.rows_affected())
+}
+
+/// Borra una notificación del usuario
+///
+/// Retorna `false` si no existe o es de otro usuario.
+///
+/// # Errors
+/// Retorna error si falla el borrado
+pub async fn delete_notification(
+    pool: &PgPool,
+    user_id: i32,
+    id: i32,
+) -> Result<bool, sqlx::Error> {
+    let deleted = sqlx::query!(
+        "DELETE FROM notifications WHERE id = $1 AND user_id = $2",
+        id,
+        user_id
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(deleted.rows_affected() > 0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Requiere `DATABASE_URL` con las migraciones aplicadas
+    #[tokio::test]
+    #[ignore]
+    async fn test_batch_insert_and_pagination() {
+        let pool = PgPool::connect(&std::env::var("DATABASE_URL").unwrap())
+            .await
+            .unwrap();
+        let project_id: i32 = sqlx::query_scalar(
+            "INSERT INTO projects (name, repository_path) VALUES ('Notifications', '/tmp/notifications') RETURNING id",
+        )
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+        let mut users = Vec::new();
+        for name in ["notifications_ana", "notifications_bob"] {
+            let id: i32 = sqlx::query_scalar(
+                "INSERT INTO users (username, email, password_hash) VALUES ($1, $1 || '@example.com', 'x') RETURNING id",
+            )
+            .bind(name)
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+            users.push(id);
+        }
+        let (ana, bob) = (users[0], users[1]);
+
+        let notification = |project_id, kind, title: &str| CreateNotification {
+            project_id,
+            kind,
+            title: title.to_string(),
+            message: String::new(),
+            link: None,
+        };
+        let batch = insert_notifications(
+            &pool,
+            &users,
+            &notification(
+                Some(project_id),
+                NotificationType::Lock,
+                "Proyecto bloqueado",
+            ),
+        )
+        .await
+        .unwrap();
+        insert_notifications(
+            &pool,
+            &[ana],
+            &notification(None, NotificationType::Mention, "Te mencionaron"),
+        )
+        .await
+        .unwrap();
+        insert_notifications(
+            &pool,
+            &[ana],
+            &notification(
+                Some(project_id),
+                NotificationType::WorkingCopy,
+                "Cambios sin commitear",
+            ),
+        )
+        .await
+        .unwrap();
+        let empty = insert_notifications(
+            &pool,
+            &[],
+            &notification(None, NotificationType::Comment, "-"),
+        )
+        .await
+        .unwrap();
+
+        let all = NotificationFilter::default();
+        let first_page = list_notifications(&pool, ana, all, None, 2).await.unwrap();
+        let second_page = list_notifications(&pool, ana, all, Some(first_page[1].id), 2)
+            .await
+            .unwrap();
+        let locks = NotificationFilter {
+            kind: Some(NotificationType::Lock),
+            ..all
+        };
+        let by_kind = list_notifications(&pool, ana, locks, None, 10)
+            .await
+            .unwrap();
+        let in_project = NotificationFilter {
+            project_id: Some(project_id),
+            ..all
+        };
+        let by_project = list_notifications(&pool, ana, in_project, None, 10)
+            .await
+            .unwrap();
+
+        let foreign = mark_notification_read(&pool, bob, first_page[0].id)
+            .await
+            .unwrap();
+        let read = mark_notification_read(&pool, ana, first_page[0].id)
+            .await
+            .unwrap();
+        let unread = NotificationFilter {
+            unread_only: true,
+            ..all
+        };
+        let unread_page = list_notifications(&pool, ana, unread, None, 10)
+            .await
+            .unwrap();
+        let unread_count = count_unread_notifications(&pool, ana).await.unwrap();
+        let marked = mark_all_notifications_read(&pool, ana).await.unwrap();
+        let deleted = delete_notification(&pool, ana, second_page[0].id)
+            .await
+            .unwrap();
+        let deleted_foreign = delete_notification(&pool, bob, first_page[1].id)
+            .await
+            .unwrap();
+        let remaining = list_notifications(&pool, ana, all, None, 10).await.unwrap();
+        let bob_notifications = list_notifications(&pool, bob, all, None, 10).await.unwrap();
+
+        sqlx::query("DELETE FROM projects WHERE id = $1")
+            .bind(project_id)
+            .execute(&pool)
+            .await
+            .unwrap();
+        sqlx::query("DELETE FROM users WHERE id = ANY($1)")
+            .bind(&users)
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        // Una fila por destinatario con los mismos datos
+        let recipients: Vec<i32> = batch.iter().map(|n| n.user_id).collect();
+        assert_eq!(recipients, users);
+        assert!(batch
+            .iter()
+            .all(|n| n.kind == NotificationType::Lock && !n.is_read));
+        assert!(empty.is_empty());
+
+        // Páginas de la más reciente a la más antigua, sin solaparse
+        let titles =
+            |page: &[Notification]| page.iter().map(|n| n.title.clone()).collect::<Vec<_>>();
+        assert_eq!(
+            titles(&first_page),
+            vec!["Cambios sin commitear", "Te mencionaron"]
+        );
+        assert_eq!(titles(&second_page), vec!["Proyecto bloqueado"]);
+        assert_eq!(first_page[0].kind, NotificationType::WorkingCopy);
+        assert_eq!(titles(&by_kind), vec!["Proyecto bloqueado"]);
+        assert_eq!(by_project.len(), 2);
+
+        assert!(foreign.is_none());
+        assert!(read.unwrap().is_read);
+        assert_eq!(unread_page.len(), 2);
+        assert_eq!(unread_count, 2);
+        assert_eq!(marked, 2);
+        assert!(deleted);
+        assert!(!deleted_foreign);
+        assert_eq!(remaining.len(), 2);
+        assert_eq!(bob_notifications.len(), 1);
+    }
+}
@@ -0,0 +1,142 @@
+use crate::models::{CodeOwner, OwnerMapping, OwnerUser};
+use sqlx::PgPool;
+
+/// Resuelve dueños de `CODEOWNERS` a usuarios y usuarios de Telegram
+///
+/// Se usa el mapeo explícito si existe; si no, `@usuario` se busca por
+/// nombre de usuario y un email por el email de la cuenta, siempre entre
+/// usuarios activos. El resultado conserva el orden de `owners`.
+///
+/// # Errors
+/// Retorna error si falla la consulta
+pub async fn resolve_owners(
+    pool: &PgPool,
+    owners: &[String],
+) -> Result<Vec<CodeOwner>, sqlx::Error> {
+    if owners.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let rows = sqlx::query!(
+        r#"
+        SELECT
+            o.owner as "owner!",
+            u.id as "user_id?",
+            u.username as "username?",
+            m.telegram_handle
+        FROM UNNEST($1::text[]) WITH ORDINALITY AS o(owner, position)
+        LEFT JOIN owner_mappings m ON LOWER(m.owner) = LOWER(o.owner)
+        LEFT JOIN users u ON u.is_active = TRUE AND (
+            CASE
+                WHEN m.id IS NOT NULL THEN u.id = m.user_id
+                WHEN o.owner LIKE '@%' THEN LOWER(u.username) = LOWER(SUBSTRING(o.owner FROM 2))
+                ELSE LOWER(u.email) = LOWER(o.owner)
+            END
+        )
+        ORDER BY o.position
+        "#,
+        owners
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(rows
+        .into_iter()
+        .map(|row| CodeOwner {
+            owner: row.owner,
+            user: row
+                .user_id
+                .zip(row.username)
+                .map(|(id, username)| OwnerUser { id, username }),
+            telegram_handle: row.telegram_handle,
+        })
+        .collect())
+}
+
+/// Lista los mapeos de dueños con el nombre del usuario asociado
+///
+/// # Errors
+/// Retorna error si falla la consulta
+pub async fn list_owner_mappings(pool: &PgPool) -> Result<Vec<OwnerMapping>, sqlx::Error> {
+    sqlx::query_as!(
+        OwnerMapping,
+        r#"
+        SELECT
+            m.id,
+            m.owner,
+            m.user_id,
+            u.username as "username?",
+            m.telegram_handle,
+            m.created_by,
+            m.created_at,
+            m.updated_at
+        FROM owner_mappings m
+        LEFT JOIN users u ON u.id = m.user_id
+        ORDER BY LOWER(m.owner)
+        "#
+    )
+    .fetch_all(pool)
+    .await
+}
+
+/// Crea o reemplaza el mapeo de un dueño
+///
+/// El dueño se compara sin distinguir mayúsculas; si ya existía se
+/// sustituyen el usuario y el usuario de Telegram.
+///
+/// # Errors
+/// Retorna error si falla la consulta; una violación de clave foránea
+/// indica que el usuario no existe
+pub async fn upsert_owner_mapping(
+    pool: &PgPool,
+    owner: &str,
+    user_id: Option<i32>,
+    telegram_handle: Option<&str>,
+    created_by: i32,
+) -> Result<OwnerMapping, sqlx::Error> {
+    sqlx::query_as!(
+        OwnerMapping,
+        r#"
+        WITH saved AS (
+            INSERT INTO owner_mappings (owner, user_id, telegram_handle, created_by)
+            VALUES ($1, $2, $3, $4)
+            ON CONFLICT (LOWER(owner)) DO UPDATE
+                SET owner = EXCLUDED.owner,
+                    user_id = EXCLUDED.user_id,
+                    telegram_handle = EXCLUDED.telegram_handle
+            RETURNING *
+        )
+        SELECT
+            s.id as "id!",
+            s.owner as "owner!",
+            s.user_id,
+            u.username as "username?",
+            s.telegram_handle,
+            s.created_by,
+            s.created_at as "created_at!",
+            s.updated_at as "updated_at!"
+        FROM saved s
+        LEFT JOIN users u ON u.id = s.user_id
+        "#,
+        owner,
+        user_id,
+        telegram_handle,
+        created_by
+    )
+    .fetch_one(pool)
+    .await
+}
+
+/// Elimina el mapeo de un dueño
+///
+/// Retorna `false` si el mapeo no existía.
+///
+/// # Errors
+/// Retorna error si falla la consulta
+pub async fn delete_owner_mapping(pool: &PgPool, id: i32) -> Result<bool, sqlx::Error> {
+    let result = sqlx::query!("DELETE FROM owner_mappings WHERE id = $1", id)
+        .execute(pool)
+        .await?;
+
+    Ok(result.rows_affected() > 0)
+}
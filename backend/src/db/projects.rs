@@ -0,0 +1,418 @@
+use sqlx::PgPool;
+use crate::models::{CreateProject, Project, ProjectMember, ProjectRole};
+
+/// Errores relacionados con operaciones de proyectos
+#[derive(Debug, thiserror::Error)]
+pub enum ProjectError {
+    #[error("Proyecto no encontrado")]
+    NotFound,
+
+    #[error("Error de base de datos: {0}")]
+    Database(#[from] sqlx::Error),
+}
+
+pub type Result<T> = std::result::Result<T, ProjectError>;
+
+/// Busca un proyecto por ID
+///
+/// # Errors
+/// - `ProjectError::NotFound` si el proyecto no existe
+/// - `ProjectError::Database` si hay un error de base de datos
+pub async fn find_project_by_id(pool: &PgPool, id: i32) -> Result<Project> {
+    sqlx::query_as!(
+        Project,
+        r#"
+        SELECT
+            id,
+            name,
+            repository_path,
+            description,
+            is_public as "is_public!",
+            last_scanned_at,
+            last_pull_at,
+            is_blocked,
+            lock_reason,
+            locked_by,
+            locked_at,
+            created_at as "created_at!",
+            updated_at as "updated_at!"
+        FROM projects
+        WHERE id = $1
+        "#,
+        id
+    )
+    .fetch_optional(pool)
+    .await?
+    .ok_or(ProjectError::NotFound)
+}
+
+/// Lista todos los proyectos ordenados por ID
+///
+/// # Errors
+/// - `ProjectError::Database` si hay un error de base de datos
+pub async fn list_projects(pool: &PgPool) -> Result<Vec<Project>> {
+    let projects = sqlx::query_as!(
+        Project,
+        r#"
+        SELECT
+            id,
+            name,
+            repository_path,
+            description,
+            is_public as "is_public!",
+            last_scanned_at,
+            last_pull_at,
+            is_blocked,
+            lock_reason,
+            locked_by,
+            locked_at,
+            created_at as "created_at!",
+            updated_at as "updated_at!"
+        FROM projects
+        ORDER BY id
+        "#
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(projects)
+}
+
+/// Lista los proyectos que puede ver un usuario, ordenados por ID
+///
+/// Mismo criterio que [`can_view_project`]: públicos, aquellos de los que
+/// es miembro o todos si es administrador.
+///
+/// # Errors
+/// - `ProjectError::Database` si hay un error de base de datos
+pub async fn list_visible_projects(
+    pool: &PgPool,
+    user_id: Option<i32>,
+    is_admin: bool,
+) -> Result<Vec<Project>> {
+    let projects = sqlx::query_as!(
+        Project,
+        r#"
+        SELECT
+            p.id,
+            p.name,
+            p.repository_path,
+            p.description,
+            p.is_public as "is_public!",
+            p.last_scanned_at,
+            p.last_pull_at,
+            p.is_blocked,
+            p.lock_reason,
+            p.locked_by,
+            p.locked_at,
+            p.created_at as "created_at!",
+            p.updated_at as "updated_at!"
+        FROM projects p
+        WHERE COALESCE(p.is_public, FALSE)
+            OR $2
+            OR EXISTS (SELECT 1 FROM project_members m WHERE m.project_id = p.id AND m.user_id = $1)
+        ORDER BY p.id
+        "#,
+        user_id,
+        is_admin
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(projects)
+}
+
+/// Registra un proyecto
+///
+/// Retorna `None` si ya hay un proyecto con la misma ruta.
+///
+/// # Errors
+/// - `ProjectError::Database` si hay un error de base de datos
+pub async fn create_project(pool: &PgPool, project: CreateProject) -> Result<Option<Project>> {
+    let project = sqlx::query_as!(
+        Project,
+        r#"
+        INSERT INTO projects (name, repository_path)
+        VALUES ($1, $2)
+        ON CONFLICT (repository_path) DO NOTHING
+        RETURNING
+            id,
+            name,
+            repository_path,
+            description,
+            is_public as "is_public!",
+            last_scanned_at,
+            last_pull_at,
+            is_blocked,
+            lock_reason,
+            locked_by,
+            locked_at,
+            created_at as "created_at!",
+            updated_at as "updated_at!"
+        "#,
+        project.name,
+        project.repository_path
+    )
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(project)
+}
+
+/// Elimina un proyecto y, en cascada, todos sus datos
+///
+/// # Errors
+/// - `ProjectError::NotFound` si el proyecto no existe
+/// - `ProjectError::Database` si hay un error de base de datos
+pub async fn delete_project(pool: &PgPool, id: i32) -> Result<()> {
+    let result = sqlx::query!("DELETE FROM projects WHERE id = $1", id)
+        .execute(pool)
+        .await?;
+
+    if result.rows_affected() == 0 {
+        return Err(ProjectError::NotFound);
+    }
+
+    Ok(())
+}
+
+/// Registra que se detectó un pull y que la caché de commits está al día
+///
+/// # Errors
+/// - `ProjectError::NotFound` si el proyecto no existe
+/// - `ProjectError::Database` si hay un error de base de datos
+pub async fn update_last_pull_at(pool: &PgPool, id: i32) -> Result<()> {
+    let result = sqlx::query!(
+        r#"
+        UPDATE projects
+        SET last_pull_at = NOW(), last_scanned_at = NOW()
+        WHERE id = $1
+        "#,
+        id
+    )
+    .execute(pool)
+    .await?;
+
+    if result.rows_affected() == 0 {
+        return Err(ProjectError::NotFound);
+    }
+
+    Ok(())
+}
+
+/// Indica si un usuario puede ver un proyecto
+///
+/// Un proyecto es visible si es público, si el usuario es miembro o si es
+/// administrador. Los anónimos (`user_id = None`) solo ven proyectos públicos.
+///
+/// # Errors
+/// - `ProjectError::NotFound` si el proyecto no existe
+/// - `ProjectError::Database` si hay un error de base de datos
+pub async fn can_view_project(
+    pool: &PgPool,
+    id: i32,
+    user_id: Option<i32>,
+    is_admin: bool,
+) -> Result<bool> {
+    sqlx::query_scalar!(
+        r#"
+        SELECT (
+            COALESCE(p.is_public, FALSE)
+            OR $3
+            OR EXISTS (SELECT 1 FROM project_members m WHERE m.project_id = p.id AND m.user_id = $2)
+        ) as "visible!"
+        FROM projects p
+        WHERE p.id = $1
+        "#,
+        id,
+        user_id,
+        is_admin
+    )
+    .fetch_optional(pool)
+    .await?
+    .ok_or(ProjectError::NotFound)
+}
+
+/// Indica si un usuario es administrador de un proyecto (`project_members.role = 'admin'`)
+///
+/// No tiene en cuenta el rol global: los administradores globales lo son
+/// de todos los proyectos y se comprueban aparte.
+///
+/// # Errors
+/// - `ProjectError::Database` si hay un error de base de datos
+pub async fn is_project_admin(pool: &PgPool, id: i32, user_id: i32) -> Result<bool> {
+    let is_admin = sqlx::query_scalar!(
+        r#"
+        SELECT EXISTS (
+            SELECT 1 FROM project_members
+            WHERE project_id = $1 AND user_id = $2 AND role = 'admin'
+        ) as "is_admin!"
+        "#,
+        id,
+        user_id
+    )
+    .fetch_one(pool)
+    .await?;
+
+    Ok(is_admin)
+}
+
+/// Indica si un usuario puede comentar en un proyecto (`project_members.role`
+/// `contributor` o `admin`)
+///
+/// Como [`is_project_admin`], no tiene en cuenta el rol global.
+///
+/// # Errors
+/// - `ProjectError::Database` si hay un error de base de datos
+pub async fn is_project_contributor(pool: &PgPool, id: i32, user_id: i32) -> Result<bool> {
+    let is_contributor = sqlx::query_scalar!(
+        r#"
+        SELECT EXISTS (
+            SELECT 1 FROM project_members
+            WHERE project_id = $1 AND user_id = $2 AND role IN ('contributor', 'admin')
+        ) as "is_contributor!"
+        "#,
+        id,
+        user_id
+    )
+    .fetch_one(pool)
+    .await?;
+
+    Ok(is_contributor)
+}
+
+/// Usuarios activos que administran un proyecto
+///
+/// Incluye a los administradores globales y a los miembros con rol `admin`.
+///
+/// # Errors
+/// - `ProjectError::Database` si hay un error de base de datos
+pub async fn list_project_admin_ids(pool: &PgPool, id: i32) -> Result<Vec<i32>> {
+    let ids = sqlx::query_scalar!(
+        r#"
+        SELECT u.id
+        FROM users u
+        WHERE COALESCE(u.is_active, TRUE)
+          AND (
+              u.role = 'admin'
+              OR EXISTS (
+                  SELECT 1 FROM project_members m
+                  WHERE m.project_id = $1 AND m.user_id = u.id AND m.role = 'admin'
+              )
+          )
+        ORDER BY u.id
+        "#,
+        id
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(ids)
+}
+
+/// Miembros de un proyecto cuyo nombre de usuario empieza por `prefix`
+///
+/// No distingue mayúsculas. Solo incluye usuarios activos, ordenados por
+/// nombre de usuario.
+///
+/// # Errors
+/// - `ProjectError::Database` si hay un error de base de datos
+pub async fn search_project_members(
+    pool: &PgPool,
+    id: i32,
+    prefix: &str,
+    limit: i64,
+) -> Result<Vec<ProjectMember>> {
+    let members = sqlx::query_as!(
+        ProjectMember,
+        r#"
+        SELECT u.id as user_id, u.username, m.role as "role: ProjectRole"
+        FROM project_members m
+        JOIN users u ON u.id = m.user_id
+        WHERE m.project_id = $1
+          AND COALESCE(u.is_active, TRUE)
+          AND starts_with(LOWER(u.username), LOWER($2))
+        ORDER BY LOWER(u.username)
+        LIMIT $3
+        "#,
+        id,
+        prefix,
+        limit
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(members)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Requiere `DATABASE_URL` con las migraciones aplicadas
+    #[tokio::test]
+    #[ignore]
+    async fn test_only_contributors_and_admins_can_comment() {
+        let pool = PgPool::connect(&std::env::var("DATABASE_URL").unwrap())
+            .await
+            .unwrap();
+        let project_id: i32 = sqlx::query_scalar(
+            "INSERT INTO projects (name, repository_path) VALUES ('Roles', '/tmp/roles') RETURNING id",
+        )
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+        let mut users = Vec::new();
+        for name in [
+            "roles_viewer",
+            "roles_contributor",
+            "roles_admin",
+            "roles_outsider",
+        ] {
+            let id: i32 = sqlx::query_scalar(
+                "INSERT INTO users (username, email, password_hash) VALUES ($1, $1 || '@example.com', 'x') RETURNING id",
+            )
+            .bind(name)
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+            users.push(id);
+        }
+        for (user_id, role) in users.iter().zip(["viewer", "contributor", "admin"]) {
+            sqlx::query(
+                "INSERT INTO project_members (project_id, user_id, role) VALUES ($1, $2, $3)",
+            )
+            .bind(project_id)
+            .bind(user_id)
+            .bind(role)
+            .execute(&pool)
+            .await
+            .unwrap();
+        }
+
+        let mut contributors = Vec::new();
+        let mut admins = Vec::new();
+        for &user_id in &users {
+            contributors.push(
+                is_project_contributor(&pool, project_id, user_id)
+                    .await
+                    .unwrap(),
+            );
+            admins.push(is_project_admin(&pool, project_id, user_id).await.unwrap());
+        }
+
+        sqlx::query("DELETE FROM projects WHERE id = $1")
+            .bind(project_id)
+            .execute(&pool)
+            .await
+            .unwrap();
+        sqlx::query("DELETE FROM users WHERE id = ANY($1)")
+            .bind(&users)
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        assert_eq!(contributors, [false, true, true, false]);
+        assert_eq!(admins, [false, false, true, false]);
+    }
+}
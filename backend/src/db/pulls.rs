@@ -0,0 +1,59 @@
+use sqlx::PgPool;
+use crate::models::{CreatePullEvent, PullEvent};
+
+/// Registra un pull detectado en un proyecto
+///
+/// # Errors
+/// Retorna error si falla la inserción
+pub async fn create_pull_event(pool: &PgPool, event: CreatePullEvent) -> Result<PullEvent, sqlx::Error> {
+    sqlx::query_as!(
+        PullEvent,
+        r#"
+        INSERT INTO pull_events (project_id, ref_name, old_commit, new_commit, commit_count)
+        VALUES ($1, $2, $3, $4, $5)
+        RETURNING
+            id,
+            project_id,
+            ref_name,
+            old_commit,
+            new_commit,
+            commit_count,
+            detected_at as "detected_at!"
+        "#,
+        event.project_id,
+        event.ref_name,
+        event.old_commit,
+        event.new_commit,
+        event.commit_count
+    )
+    .fetch_one(pool)
+    .await
+}
+
+/// Lista los últimos pulls de un proyecto, del más reciente al más antiguo
+///
+/// # Errors
+/// Retorna error si falla la consulta
+pub async fn list_pull_events(pool: &PgPool, project_id: i32, limit: i64) -> Result<Vec<PullEvent>, sqlx::Error> {
+    sqlx::query_as!(
+        PullEvent,
+        r#"
+        SELECT
+            id,
+            project_id,
+            ref_name,
+            old_commit,
+            new_commit,
+            commit_count,
+            detected_at as "detected_at!"
+        FROM pull_events
+        WHERE project_id = $1
+        ORDER BY detected_at DESC, id DESC
+        LIMIT $2
+        "#,
+        project_id,
+        limit
+    )
+    .fetch_all(pool)
+    .await
+}
@@ -0,0 +1,161 @@
+use crate::models::{CommitSearchHit, ProjectSearchHit};
+use sqlx::PgPool;
+
+// Las consultas convierten el texto del usuario en una tsquery con el mismo
+// parser que indexa (`to_tsvector('simple', ...)`) y buscan cada término como
+// prefijo: "auth log" encuentra "authentication logger". El texto nunca se
+// interpreta como sintaxis de tsquery.
+//
+// Visibilidad: un proyecto es visible si es público, si el usuario es
+// miembro o si es administrador. Los anónimos (`user_id = None`) solo ven
+// proyectos públicos.
+
+/// Busca proyectos por nombre y descripción
+///
+/// # Errors
+/// Retorna error si falla la consulta
+pub async fn search_projects(
+    pool: &PgPool,
+    query: &str,
+    user_id: Option<i32>,
+    is_admin: bool,
+    limit: i64,
+) -> Result<Vec<ProjectSearchHit>, sqlx::Error> {
+    sqlx::query_as!(
+        ProjectSearchHit,
+        r#"
+        WITH q AS (
+            SELECT to_tsquery('simple', string_agg(quote_literal(lexeme) || ':*', ' & ')) AS query
+            FROM unnest(to_tsvector('simple', $1))
+        )
+        SELECT
+            p.id,
+            p.name,
+            p.description,
+            p.is_public as "is_public!",
+            ts_rank(p.search_vector, q.query) as "rank!"
+        FROM projects p, q
+        WHERE p.search_vector @@ q.query
+          AND (
+            COALESCE(p.is_public, FALSE)
+            OR $3
+            OR EXISTS (SELECT 1 FROM project_members m WHERE m.project_id = p.id AND m.user_id = $2)
+          )
+        ORDER BY 5 DESC, p.name
+        LIMIT $4
+        "#,
+        query,
+        user_id,
+        is_admin,
+        limit
+    )
+    .fetch_all(pool)
+    .await
+}
+
+/// Busca commits de la caché por mensaje y autor, opcionalmente en un solo proyecto
+///
+/// # Errors
+/// Retorna error si falla la consulta
+pub async fn search_commits(
+    pool: &PgPool,
+    query: &str,
+    project_id: Option<i32>,
+    user_id: Option<i32>,
+    is_admin: bool,
+    limit: i64,
+) -> Result<Vec<CommitSearchHit>, sqlx::Error> {
+    sqlx::query_as!(
+        CommitSearchHit,
+        r#"
+        WITH q AS (
+            SELECT to_tsquery('simple', string_agg(quote_literal(lexeme) || ':*', ' & ')) AS query
+            FROM unnest(to_tsvector('simple', $1))
+        )
+        SELECT
+            p.id as project_id,
+            p.name as project_name,
+            c.commit_hash as hash,
+            c.author_name,
+            c.author_email,
+            c.message,
+            c.committed_at,
+            ts_rank(c.search_vector, q.query) as "rank!"
+        FROM commits c
+        JOIN projects p ON p.id = c.project_id, q
+        WHERE c.search_vector @@ q.query
+          AND ($2::int IS NULL OR c.project_id = $2)
+          AND (
+            COALESCE(p.is_public, FALSE)
+            OR $4
+            OR EXISTS (SELECT 1 FROM project_members m WHERE m.project_id = p.id AND m.user_id = $3)
+          )
+        ORDER BY 8 DESC, c.committed_at DESC
+        LIMIT $5
+        "#,
+        query,
+        project_id,
+        user_id,
+        is_admin,
+        limit
+    )
+    .fetch_all(pool)
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Requiere `DATABASE_URL` con las migraciones aplicadas
+    #[tokio::test]
+    #[ignore]
+    async fn test_search_respects_visibility() {
+        let pool = PgPool::connect(&std::env::var("DATABASE_URL").unwrap())
+            .await
+            .unwrap();
+        let mut tx = pool.begin().await.unwrap();
+
+        let public_id: i32 = sqlx::query_scalar(
+            "INSERT INTO projects (name, repository_path, description, is_public) VALUES ('Zebra Billing', '/tmp/zb-pub', 'Facturación', TRUE) RETURNING id",
+        )
+        .fetch_one(&mut *tx)
+        .await
+        .unwrap();
+        sqlx::query("INSERT INTO projects (name, repository_path, is_public) VALUES ('Zebra Secret', '/tmp/zb-priv', FALSE)")
+            .execute(&mut *tx)
+            .await
+            .unwrap();
+        sqlx::query("INSERT INTO commits (project_id, commit_hash, author_name, author_email, message, committed_at) VALUES ($1, 'abc', 'Jane', 'jane@example.com', 'Fix zebrafish invoice rounding', NOW())")
+            .bind(public_id)
+            .execute(&mut *tx)
+            .await
+            .unwrap();
+        tx.commit().await.unwrap();
+
+        let anonymous = search_projects(&pool, "zeb", None, false, 10)
+            .await
+            .unwrap();
+        let admin = search_projects(&pool, "zeb", None, true, 10).await.unwrap();
+        let commits = search_commits(&pool, "invoice jane@example.com", None, None, false, 10)
+            .await
+            .unwrap();
+        let nothing = search_projects(&pool, " :* & !! ", None, true, 10)
+            .await
+            .unwrap();
+
+        sqlx::query(
+            "DELETE FROM projects WHERE repository_path IN ('/tmp/zb-pub', '/tmp/zb-priv')",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        assert_eq!(anonymous.len(), 1);
+        assert_eq!(anonymous[0].name, "Zebra Billing");
+        assert_eq!(admin.len(), 2);
+        assert_eq!(commits.len(), 1);
+        assert_eq!(commits[0].project_name, "Zebra Billing");
+        assert!(nothing.is_empty());
+    }
+}
@@ -0,0 +1,86 @@
+use crate::models::{SignatureKind, SigningKey};
+use sqlx::PgPool;
+
+/// Lista las claves de confianza, opcionalmente de un solo tipo
+///
+/// # Errors
+/// Retorna error si falla la consulta
+pub async fn list_signing_keys(
+    pool: &PgPool,
+    kind: Option<SignatureKind>,
+) -> Result<Vec<SigningKey>, sqlx::Error> {
+    sqlx::query_as!(
+        SigningKey,
+        r#"
+        SELECT
+            id,
+            kind as "kind: SignatureKind",
+            fingerprint,
+            principals,
+            public_key,
+            comment,
+            created_by,
+            created_at
+        FROM signing_keys
+        WHERE $1::varchar IS NULL OR kind = $1
+        ORDER BY kind, id
+        "#,
+        kind as Option<SignatureKind>
+    )
+    .fetch_all(pool)
+    .await
+}
+
+/// Guarda una clave de confianza
+///
+/// # Errors
+/// Retorna error si falla la inserción; una violación de unicidad indica
+/// que ya existe una clave con la misma huella
+pub async fn insert_signing_key(
+    pool: &PgPool,
+    kind: SignatureKind,
+    fingerprint: &str,
+    principals: &[String],
+    public_key: &str,
+    comment: Option<&str>,
+    created_by: i32,
+) -> Result<SigningKey, sqlx::Error> {
+    sqlx::query_as!(
+        SigningKey,
+        r#"
+        INSERT INTO signing_keys (kind, fingerprint, principals, public_key, comment, created_by)
+        VALUES ($1, $2, $3, $4, $5, $6)
+        RETURNING
+            id,
+            kind as "kind: SignatureKind",
+            fingerprint,
+            principals,
+            public_key,
+            comment,
+            created_by,
+            created_at
+        "#,
+        kind as SignatureKind,
+        fingerprint,
+        principals,
+        public_key,
+        comment,
+        created_by
+    )
+    .fetch_one(pool)
+    .await
+}
+
+/// Elimina una clave de confianza
+///
+/// Retorna `false` si la clave no existía.
+///
+/// # Errors
+/// Retorna error si falla la consulta
+pub async fn delete_signing_key(pool: &PgPool, id: i32) -> Result<bool, sqlx::Error> {
+    let result = sqlx::query!("DELETE FROM signing_keys WHERE id = $1", id)
+        .execute(pool)
+        .await?;
+
+    Ok(result.rows_affected() > 0)
+}
@@ -0,0 +1,433 @@
+use crate::models::{
+    ActivityPoint, ActivityTotals, AuthorActivity, AuthorStatsRow, ProjectActivity, StatsInterval,
+};
+use chrono::NaiveDate;
+use sqlx::PgPool;
+
+// Todas las consultas trabajan sobre la caché de commits. Los rangos de
+// fechas son inclusivos (`since` y `until` son días completos en UTC) y las
+// sumas de líneas ignoran los commits sin estadísticas (merges y commits
+// cacheados antes de guardarlas).
+
+/// Commits y líneas cambiadas por periodo en un proyecto
+///
+/// Devuelve un punto por cada periodo entre `since` y `until`, también los
+/// que no tienen commits. Con `StatsInterval::Week` los periodos empiezan
+/// en lunes y el primero puede empezar antes de `since`, pero solo cuenta
+/// commits dentro del rango.
+///
+/// # Errors
+/// Retorna error si falla la consulta
+pub async fn project_activity(
+    pool: &PgPool,
+    project_id: i32,
+    interval: StatsInterval,
+    since: NaiveDate,
+    until: NaiveDate,
+) -> Result<Vec<ActivityPoint>, sqlx::Error> {
+    sqlx::query_as!(
+        ActivityPoint,
+        r#"
+        WITH periods AS (
+            SELECT generate_series(
+                date_trunc($2::text, $3::date::timestamp),
+                date_trunc($2::text, $4::date::timestamp),
+                ('1 ' || $2::text)::interval
+            ) AS start
+        )
+        SELECT
+            p.start::date as "period!",
+            COUNT(c.id) as "commits!",
+            COALESCE(SUM(c.additions), 0)::bigint as "additions!",
+            COALESCE(SUM(c.deletions), 0)::bigint as "deletions!"
+        FROM periods p
+        LEFT JOIN commits c
+            ON c.project_id = $1
+           AND c.committed_at >= GREATEST(p.start, $3::date::timestamp)
+           AND c.committed_at < LEAST(p.start + ('1 ' || $2::text)::interval, ($4::date + 1)::timestamp)
+        GROUP BY p.start
+        ORDER BY p.start
+        "#,
+        project_id,
+        interval.as_sql(),
+        since,
+        until
+    )
+    .fetch_all(pool)
+    .await
+}
+
+/// Commits agrupados por nombre y email normalizado, sin aplicar `.mailmap`
+///
+/// # Errors
+/// Retorna error si falla la consulta
+pub async fn project_author_rows(
+    pool: &PgPool,
+    project_id: i32,
+    since: Option<NaiveDate>,
+    until: Option<NaiveDate>,
+) -> Result<Vec<AuthorStatsRow>, sqlx::Error> {
+    sqlx::query_as!(
+        AuthorStatsRow,
+        r#"
+        SELECT
+            author_name,
+            lower(trim(COALESCE(author_email, ''))) as "email!",
+            COUNT(*) as "commits!",
+            COALESCE(SUM(additions), 0)::bigint as "additions!",
+            COALESCE(SUM(deletions), 0)::bigint as "deletions!",
+            MIN(committed_at) as "first_commit_at!",
+            MAX(committed_at) as "last_commit_at!"
+        FROM commits
+        WHERE project_id = $1
+          AND ($2::date IS NULL OR committed_at >= $2::date)
+          AND ($3::date IS NULL OR committed_at < $3::date + 1)
+        GROUP BY 1, 2
+        "#,
+        project_id,
+        since,
+        until
+    )
+    .fetch_all(pool)
+    .await
+}
+
+/// Commits por día de la semana (0 = lunes) y hora
+///
+/// `tz_offset` (minutos) desplaza las fechas UTC antes de agruparlas. Solo
+/// devuelve las celdas con commits.
+///
+/// # Errors
+/// Retorna error si falla la consulta
+pub async fn project_heatmap(
+    pool: &PgPool,
+    project_id: i32,
+    since: Option<NaiveDate>,
+    until: Option<NaiveDate>,
+    tz_offset: i32,
+) -> Result<Vec<(i32, i32, i64)>, sqlx::Error> {
+    let rows = sqlx::query!(
+        r#"
+        SELECT
+            (EXTRACT(ISODOW FROM local_time) - 1)::int as "weekday!",
+            EXTRACT(HOUR FROM local_time)::int as "hour!",
+            COUNT(*) as "commits!"
+        FROM (
+            SELECT committed_at + make_interval(mins => $4) AS local_time
+            FROM commits
+            WHERE project_id = $1
+              AND ($2::date IS NULL OR committed_at >= $2::date)
+              AND ($3::date IS NULL OR committed_at < $3::date + 1)
+        ) c
+        GROUP BY 1, 2
+        "#,
+        project_id,
+        since,
+        until,
+        tz_offset
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(rows
+        .into_iter()
+        .map(|r| (r.weekday, r.hour, r.commits))
+        .collect())
+}
+
+/// Totales de actividad en los proyectos visibles para un usuario
+///
+/// Visibilidad: proyectos públicos, aquellos de los que el usuario es
+/// miembro o todos si es administrador.
+///
+/// # Errors
+/// Retorna error si falla la consulta
+pub async fn activity_totals(
+    pool: &PgPool,
+    user_id: Option<i32>,
+    is_admin: bool,
+    since: NaiveDate,
+    until: NaiveDate,
+) -> Result<ActivityTotals, sqlx::Error> {
+    sqlx::query_as!(
+        ActivityTotals,
+        r#"
+        SELECT
+            COUNT(*) as "commits!",
+            COUNT(DISTINCT c.project_id) as "active_projects!",
+            COUNT(DISTINCT lower(trim(COALESCE(c.author_email, c.author_name)))) as "active_authors!",
+            COALESCE(SUM(c.additions), 0)::bigint as "additions!",
+            COALESCE(SUM(c.deletions), 0)::bigint as "deletions!"
+        FROM commits c
+        JOIN projects p ON p.id = c.project_id
+        WHERE c.committed_at >= $3::date
+          AND c.committed_at < $4::date + 1
+          AND (
+            COALESCE(p.is_public, FALSE)
+            OR $2
+            OR EXISTS (SELECT 1 FROM project_members m WHERE m.project_id = p.id AND m.user_id = $1)
+          )
+        "#,
+        user_id,
+        is_admin,
+        since,
+        until
+    )
+    .fetch_one(pool)
+    .await
+}
+
+/// Commits y líneas cambiadas por día en los proyectos visibles para un usuario
+///
+/// # Errors
+/// Retorna error si falla la consulta
+pub async fn daily_activity(
+    pool: &PgPool,
+    user_id: Option<i32>,
+    is_admin: bool,
+    since: NaiveDate,
+    until: NaiveDate,
+) -> Result<Vec<ActivityPoint>, sqlx::Error> {
+    sqlx::query_as!(
+        ActivityPoint,
+        r#"
+        WITH days AS (
+            SELECT generate_series($3::date::timestamp, $4::date::timestamp, '1 day') AS start
+        ),
+        visible AS (
+            SELECT c.committed_at, c.additions, c.deletions
+            FROM commits c
+            JOIN projects p ON p.id = c.project_id
+            WHERE c.committed_at >= $3::date
+              AND c.committed_at < $4::date + 1
+              AND (
+                COALESCE(p.is_public, FALSE)
+                OR $2
+                OR EXISTS (SELECT 1 FROM project_members m WHERE m.project_id = p.id AND m.user_id = $1)
+              )
+        )
+        SELECT
+            d.start::date as "period!",
+            COUNT(v.committed_at) as "commits!",
+            COALESCE(SUM(v.additions), 0)::bigint as "additions!",
+            COALESCE(SUM(v.deletions), 0)::bigint as "deletions!"
+        FROM days d
+        LEFT JOIN visible v ON v.committed_at >= d.start AND v.committed_at < d.start + interval '1 day'
+        GROUP BY d.start
+        ORDER BY d.start
+        "#,
+        user_id,
+        is_admin,
+        since,
+        until
+    )
+    .fetch_all(pool)
+    .await
+}
+
+/// Proyectos visibles con más commits en el rango
+///
+/// # Errors
+/// Retorna error si falla la consulta
+pub async fn top_projects(
+    pool: &PgPool,
+    user_id: Option<i32>,
+    is_admin: bool,
+    since: NaiveDate,
+    until: NaiveDate,
+    limit: i64,
+) -> Result<Vec<ProjectActivity>, sqlx::Error> {
+    sqlx::query_as!(
+        ProjectActivity,
+        r#"
+        SELECT
+            p.id as project_id,
+            p.name as project_name,
+            COUNT(*) as "commits!"
+        FROM commits c
+        JOIN projects p ON p.id = c.project_id
+        WHERE c.committed_at >= $3::date
+          AND c.committed_at < $4::date + 1
+          AND (
+            COALESCE(p.is_public, FALSE)
+            OR $2
+            OR EXISTS (SELECT 1 FROM project_members m WHERE m.project_id = p.id AND m.user_id = $1)
+          )
+        GROUP BY p.id, p.name
+        ORDER BY 3 DESC, p.name
+        LIMIT $5
+        "#,
+        user_id,
+        is_admin,
+        since,
+        until,
+        limit
+    )
+    .fetch_all(pool)
+    .await
+}
+
+/// Autores con más commits en los proyectos visibles, unificados por email
+///
+/// No aplica `.mailmap`: el resumen cruza proyectos y cada repositorio
+/// tiene el suyo.
+///
+/// # Errors
+/// Retorna error si falla la consulta
+pub async fn top_authors(
+    pool: &PgPool,
+    user_id: Option<i32>,
+    is_admin: bool,
+    since: NaiveDate,
+    until: NaiveDate,
+    limit: i64,
+) -> Result<Vec<AuthorActivity>, sqlx::Error> {
+    sqlx::query_as!(
+        AuthorActivity,
+        r#"
+        SELECT
+            (array_agg(c.author_name ORDER BY c.committed_at DESC))[1] as "name!",
+            lower(trim(COALESCE(c.author_email, ''))) as "email!",
+            COUNT(*) as "commits!"
+        FROM commits c
+        JOIN projects p ON p.id = c.project_id
+        WHERE c.committed_at >= $3::date
+          AND c.committed_at < $4::date + 1
+          AND (
+            COALESCE(p.is_public, FALSE)
+            OR $2
+            OR EXISTS (SELECT 1 FROM project_members m WHERE m.project_id = p.id AND m.user_id = $1)
+          )
+        GROUP BY 2, CASE WHEN trim(COALESCE(c.author_email, '')) = '' THEN c.author_name END
+        ORDER BY 3 DESC, 1
+        LIMIT $5
+        "#,
+        user_id,
+        is_admin,
+        since,
+        until,
+        limit
+    )
+    .fetch_all(pool)
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Requiere `DATABASE_URL` con las migraciones aplicadas
+    #[tokio::test]
+    #[ignore]
+    async fn test_stats_queries() {
+        let pool = PgPool::connect(&std::env::var("DATABASE_URL").unwrap())
+            .await
+            .unwrap();
+
+        let public_id: i32 = sqlx::query_scalar(
+            "INSERT INTO projects (name, repository_path, is_public) VALUES ('Stats Public', '/tmp/st-pub', TRUE) RETURNING id",
+        )
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+        let private_id: i32 = sqlx::query_scalar(
+            "INSERT INTO projects (name, repository_path, is_public) VALUES ('Stats Private', '/tmp/st-priv', FALSE) RETURNING id",
+        )
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+
+        // Lunes 6 y martes 7 de enero de 2025
+        for (project, hash, email, date, additions) in [
+            (
+                public_id,
+                "a1",
+                "Ana@Example.com ",
+                "2025-01-06 09:30",
+                Some(10),
+            ),
+            (
+                public_id,
+                "a2",
+                "ana@example.com",
+                "2025-01-07 18:05",
+                Some(5),
+            ),
+            (public_id, "a3", "bob@example.com", "2025-01-07 18:45", None),
+            (
+                private_id,
+                "b1",
+                "eve@example.com",
+                "2025-01-07 10:00",
+                Some(1),
+            ),
+        ] {
+            sqlx::query("INSERT INTO commits (project_id, commit_hash, author_name, author_email, message, committed_at, additions, deletions) VALUES ($1, $2, 'Dev', $3, 'msg', $4::timestamp, $5, 0)")
+                .bind(project)
+                .bind(hash)
+                .bind(email)
+                .bind(date)
+                .bind(additions)
+                .execute(&pool)
+                .await
+                .unwrap();
+        }
+
+        let day = |d: u32| NaiveDate::from_ymd_opt(2025, 1, d).unwrap();
+        let activity = project_activity(&pool, public_id, StatsInterval::Day, day(5), day(8))
+            .await
+            .unwrap();
+        let weekly = project_activity(&pool, public_id, StatsInterval::Week, day(7), day(7))
+            .await
+            .unwrap();
+        let authors = project_author_rows(&pool, public_id, None, None)
+            .await
+            .unwrap();
+        let heatmap = project_heatmap(&pool, public_id, None, None, 60)
+            .await
+            .unwrap();
+        let anonymous = activity_totals(&pool, None, false, day(1), day(31))
+            .await
+            .unwrap();
+        let admin = activity_totals(&pool, None, true, day(1), day(31))
+            .await
+            .unwrap();
+        let top = top_authors(&pool, None, false, day(1), day(31), 5)
+            .await
+            .unwrap();
+
+        sqlx::query("DELETE FROM projects WHERE id IN ($1, $2)")
+            .bind(public_id)
+            .bind(private_id)
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        let commits: Vec<_> = activity.iter().map(|p| p.commits).collect();
+        assert_eq!(commits, vec![0, 1, 2, 0]);
+        assert_eq!(activity[2].additions, 5);
+
+        // La semana empieza el lunes 6 pero solo cuenta el martes 7
+        assert_eq!(weekly.len(), 1);
+        assert_eq!(weekly[0].period, day(6));
+        assert_eq!(weekly[0].commits, 2);
+
+        let ana = authors
+            .iter()
+            .find(|a| a.email == "ana@example.com")
+            .unwrap();
+        assert_eq!(ana.commits, 2);
+        assert_eq!(ana.additions, 15);
+
+        // 18:05 y 18:45 UTC caen a las 19 con +01:00, el martes (1)
+        assert!(heatmap.contains(&(1, 19, 2)));
+        assert!(heatmap.contains(&(0, 10, 1)));
+
+        assert_eq!(anonymous.commits, 3);
+        assert_eq!(anonymous.active_projects, 1);
+        assert_eq!(anonymous.active_authors, 2);
+        assert_eq!(admin.commits, 4);
+        assert_eq!(top[0].email, "ana@example.com");
+        assert_eq!(top[0].commits, 2);
+    }
+}
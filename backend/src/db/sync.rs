@@ -0,0 +1,94 @@
+use crate::models::{CreateSyncOperation, SyncOperation, SyncStatus};
+use sqlx::PgPool;
+
+/// Registra el resultado de una operación de sincronización
+///
+/// # Errors
+/// Retorna error si falla la inserción
+pub async fn create_sync_operation(
+    pool: &PgPool,
+    operation: CreateSyncOperation,
+) -> Result<SyncOperation, sqlx::Error> {
+    let id = sqlx::query_scalar!(
+        r#"
+        INSERT INTO sync_operations
+            (project_id, triggered_by, remote, branch, status, old_commit, new_commit, message, duration_ms)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+        RETURNING id
+        "#,
+        operation.project_id,
+        operation.triggered_by,
+        operation.remote,
+        operation.branch,
+        operation.status as SyncStatus,
+        operation.old_commit,
+        operation.new_commit,
+        operation.message,
+        operation.duration_ms
+    )
+    .fetch_one(pool)
+    .await?;
+
+    sqlx::query_as!(
+        SyncOperation,
+        r#"
+        SELECT
+            s.id,
+            s.project_id,
+            s.triggered_by,
+            u.username as "triggered_by_username?",
+            s.remote,
+            s.branch,
+            s.status as "status: SyncStatus",
+            s.old_commit,
+            s.new_commit,
+            s.message,
+            s.duration_ms,
+            s.created_at as "created_at!"
+        FROM sync_operations s
+        LEFT JOIN users u ON u.id = s.triggered_by
+        WHERE s.id = $1
+        "#,
+        id
+    )
+    .fetch_one(pool)
+    .await
+}
+
+/// Lista las últimas operaciones de sincronización de un proyecto
+///
+/// # Errors
+/// Retorna error si falla la consulta
+pub async fn list_sync_operations(
+    pool: &PgPool,
+    project_id: i32,
+    limit: i64,
+) -> Result<Vec<SyncOperation>, sqlx::Error> {
+    sqlx::query_as!(
+        SyncOperation,
+        r#"
+        SELECT
+            s.id,
+            s.project_id,
+            s.triggered_by,
+            u.username as "triggered_by_username?",
+            s.remote,
+            s.branch,
+            s.status as "status: SyncStatus",
+            s.old_commit,
+            s.new_commit,
+            s.message,
+            s.duration_ms,
+            s.created_at as "created_at!"
+        FROM sync_operations s
+        LEFT JOIN users u ON u.id = s.triggered_by
+        WHERE s.project_id = $1
+        ORDER BY s.created_at DESC, s.id DESC
+        LIMIT $2
+        "#,
+        project_id,
+        limit
+    )
+    .fetch_all(pool)
+    .await
+}
@@ -0,0 +1,81 @@
+use axum::{
+    extract::{Path, Query, State},
+    Json,
+};
+use serde::Deserialize;
+
+use super::{ApiError, AppState};
+use crate::db::audit::list_project_audit;
+use crate::db::projects::find_project_by_id;
+use crate::middleware::AuthUser;
+use crate::models::{AuditAction, AuditLogResponse};
+
+/// Entradas devueltas por defecto en `/audit`
+const DEFAULT_AUDIT_LIMIT: i64 = 50;
+
+/// Máximo de entradas que se pueden pedir en `/audit`
+const MAX_AUDIT_LIMIT: i64 = 500;
+
+/// Parámetros de consulta del log de auditoría
+#[derive(Debug, Deserialize)]
+pub struct AuditQuery {
+    /// Filtra por acción (p. ej. `archive_download`)
+    pub action: Option<AuditAction>,
+    /// Número de entradas a devolver (default: 50, máximo: 500)
+    pub limit: Option<i64>,
+}
+
+/// Handler que retorna el log de auditoría de un proyecto
+///
+/// Solo para administradores. Las entradas van de la más reciente a la
+/// más antigua; `user_id` es `null` en los accesos anónimos.
+///
+/// # Endpoint
+/// `GET /api/projects/:id/audit?action=&limit=`
+///
+/// # Response
+/// - **200 OK**: Entradas del log
+/// - **401 Unauthorized**: No autenticado
+/// - **403 Forbidden**: El usuario no es administrador
+/// - **404 Not Found**: El proyecto no existe
+///
+/// # Example Response
+/// ```json
+/// {
+///   "entries": [
+///     {
+///       "id": 7,
+///       "project_id": 1,
+///       "user_id": 2,
+///       "username": "ada",
+///       "action": "archive_download",
+///       "details": {
+///         "ref": "main",
+///         "commit": "9fceb02d0ae598e95dc970b74767f19372d61af8",
+///         "path": "",
+///         "format": "zip",
+///         "files": 84,
+///         "size": 412733
+///       },
+///       "created_at": "2025-10-21T03:15:42"
+///     }
+///   ]
+/// }
+/// ```
+pub async fn audit_log_handler(
+    State(state): State<AppState>,
+    Path(project_id): Path<i32>,
+    Query(query): Query<AuditQuery>,
+    auth: AuthUser,
+) -> Result<Json<AuditLogResponse>, ApiError> {
+    auth.require_admin()?;
+    find_project_by_id(&state.db, project_id).await?;
+
+    let limit = query
+        .limit
+        .unwrap_or(DEFAULT_AUDIT_LIMIT)
+        .clamp(1, MAX_AUDIT_LIMIT);
+    let entries = list_project_audit(&state.db, project_id, query.action, limit).await?;
+
+    Ok(Json(AuditLogResponse { entries }))
+}
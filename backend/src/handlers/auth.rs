@@ -0,0 +1,96 @@
+use axum::{extract::State, Json};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::{ApiError, AppState};
+use crate::db::users::{find_user_by_username, UserError};
+use crate::middleware::AuthUser;
+use crate::models::UserResponse;
+use crate::services::auth::{issue_token, verify_password};
+
+/// Credenciales de inicio de sesión
+#[derive(Debug, Deserialize)]
+pub struct LoginRequest {
+    pub username: String,
+    pub password: String,
+}
+
+/// Respuesta de inicio de sesión
+#[derive(Debug, Serialize)]
+pub struct LoginResponse {
+    pub user: UserResponse,
+    /// Token para la cabecera `Authorization: Bearer <token>`
+    pub token: String,
+    pub expires_at: DateTime<Utc>,
+}
+
+/// Handler de inicio de sesión con usuario y contraseña
+///
+/// Las credenciales incorrectas, el usuario inexistente y el usuario
+/// desactivado responden igual para no revelar qué cuentas existen.
+///
+/// # Endpoint
+/// `POST /api/auth/login`
+///
+/// # Request Body
+/// ```json
+/// { "username": "admin", "password": "..." }
+/// ```
+///
+/// # Response
+/// - **200 OK**: Token y datos del usuario
+/// - **401 Unauthorized**: Credenciales inválidas
+///
+/// # Example Response
+/// ```json
+/// {
+///   "user": { "id": 1, "username": "admin", "email": "admin@example.com", "role": "admin", "is_active": true, "created_at": "2025-10-21T03:15:42", "updated_at": "2025-10-21T03:15:42" },
+///   "token": "eyJ0eXAiOiJKV1QiLCJhbGciOiJIUzI1NiJ9...",
+///   "expires_at": "2025-10-22T03:15:42Z"
+/// }
+/// ```
+pub async fn login_handler(
+    State(state): State<AppState>,
+    Json(request): Json<LoginRequest>,
+) -> Result<Json<LoginResponse>, ApiError> {
+    let user = match find_user_by_username(&state.db, request.username.trim()).await {
+        Ok(user) => Some(user),
+        Err(UserError::NotFound) => None,
+        Err(e) => return Err(e.into()),
+    };
+
+    let hash = user.as_ref().map(|u| u.password_hash.clone());
+    let password = request.password;
+    let valid =
+        tokio::task::spawn_blocking(move || verify_password(&password, hash.as_deref())).await?;
+
+    let user = match user {
+        Some(user) if valid && user.is_active => user,
+        _ => return Err(ApiError::Unauthorized("Credenciales inválidas".to_string())),
+    };
+
+    let (token, expires_at) =
+        issue_token(&state.auth, &user).map_err(|e| ApiError::Internal(e.to_string()))?;
+
+    Ok(Json(LoginResponse {
+        user: user.into(),
+        token,
+        expires_at,
+    }))
+}
+
+/// Handler que retorna el usuario autenticado
+///
+/// # Endpoint
+/// `GET /api/auth/me`
+///
+/// # Response
+/// - **200 OK**: Datos del usuario
+/// - **401 Unauthorized**: Token ausente, inválido o expirado
+pub async fn me_handler(
+    State(state): State<AppState>,
+    auth: AuthUser,
+) -> Result<Json<UserResponse>, ApiError> {
+    let user = crate::db::users::find_user_by_id(&state.db, auth.id).await?;
+    Ok(Json(user.into()))
+}
@@ -0,0 +1,919 @@
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    Json,
+};
+use serde::Deserialize;
+use sqlx::PgPool;
+
+use super::projects::{ensure_project_contributor, ensure_project_visible};
+use super::{ApiError, AppState};
+use crate::db::comments::{
+    add_reaction, find_comment, insert_comment, list_comment_revisions, list_comments,
+    remove_reaction, resolve_comment, soft_delete_comment, unresolve_comment, update_comment,
+    CommentScope, NewComment,
+};
+use crate::db::projects::{find_project_by_id, is_project_admin};
+use crate::middleware::AuthUser;
+use crate::models::{
+    Comment, CommentRevisionsResponse, CommentsResponse, CreateComment, CreateReaction, Project,
+    UpdateComment,
+};
+use crate::services::{comments, git};
+
+/// Longitud máxima del contenido de un comentario, en caracteres
+const MAX_COMMENT_LENGTH: usize = 10_000;
+
+/// Longitud máxima de una reacción, en bytes (secuencias con modificadores incluidas)
+const MAX_EMOJI_LENGTH: usize = 32;
+
+/// Parámetros de consulta de los comentarios de un proyecto
+#[derive(Debug, Deserialize)]
+pub struct CommentsQuery {
+    /// Hash, rama o tag del commit; sin él se listan los comentarios del proyecto
+    pub commit: Option<String>,
+    /// Archivo cuyos comentarios se listan, según su posición actual
+    pub path: Option<String>,
+    /// Solo los hilos resueltos (`true`) o abiertos (`false`)
+    pub resolved: Option<bool>,
+}
+
+/// Handler que lista los comentarios de un proyecto o de un commit
+///
+/// Retorna los comentarios raíz y sus respuestas en una lista plana, del
+/// más antiguo al más reciente; las respuestas indican su comentario raíz en
+/// `parent_comment_id`. Un comentario eliminado aparece sin contenido
+/// mientras tenga respuestas.
+///
+/// `content` es el Markdown original y `content_html` su versión
+/// renderizada y saneada, que es la que debe mostrarse: los hashes de
+/// commits y las referencias `#id` a otros comentarios se enlazan y el HTML
+/// embebido se muestra como texto.
+///
+/// Con `commit` se listan los comentarios de ese commit, incluidos los de
+/// sus archivos. Con `path`, los comentarios cuya posición actual (en la
+/// punta de la rama por defecto) está en ese archivo, también los
+/// desactualizados. Sin ninguno, los comentarios del proyecto. Con
+/// `resolved` se filtran los hilos (comentario raíz y respuestas) por su
+/// estado.
+///
+/// # Endpoint
+/// `GET /api/projects/:id/comments?commit=|path=&resolved=`
+///
+/// # Response
+/// - **200 OK**: Comentarios con su autor
+/// - **400 Bad Request**: Se indicaron `commit` y `path` a la vez, o la ruta es inválida
+/// - **404 Not Found**: Proyecto inexistente o no visible, o commit inexistente
+///
+/// # Example Response
+/// ```json
+/// {
+///   "comments": [
+///     {
+///       "id": 12,
+///       "project_id": 1,
+///       "user_id": 3,
+///       "commit_hash": null,
+///       "content": "¿Movemos la configuración a `config/`?",
+///       "content_html": "<p>¿Movemos la configuración a <code>config/</code>?</p>",
+///       "file_path": null,
+///       "line_start": null,
+///       "line_end": null,
+///       "position": null,
+///       "is_outdated": false,
+///       "mentions": [],
+///       "parent_comment_id": null,
+///       "revision_count": 0,
+///       "reactions": [{ "emoji": "👍", "count": 2, "users": ["jane", "john"] }],
+///       "resolved_at": null,
+///       "resolved_by": null,
+///       "resolved_by_username": null,
+///       "created_at": "2025-02-02T10:15:00",
+///       "updated_at": "2025-02-02T10:15:00",
+///       "deleted_at": null,
+///       "user": { "id": 3, "username": "jane", "email": "jane@example.com", "role": "user", ... }
+///     }
+///   ]
+/// }
+/// ```
+pub async fn comments_handler(
+    State(state): State<AppState>,
+    Path(project_id): Path<i32>,
+    Query(query): Query<CommentsQuery>,
+    auth: Option<AuthUser>,
+) -> Result<Json<CommentsResponse>, ApiError> {
+    ensure_project_visible(&state, project_id, auth.as_ref()).await?;
+
+    let (commit_hash, path) = match (non_empty(query.commit), non_empty(query.path)) {
+        (Some(_), Some(_)) => {
+            return Err(ApiError::BadRequest(
+                "Indica `commit` o `path`, no ambos".to_string(),
+            ))
+        }
+        (Some(rev), None) => (
+            Some(resolve_commit_hash(&state, project_id, rev).await?),
+            None,
+        ),
+        (None, Some(path)) => (None, Some(git::normalize_path(Some(&path))?)),
+        (None, None) => (None, None),
+    };
+    let scope = match (&commit_hash, &path) {
+        (Some(hash), _) => CommentScope::Commit(hash),
+        (None, Some(path)) => CommentScope::File(path),
+        (None, None) => CommentScope::Project,
+    };
+    let mut comments = list_comments(&state.db, project_id, scope, query.resolved).await?;
+    let project = find_project_by_id(&state.db, project_id).await?;
+    comments::render_comments(&state.db, &project, &mut comments).await?;
+
+    Ok(Json(CommentsResponse { comments }))
+}
+
+/// Handler que crea un comentario en un proyecto o en un commit
+///
+/// Solo comentan los colaboradores y administradores del proyecto y los
+/// administradores globales. Las respuestas (`parent_comment_id`) deben
+/// apuntar a un comentario raíz del mismo proyecto y heredan su commit.
+///
+/// Con `file_path` el comentario se ancla al archivo (y a las líneas
+/// `line_start`..`line_end`, si se indican) en `commit_hash`, o en `HEAD`
+/// si no se indica commit. A partir de ahí su posición se traslada a la
+/// punta de la rama por defecto conforme llegan commits.
+///
+/// Los usuarios mencionados con `@usuario` que pueden ver el proyecto
+/// reciben una notificación; las menciones dentro de código se ignoran.
+///
+/// # Endpoint
+/// `POST /api/projects/:id/comments`
+///
+/// # Request Body
+/// ```json
+/// {
+///   "content": "Esto rompe el build en ARM",
+///   "commit_hash": "9fceb02",
+///   "file_path": "src/build.rs",
+///   "line_start": 12,
+///   "line_end": 14
+/// }
+/// ```
+///
+/// # Response
+/// - **201 Created**: Comentario creado
+/// - **400 Bad Request**: Contenido vacío o demasiado largo, respuesta a una respuesta,
+///   líneas fuera del archivo o en un archivo binario
+/// - **401 Unauthorized**: No autenticado
+/// - **403 Forbidden**: No es colaborador del proyecto
+/// - **404 Not Found**: Proyecto, commit, archivo o comentario padre inexistente
+pub async fn create_comment_handler(
+    State(state): State<AppState>,
+    Path(project_id): Path<i32>,
+    auth: AuthUser,
+    Json(body): Json<CreateComment>,
+) -> Result<(StatusCode, Json<Comment>), ApiError> {
+    ensure_project_contributor(&state, project_id, &auth).await?;
+    let content = validate_content(&body.content)?;
+
+    let lines = validate_lines(body.line_start, body.line_end)?;
+    let file_path = non_empty(body.file_path);
+    if lines.is_some() && file_path.is_none() {
+        return Err(ApiError::BadRequest(
+            "Las líneas requieren `file_path`".to_string(),
+        ));
+    }
+    if file_path.is_some() && body.parent_comment_id.is_some() {
+        return Err(ApiError::BadRequest(
+            "Las respuestas no se anclan a un archivo".to_string(),
+        ));
+    }
+
+    let commit_rev = non_empty(body.commit_hash);
+    let (mut commit_hash, file_path) = match file_path {
+        Some(path) => {
+            let (hash, path) =
+                resolve_file_anchor(&state, project_id, commit_rev, path, lines).await?;
+            (Some(hash), Some(path))
+        }
+        None => match commit_rev {
+            Some(rev) => (
+                Some(resolve_commit_hash(&state, project_id, rev).await?),
+                None,
+            ),
+            None => (None, None),
+        },
+    };
+
+    if let Some(parent_id) = body.parent_comment_id {
+        let parent = find_reply_parent(&state.db, project_id, parent_id).await?;
+        if commit_hash.is_some() && commit_hash != parent.commit_hash {
+            return Err(ApiError::BadRequest(
+                "La respuesta debe ser del mismo commit que el comentario".to_string(),
+            ));
+        }
+        commit_hash = parent.commit_hash;
+    }
+
+    let mut comment = insert_comment(
+        &state.db,
+        NewComment {
+            project_id,
+            user_id: auth.id,
+            commit_hash: commit_hash.as_deref(),
+            file_path: file_path.as_deref(),
+            lines,
+            parent_comment_id: body.parent_comment_id,
+            content: &content,
+        },
+    )
+    .await?;
+
+    // Trasladar ya la posición si se comentó un commit anterior a la punta
+    let project = find_project_by_id(&state.db, project_id).await?;
+    if comment.file_path.is_some() {
+        match comments::remap_project_anchors(&state.db, &project).await {
+            Ok(0) => {}
+            Ok(_) => {
+                if let Some(remapped) = find_comment(&state.db, project_id, comment.id).await? {
+                    comment = remapped;
+                }
+            }
+            Err(e) => tracing::warn!("No se pudo trasladar el comentario #{}: {}", comment.id, e),
+        }
+    }
+    let comment = record_mentions(&state, &project, comment).await?;
+    let comment = render_comment(&state, &project, comment).await?;
+
+    tracing::info!(
+        "Comentario #{} en el proyecto {} por {}",
+        comment.id,
+        project_id,
+        auth.username
+    );
+
+    Ok((StatusCode::CREATED, Json(comment)))
+}
+
+/// Handler que edita un comentario propio
+///
+/// El contenido anterior se guarda en el historial de revisiones. Se
+/// notifica solo a los usuarios mencionados por primera vez.
+///
+/// # Endpoint
+/// `PATCH /api/projects/:id/comments/:comment_id`
+///
+/// # Request Body
+/// ```json
+/// { "content": "Esto rompe el build en ARM64" }
+/// ```
+///
+/// # Response
+/// - **200 OK**: Comentario editado
+/// - **400 Bad Request**: Contenido vacío o demasiado largo
+/// - **401 Unauthorized**: No autenticado
+/// - **403 Forbidden**: El comentario es de otro usuario
+/// - **404 Not Found**: Proyecto inexistente o no visible, o comentario inexistente o eliminado
+pub async fn update_comment_handler(
+    State(state): State<AppState>,
+    Path((project_id, comment_id)): Path<(i32, i32)>,
+    auth: AuthUser,
+    Json(body): Json<UpdateComment>,
+) -> Result<Json<Comment>, ApiError> {
+    ensure_project_visible(&state, project_id, Some(&auth)).await?;
+    let comment = find_live_comment(&state, project_id, comment_id).await?;
+    if comment.user_id != Some(auth.id) {
+        return Err(ApiError::Forbidden(
+            "Solo puedes editar tus propios comentarios".to_string(),
+        ));
+    }
+
+    let content = validate_content(&body.content)?;
+    let comment = update_comment(&state.db, project_id, comment_id, &content, auth.id)
+        .await?
+        .ok_or_else(|| ApiError::NotFound("Comentario no encontrado".to_string()))?;
+
+    let project = find_project_by_id(&state.db, project_id).await?;
+    let comment = record_mentions(&state, &project, comment).await?;
+    let comment = render_comment(&state, &project, comment).await?;
+
+    Ok(Json(comment))
+}
+
+/// Handler que elimina un comentario
+///
+/// El autor puede eliminar sus comentarios y los administradores del
+/// proyecto cualquiera. El comentario se marca como eliminado: sus
+/// respuestas se conservan y él aparece sin contenido.
+///
+/// # Endpoint
+/// `DELETE /api/projects/:id/comments/:comment_id`
+///
+/// # Response
+/// - **204 No Content**: Comentario eliminado
+/// - **401 Unauthorized**: No autenticado
+/// - **403 Forbidden**: Ni es el autor ni administra el proyecto
+/// - **404 Not Found**: Proyecto inexistente o no visible, o comentario inexistente o ya eliminado
+pub async fn delete_comment_handler(
+    State(state): State<AppState>,
+    Path((project_id, comment_id)): Path<(i32, i32)>,
+    auth: AuthUser,
+) -> Result<StatusCode, ApiError> {
+    ensure_project_visible(&state, project_id, Some(&auth)).await?;
+    let comment = find_live_comment(&state, project_id, comment_id).await?;
+
+    let is_author = comment.user_id == Some(auth.id);
+    if !is_author && !auth.is_admin() && !is_project_admin(&state.db, project_id, auth.id).await? {
+        return Err(ApiError::Forbidden(
+            "Solo puedes eliminar tus propios comentarios".to_string(),
+        ));
+    }
+
+    if !soft_delete_comment(&state.db, project_id, comment_id).await? {
+        return Err(ApiError::NotFound("Comentario no encontrado".to_string()));
+    }
+
+    tracing::info!(
+        "Comentario #{} del proyecto {} eliminado por {}",
+        comment_id,
+        project_id,
+        auth.username
+    );
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Handler que marca un hilo como resuelto
+///
+/// Solo se resuelven comentarios raíz; sus respuestas siguen el estado del
+/// hilo. Pueden resolver los colaboradores y administradores del proyecto.
+///
+/// # Endpoint
+/// `POST /api/projects/:id/comments/:comment_id/resolve`
+///
+/// # Response
+/// - **200 OK**: Comentario con `resolved_at` y `resolved_by`
+/// - **400 Bad Request**: El comentario es una respuesta
+/// - **401 Unauthorized**: No autenticado
+/// - **403 Forbidden**: No es colaborador del proyecto
+/// - **404 Not Found**: Proyecto, o comentario inexistente o eliminado
+/// - **409 Conflict**: El hilo ya estaba resuelto
+pub async fn resolve_comment_handler(
+    State(state): State<AppState>,
+    Path((project_id, comment_id)): Path<(i32, i32)>,
+    auth: AuthUser,
+) -> Result<Json<Comment>, ApiError> {
+    ensure_project_contributor(&state, project_id, &auth).await?;
+    let comment = find_live_comment(&state, project_id, comment_id).await?;
+    if comment.parent_comment_id.is_some() {
+        return Err(ApiError::BadRequest(
+            "Solo se pueden resolver los comentarios raíz".to_string(),
+        ));
+    }
+
+    if !resolve_comment(&state.db, project_id, comment_id, auth.id).await? {
+        return Err(ApiError::Conflict("El hilo ya está resuelto".to_string()));
+    }
+
+    tracing::info!(
+        "Hilo #{} del proyecto {} resuelto por {}",
+        comment_id,
+        project_id,
+        auth.username
+    );
+
+    Ok(Json(
+        rendered_comment(&state, project_id, comment_id).await?,
+    ))
+}
+
+/// Handler que vuelve a abrir un hilo resuelto
+///
+/// # Endpoint
+/// `POST /api/projects/:id/comments/:comment_id/unresolve`
+///
+/// # Response
+/// - **200 OK**: Comentario abierto
+/// - **401 Unauthorized**: No autenticado
+/// - **403 Forbidden**: No es colaborador del proyecto
+/// - **404 Not Found**: Proyecto, o comentario inexistente o eliminado
+/// - **409 Conflict**: El hilo no estaba resuelto
+pub async fn unresolve_comment_handler(
+    State(state): State<AppState>,
+    Path((project_id, comment_id)): Path<(i32, i32)>,
+    auth: AuthUser,
+) -> Result<Json<Comment>, ApiError> {
+    ensure_project_contributor(&state, project_id, &auth).await?;
+    find_live_comment(&state, project_id, comment_id).await?;
+
+    if !unresolve_comment(&state.db, project_id, comment_id).await? {
+        return Err(ApiError::Conflict("El hilo no está resuelto".to_string()));
+    }
+
+    tracing::info!(
+        "Hilo #{} del proyecto {} reabierto por {}",
+        comment_id,
+        project_id,
+        auth.username
+    );
+
+    Ok(Json(
+        rendered_comment(&state, project_id, comment_id).await?,
+    ))
+}
+
+/// Handler que añade una reacción a un comentario
+///
+/// Cualquier usuario que vea el proyecto puede reaccionar, una vez por
+/// emoji. Repetir una reacción no tiene efecto.
+///
+/// # Endpoint
+/// `POST /api/projects/:id/comments/:comment_id/reactions`
+///
+/// # Request Body
+/// ```json
+/// { "emoji": "👍" }
+/// ```
+///
+/// # Response
+/// - **200 OK**: Comentario con las reacciones actualizadas
+/// - **400 Bad Request**: No es un emoji
+/// - **401 Unauthorized**: No autenticado
+/// - **404 Not Found**: Proyecto inexistente o no visible, o comentario inexistente o eliminado
+pub async fn add_reaction_handler(
+    State(state): State<AppState>,
+    Path((project_id, comment_id)): Path<(i32, i32)>,
+    auth: AuthUser,
+    Json(body): Json<CreateReaction>,
+) -> Result<Json<Comment>, ApiError> {
+    ensure_project_visible(&state, project_id, Some(&auth)).await?;
+    find_live_comment(&state, project_id, comment_id).await?;
+    let emoji = validate_emoji(&body.emoji)?;
+
+    add_reaction(&state.db, comment_id, auth.id, &emoji).await?;
+
+    Ok(Json(
+        rendered_comment(&state, project_id, comment_id).await?,
+    ))
+}
+
+/// Handler que quita una reacción propia de un comentario
+///
+/// Quitar una reacción que no existe no tiene efecto.
+///
+/// # Endpoint
+/// `DELETE /api/projects/:id/comments/:comment_id/reactions/:emoji`
+///
+/// # Response
+/// - **200 OK**: Comentario con las reacciones actualizadas
+/// - **401 Unauthorized**: No autenticado
+/// - **404 Not Found**: Proyecto inexistente o no visible, o comentario inexistente o eliminado
+pub async fn remove_reaction_handler(
+    State(state): State<AppState>,
+    Path((project_id, comment_id, emoji)): Path<(i32, i32, String)>,
+    auth: AuthUser,
+) -> Result<Json<Comment>, ApiError> {
+    ensure_project_visible(&state, project_id, Some(&auth)).await?;
+    find_live_comment(&state, project_id, comment_id).await?;
+
+    remove_reaction(&state.db, comment_id, auth.id, emoji.trim()).await?;
+
+    Ok(Json(
+        rendered_comment(&state, project_id, comment_id).await?,
+    ))
+}
+
+/// Handler que retorna el historial de ediciones de un comentario
+///
+/// Cada revisión es una versión anterior del comentario, con quién y
+/// cuándo la reemplazó; la versión actual es el propio comentario. El
+/// historial de un comentario eliminado solo lo ven los administradores
+/// del proyecto.
+///
+/// # Endpoint
+/// `GET /api/projects/:id/comments/:comment_id/revisions`
+///
+/// # Response
+/// - **200 OK**: Versiones anteriores, de la más antigua a la más reciente
+/// - **404 Not Found**: Proyecto inexistente o no visible, o comentario inexistente
+///
+/// # Example Response
+/// ```json
+/// {
+///   "revisions": [
+///     {
+///       "id": 4,
+///       "comment_id": 12,
+///       "content": "Esto rompe el build en ARM",
+///       "content_html": "<p>Esto rompe el build en ARM</p>",
+///       "edited_by": 3,
+///       "edited_by_username": "jane",
+///       "edited_at": "2025-02-02T10:20:00"
+///     }
+///   ]
+/// }
+/// ```
+pub async fn comment_revisions_handler(
+    State(state): State<AppState>,
+    Path((project_id, comment_id)): Path<(i32, i32)>,
+    auth: Option<AuthUser>,
+) -> Result<Json<CommentRevisionsResponse>, ApiError> {
+    ensure_project_visible(&state, project_id, auth.as_ref()).await?;
+    let comment = find_comment(&state.db, project_id, comment_id)
+        .await?
+        .ok_or_else(|| ApiError::NotFound("Comentario no encontrado".to_string()))?;
+
+    if comment.deleted_at.is_some() {
+        let is_moderator = match &auth {
+            Some(auth) => {
+                auth.is_admin() || is_project_admin(&state.db, project_id, auth.id).await?
+            }
+            None => false,
+        };
+        if !is_moderator {
+            return Err(ApiError::NotFound("Comentario no encontrado".to_string()));
+        }
+    }
+
+    let mut revisions = list_comment_revisions(&state.db, comment_id).await?;
+    let project = find_project_by_id(&state.db, project_id).await?;
+    comments::render_revisions(&state.db, &project, &mut revisions).await?;
+
+    Ok(Json(CommentRevisionsResponse { revisions }))
+}
+
+/// Busca un comentario no eliminado del proyecto o responde 404
+async fn find_live_comment(
+    state: &AppState,
+    project_id: i32,
+    comment_id: i32,
+) -> Result<Comment, ApiError> {
+    find_comment(&state.db, project_id, comment_id)
+        .await?
+        .filter(|comment| comment.deleted_at.is_none())
+        .ok_or_else(|| ApiError::NotFound("Comentario no encontrado".to_string()))
+}
+
+/// Busca el comentario al que se responde
+///
+/// Los hilos tienen un solo nivel: solo se responde a comentarios raíz no
+/// eliminados del proyecto.
+///
+/// # Errors
+/// - `ApiError::NotFound` si el comentario no existe o está eliminado
+/// - `ApiError::BadRequest` si es a su vez una respuesta
+async fn find_reply_parent(
+    pool: &PgPool,
+    project_id: i32,
+    parent_id: i32,
+) -> Result<Comment, ApiError> {
+    let parent = find_comment(pool, project_id, parent_id)
+        .await?
+        .filter(|parent| parent.deleted_at.is_none())
+        .ok_or_else(|| ApiError::NotFound("Comentario no encontrado".to_string()))?;
+    if parent.parent_comment_id.is_some() {
+        return Err(ApiError::BadRequest(
+            "Solo se puede responder a un comentario raíz".to_string(),
+        ));
+    }
+
+    Ok(parent)
+}
+
+/// Registra las menciones de un comentario recién guardado y lo retorna con ellas
+///
+/// Un fallo al notificar no impide guardar el comentario.
+async fn record_mentions(
+    state: &AppState,
+    project: &Project,
+    comment: Comment,
+) -> Result<Comment, ApiError> {
+    match comments::record_mentions(&state.db, project, &comment).await {
+        Ok(notified) => {
+            if notified > 0 {
+                tracing::debug!(
+                    "{} usuarios mencionados en el comentario #{}",
+                    notified,
+                    comment.id
+                );
+            }
+            Ok(find_comment(&state.db, project.id, comment.id)
+                .await?
+                .unwrap_or(comment))
+        }
+        Err(e) => {
+            tracing::warn!(
+                "No se pudieron registrar las menciones del comentario #{}: {}",
+                comment.id,
+                e
+            );
+            Ok(comment)
+        }
+    }
+}
+
+/// Vuelve a leer un comentario y lo retorna renderizado
+async fn rendered_comment(
+    state: &AppState,
+    project_id: i32,
+    comment_id: i32,
+) -> Result<Comment, ApiError> {
+    let comment = find_live_comment(state, project_id, comment_id).await?;
+    let project = find_project_by_id(&state.db, project_id).await?;
+    render_comment(state, &project, comment).await
+}
+
+/// Renderiza el contenido de un comentario recién guardado
+async fn render_comment(
+    state: &AppState,
+    project: &Project,
+    mut comment: Comment,
+) -> Result<Comment, ApiError> {
+    comments::render_comments(&state.db, project, std::slice::from_mut(&mut comment)).await?;
+    Ok(comment)
+}
+
+/// Resuelve una revisión del repositorio del proyecto al hash completo de su commit
+async fn resolve_commit_hash(
+    state: &AppState,
+    project_id: i32,
+    rev: String,
+) -> Result<String, ApiError> {
+    let project = find_project_by_id(&state.db, project_id).await?;
+
+    let hash = tokio::task::spawn_blocking(move || -> git::Result<String> {
+        let repo = git::open_repository(&project.repository_path)?;
+        let commit = git::resolve_commit(&repo, Some(&rev))?;
+        Ok(commit.id().to_string())
+    })
+    .await??;
+
+    Ok(hash)
+}
+
+/// Resuelve el commit de un comentario de archivo (`HEAD` por defecto) y
+/// comprueba que la ruta es un archivo y que las líneas existen
+async fn resolve_file_anchor(
+    state: &AppState,
+    project_id: i32,
+    rev: Option<String>,
+    path: String,
+    lines: Option<(i32, i32)>,
+) -> Result<(String, String), ApiError> {
+    let project = find_project_by_id(&state.db, project_id).await?;
+    let limit = state.git.max_blob_size;
+
+    let (hash, path, line_count) = tokio::task::spawn_blocking(move || -> git::Result<_> {
+        let repo = git::open_repository(&project.repository_path)?;
+        let commit = git::resolve_commit(&repo, rev.as_deref())?;
+        let path = git::normalize_path(Some(&path))?;
+        if path.is_empty() {
+            return Err(git::GitError::NotAFile(path));
+        }
+        let line_count = match lines {
+            Some(_) => git::line_count(&repo, &commit, &path, limit)?,
+            None => {
+                // Solo comprobar que el archivo existe
+                let tree = commit.tree()?;
+                let entry = tree
+                    .get_path(std::path::Path::new(&path))
+                    .map_err(|_| git::GitError::PathNotFound(path.clone()))?;
+                if entry.kind() != Some(git2::ObjectType::Blob) {
+                    return Err(git::GitError::NotAFile(path));
+                }
+                None
+            }
+        };
+        Ok((commit.id().to_string(), path, line_count))
+    })
+    .await??;
+
+    if let Some((_, end)) = lines {
+        match line_count {
+            None => {
+                return Err(ApiError::BadRequest(
+                    "No se pueden comentar líneas de un archivo binario".to_string(),
+                ))
+            }
+            Some(count) if i64::from(end) > i64::from(count) => {
+                return Err(ApiError::BadRequest(format!(
+                    "El archivo tiene {} líneas",
+                    count
+                )))
+            }
+            Some(_) => {}
+        }
+    }
+
+    Ok((hash, path))
+}
+
+/// Valida el rango de líneas; sin `line_end` se comenta una sola línea
+fn validate_lines(
+    line_start: Option<i32>,
+    line_end: Option<i32>,
+) -> Result<Option<(i32, i32)>, ApiError> {
+    match (line_start, line_end) {
+        (None, None) => Ok(None),
+        (None, Some(_)) => Err(ApiError::BadRequest(
+            "`line_end` requiere `line_start`".to_string(),
+        )),
+        (Some(start), end) => {
+            let end = end.unwrap_or(start);
+            if start < 1 || end < start {
+                return Err(ApiError::BadRequest(format!(
+                    "Rango de líneas inválido: {}-{}",
+                    start, end
+                )));
+            }
+            Ok(Some((start, end)))
+        }
+    }
+}
+
+fn non_empty(value: Option<String>) -> Option<String> {
+    value
+        .map(|v| v.trim().to_string())
+        .filter(|v| !v.is_empty())
+}
+
+fn validate_content(content: &str) -> Result<String, ApiError> {
+    let content = content.trim();
+    if content.is_empty() {
+        return Err(ApiError::BadRequest(
+            "El comentario no puede estar vacío".to_string(),
+        ));
+    }
+    if content.chars().count() > MAX_COMMENT_LENGTH {
+        return Err(ApiError::BadRequest(format!(
+            "El comentario supera los {} caracteres",
+            MAX_COMMENT_LENGTH
+        )));
+    }
+    Ok(content.to_string())
+}
+
+/// Valida que una reacción esté formada solo por emojis
+///
+/// Se admiten las secuencias con selector de variación, tono de piel,
+/// unión (ZWJ), banderas y teclas (`1️⃣`).
+fn validate_emoji(emoji: &str) -> Result<String, ApiError> {
+    let emoji = emoji.trim();
+    let is_pictograph = |c: char| {
+        matches!(
+            u32::from(c),
+            0x1F000..=0x1FAFF
+                | 0x2190..=0x21FF
+                | 0x2300..=0x23FF
+                | 0x2600..=0x27BF
+                | 0x2B00..=0x2BFF
+                | 0x00A9
+                | 0x00AE
+                | 0x203C
+                | 0x2049
+                | 0x2122
+                | 0x2139
+                | 0x3030
+                | 0x303D
+                | 0x3297
+                | 0x3299
+        )
+    };
+    let is_modifier = |c: char| {
+        matches!(u32::from(c), 0x200D | 0xFE0F | 0x20E3 | 0xE0020..=0xE007F)
+            || c.is_ascii_digit()
+            || matches!(c, '#' | '*')
+    };
+
+    // Las teclas (`1️⃣`) son un dígito seguido del modificador U+20E3
+    let has_emoji = emoji.chars().any(is_pictograph) || emoji.contains('\u{20E3}');
+    let valid = emoji.len() <= MAX_EMOJI_LENGTH
+        && has_emoji
+        && emoji.chars().all(|c| is_pictograph(c) || is_modifier(c));
+    if !valid {
+        return Err(ApiError::BadRequest(
+            "La reacción debe ser un emoji".to_string(),
+        ));
+    }
+    Ok(emoji.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_content() {
+        assert_eq!(validate_content("  ¡Bien!\n").unwrap(), "¡Bien!");
+        assert!(matches!(
+            validate_content(" \n\t "),
+            Err(ApiError::BadRequest(_))
+        ));
+        assert!(validate_content(&"á".repeat(MAX_COMMENT_LENGTH)).is_ok());
+        assert!(matches!(
+            validate_content(&"á".repeat(MAX_COMMENT_LENGTH + 1)),
+            Err(ApiError::BadRequest(_))
+        ));
+    }
+
+    #[test]
+    fn test_validate_lines() {
+        assert_eq!(validate_lines(None, None).unwrap(), None);
+        assert_eq!(validate_lines(Some(4), None).unwrap(), Some((4, 4)));
+        assert_eq!(validate_lines(Some(4), Some(9)).unwrap(), Some((4, 9)));
+        assert!(validate_lines(None, Some(9)).is_err());
+        assert!(validate_lines(Some(0), Some(2)).is_err());
+        assert!(validate_lines(Some(5), Some(4)).is_err());
+    }
+
+    #[test]
+    fn test_validate_emoji() {
+        for emoji in ["👍", " 🎉 ", "❤️", "👍🏽", "👩‍💻", "🇪🇸", "1️⃣", "🚀"]
+        {
+            assert_eq!(validate_emoji(emoji).unwrap(), emoji.trim(), "{emoji}");
+        }
+        for invalid in ["", "ok", "+1", "1", "<b>", "👍 ok", "á", &"👍".repeat(9)] {
+            assert!(validate_emoji(invalid).is_err(), "{invalid}");
+        }
+    }
+
+    #[test]
+    fn test_non_empty() {
+        assert_eq!(
+            non_empty(Some(" main ".to_string())).as_deref(),
+            Some("main")
+        );
+        assert_eq!(non_empty(Some("  ".to_string())), None);
+        assert_eq!(non_empty(None), None);
+    }
+
+    /// Requiere `DATABASE_URL` con las migraciones aplicadas
+    #[tokio::test]
+    #[ignore]
+    async fn test_replies_are_one_level_deep() {
+        let pool = PgPool::connect(&std::env::var("DATABASE_URL").unwrap())
+            .await
+            .unwrap();
+        let mut projects = Vec::new();
+        for path in ["/tmp/replies", "/tmp/replies-other"] {
+            let id: i32 = sqlx::query_scalar(
+                "INSERT INTO projects (name, repository_path) VALUES ('Replies', $1) RETURNING id",
+            )
+            .bind(path)
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+            projects.push(id);
+        }
+        let (project_id, other_id) = (projects[0], projects[1]);
+        let user_id: i32 = sqlx::query_scalar(
+            "INSERT INTO users (username, email, password_hash) VALUES ('replies_ana', 'replies_ana@example.com', 'x') RETURNING id",
+        )
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+
+        let comment = |project_id, parent_comment_id| NewComment {
+            project_id,
+            user_id,
+            commit_hash: None,
+            file_path: None,
+            lines: None,
+            parent_comment_id,
+            content: "Hilo",
+        };
+        let root = insert_comment(&pool, comment(project_id, None))
+            .await
+            .unwrap();
+        let reply = insert_comment(&pool, comment(project_id, Some(root.id)))
+            .await
+            .unwrap();
+        let removed = insert_comment(&pool, comment(project_id, None))
+            .await
+            .unwrap();
+        soft_delete_comment(&pool, project_id, removed.id)
+            .await
+            .unwrap();
+        let foreign = insert_comment(&pool, comment(other_id, None))
+            .await
+            .unwrap();
+
+        let to_root = find_reply_parent(&pool, project_id, root.id).await;
+        let to_reply = find_reply_parent(&pool, project_id, reply.id).await;
+        let to_removed = find_reply_parent(&pool, project_id, removed.id).await;
+        let to_foreign = find_reply_parent(&pool, project_id, foreign.id).await;
+
+        sqlx::query("DELETE FROM projects WHERE id = ANY($1)")
+            .bind(&projects)
+            .execute(&pool)
+            .await
+            .unwrap();
+        sqlx::query("DELETE FROM users WHERE id = $1")
+            .bind(user_id)
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        assert_eq!(to_root.unwrap().id, root.id);
+        assert!(matches!(to_reply, Err(ApiError::BadRequest(_))));
+        assert!(matches!(to_removed, Err(ApiError::NotFound(_))));
+        assert!(matches!(to_foreign, Err(ApiError::NotFound(_))));
+    }
+}
@@ -0,0 +1,435 @@
+use axum::{
+    extract::{Path, Query, State},
+    http::header,
+    response::{IntoResponse, Response},
+    Json,
+};
+use serde::Deserialize;
+use std::sync::Arc;
+
+use super::projects::ensure_project_visible;
+use super::{ApiError, AppState};
+use crate::config::GitConfig;
+use crate::db::owners::resolve_owners;
+use crate::db::projects::find_project_by_id;
+use crate::middleware::AuthUser;
+use crate::models::{
+    ChangelogEntry, ChangelogFormat, ChangelogResponse, CommitDetailResponse, CompareResponse,
+    DiffFormat,
+};
+use crate::services::changelog::{group_entries, render_markdown};
+use crate::services::conventional::parse_commit_message;
+use crate::services::git;
+
+/// Commits máximos listados en una comparación
+const MAX_COMPARE_COMMITS: usize = 250;
+
+/// Commits máximos incluidos en un changelog
+const MAX_CHANGELOG_COMMITS: usize = 1000;
+
+/// Parámetros de consulta del detalle de un commit
+#[derive(Debug, Deserialize)]
+pub struct CommitQuery {
+    /// `structured` (por defecto) o `unified`
+    #[serde(default)]
+    pub format: DiffFormat,
+}
+
+/// Parámetros de consulta para comparar dos revisiones
+#[derive(Debug, Deserialize)]
+pub struct CompareQuery {
+    /// Revisión de origen (rama, tag o hash)
+    pub base: String,
+    /// Revisión de destino (rama, tag o hash)
+    pub head: String,
+    /// `structured` (por defecto) o `unified`
+    #[serde(default)]
+    pub format: DiffFormat,
+    /// Compara los árboles directamente en lugar de partir del ancestro común
+    #[serde(default)]
+    pub straight: bool,
+}
+
+/// Parámetros de consulta del changelog
+#[derive(Debug, Deserialize)]
+pub struct ChangelogQuery {
+    /// Revisión de origen, excluida; por defecto el tag anterior a `to`
+    pub from: Option<String>,
+    /// Revisión de destino; `HEAD` por defecto
+    pub to: Option<String>,
+    /// `json` (por defecto) o `markdown`
+    #[serde(default)]
+    pub format: ChangelogFormat,
+}
+
+/// Handler que retorna el detalle de un commit con su diff
+///
+/// El diff se calcula contra el primer padre (o contra el árbol vacío en el
+/// commit raíz) con detección de renombrados. Los totales cubren siempre el
+/// diff completo, pero el detalle se limita a `GIT_MAX_DIFF_FILES` archivos
+/// y `GIT_MAX_DIFF_LINES` líneas; lo omitido se marca con `truncated`.
+/// `signature` indica si el commit está firmado por una clave de confianza
+/// y `owners` reúne los dueños de los archivos tocados según el
+/// `CODEOWNERS` actual de `HEAD`.
+///
+/// # Endpoint
+/// `GET /api/projects/:id/commits/:hash?format=structured|unified`
+///
+/// # Response
+/// - **200 OK**: Metadatos del commit, estadísticas y cambios por archivo
+/// - **404 Not Found**: Proyecto inexistente o no visible, o commit inexistente
+///
+/// # Example Response
+/// ```json
+/// {
+///   "hash": "9fceb02d0ae598e95dc970b74767f19372d61af8",
+///   "tree": "4b825dc642cb6eb9a060e54bf8d69288fbee4904",
+///   "parents": ["1a410efbd13591db07496601ebc7a059dd55cfe9"],
+///   "summary": "Add health endpoint",
+///   "message": "Add health endpoint\n",
+///   "author_name": "Jane Doe",
+///   "author_email": "jane@example.com",
+///   "authored_at": "2025-10-21T03:15:42Z",
+///   "committer_name": "Jane Doe",
+///   "committer_email": "jane@example.com",
+///   "committed_at": "2025-10-21T03:15:42Z",
+///   "signature": {
+///     "status": "verified",
+///     "kind": "ssh",
+///     "key_fingerprint": "SHA256:ogV1rIk6Nxb4ThAd4sW2fUT1yrtMnZ0Uf3tBa4mVqNI",
+///     "signer": "jane@example.com",
+///     "reason": null
+///   },
+///   "owners": [
+///     { "owner": "@jane", "user": { "id": 3, "username": "jane" }, "telegram_handle": "jane_doe" }
+///   ],
+///   "stats": { "files_changed": 1, "additions": 1, "deletions": 0 },
+///   "files": [
+///     {
+///       "path": "src/main.rs",
+///       "old_path": null,
+///       "status": "modified",
+///       "old_mode": "100644",
+///       "new_mode": "100644",
+///       "binary": false,
+///       "additions": 1,
+///       "deletions": 0,
+///       "truncated": false,
+///       "hunks": [
+///         {
+///           "header": "@@ -1,2 +1,3 @@",
+///           "old_start": 1,
+///           "old_lines": 2,
+///           "new_start": 1,
+///           "new_lines": 3,
+///           "lines": [
+///             { "origin": "context", "old_lineno": 1, "new_lineno": 1, "content": "fn main() {" },
+///             { "origin": "addition", "old_lineno": null, "new_lineno": 2, "content": "    health();" },
+///             { "origin": "context", "old_lineno": 2, "new_lineno": 3, "content": "}" }
+///           ]
+///         }
+///       ]
+///     }
+///   ],
+///   "truncated": false
+/// }
+/// ```
+pub async fn commit_handler(
+    State(state): State<AppState>,
+    Path((project_id, hash)): Path<(i32, String)>,
+    Query(query): Query<CommitQuery>,
+    auth: Option<AuthUser>,
+) -> Result<Json<CommitDetailResponse>, ApiError> {
+    ensure_project_visible(&state, project_id, auth.as_ref()).await?;
+    let project = find_project_by_id(&state.db, project_id).await?;
+    let limits = diff_limits(&state.git);
+    let signatures = Arc::clone(&state.signatures);
+
+    let (mut response, owners) = tokio::task::spawn_blocking(move || -> git::Result<_> {
+        let repo = git::open_repository(&project.repository_path)?;
+        let commit = git::resolve_commit(&repo, Some(&hash))?;
+        let diff = git::diff_commit(&repo, &commit, query.format, limits)?;
+        let signature = signatures.verify(&repo, &commit)?;
+        let owners = touched_owners(&repo, &commit)?;
+
+        let author = commit.author();
+        let committer = commit.committer();
+        let lossy = |bytes: &[u8]| String::from_utf8_lossy(bytes).into_owned();
+
+        let response = CommitDetailResponse {
+            hash: commit.id().to_string(),
+            tree: commit.tree_id().to_string(),
+            parents: commit.parent_ids().map(|oid| oid.to_string()).collect(),
+            summary: lossy(commit.summary_bytes().unwrap_or_default()),
+            message: lossy(commit.message_bytes()),
+            author_name: lossy(author.name_bytes()),
+            author_email: lossy(author.email_bytes()),
+            authored_at: git::git_time_to_utc(author.when()),
+            committer_name: lossy(committer.name_bytes()),
+            committer_email: lossy(committer.email_bytes()),
+            committed_at: git::git_time_to_utc(committer.when()),
+            signature,
+            owners: Vec::new(),
+            diff,
+        };
+
+        Ok((response, owners))
+    })
+    .await??;
+    response.owners = resolve_owners(&state.db, &owners).await?;
+
+    Ok(Json(response))
+}
+
+/// Handler que compara dos revisiones
+///
+/// Por defecto funciona como `git diff base...head`: el diff parte del
+/// ancestro común y solo muestra lo que `head` introduce. Con
+/// `straight=true` se comparan los árboles de `base` y `head` tal cual.
+/// Incluye cuántos commits lleva cada lado de ventaja y la lista de commits
+/// de `base..head` (hasta 250) con el estado de la firma de cada uno.
+///
+/// # Endpoint
+/// `GET /api/projects/:id/compare?base=&head=&format=&straight=`
+///
+/// # Response
+/// - **200 OK**: Resumen de la comparación y diff
+/// - **404 Not Found**: Proyecto inexistente o no visible, o revisión inexistente
+///
+/// # Example Response
+/// ```json
+/// {
+///   "base": "1a410efbd13591db07496601ebc7a059dd55cfe9",
+///   "head": "9fceb02d0ae598e95dc970b74767f19372d61af8",
+///   "merge_base": "1a410efbd13591db07496601ebc7a059dd55cfe9",
+///   "ahead_by": 1,
+///   "behind_by": 0,
+///   "commits": [
+///     {
+///       "hash": "9fceb02d0ae598e95dc970b74767f19372d61af8",
+///       "summary": "Add health endpoint",
+///       "author_name": "Jane Doe",
+///       "author_email": "jane@example.com",
+///       "authored_at": "2025-10-21T03:15:42Z",
+///       "signature": {
+///         "status": "unsigned",
+///         "kind": null,
+///         "key_fingerprint": null,
+///         "signer": null,
+///         "reason": null
+///       }
+///     }
+///   ],
+///   "stats": { "files_changed": 1, "additions": 1, "deletions": 0 },
+///   "files": [],
+///   "truncated": false
+/// }
+/// ```
+pub async fn compare_handler(
+    State(state): State<AppState>,
+    Path(project_id): Path<i32>,
+    Query(query): Query<CompareQuery>,
+    auth: Option<AuthUser>,
+) -> Result<Json<CompareResponse>, ApiError> {
+    ensure_project_visible(&state, project_id, auth.as_ref()).await?;
+    let project = find_project_by_id(&state.db, project_id).await?;
+    let limits = diff_limits(&state.git);
+    let signatures = Arc::clone(&state.signatures);
+
+    let response = tokio::task::spawn_blocking(move || -> git::Result<CompareResponse> {
+        let repo = git::open_repository(&project.repository_path)?;
+        let base = git::resolve_commit(&repo, Some(&query.base))?;
+        let head = git::resolve_commit(&repo, Some(&query.head))?;
+        let comparison = git::compare(
+            &repo,
+            &base,
+            &head,
+            query.straight,
+            query.format,
+            limits,
+            MAX_COMPARE_COMMITS,
+        )?;
+
+        let mut commits = comparison.commits;
+        for summary in &mut commits {
+            let oid = git2::Oid::from_str(&summary.hash)?;
+            summary.signature = Some(signatures.verify(&repo, &repo.find_commit(oid)?)?);
+        }
+
+        Ok(CompareResponse {
+            base: base.id().to_string(),
+            head: head.id().to_string(),
+            merge_base: comparison.merge_base.map(|oid| oid.to_string()),
+            ahead_by: comparison.ahead_by,
+            behind_by: comparison.behind_by,
+            commits,
+            diff: comparison.diff,
+        })
+    })
+    .await??;
+
+    Ok(Json(response))
+}
+
+/// Handler que genera el changelog entre dos revisiones
+///
+/// Incluye los commits de `from..to` (sin merges, hasta 1000) agrupados por
+/// tipo de Conventional Commit; los cambios incompatibles se repiten en su
+/// propia sección y los mensajes que no siguen el formato van a `other`.
+/// Sin `from` se parte del tag más cercano anterior a `to`, o del primer
+/// commit si no hay ninguno.
+///
+/// # Endpoint
+/// `GET /api/projects/:id/changelog?from=&to=&format=json|markdown`
+///
+/// # Response
+/// - **200 OK**: Changelog en JSON o `text/markdown`
+/// - **404 Not Found**: Proyecto inexistente o no visible, o revisión inexistente
+///
+/// # Example Response
+/// ```json
+/// {
+///   "from": "v1.0.0",
+///   "from_commit": "1a410efbd13591db07496601ebc7a059dd55cfe9",
+///   "to": "v1.1.0",
+///   "to_commit": "9fceb02d0ae598e95dc970b74767f19372d61af8",
+///   "date": "2025-10-21T03:15:42Z",
+///   "total_commits": 1,
+///   "truncated": false,
+///   "sections": [
+///     {
+///       "kind": "feat",
+///       "title": "Features",
+///       "entries": [
+///         {
+///           "hash": "9fceb02d0ae598e95dc970b74767f19372d61af8",
+///           "scope": "api",
+///           "description": "add health endpoint",
+///           "breaking": false,
+///           "breaking_note": null,
+///           "author_name": "Jane Doe",
+///           "authored_at": "2025-10-21T03:15:42Z",
+///           "co_authors": ["Ada Lovelace <ada@example.com>"],
+///           "issues": ["#12"]
+///         }
+///       ]
+///     }
+///   ]
+/// }
+/// ```
+pub async fn changelog_handler(
+    State(state): State<AppState>,
+    Path(project_id): Path<i32>,
+    Query(query): Query<ChangelogQuery>,
+    auth: Option<AuthUser>,
+) -> Result<Response, ApiError> {
+    ensure_project_visible(&state, project_id, auth.as_ref()).await?;
+    let project = find_project_by_id(&state.db, project_id).await?;
+
+    let to_name = query
+        .to
+        .clone()
+        .filter(|r| !r.trim().is_empty())
+        .unwrap_or_else(|| "HEAD".to_string());
+    let from_name = query.from.clone().filter(|r| !r.trim().is_empty());
+
+    let changelog = tokio::task::spawn_blocking(move || -> git::Result<ChangelogResponse> {
+        let repo = git::open_repository(&project.repository_path)?;
+        let to = git::resolve_commit(&repo, Some(&to_name))?;
+        let to_id = to.id();
+        let date = git::git_time_to_utc(to.committer().when());
+        let from = match from_name {
+            Some(name) => {
+                let oid = git::resolve_commit(&repo, Some(&name))?.id();
+                Some((name, oid))
+            }
+            None => git::previous_tag(&repo, to_id)?,
+        };
+
+        let (commits, truncated) = git::changelog_commits(
+            &repo,
+            to_id,
+            from.as_ref().map(|(_, oid)| *oid),
+            MAX_CHANGELOG_COMMITS,
+        )?;
+        let total_commits = commits.len();
+
+        let entries = commits
+            .into_iter()
+            .map(|commit| {
+                let parsed = parse_commit_message(&commit.message);
+                let entry = ChangelogEntry {
+                    hash: commit.hash,
+                    scope: parsed.scope.clone(),
+                    description: parsed.description.clone(),
+                    breaking: parsed.breaking,
+                    breaking_note: parsed.breaking_note.clone(),
+                    author_name: commit.author_name,
+                    authored_at: commit.authored_at,
+                    co_authors: parsed
+                        .trailers
+                        .iter()
+                        .filter(|t| t.key.eq_ignore_ascii_case("co-authored-by"))
+                        .map(|t| t.value.clone())
+                        .collect(),
+                    issues: parsed.issues.clone(),
+                };
+                (parsed, entry)
+            })
+            .collect();
+
+        Ok(ChangelogResponse {
+            from_commit: from.as_ref().map(|(_, oid)| oid.to_string()),
+            from: from.map(|(name, _)| name),
+            to: to_name,
+            to_commit: to_id.to_string(),
+            date,
+            total_commits,
+            truncated,
+            sections: group_entries(entries),
+        })
+    })
+    .await??;
+
+    Ok(match query.format {
+        ChangelogFormat::Json => Json(changelog).into_response(),
+        ChangelogFormat::Markdown => (
+            [(header::CONTENT_TYPE, "text/markdown; charset=utf-8")],
+            render_markdown(&changelog),
+        )
+            .into_response(),
+    })
+}
+
+/// Dueños de las rutas que toca un commit, sin repetir
+///
+/// Se usa el `CODEOWNERS` de `HEAD` y no el del commit, para notificar a
+/// los dueños actuales aunque el commit sea antiguo.
+fn touched_owners(repo: &git2::Repository, commit: &git2::Commit) -> git::Result<Vec<String>> {
+    let head = match git::resolve_commit(repo, None) {
+        Ok(head) => head,
+        Err(git::GitError::RevisionNotFound(_)) => return Ok(Vec::new()),
+        Err(e) => return Err(e),
+    };
+    let Some(codeowners) = git::find_codeowners(repo, &head)? else {
+        return Ok(Vec::new());
+    };
+
+    let mut owners: Vec<String> = Vec::new();
+    for path in git::changed_paths(repo, commit)? {
+        for owner in codeowners.owners_of(&path) {
+            if !owners.iter().any(|o| o.eq_ignore_ascii_case(owner)) {
+                owners.push(owner.clone());
+            }
+        }
+    }
+
+    Ok(owners)
+}
+
+pub(crate) fn diff_limits(config: &GitConfig) -> git::DiffLimits {
+    git::DiffLimits {
+        max_files: config.max_diff_files,
+        max_lines: config.max_diff_lines,
+    }
+}
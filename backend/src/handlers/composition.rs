@@ -0,0 +1,193 @@
+use std::collections::HashMap;
+
+use axum::{
+    extract::{Path, Query, State},
+    Json,
+};
+use serde::Deserialize;
+
+use super::projects::ensure_project_visible;
+use super::{ApiError, AppState};
+use crate::db::composition::{latest_snapshot, list_snapshots, snapshot_languages};
+use crate::middleware::AuthUser;
+use crate::models::{
+    CompositionHistoryResponse, CompositionPoint, CompositionResponse, LanguageBytes, LanguageShare,
+};
+
+/// Puntos devueltos por defecto en `/languages/history`
+const DEFAULT_HISTORY_LIMIT: i64 = 100;
+
+/// Máximo de puntos que se pueden pedir en `/languages/history`
+const MAX_HISTORY_LIMIT: i64 = 1000;
+
+/// Parámetros de consulta de la evolución de la composición
+#[derive(Debug, Deserialize)]
+pub struct CompositionHistoryQuery {
+    /// Commits analizados a devolver, los más recientes (default: 100, máximo: 1000)
+    pub limit: Option<i64>,
+}
+
+/// Handler que retorna los lenguajes y el tamaño de la rama por defecto
+///
+/// Devuelve el último análisis guardado por el analizador en segundo
+/// plano. Los porcentajes se calculan sobre los bytes con lenguaje
+/// detectado; los archivos vendorizados, generados y de documentación no
+/// cuentan (se pueden ajustar con `linguist-*` en `.gitattributes`).
+///
+/// # Endpoint
+/// `GET /api/projects/:id/languages`
+///
+/// # Response
+/// - **200 OK**: Composición y lenguajes
+/// - **404 Not Found**: Proyecto inexistente o no visible, o aún sin analizar
+///
+/// # Example Response
+/// ```json
+/// {
+///   "snapshot": {
+///     "id": 12,
+///     "project_id": 1,
+///     "commit_hash": "9fceb02d0ae598e95dc970b74767f19372d61af8",
+///     "branch": "main",
+///     "committed_at": "2025-10-21T03:15:40",
+///     "total_files": 214,
+///     "total_bytes": 1830422,
+///     "excluded_files": 37,
+///     "disk_size": 9437184,
+///     "pack_count": 2,
+///     "pack_size": 8912345,
+///     "loose_objects": 41,
+///     "loose_size": 96313,
+///     "analyzed_at": "2025-10-21T03:15:44"
+///   },
+///   "languages": [
+///     { "language": "Rust", "files": 96, "bytes": 1203311, "percentage": 71.42 },
+///     { "language": "TypeScript", "files": 58, "bytes": 481560, "percentage": 28.58 }
+///   ]
+/// }
+/// ```
+pub async fn languages_handler(
+    State(state): State<AppState>,
+    Path(project_id): Path<i32>,
+    auth: Option<AuthUser>,
+) -> Result<Json<CompositionResponse>, ApiError> {
+    ensure_project_visible(&state, project_id, auth.as_ref()).await?;
+
+    let snapshot = latest_snapshot(&state.db, project_id)
+        .await?
+        .ok_or_else(|| {
+            ApiError::NotFound("La composición del proyecto aún no se ha analizado".to_string())
+        })?;
+    let languages = snapshot_languages(&state.db, &[snapshot.id])
+        .await?
+        .into_iter()
+        .map(|(_, language)| language)
+        .collect();
+
+    Ok(Json(CompositionResponse {
+        snapshot,
+        languages: language_shares(languages),
+    }))
+}
+
+/// Handler que retorna la evolución de la composición por commit analizado
+///
+/// Un punto por cada punta de la rama por defecto analizada, del más
+/// antiguo al más reciente.
+///
+/// # Endpoint
+/// `GET /api/projects/:id/languages/history?limit=`
+///
+/// # Response
+/// - **200 OK**: Serie de composiciones (vacía si aún no hay análisis)
+/// - **404 Not Found**: Proyecto inexistente o no visible
+///
+/// # Example Response
+/// ```json
+/// {
+///   "points": [
+///     {
+///       "commit_hash": "9fceb02d0ae598e95dc970b74767f19372d61af8",
+///       "committed_at": "2025-10-21T03:15:40",
+///       "total_files": 214,
+///       "total_bytes": 1830422,
+///       "disk_size": 9437184,
+///       "languages": [{ "language": "Rust", "files": 96, "bytes": 1203311 }]
+///     }
+///   ]
+/// }
+/// ```
+pub async fn languages_history_handler(
+    State(state): State<AppState>,
+    Path(project_id): Path<i32>,
+    Query(query): Query<CompositionHistoryQuery>,
+    auth: Option<AuthUser>,
+) -> Result<Json<CompositionHistoryResponse>, ApiError> {
+    ensure_project_visible(&state, project_id, auth.as_ref()).await?;
+
+    let limit = query
+        .limit
+        .unwrap_or(DEFAULT_HISTORY_LIMIT)
+        .clamp(1, MAX_HISTORY_LIMIT);
+    let snapshots = list_snapshots(&state.db, project_id, limit).await?;
+    let ids: Vec<i32> = snapshots.iter().map(|s| s.id).collect();
+
+    let mut languages: HashMap<i32, Vec<LanguageBytes>> = HashMap::new();
+    for (snapshot_id, language) in snapshot_languages(&state.db, &ids).await? {
+        languages.entry(snapshot_id).or_default().push(language);
+    }
+
+    let points = snapshots
+        .into_iter()
+        .map(|s| CompositionPoint {
+            languages: languages.remove(&s.id).unwrap_or_default(),
+            commit_hash: s.commit_hash,
+            committed_at: s.committed_at,
+            total_files: s.total_files,
+            total_bytes: s.total_bytes,
+            disk_size: s.disk_size,
+        })
+        .collect();
+
+    Ok(Json(CompositionHistoryResponse { points }))
+}
+
+/// Calcula el porcentaje de cada lenguaje, redondeado a dos decimales
+fn language_shares(languages: Vec<LanguageBytes>) -> Vec<LanguageShare> {
+    let total: i64 = languages.iter().map(|l| l.bytes).sum();
+
+    languages
+        .into_iter()
+        .map(|l| LanguageShare {
+            percentage: if total > 0 {
+                (l.bytes as f64 * 10_000.0 / total as f64).round() / 100.0
+            } else {
+                0.0
+            },
+            language: l.language,
+            files: l.files,
+            bytes: l.bytes,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_language_shares() {
+        let language = |name: &str, bytes| LanguageBytes {
+            language: name.to_string(),
+            files: 1,
+            bytes,
+        };
+
+        let shares = language_shares(vec![language("Rust", 2), language("TOML", 1)]);
+        assert_eq!(shares[0].percentage, 66.67);
+        assert_eq!(shares[1].percentage, 33.33);
+
+        let shares = language_shares(vec![language("Text", 0)]);
+        assert_eq!(shares[0].percentage, 0.0);
+    }
+}
@@ -0,0 +1,217 @@
+use axum::{
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Json,
+};
+use serde_json::json;
+
+use crate::db::locks::LockError;
+use crate::db::projects::ProjectError;
+use crate::db::users::UserError;
+use crate::services::git::GitError;
+use crate::services::health::HealthError;
+use crate::services::signature::SignatureError;
+use crate::services::vault::VaultError;
+
+/// Error de la API convertible en respuesta HTTP
+///
+/// El cuerpo sigue el mismo formato que `/health`:
+/// `{ "status": "error", "error": "<mensaje>" }`. Los errores internos se
+/// registran con `tracing` y se responden con un mensaje genérico.
+#[derive(Debug, thiserror::Error)]
+pub enum ApiError {
+    #[error("{0}")]
+    NotFound(String),
+
+    #[error("{0}")]
+    BadRequest(String),
+
+    #[error("{0}")]
+    Unauthorized(String),
+
+    #[error("{0}")]
+    Forbidden(String),
+
+    #[error("{0}")]
+    Conflict(String),
+
+    #[error("{0}")]
+    PayloadTooLarge(String),
+
+    #[error("{0}")]
+    BadGateway(String),
+
+    #[error("{0}")]
+    ServiceUnavailable(String),
+
+    #[error("{0}")]
+    Internal(String),
+}
+
+impl ApiError {
+    fn status_code(&self) -> StatusCode {
+        match self {
+            ApiError::NotFound(_) => StatusCode::NOT_FOUND,
+            ApiError::BadRequest(_) => StatusCode::BAD_REQUEST,
+            ApiError::Unauthorized(_) => StatusCode::UNAUTHORIZED,
+            ApiError::Forbidden(_) => StatusCode::FORBIDDEN,
+            ApiError::Conflict(_) => StatusCode::CONFLICT,
+            ApiError::PayloadTooLarge(_) => StatusCode::PAYLOAD_TOO_LARGE,
+            ApiError::BadGateway(_) => StatusCode::BAD_GATEWAY,
+            ApiError::ServiceUnavailable(_) => StatusCode::SERVICE_UNAVAILABLE,
+            ApiError::Internal(_) => StatusCode::INTERNAL_SERVER_ERROR,
+        }
+    }
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        let status = self.status_code();
+        let message = match &self {
+            ApiError::Internal(detail) => {
+                tracing::error!("Error interno: {}", detail);
+                "Error interno del servidor".to_string()
+            }
+            other => other.to_string(),
+        };
+
+        (status, Json(json!({ "status": "error", "error": message }))).into_response()
+    }
+}
+
+impl From<ProjectError> for ApiError {
+    fn from(err: ProjectError) -> Self {
+        match err {
+            ProjectError::NotFound => ApiError::NotFound(err.to_string()),
+            ProjectError::Database(e) => ApiError::Internal(e.to_string()),
+        }
+    }
+}
+
+impl From<UserError> for ApiError {
+    fn from(err: UserError) -> Self {
+        match err {
+            UserError::NotFound => ApiError::NotFound(err.to_string()),
+            UserError::AlreadyExists => ApiError::Conflict(err.to_string()),
+            UserError::Database(e) => ApiError::Internal(e.to_string()),
+        }
+    }
+}
+
+impl From<LockError> for ApiError {
+    fn from(err: LockError) -> Self {
+        match err {
+            LockError::ProjectNotFound | LockError::RequestNotFound => {
+                ApiError::NotFound(err.to_string())
+            }
+            LockError::AlreadyLocked
+            | LockError::NotLocked
+            | LockError::RequestAlreadyPending
+            | LockError::RequestAlreadyReviewed => ApiError::Conflict(err.to_string()),
+            LockError::Database(e) => ApiError::Internal(e.to_string()),
+        }
+    }
+}
+
+impl From<GitError> for ApiError {
+    fn from(err: GitError) -> Self {
+        match err {
+            GitError::RevisionNotFound(_) | GitError::PathNotFound(_) => {
+                ApiError::NotFound(err.to_string())
+            }
+            GitError::InvalidPath(_) | GitError::NotADirectory(_) | GitError::NotAFile(_) => {
+                ApiError::BadRequest(err.to_string())
+            }
+            GitError::BlobTooLarge { .. } => ApiError::PayloadTooLarge(err.to_string()),
+            GitError::HookConflict(_)
+            | GitError::DetachedHead
+            | GitError::NoUpstream(_)
+            | GitError::NotFastForward(_)
+            | GitError::DirtyWorkingCopy
+            | GitError::BareRepository
+            | GitError::RepositoryLocked => ApiError::Conflict(err.to_string()),
+            GitError::Remote(_) => ApiError::BadGateway(err.to_string()),
+            GitError::RepositoryNotFound | GitError::Git(_) | GitError::Io(_) => {
+                ApiError::Internal(err.to_string())
+            }
+        }
+    }
+}
+
+impl From<VaultError> for ApiError {
+    fn from(err: VaultError) -> Self {
+        match err {
+            VaultError::NotConfigured => ApiError::ServiceUnavailable(err.to_string()),
+            VaultError::Encryption | VaultError::Decryption => ApiError::Internal(err.to_string()),
+        }
+    }
+}
+
+impl From<SignatureError> for ApiError {
+    fn from(err: SignatureError) -> Self {
+        match err {
+            SignatureError::InvalidKey(_) => ApiError::BadRequest(err.to_string()),
+            SignatureError::GpgUnavailable => ApiError::ServiceUnavailable(err.to_string()),
+            SignatureError::Gpg(_) | SignatureError::Io(_) | SignatureError::Database(_) => {
+                ApiError::Internal(err.to_string())
+            }
+        }
+    }
+}
+
+impl From<HealthError> for ApiError {
+    fn from(err: HealthError) -> Self {
+        ApiError::Internal(err.to_string())
+    }
+}
+
+impl From<sqlx::Error> for ApiError {
+    fn from(err: sqlx::Error) -> Self {
+        ApiError::Internal(err.to_string())
+    }
+}
+
+impl From<tokio::task::JoinError> for ApiError {
+    fn from(err: tokio::task::JoinError) -> Self {
+        ApiError::Internal(err.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_git_error_status_codes() {
+        let cases = [
+            (GitError::RevisionNotFound("x".into()), StatusCode::NOT_FOUND),
+            (GitError::PathNotFound("x".into()), StatusCode::NOT_FOUND),
+            (GitError::InvalidPath("..".into()), StatusCode::BAD_REQUEST),
+            (GitError::NotADirectory("x".into()), StatusCode::BAD_REQUEST),
+            (GitError::NotAFile("x".into()), StatusCode::BAD_REQUEST),
+            (
+                GitError::BlobTooLarge { size: 10, limit: 5 },
+                StatusCode::PAYLOAD_TOO_LARGE,
+            ),
+            (GitError::NotFastForward("main".into()), StatusCode::CONFLICT),
+            (GitError::RepositoryLocked, StatusCode::CONFLICT),
+            (GitError::Remote("x".into()), StatusCode::BAD_GATEWAY),
+            (GitError::RepositoryNotFound, StatusCode::INTERNAL_SERVER_ERROR),
+        ];
+
+        for (err, status) in cases {
+            assert_eq!(ApiError::from(err).status_code(), status);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_internal_error_hides_details() {
+        let response = ApiError::Internal("connection refused to 10.0.0.1".into()).into_response();
+        assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
+
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(json["status"], "error");
+        assert_eq!(json["error"], "Error interno del servidor");
+    }
+}
@@ -2,11 +2,27 @@ use axum::{extract::State, http::StatusCode, Json};
 use chrono::Utc;
 use serde_json::{json, Value};
 use sqlx::PgPool;
+use std::sync::Arc;
+
+use crate::config::{AuthConfig, GitConfig, HealthConfig};
+use crate::db::health::health_summary;
+use crate::middleware::AuthUser;
+use crate::services::events::EventBus;
+use crate::services::git::BlameCache;
+use crate::services::signature::SignatureVerifier;
+use crate::services::vault::CredentialVault;
 
 /// Estado de la aplicación compartido entre handlers
 #[derive(Clone)]
 pub struct AppState {
     pub db: PgPool,
+    pub auth: AuthConfig,
+    pub git: GitConfig,
+    pub health: HealthConfig,
+    pub blame_cache: Arc<BlameCache>,
+    pub signatures: Arc<SignatureVerifier>,
+    pub events: EventBus,
+    pub vault: CredentialVault,
 }
 
 /// Handler para la ruta raíz que retorna información de la API
@@ -43,7 +59,58 @@ pub async fn root_handler() -> Json<Value> {
         "endpoints": {
             "health": "GET /health",
             "documentation": "Coming soon",
-            "api_v1": "Coming soon"
+            "auth_login": "POST /api/auth/login",
+            "auth_me": "GET /api/auth/me",
+            "search": "GET /api/search?q=&type=&project_id=&limit=",
+            "notifications": "GET /api/notifications?cursor=&limit=&type=&project_id=&unread=",
+            "notifications_unread_count": "GET /api/notifications/unread-count",
+            "notifications_read_all": "POST /api/notifications/read-all",
+            "notification_read": "POST /api/notifications/:id/read",
+            "notification": "DELETE /api/notifications/:id",
+            "stats_summary": "GET /api/stats/summary",
+            "signing_keys": "GET|POST /api/signing-keys (admin)",
+            "signing_key": "DELETE /api/signing-keys/:key_id (admin)",
+            "owner_mappings": "GET|PUT /api/owner-mappings (admin)",
+            "owner_mapping": "DELETE /api/owner-mappings/:mapping_id (admin)",
+            "project": "GET /api/projects/:id",
+            "project_tree": "GET /api/projects/:id/tree?ref=&path=",
+            "project_blob": "GET /api/projects/:id/blob?ref=&path=&highlight=&render=",
+            "project_raw": "GET /api/projects/:id/raw?ref=&path=&download=",
+            "project_blame": "GET /api/projects/:id/blame?ref=&path=",
+            "project_archive": "GET /api/projects/:id/archive?ref=&path=&format=tar.gz|zip",
+            "project_pulls": "GET /api/projects/:id/pulls?limit=",
+            "project_audit": "GET /api/projects/:id/audit?action=&limit=",
+            "project_health": "GET|POST /api/projects/:id/health?limit=",
+            "project_status": "GET /api/projects/:id/status?format= (project admin)",
+            "project_branches": "GET /api/projects/:id/branches",
+            "project_tags": "GET /api/projects/:id/tags",
+            "project_commit": "GET /api/projects/:id/commits/:hash?format=",
+            "project_compare": "GET /api/projects/:id/compare?base=&head=&format=&straight=",
+            "project_changelog": "GET /api/projects/:id/changelog?from=&to=&format=json|markdown",
+            "project_owners": "GET /api/projects/:id/owners?path=&ref=",
+            "project_members": "GET /api/projects/:id/members?q=&limit=",
+            "project_comments": "GET|POST /api/projects/:id/comments?commit=|path=&resolved=",
+            "project_comment": "PATCH|DELETE /api/projects/:id/comments/:comment_id",
+            "project_comment_revisions": "GET /api/projects/:id/comments/:comment_id/revisions",
+            "project_comment_resolve": "POST /api/projects/:id/comments/:comment_id/resolve",
+            "project_comment_unresolve": "POST /api/projects/:id/comments/:comment_id/unresolve",
+            "project_comment_reactions": "POST /api/projects/:id/comments/:comment_id/reactions",
+            "project_comment_reaction": "DELETE /api/projects/:id/comments/:comment_id/reactions/:emoji",
+            "project_code_search": "GET /api/projects/:id/search/code?q=&regex=&case_sensitive=&path=&ref=",
+            "project_languages": "GET /api/projects/:id/languages",
+            "project_languages_history": "GET /api/projects/:id/languages/history?limit=",
+            "project_stats_activity": "GET /api/projects/:id/stats/activity?interval=day|week&since=&until=",
+            "project_stats_authors": "GET /api/projects/:id/stats/authors?since=&until=&limit=",
+            "project_stats_heatmap": "GET /api/projects/:id/stats/heatmap?since=&until=&tz_offset=",
+            "project_lock": "GET|POST /api/projects/:id/lock",
+            "project_unlock": "POST /api/projects/:id/unlock",
+            "project_lock_history": "GET /api/projects/:id/lock/history?limit=",
+            "project_lock_requests": "GET|POST /api/projects/:id/lock/requests?status=",
+            "project_lock_request_review": "POST /api/projects/:id/lock/requests/:request_id/{approve,reject}",
+            "project_lock_hook": "GET|POST|DELETE /api/projects/:id/lock/hook",
+            "project_sync": "POST /api/projects/:id/sync",
+            "project_sync_history": "GET /api/projects/:id/sync/history?limit=",
+            "project_credentials": "GET|PUT|DELETE /api/projects/:id/credentials"
         },
         "features": [
             "Git repository monitoring",
@@ -58,6 +125,12 @@ pub async fn root_handler() -> Json<Value> {
 
 /// Handler de health check que verifica la conectividad de la base de datos
 ///
+/// Incluye además cuántos proyectos están en cada estado según su última
+/// revisión de salud (ver `GET /api/projects/:id/health`), contando solo los
+/// proyectos que puede ver quien consulta: sin token, los públicos. Los
+/// repositorios con problemas no cambian el código de respuesta, que solo
+/// depende de la base de datos.
+///
 /// # Endpoint
 /// `GET /health`
 ///
@@ -70,7 +143,8 @@ pub async fn root_handler() -> Json<Value> {
 /// {
 ///   "status": "ok",
 ///   "database": "healthy",
-///   "version": "0.1.0"
+///   "version": "0.1.0",
+///   "repositories": { "healthy": 5, "warning": 1, "failing": 0, "unchecked": 2 }
 /// }
 /// ```
 ///
@@ -84,6 +158,7 @@ pub async fn root_handler() -> Json<Value> {
 /// ```
 pub async fn health_handler(
     State(state): State<AppState>,
+    auth: Option<AuthUser>,
 ) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
     // Intentar ejecutar una query simple para verificar la conexión
     let db_healthy = sqlx::query("SELECT 1")
@@ -103,10 +178,21 @@ pub async fn health_handler(
         ));
     }
 
+    let user_id = auth.as_ref().map(|user| user.id);
+    let is_admin = auth.as_ref().is_some_and(AuthUser::is_admin);
+    let repositories = match health_summary(&state.db, user_id, is_admin).await {
+        Ok(summary) => Some(summary),
+        Err(e) => {
+            tracing::warn!("No se pudo resumir la salud de los repositorios: {}", e);
+            None
+        }
+    };
+
     Ok(Json(json!({
         "status": "ok",
         "database": "healthy",
-        "version": env!("CARGO_PKG_VERSION")
+        "version": env!("CARGO_PKG_VERSION"),
+        "repositories": repositories
     })))
 }
 
@@ -0,0 +1,519 @@
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    Json,
+};
+use serde::Deserialize;
+
+use super::projects::{ensure_project_contributor, ensure_project_visible};
+use super::{ApiError, AppState};
+use crate::db::locks;
+use crate::db::projects::find_project_by_id;
+use crate::middleware::AuthUser;
+use crate::models::{
+    CreateLockRequest, LockAction, LockHistoryResponse, LockHookStatus, LockProject, LockRequest,
+    LockRequestStatus, LockRequestsResponse, LockStatus, ReviewLockRequest, UnlockProject,
+};
+use crate::services::events::ProjectEvent;
+use crate::services::git;
+
+/// Longitud máxima del motivo de un bloqueo o del comentario de una revisión
+const MAX_REASON_LENGTH: usize = 1000;
+
+/// Parámetros de consulta de `GET /api/projects/:id/lock/history`
+#[derive(Debug, Deserialize)]
+pub struct LockHistoryQuery {
+    pub limit: Option<i64>,
+}
+
+/// Parámetros de consulta de `GET /api/projects/:id/lock/requests`
+#[derive(Debug, Deserialize)]
+pub struct LockRequestsQuery {
+    pub status: Option<LockRequestStatus>,
+}
+
+/// Handler que retorna el estado de bloqueo de un proyecto
+///
+/// # Endpoint
+/// `GET /api/projects/:id/lock`
+///
+/// # Response
+/// - **200 OK**: Estado de bloqueo
+/// - **404 Not Found**: Proyecto inexistente o no visible
+///
+/// # Example Response
+/// ```json
+/// {
+///   "project_id": 1,
+///   "is_blocked": true,
+///   "reason": "Migración de base de datos en curso",
+///   "locked_by": 1,
+///   "locked_by_username": "admin",
+///   "locked_at": "2025-10-21T03:15:42"
+/// }
+/// ```
+pub async fn lock_status_handler(
+    State(state): State<AppState>,
+    Path(project_id): Path<i32>,
+    auth: Option<AuthUser>,
+) -> Result<Json<LockStatus>, ApiError> {
+    ensure_project_visible(&state, project_id, auth.as_ref()).await?;
+    Ok(Json(locks::find_lock_status(&state.db, project_id).await?))
+}
+
+/// Handler que bloquea un proyecto (administradores)
+///
+/// Además de marcar el proyecto como bloqueado, crea el marcador que el hook
+/// Git consulta para rechazar pulls. Si no se puede escribir el marcador el
+/// bloqueo no se aplica.
+///
+/// # Endpoint
+/// `POST /api/projects/:id/lock`
+///
+/// # Request Body
+/// ```json
+/// { "reason": "Migración de base de datos en curso" }
+/// ```
+///
+/// # Response
+/// - **200 OK**: Nuevo estado de bloqueo
+/// - **400 Bad Request**: Motivo vacío o demasiado largo
+/// - **401 Unauthorized** / **403 Forbidden**: No autenticado o no administrador
+/// - **404 Not Found**: El proyecto no existe
+/// - **409 Conflict**: El proyecto ya está bloqueado
+pub async fn lock_handler(
+    State(state): State<AppState>,
+    Path(project_id): Path<i32>,
+    auth: AuthUser,
+    Json(body): Json<LockProject>,
+) -> Result<Json<LockStatus>, ApiError> {
+    auth.require_admin()?;
+    let reason = validate_text(&body.reason, "motivo")?;
+    let project = find_project_by_id(&state.db, project_id).await?;
+
+    let mut tx = state.db.begin().await?;
+    let status = locks::lock_project(&mut tx, project_id, auth.id, &reason, None).await?;
+    sync_lock_marker(project.repository_path, Some(reason)).await?;
+    tx.commit().await?;
+
+    publish_lock_changed(&state, LockAction::Locked, &status, auth.id);
+
+    Ok(Json(status))
+}
+
+/// Handler que desbloquea un proyecto (administradores)
+///
+/// # Endpoint
+/// `POST /api/projects/:id/unlock`
+///
+/// # Request Body (opcional)
+/// ```json
+/// { "reason": "Migración terminada" }
+/// ```
+///
+/// # Response
+/// - **200 OK**: Nuevo estado de bloqueo
+/// - **401 Unauthorized** / **403 Forbidden**: No autenticado o no administrador
+/// - **404 Not Found**: El proyecto no existe
+/// - **409 Conflict**: El proyecto no está bloqueado
+pub async fn unlock_handler(
+    State(state): State<AppState>,
+    Path(project_id): Path<i32>,
+    auth: AuthUser,
+    body: Option<Json<UnlockProject>>,
+) -> Result<Json<LockStatus>, ApiError> {
+    auth.require_admin()?;
+    let body = body.map(|Json(body)| body).unwrap_or_default();
+    let reason = match body
+        .reason
+        .as_deref()
+        .map(str::trim)
+        .filter(|r| !r.is_empty())
+    {
+        Some(reason) => Some(validate_text(reason, "motivo")?),
+        None => None,
+    };
+    let project = find_project_by_id(&state.db, project_id).await?;
+
+    let mut tx = state.db.begin().await?;
+    let status = locks::unlock_project(&mut tx, project_id, auth.id, reason.as_deref()).await?;
+    sync_lock_marker(project.repository_path, None).await?;
+    tx.commit().await?;
+
+    publish_lock_changed(&state, LockAction::Unlocked, &status, auth.id);
+
+    Ok(Json(status))
+}
+
+/// Handler que lista el historial de bloqueos de un proyecto
+///
+/// # Endpoint
+/// `GET /api/projects/:id/lock/history?limit=`
+///
+/// # Query Parameters
+/// - `limit` (opcional): Máximo de entradas, entre 1 y 200 (por defecto 50)
+///
+/// # Response
+/// - **200 OK**: Historial, del cambio más reciente al más antiguo
+/// - **401 Unauthorized**: No autenticado
+/// - **404 Not Found**: Proyecto inexistente o no visible
+pub async fn lock_history_handler(
+    State(state): State<AppState>,
+    Path(project_id): Path<i32>,
+    auth: AuthUser,
+    Query(query): Query<LockHistoryQuery>,
+) -> Result<Json<LockHistoryResponse>, ApiError> {
+    ensure_project_visible(&state, project_id, Some(&auth)).await?;
+    let limit = query.limit.unwrap_or(50).clamp(1, 200);
+    let history = locks::list_lock_history(&state.db, project_id, limit).await?;
+
+    Ok(Json(LockHistoryResponse { history }))
+}
+
+/// Handler con el que un usuario solicita bloquear un proyecto
+///
+/// La solicitud queda pendiente hasta que un administrador la apruebe o la
+/// rechace. Solo pueden pedirlo los colaboradores del proyecto y cada uno
+/// puede tener una sola solicitud pendiente por proyecto.
+///
+/// # Endpoint
+/// `POST /api/projects/:id/lock/requests`
+///
+/// # Request Body
+/// ```json
+/// { "reason": "Voy a reescribir el historial de la rama main" }
+/// ```
+///
+/// # Response
+/// - **201 Created**: Solicitud creada
+/// - **400 Bad Request**: Motivo vacío o demasiado largo
+/// - **401 Unauthorized**: No autenticado
+/// - **403 Forbidden**: No es colaborador del proyecto
+/// - **404 Not Found**: Proyecto inexistente o no visible
+/// - **409 Conflict**: El proyecto ya está bloqueado o ya hay una solicitud pendiente
+pub async fn create_lock_request_handler(
+    State(state): State<AppState>,
+    Path(project_id): Path<i32>,
+    auth: AuthUser,
+    Json(body): Json<CreateLockRequest>,
+) -> Result<(StatusCode, Json<LockRequest>), ApiError> {
+    ensure_project_contributor(&state, project_id, &auth).await?;
+    let reason = validate_text(&body.reason, "motivo")?;
+    let request = locks::create_lock_request(&state.db, project_id, auth.id, &reason).await?;
+
+    tracing::info!(
+        "Solicitud de bloqueo #{} del proyecto {} por {}",
+        request.id,
+        project_id,
+        auth.username
+    );
+    state.events.publish(ProjectEvent::LockRequested {
+        request: request.clone(),
+    });
+
+    Ok((StatusCode::CREATED, Json(request)))
+}
+
+/// Handler que lista las solicitudes de bloqueo de un proyecto (administradores)
+///
+/// # Endpoint
+/// `GET /api/projects/:id/lock/requests?status=`
+///
+/// # Query Parameters
+/// - `status` (opcional): `pending`, `approved` o `rejected`
+///
+/// # Response
+/// - **200 OK**: Solicitudes, de la más reciente a la más antigua
+/// - **401 Unauthorized** / **403 Forbidden**: No autenticado o no administrador
+/// - **404 Not Found**: El proyecto no existe
+pub async fn list_lock_requests_handler(
+    State(state): State<AppState>,
+    Path(project_id): Path<i32>,
+    auth: AuthUser,
+    Query(query): Query<LockRequestsQuery>,
+) -> Result<Json<LockRequestsResponse>, ApiError> {
+    auth.require_admin()?;
+    locks::find_lock_status(&state.db, project_id).await?;
+    let requests = locks::list_lock_requests(&state.db, project_id, query.status).await?;
+
+    Ok(Json(LockRequestsResponse { requests }))
+}
+
+/// Handler que aprueba una solicitud de bloqueo (administradores)
+///
+/// La aprobación bloquea el proyecto en la misma transacción, con el motivo
+/// de la solicitud.
+///
+/// # Endpoint
+/// `POST /api/projects/:id/lock/requests/:request_id/approve`
+///
+/// # Request Body (opcional)
+/// ```json
+/// { "comment": "Adelante, avisa al terminar" }
+/// ```
+///
+/// # Response
+/// - **200 OK**: Solicitud aprobada
+/// - **401 Unauthorized** / **403 Forbidden**: No autenticado o no administrador
+/// - **404 Not Found**: El proyecto o la solicitud no existen
+/// - **409 Conflict**: La solicitud ya fue revisada o el proyecto ya está bloqueado
+pub async fn approve_lock_request_handler(
+    State(state): State<AppState>,
+    Path((project_id, request_id)): Path<(i32, i32)>,
+    auth: AuthUser,
+    body: Option<Json<ReviewLockRequest>>,
+) -> Result<Json<LockRequest>, ApiError> {
+    auth.require_admin()?;
+    let comment = review_comment(body)?;
+    let project = find_project_by_id(&state.db, project_id).await?;
+
+    let mut tx = state.db.begin().await?;
+    let request = locks::review_lock_request(
+        &mut tx,
+        project_id,
+        request_id,
+        auth.id,
+        LockRequestStatus::Approved,
+        comment.as_deref(),
+    )
+    .await?;
+    let status = locks::lock_project(
+        &mut tx,
+        project_id,
+        auth.id,
+        &request.reason,
+        Some(request.id),
+    )
+    .await?;
+    sync_lock_marker(project.repository_path, Some(request.reason.clone())).await?;
+    tx.commit().await?;
+
+    state.events.publish(ProjectEvent::LockRequestReviewed {
+        request: request.clone(),
+    });
+    publish_lock_changed(&state, LockAction::Locked, &status, auth.id);
+
+    Ok(Json(request))
+}
+
+/// Handler que rechaza una solicitud de bloqueo (administradores)
+///
+/// # Endpoint
+/// `POST /api/projects/:id/lock/requests/:request_id/reject`
+///
+/// # Request Body (opcional)
+/// ```json
+/// { "comment": "Espera a que termine el release" }
+/// ```
+///
+/// # Response
+/// - **200 OK**: Solicitud rechazada
+/// - **401 Unauthorized** / **403 Forbidden**: No autenticado o no administrador
+/// - **404 Not Found**: La solicitud no existe
+/// - **409 Conflict**: La solicitud ya fue revisada
+pub async fn reject_lock_request_handler(
+    State(state): State<AppState>,
+    Path((project_id, request_id)): Path<(i32, i32)>,
+    auth: AuthUser,
+    body: Option<Json<ReviewLockRequest>>,
+) -> Result<Json<LockRequest>, ApiError> {
+    auth.require_admin()?;
+    let comment = review_comment(body)?;
+
+    let mut conn = state.db.acquire().await?;
+    let request = locks::review_lock_request(
+        &mut conn,
+        project_id,
+        request_id,
+        auth.id,
+        LockRequestStatus::Rejected,
+        comment.as_deref(),
+    )
+    .await?;
+
+    state.events.publish(ProjectEvent::LockRequestReviewed {
+        request: request.clone(),
+    });
+
+    Ok(Json(request))
+}
+
+/// Handler que informa si el hook de bloqueo está instalado (administradores)
+///
+/// # Endpoint
+/// `GET /api/projects/:id/lock/hook`
+///
+/// # Example Response
+/// ```json
+/// {
+///   "path": "/srv/git/project/.git/hooks/reference-transaction",
+///   "installed": true,
+///   "foreign_hook": false
+/// }
+/// ```
+pub async fn lock_hook_status_handler(
+    State(state): State<AppState>,
+    Path(project_id): Path<i32>,
+    auth: AuthUser,
+) -> Result<Json<LockHookStatus>, ApiError> {
+    auth.require_admin()?;
+    let project = find_project_by_id(&state.db, project_id).await?;
+
+    let status = tokio::task::spawn_blocking(move || {
+        git::lock_hook_status(&git::open_repository(&project.repository_path)?)
+    })
+    .await??;
+
+    Ok(Json(status))
+}
+
+/// Handler que instala el hook Git que rechaza pulls mientras el proyecto
+/// está bloqueado (administradores)
+///
+/// Se instala como `reference-transaction` (Git ≥ 2.28), que cubre `pull`,
+/// `fetch` sobre ramas locales, `merge`, `commit` y `reset`. Si el proyecto
+/// ya está bloqueado se crea también el marcador.
+///
+/// # Endpoint
+/// `POST /api/projects/:id/lock/hook`
+///
+/// # Response
+/// - **200 OK**: Estado del hook
+/// - **401 Unauthorized** / **403 Forbidden**: No autenticado o no administrador
+/// - **404 Not Found**: El proyecto no existe
+/// - **409 Conflict**: Ya existe un hook `reference-transaction` ajeno
+pub async fn install_lock_hook_handler(
+    State(state): State<AppState>,
+    Path(project_id): Path<i32>,
+    auth: AuthUser,
+) -> Result<Json<LockHookStatus>, ApiError> {
+    auth.require_admin()?;
+    let project = find_project_by_id(&state.db, project_id).await?;
+
+    let status = tokio::task::spawn_blocking(move || -> git::Result<LockHookStatus> {
+        let repo = git::open_repository(&project.repository_path)?;
+        let status = git::install_lock_hook(&repo)?;
+        if project.is_blocked {
+            git::write_lock_marker(&repo, project.lock_reason.as_deref().unwrap_or_default())?;
+        } else {
+            git::remove_lock_marker(&repo)?;
+        }
+        Ok(status)
+    })
+    .await??;
+
+    tracing::info!(
+        "Hook de bloqueo instalado en el proyecto {} por {}",
+        project_id,
+        auth.username
+    );
+
+    Ok(Json(status))
+}
+
+/// Handler que desinstala el hook de bloqueo (administradores)
+///
+/// # Endpoint
+/// `DELETE /api/projects/:id/lock/hook`
+///
+/// # Response
+/// - **200 OK**: Estado del hook
+/// - **401 Unauthorized** / **403 Forbidden**: No autenticado o no administrador
+/// - **404 Not Found**: El proyecto no existe
+/// - **409 Conflict**: El hook existente no es de LumaStack
+pub async fn uninstall_lock_hook_handler(
+    State(state): State<AppState>,
+    Path(project_id): Path<i32>,
+    auth: AuthUser,
+) -> Result<Json<LockHookStatus>, ApiError> {
+    auth.require_admin()?;
+    let project = find_project_by_id(&state.db, project_id).await?;
+
+    let status = tokio::task::spawn_blocking(move || {
+        git::uninstall_lock_hook(&git::open_repository(&project.repository_path)?)
+    })
+    .await??;
+
+    tracing::info!(
+        "Hook de bloqueo desinstalado del proyecto {} por {}",
+        project_id,
+        auth.username
+    );
+
+    Ok(Json(status))
+}
+
+/// Crea (`Some(motivo)`) o elimina (`None`) el marcador que consulta el hook
+async fn sync_lock_marker(repository_path: String, reason: Option<String>) -> Result<(), ApiError> {
+    tokio::task::spawn_blocking(move || -> git::Result<()> {
+        let repo = git::open_repository(&repository_path)?;
+        match reason {
+            Some(reason) => git::write_lock_marker(&repo, &reason),
+            None => git::remove_lock_marker(&repo),
+        }
+    })
+    .await??;
+
+    Ok(())
+}
+
+fn publish_lock_changed(state: &AppState, action: LockAction, status: &LockStatus, actor_id: i32) {
+    tracing::info!(
+        "Proyecto {} {} por el usuario {}",
+        status.project_id,
+        match action {
+            LockAction::Locked => "bloqueado",
+            LockAction::Unlocked => "desbloqueado",
+        },
+        actor_id
+    );
+    state.events.publish(ProjectEvent::LockChanged {
+        action,
+        status: status.clone(),
+        actor_id,
+    });
+}
+
+fn review_comment(body: Option<Json<ReviewLockRequest>>) -> Result<Option<String>, ApiError> {
+    let comment = body.and_then(|Json(body)| body.comment);
+    match comment.as_deref().map(str::trim).filter(|c| !c.is_empty()) {
+        Some(comment) => Ok(Some(validate_text(comment, "comentario")?)),
+        None => Ok(None),
+    }
+}
+
+fn validate_text(text: &str, field: &str) -> Result<String, ApiError> {
+    let text = text.trim();
+    if text.is_empty() {
+        return Err(ApiError::BadRequest(format!(
+            "El {} no puede estar vacío",
+            field
+        )));
+    }
+    if text.chars().count() > MAX_REASON_LENGTH {
+        return Err(ApiError::BadRequest(format!(
+            "El {} supera los {} caracteres",
+            field, MAX_REASON_LENGTH
+        )));
+    }
+    Ok(text.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_text() {
+        assert_eq!(validate_text("  motivo  ", "motivo").unwrap(), "motivo");
+        assert!(matches!(
+            validate_text("   ", "motivo"),
+            Err(ApiError::BadRequest(_))
+        ));
+        let long = "x".repeat(MAX_REASON_LENGTH + 1);
+        assert!(matches!(
+            validate_text(&long, "motivo"),
+            Err(ApiError::BadRequest(_))
+        ));
+    }
+}
@@ -1,4 +1,62 @@
+pub mod audit;
+pub mod auth;
+pub mod comments;
+pub mod commits;
+pub mod composition;
+pub mod error;
 pub mod health;
+pub mod locks;
+pub mod notifications;
+pub mod owners;
+pub mod projects;
+pub mod refs;
+pub mod repository;
+pub mod search;
+pub mod signatures;
+pub mod stats;
+pub mod status;
+pub mod sync;
 
 // Re-export para uso conveniente
+pub use audit::audit_log_handler;
+pub use auth::{login_handler, me_handler};
+pub use comments::{
+    add_reaction_handler, comment_revisions_handler, comments_handler, create_comment_handler,
+    delete_comment_handler, remove_reaction_handler, resolve_comment_handler,
+    unresolve_comment_handler, update_comment_handler,
+};
+pub use commits::{changelog_handler, commit_handler, compare_handler};
+pub use composition::{languages_handler, languages_history_handler};
+pub use error::ApiError;
 pub use health::{root_handler, health_handler, AppState};
+pub use locks::{
+    approve_lock_request_handler, create_lock_request_handler, install_lock_hook_handler,
+    list_lock_requests_handler, lock_handler, lock_history_handler, lock_hook_status_handler,
+    lock_status_handler, reject_lock_request_handler, uninstall_lock_hook_handler, unlock_handler,
+};
+pub use notifications::{
+    delete_notification_handler, mark_all_notifications_read_handler,
+    mark_notification_read_handler, notifications_handler, unread_count_handler,
+};
+pub use owners::{
+    delete_owner_mapping_handler, owner_mappings_handler, owners_handler,
+    set_owner_mapping_handler,
+};
+pub use projects::{
+    check_project_health_handler, delete_project_handler, project_handler, project_health_handler,
+    project_members_handler, pulls_handler, scan_projects_handler,
+};
+pub use refs::{branches_handler, tags_handler};
+pub use repository::{archive_handler, blame_handler, blob_handler, raw_handler, tree_handler};
+pub use search::{code_search_handler, search_handler};
+pub use signatures::{
+    create_signing_key_handler, delete_signing_key_handler, signing_keys_handler,
+};
+pub use stats::{
+    activity_stats_handler, author_stats_handler, dashboard_summary_handler, heatmap_handler,
+};
+pub use status::status_handler;
+pub use sync::{
+    credential_handler, delete_credential_handler, set_credential_handler, sync_handler,
+    sync_history_handler,
+};
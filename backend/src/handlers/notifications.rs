@@ -0,0 +1,178 @@
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    Json,
+};
+use serde::Deserialize;
+
+use super::{ApiError, AppState};
+use crate::db::notifications::{
+    count_unread_notifications, delete_notification, list_notifications,
+    mark_all_notifications_read, mark_notification_read, NotificationFilter,
+};
+use crate::middleware::AuthUser;
+use crate::models::{
+    MarkAllReadResponse, Notification, NotificationType, NotificationsResponse, UnreadCountResponse,
+};
+
+/// Notificaciones devueltas por defecto en `/notifications`
+const DEFAULT_NOTIFICATIONS_LIMIT: i64 = 20;
+
+/// Máximo de notificaciones que se pueden pedir en `/notifications`
+const MAX_NOTIFICATIONS_LIMIT: i64 = 100;
+
+/// Parámetros de consulta de las notificaciones
+#[derive(Debug, Deserialize)]
+pub struct NotificationsQuery {
+    /// `next_cursor` de la página anterior
+    pub cursor: Option<i32>,
+    /// Número de notificaciones a devolver (default: 20, máximo: 100)
+    pub limit: Option<i64>,
+    /// `commit`, `comment`, `mention`, `lock` o `working_copy`
+    #[serde(rename = "type")]
+    pub kind: Option<NotificationType>,
+    pub project_id: Option<i32>,
+    /// Solo las no leídas
+    pub unread: Option<bool>,
+}
+
+/// Handler que lista las notificaciones del usuario autenticado
+///
+/// Las notificaciones se devuelven de la más reciente a la más antigua.
+/// Para pedir la página siguiente se pasa en `cursor` el `next_cursor` de
+/// la respuesta, manteniendo los mismos filtros; las notificaciones nuevas
+/// no desplazan las páginas ya pedidas.
+///
+/// # Endpoint
+/// `GET /api/notifications?cursor=&limit=&type=&project_id=&unread=`
+///
+/// # Response
+/// - **200 OK**: Página de notificaciones
+/// - **400 Bad Request**: Parámetros inválidos (p. ej. `type` desconocido)
+/// - **401 Unauthorized**: No autenticado
+///
+/// # Example Response
+/// ```json
+/// {
+///   "notifications": [
+///     {
+///       "id": 41,
+///       "user_id": 3,
+///       "project_id": 1,
+///       "type": "mention",
+///       "title": "john te mencionó en lumastack",
+///       "message": "@jane ¿puedes revisar el cambio del build?",
+///       "link": "/projects/1/tree/src/build.rs#comment-12",
+///       "is_read": false,
+///       "created_at": "2025-02-04T09:12:00"
+///     }
+///   ],
+///   "next_cursor": 41
+/// }
+/// ```
+pub async fn notifications_handler(
+    State(state): State<AppState>,
+    Query(query): Query<NotificationsQuery>,
+    auth: AuthUser,
+) -> Result<Json<NotificationsResponse>, ApiError> {
+    let limit = query
+        .limit
+        .unwrap_or(DEFAULT_NOTIFICATIONS_LIMIT)
+        .clamp(1, MAX_NOTIFICATIONS_LIMIT);
+    let filter = NotificationFilter {
+        kind: query.kind,
+        project_id: query.project_id,
+        unread_only: query.unread.unwrap_or(false),
+    };
+
+    // Pedir una más para saber si hay página siguiente
+    let mut notifications =
+        list_notifications(&state.db, auth.id, filter, query.cursor, limit + 1).await?;
+    let next_cursor = if notifications.len() as i64 > limit {
+        notifications.truncate(limit as usize);
+        notifications.last().map(|notification| notification.id)
+    } else {
+        None
+    };
+
+    Ok(Json(NotificationsResponse {
+        notifications,
+        next_cursor,
+    }))
+}
+
+/// Handler que retorna cuántas notificaciones sin leer tiene el usuario
+///
+/// # Endpoint
+/// `GET /api/notifications/unread-count`
+///
+/// # Response
+/// - **200 OK**: `{ "unread": 3 }`
+/// - **401 Unauthorized**: No autenticado
+pub async fn unread_count_handler(
+    State(state): State<AppState>,
+    auth: AuthUser,
+) -> Result<Json<UnreadCountResponse>, ApiError> {
+    let unread = count_unread_notifications(&state.db, auth.id).await?;
+
+    Ok(Json(UnreadCountResponse { unread }))
+}
+
+/// Handler que marca una notificación como leída
+///
+/// # Endpoint
+/// `POST /api/notifications/:id/read`
+///
+/// # Response
+/// - **200 OK**: Notificación actualizada
+/// - **401 Unauthorized**: No autenticado
+/// - **404 Not Found**: La notificación no existe o es de otro usuario
+pub async fn mark_notification_read_handler(
+    State(state): State<AppState>,
+    Path(notification_id): Path<i32>,
+    auth: AuthUser,
+) -> Result<Json<Notification>, ApiError> {
+    let notification = mark_notification_read(&state.db, auth.id, notification_id)
+        .await?
+        .ok_or_else(|| ApiError::NotFound("Notificación no encontrada".to_string()))?;
+
+    Ok(Json(notification))
+}
+
+/// Handler que marca como leídas todas las notificaciones del usuario
+///
+/// # Endpoint
+/// `POST /api/notifications/read-all`
+///
+/// # Response
+/// - **200 OK**: `{ "updated": 5 }`, cuántas estaban sin leer
+/// - **401 Unauthorized**: No autenticado
+pub async fn mark_all_notifications_read_handler(
+    State(state): State<AppState>,
+    auth: AuthUser,
+) -> Result<Json<MarkAllReadResponse>, ApiError> {
+    let updated = mark_all_notifications_read(&state.db, auth.id).await?;
+
+    Ok(Json(MarkAllReadResponse { updated }))
+}
+
+/// Handler que borra una notificación
+///
+/// # Endpoint
+/// `DELETE /api/notifications/:id`
+///
+/// # Response
+/// - **204 No Content**: Notificación borrada
+/// - **401 Unauthorized**: No autenticado
+/// - **404 Not Found**: La notificación no existe o es de otro usuario
+pub async fn delete_notification_handler(
+    State(state): State<AppState>,
+    Path(notification_id): Path<i32>,
+    auth: AuthUser,
+) -> Result<StatusCode, ApiError> {
+    if !delete_notification(&state.db, auth.id, notification_id).await? {
+        return Err(ApiError::NotFound("Notificación no encontrada".to_string()));
+    }
+
+    Ok(StatusCode::NO_CONTENT)
+}
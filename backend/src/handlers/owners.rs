@@ -0,0 +1,260 @@
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    Json,
+};
+use serde::Deserialize;
+
+use super::projects::ensure_project_visible;
+use super::{ApiError, AppState};
+use crate::db::owners::{
+    delete_owner_mapping, list_owner_mappings, resolve_owners, upsert_owner_mapping,
+};
+use crate::db::projects::find_project_by_id;
+use crate::middleware::AuthUser;
+use crate::models::{
+    OwnerMapping, OwnerMappingsResponse, OwnersResponse, OwnershipRule, SetOwnerMapping,
+};
+use crate::services::git;
+
+/// Parámetros de consulta de los dueños de una ruta
+#[derive(Debug, Deserialize)]
+pub struct OwnersQuery {
+    /// Rama, tag o hash cuyo `CODEOWNERS` se usa; `HEAD` por defecto
+    #[serde(rename = "ref")]
+    pub git_ref: Option<String>,
+    /// Ruta relativa a la raíz
+    pub path: String,
+}
+
+/// Handler que retorna los dueños de una ruta según `CODEOWNERS`
+///
+/// Se lee el primer `CODEOWNERS` que exista en `.github/`, la raíz o
+/// `docs/` y se aplica la última regla que coincide con la ruta, como en
+/// GitHub. Cada dueño se resuelve a un usuario de LumaStack y/o a un
+/// usuario de Telegram mediante los mapeos de `/api/owner-mappings`, o por
+/// nombre de usuario o email si no hay mapeo.
+///
+/// # Endpoint
+/// `GET /api/projects/:id/owners?path=&ref=`
+///
+/// # Response
+/// - **200 OK**: Regla aplicada y dueños (vacío si la ruta no tiene dueño)
+/// - **400 Bad Request**: Ruta inválida
+/// - **404 Not Found**: Proyecto o referencia inexistente
+///
+/// # Example Response
+/// ```json
+/// {
+///   "commit": "9fceb02d0ae598e95dc970b74767f19372d61af8",
+///   "source": ".github/CODEOWNERS",
+///   "path": "src/handlers/auth.rs",
+///   "rule": { "line": 4, "pattern": "/src/handlers/" },
+///   "owners": [
+///     { "owner": "@jane", "user": { "id": 3, "username": "jane" }, "telegram_handle": null },
+///     { "owner": "@acme/backend", "user": null, "telegram_handle": "acme_backend" }
+///   ]
+/// }
+/// ```
+pub async fn owners_handler(
+    State(state): State<AppState>,
+    Path(project_id): Path<i32>,
+    Query(query): Query<OwnersQuery>,
+    auth: Option<AuthUser>,
+) -> Result<Json<OwnersResponse>, ApiError> {
+    ensure_project_visible(&state, project_id, auth.as_ref()).await?;
+    let project = find_project_by_id(&state.db, project_id).await?;
+
+    let (commit, source, path, rule) = tokio::task::spawn_blocking(move || -> git::Result<_> {
+        let repo = git::open_repository(&project.repository_path)?;
+        let commit = git::resolve_commit(&repo, query.git_ref.as_deref())?;
+        let path = git::normalize_path(Some(&query.path))?;
+        let codeowners = git::find_codeowners(&repo, &commit)?;
+
+        let source = codeowners.as_ref().map(|c| c.source.clone());
+        let rule = codeowners
+            .as_ref()
+            .and_then(|c| c.rule_for(&path))
+            .map(|rule| (rule.line, rule.pattern.clone(), rule.owners.clone()));
+
+        Ok((commit.id().to_string(), source, path, rule))
+    })
+    .await??;
+
+    let (rule, owners) = match rule {
+        Some((line, pattern, owners)) => (
+            Some(OwnershipRule { line, pattern }),
+            resolve_owners(&state.db, &owners).await?,
+        ),
+        None => (None, Vec::new()),
+    };
+
+    Ok(Json(OwnersResponse {
+        commit,
+        source,
+        path,
+        rule,
+        owners,
+    }))
+}
+
+/// Handler que lista los mapeos de dueños de `CODEOWNERS` (administradores)
+///
+/// # Endpoint
+/// `GET /api/owner-mappings`
+///
+/// # Response
+/// - **200 OK**: Mapeos con el usuario y el usuario de Telegram de cada dueño
+/// - **401 Unauthorized** / **403 Forbidden**: No autenticado o no administrador
+pub async fn owner_mappings_handler(
+    State(state): State<AppState>,
+    auth: AuthUser,
+) -> Result<Json<OwnerMappingsResponse>, ApiError> {
+    auth.require_admin()?;
+    let mappings = list_owner_mappings(&state.db).await?;
+
+    Ok(Json(OwnerMappingsResponse { mappings }))
+}
+
+/// Handler que crea o reemplaza el mapeo de un dueño (administradores)
+///
+/// Hace falta para los equipos (`@org/equipo`), para los dueños cuyo nombre
+/// no coincide con el usuario de LumaStack y para asociar un usuario de
+/// Telegram. El dueño no distingue mayúsculas.
+///
+/// # Endpoint
+/// `PUT /api/owner-mappings`
+///
+/// # Request Body
+/// ```json
+/// { "owner": "@acme/backend", "user_id": null, "telegram_handle": "@acme_backend" }
+/// ```
+///
+/// # Response
+/// - **200 OK**: Mapeo guardado
+/// - **400 Bad Request**: Dueño o usuario de Telegram inválidos, sin destino o usuario inexistente
+/// - **401 Unauthorized** / **403 Forbidden**: No autenticado o no administrador
+pub async fn set_owner_mapping_handler(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Json(body): Json<SetOwnerMapping>,
+) -> Result<Json<OwnerMapping>, ApiError> {
+    auth.require_admin()?;
+
+    let owner = body.owner.trim();
+    if owner.len() > 255 || !git::is_owner(owner) || owner.contains(char::is_whitespace) {
+        return Err(ApiError::BadRequest(format!(
+            "Dueño inválido: se espera @usuario, @org/equipo o un email ({})",
+            owner
+        )));
+    }
+    let telegram_handle = body
+        .telegram_handle
+        .as_deref()
+        .map(normalize_telegram_handle)
+        .transpose()?
+        .flatten();
+    if body.user_id.is_none() && telegram_handle.is_none() {
+        return Err(ApiError::BadRequest(
+            "Indica un usuario o un usuario de Telegram".to_string(),
+        ));
+    }
+
+    let mapping = match upsert_owner_mapping(
+        &state.db,
+        owner,
+        body.user_id,
+        telegram_handle.as_deref(),
+        auth.id,
+    )
+    .await
+    {
+        Ok(mapping) => mapping,
+        Err(sqlx::Error::Database(db_err)) if db_err.is_foreign_key_violation() => {
+            return Err(ApiError::BadRequest("El usuario no existe".to_string()))
+        }
+        Err(e) => return Err(e.into()),
+    };
+
+    tracing::info!(
+        "Mapeo de dueño {} guardado por {}",
+        mapping.owner,
+        auth.username
+    );
+
+    Ok(Json(mapping))
+}
+
+/// Handler que elimina el mapeo de un dueño (administradores)
+///
+/// # Endpoint
+/// `DELETE /api/owner-mappings/:mapping_id`
+///
+/// # Response
+/// - **204 No Content**: Mapeo eliminado
+/// - **401 Unauthorized** / **403 Forbidden**: No autenticado o no administrador
+/// - **404 Not Found**: El mapeo no existe
+pub async fn delete_owner_mapping_handler(
+    State(state): State<AppState>,
+    Path(mapping_id): Path<i32>,
+    auth: AuthUser,
+) -> Result<StatusCode, ApiError> {
+    auth.require_admin()?;
+
+    if !delete_owner_mapping(&state.db, mapping_id).await? {
+        return Err(ApiError::NotFound("El mapeo no existe".to_string()));
+    }
+
+    tracing::info!(
+        "Mapeo de dueño {} eliminado por {}",
+        mapping_id,
+        auth.username
+    );
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Valida un usuario de Telegram y le quita la `@` inicial
+///
+/// Retorna `None` si está vacío. Telegram admite de 5 a 32 caracteres
+/// alfanuméricos o `_`.
+fn normalize_telegram_handle(handle: &str) -> Result<Option<String>, ApiError> {
+    let handle = handle.trim();
+    let name = handle.strip_prefix('@').unwrap_or(handle);
+    if name.is_empty() {
+        return Ok(None);
+    }
+
+    let valid = (5..=32).contains(&name.len())
+        && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+    if !valid {
+        return Err(ApiError::BadRequest(format!(
+            "Usuario de Telegram inválido: {}",
+            handle
+        )));
+    }
+
+    Ok(Some(name.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize_telegram_handle() {
+        assert_eq!(
+            normalize_telegram_handle(" @jane_doe ").unwrap().as_deref(),
+            Some("jane_doe")
+        );
+        assert_eq!(
+            normalize_telegram_handle("acme_backend")
+                .unwrap()
+                .as_deref(),
+            Some("acme_backend")
+        );
+        assert_eq!(normalize_telegram_handle("@").unwrap(), None);
+        assert!(normalize_telegram_handle("@abc").is_err());
+        assert!(normalize_telegram_handle("jane-doe").is_err());
+    }
+}
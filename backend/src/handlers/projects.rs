@@ -0,0 +1,594 @@
+use std::path::PathBuf;
+
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    Json,
+};
+use serde::Deserialize;
+
+use super::{ApiError, AppState};
+use crate::db::health::{latest_health_check, list_health_checks};
+use crate::db::projects::{
+    can_view_project, create_project, delete_project, find_project_by_id, is_project_admin,
+    is_project_contributor, list_projects, search_project_members,
+};
+use crate::db::pulls::list_pull_events;
+use crate::middleware::AuthUser;
+use crate::models::{
+    CreateProject, HealthChecksResponse, ProjectMembersResponse, ProjectResponse,
+    ProjectScanResponse, PullEventsResponse, ReadmeResponse, RepositoryHealthCheck, ScanProjects,
+    ScanSkipReason, SkippedRepository,
+};
+use crate::services::events::ProjectEvent;
+use crate::services::{git, health, markdown};
+
+/// Pulls devueltos por defecto en `/pulls`
+const DEFAULT_PULLS_LIMIT: i64 = 50;
+
+/// Máximo de pulls que se pueden pedir en `/pulls`
+const MAX_PULLS_LIMIT: i64 = 200;
+
+/// Revisiones de salud devueltas por defecto en `/health`
+const DEFAULT_HEALTH_LIMIT: i64 = 20;
+
+/// Máximo de revisiones de salud que se pueden pedir en `/health`
+const MAX_HEALTH_LIMIT: i64 = 200;
+
+/// Miembros devueltos por defecto en `/members`
+const DEFAULT_MEMBERS_LIMIT: i64 = 10;
+
+/// Máximo de miembros que se pueden pedir en `/members`
+const MAX_MEMBERS_LIMIT: i64 = 50;
+
+/// Niveles de subdirectorios que recorre `/projects/scan`
+const SCAN_MAX_DEPTH: usize = 4;
+
+/// Longitud máxima del nombre de un proyecto (`projects.name`)
+const MAX_PROJECT_NAME_LENGTH: usize = 100;
+
+/// Parámetros de consulta del historial de pulls
+#[derive(Debug, Deserialize)]
+pub struct PullsQuery {
+    /// Número de pulls a devolver (default: 50, máximo: 200)
+    pub limit: Option<i64>,
+}
+
+/// Parámetros de consulta del historial de revisiones de salud
+#[derive(Debug, Deserialize)]
+pub struct HealthQuery {
+    /// Número de revisiones a devolver (default: 20, máximo: 200)
+    pub limit: Option<i64>,
+}
+
+/// Parámetros de consulta de los miembros de un proyecto
+#[derive(Debug, Deserialize)]
+pub struct MembersQuery {
+    /// Prefijo del nombre de usuario, sin distinguir mayúsculas
+    pub q: Option<String>,
+    /// Número de miembros a devolver (default: 10, máximo: 50)
+    pub limit: Option<i64>,
+}
+
+/// Handler que retorna el detalle de un proyecto
+///
+/// Incluye la rama por defecto, el commit al que apunta `HEAD` y el README
+/// de la raíz renderizado a HTML saneado. Los README Markdown resuelven sus
+/// enlaces e imágenes relativos contra `/blob` y `/raw` en ese mismo commit;
+/// los de texto plano se devuelven escapados dentro de un `<pre>`. Si el
+/// repositorio está vacío o no es accesible, estos campos son `null`.
+/// `health` es la última revisión de salud (ver `/health`).
+///
+/// # Endpoint
+/// `GET /api/projects/:id`
+///
+/// # Response
+/// - **200 OK**: Detalle del proyecto
+/// - **404 Not Found**: Proyecto inexistente o no visible
+///
+/// # Example Response
+/// ```json
+/// {
+///   "id": 1,
+///   "name": "lumastack",
+///   "path": "/srv/git/lumastack/.git",
+///   "description": "Monitor de repositorios",
+///   "is_public": false,
+///   "last_commit_hash": "9fceb02d0ae598e95dc970b74767f19372d61af8",
+///   "default_branch": "main",
+///   "last_scanned_at": "2025-10-21T03:15:40",
+///   "last_pull_at": "2025-10-21T03:15:40",
+///   "is_blocked": false,
+///   "lock_reason": null,
+///   "locked_by": null,
+///   "locked_at": null,
+///   "created_at": "2025-10-21T03:15:42",
+///   "updated_at": "2025-10-21T03:15:42",
+///   "readme": {
+///     "path": "README.md",
+///     "html": "<h1>LumaStack</h1>\n<p><img src=\"/api/projects/1/raw?ref=9fceb02...&amp;path=docs%2Flogo.png\" alt=\"logo\"></p>\n"
+///   },
+///   "health": null
+/// }
+/// ```
+pub async fn project_handler(
+    State(state): State<AppState>,
+    Path(project_id): Path<i32>,
+    auth: Option<AuthUser>,
+) -> Result<Json<ProjectResponse>, ApiError> {
+    ensure_project_visible(&state, project_id, auth.as_ref()).await?;
+    let project = find_project_by_id(&state.db, project_id).await?;
+    let repository_path = project.repository_path.clone();
+    let max_size = state.git.max_highlight_size;
+
+    let git_info = tokio::task::spawn_blocking(move || -> git::Result<_> {
+        let repo = git::open_repository(&repository_path)?;
+        let default_branch = git::default_branch(&repo)?;
+        let commit = match git::resolve_commit(&repo, None) {
+            Ok(commit) => commit,
+            Err(git::GitError::RevisionNotFound(_)) => return Ok((default_branch, None, None)),
+            Err(e) => return Err(e),
+        };
+        let commit_hash = commit.id().to_string();
+
+        let readme = git::find_readme(&repo, &commit, max_size)?.and_then(|(path, blob)| {
+            let text = git::decode_text(&blob.data, max_size)?;
+            let html = if markdown::is_markdown(&path) {
+                let links = markdown::RepositoryLinks {
+                    project_id,
+                    commit: &commit_hash,
+                    base_dir: "",
+                };
+                markdown::render_markdown(&text.content, Some(&links))
+            } else {
+                format!("<pre>{}</pre>", ammonia::clean_text(&text.content))
+            };
+            Some(ReadmeResponse { path, html })
+        });
+
+        Ok((default_branch, Some(commit_hash), readme))
+    })
+    .await?;
+
+    let mut response = ProjectResponse::from(project);
+    response.health = latest_health_check(&state.db, project_id).await?;
+    match git_info {
+        Ok((default_branch, last_commit_hash, readme)) => {
+            response.default_branch = default_branch;
+            response.last_commit_hash = last_commit_hash;
+            response.readme = readme;
+        }
+        Err(e) => {
+            tracing::warn!("No se pudo leer el repositorio del proyecto {}: {}", project_id, e);
+        }
+    }
+
+    Ok(Json(response))
+}
+
+/// Handler que registra los repositorios Git de un directorio (administradores)
+///
+/// Recorre el directorio (hasta 4 niveles) y crea un proyecto privado por
+/// cada repositorio nuevo, con el nombre de su carpeta. Los worktrees
+/// enlazados no se registran: comparten las ramas de su repositorio
+/// principal y se omiten con `linked_worktree`, indicando el proyecto de ese
+/// repositorio si existe. Tampoco se duplican repositorios que ya tienen
+/// proyecto, aunque estén registrados con otra ruta.
+///
+/// # Endpoint
+/// `POST /api/projects/scan`
+///
+/// # Request Body
+/// ```json
+/// { "path": "/srv/git" }
+/// ```
+///
+/// # Response
+/// - **200 OK**: Proyectos creados y repositorios omitidos
+/// - **400 Bad Request**: La ruta no es un directorio absoluto existente
+/// - **401 Unauthorized** / **403 Forbidden**: No autenticado o no administrador
+///
+/// # Example Response
+/// ```json
+/// {
+///   "created": [{ "id": 4, "name": "api", "path": "/srv/git/api/.git", ... }],
+///   "skipped": [
+///     { "path": "/srv/git/lumastack/.git", "reason": "already_registered", "project_id": 1 },
+///     { "path": "/srv/git/lumastack/.git/worktrees/hotfix", "reason": "linked_worktree", "project_id": 1 }
+///   ]
+/// }
+/// ```
+pub async fn scan_projects_handler(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Json(body): Json<ScanProjects>,
+) -> Result<Json<ProjectScanResponse>, ApiError> {
+    auth.require_admin()?;
+    let root = PathBuf::from(body.path.trim());
+    if !root.is_absolute() || !root.is_dir() {
+        return Err(ApiError::BadRequest(
+            "La ruta debe ser un directorio absoluto existente".to_string(),
+        ));
+    }
+
+    let projects = list_projects(&state.db).await?;
+    let (mut known, mut discovered) = tokio::task::spawn_blocking(move || {
+        let known: Vec<_> = projects
+            .iter()
+            .filter_map(|project| {
+                let repo = git::open_repository(&project.repository_path).ok()?;
+                Some((git::repository_identity(&repo).common_dir, project.id))
+            })
+            .collect();
+        (known, git::discover_repositories(&root, SCAN_MAX_DEPTH))
+    })
+    .await?;
+
+    // Los repositorios principales primero, para asociarles sus worktrees
+    discovered.sort_by_key(|repo| repo.identity.linked_worktree);
+
+    let mut created = Vec::new();
+    let mut skipped = Vec::new();
+    for repo in discovered {
+        let path = repo.git_dir.to_string_lossy().into_owned();
+        let owner = known
+            .iter()
+            .find(|(common_dir, _)| *common_dir == repo.identity.common_dir)
+            .map(|(_, id)| *id);
+
+        if repo.identity.linked_worktree || owner.is_some() {
+            let reason = if repo.identity.linked_worktree {
+                ScanSkipReason::LinkedWorktree
+            } else {
+                ScanSkipReason::AlreadyRegistered
+            };
+            skipped.push(SkippedRepository {
+                path,
+                reason,
+                project_id: owner,
+            });
+            continue;
+        }
+
+        let new_project = CreateProject {
+            name: project_name(&repo.git_dir),
+            repository_path: path.clone(),
+        };
+        match create_project(&state.db, new_project).await? {
+            Some(project) => {
+                known.push((repo.identity.common_dir, project.id));
+                created.push(project);
+            }
+            None => skipped.push(SkippedRepository {
+                path,
+                reason: ScanSkipReason::AlreadyRegistered,
+                project_id: None,
+            }),
+        }
+    }
+
+    // Se publican al final para que el watcher recargue una sola vez
+    for project in &created {
+        state.events.publish(ProjectEvent::ProjectCreated {
+            project: project.clone(),
+        });
+    }
+
+    tracing::info!(
+        "Escaneo de {} por {}: {} proyectos nuevos, {} repositorios omitidos",
+        body.path.trim(),
+        auth.username,
+        created.len(),
+        skipped.len()
+    );
+
+    let created = created.into_iter().map(ProjectResponse::from).collect();
+    Ok(Json(ProjectScanResponse { created, skipped }))
+}
+
+/// Handler que elimina un proyecto (administradores)
+///
+/// Borra también sus comentarios, pulls, bloqueos y demás datos; el
+/// repositorio en disco no se toca. Un proyecto bloqueado debe
+/// desbloquearse antes, para no dejar instalado el marcador que rechaza los pulls.
+///
+/// # Endpoint
+/// `DELETE /api/projects/:id`
+///
+/// # Response
+/// - **204 No Content**: Proyecto eliminado
+/// - **401 Unauthorized** / **403 Forbidden**: No autenticado o no administrador
+/// - **404 Not Found**: El proyecto no existe
+/// - **409 Conflict**: El proyecto está bloqueado
+pub async fn delete_project_handler(
+    State(state): State<AppState>,
+    Path(project_id): Path<i32>,
+    auth: AuthUser,
+) -> Result<StatusCode, ApiError> {
+    auth.require_admin()?;
+    let project = find_project_by_id(&state.db, project_id).await?;
+    if project.is_blocked {
+        return Err(ApiError::Conflict(
+            "Desbloquea el proyecto antes de eliminarlo".to_string(),
+        ));
+    }
+
+    delete_project(&state.db, project_id).await?;
+    state
+        .events
+        .publish(ProjectEvent::ProjectDeleted { project_id });
+
+    tracing::info!(
+        "Proyecto {} ({}) eliminado por {}",
+        project_id,
+        project.name,
+        auth.username
+    );
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Nombre inicial de un proyecto escaneado
+///
+/// Es la carpeta del working copy, o la del repositorio bare sin el sufijo `.git`.
+fn project_name(git_dir: &std::path::Path) -> String {
+    let dir = match git_dir.file_name() {
+        Some(name) if name == ".git" => git_dir.parent().unwrap_or(git_dir),
+        _ => git_dir,
+    };
+    let name = dir
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let name = name.strip_suffix(".git").unwrap_or(&name);
+
+    name.chars().take(MAX_PROJECT_NAME_LENGTH).collect()
+}
+
+/// Handler que retorna el historial de pulls detectados en un proyecto
+///
+/// Cada entrada corresponde a una rama cuya punta cambió, del pull más
+/// reciente al más antiguo.
+///
+/// # Endpoint
+/// `GET /api/projects/:id/pulls?limit=`
+///
+/// # Response
+/// - **200 OK**: Lista de pulls
+/// - **404 Not Found**: Proyecto inexistente o no visible
+///
+/// # Example Response
+/// ```json
+/// {
+///   "pulls": [
+///     {
+///       "id": 12,
+///       "project_id": 1,
+///       "ref_name": "refs/heads/main",
+///       "old_commit": "1a410efbd13591db07496601ebc7a059dd55cfe9",
+///       "new_commit": "9fceb02d0ae598e95dc970b74767f19372d61af8",
+///       "commit_count": 3,
+///       "detected_at": "2025-10-21T03:15:40"
+///     }
+///   ]
+/// }
+/// ```
+pub async fn pulls_handler(
+    State(state): State<AppState>,
+    Path(project_id): Path<i32>,
+    Query(query): Query<PullsQuery>,
+    auth: Option<AuthUser>,
+) -> Result<Json<PullEventsResponse>, ApiError> {
+    ensure_project_visible(&state, project_id, auth.as_ref()).await?;
+
+    let limit = query
+        .limit
+        .unwrap_or(DEFAULT_PULLS_LIMIT)
+        .clamp(1, MAX_PULLS_LIMIT);
+    let pulls = list_pull_events(&state.db, project_id, limit).await?;
+
+    Ok(Json(PullEventsResponse { pulls }))
+}
+
+/// Handler que retorna las últimas revisiones de salud del repositorio
+///
+/// Cada revisión comprueba la integridad de lo alcanzable desde las
+/// referencias, cuenta objetos sueltos, colgando y packs, y mira si el
+/// working copy tiene cambios o conflictos y si `HEAD` está desacoplado.
+/// `status` es el del problema más grave de `issues`.
+///
+/// # Endpoint
+/// `GET /api/projects/:id/health?limit=`
+///
+/// # Response
+/// - **200 OK**: Revisiones, de la más reciente a la más antigua (vacío si aún no se revisó)
+/// - **404 Not Found**: Proyecto inexistente o no visible
+///
+/// # Example Response
+/// ```json
+/// {
+///   "checks": [
+///     {
+///       "id": 31,
+///       "project_id": 1,
+///       "status": "warning",
+///       "integrity_ok": true,
+///       "integrity_complete": true,
+///       "objects_checked": 18342,
+///       "unreachable_objects": 12,
+///       "loose_objects": 7211,
+///       "loose_size": 30125568,
+///       "pack_count": 3,
+///       "pack_size": 88123392,
+///       "is_bare": false,
+///       "dirty_files": 0,
+///       "detached_head": false,
+///       "unmerged_files": [],
+///       "issues": [
+///         {
+///           "kind": "loose_objects",
+///           "severity": "warning",
+///           "message": "7211 objetos sueltos sin empaquetar; ejecuta `git gc`"
+///         }
+///       ],
+///       "triggered_by": null,
+///       "triggered_by_username": null,
+///       "duration_ms": 1840,
+///       "checked_at": "2025-10-21T03:15:42"
+///     }
+///   ]
+/// }
+/// ```
+pub async fn project_health_handler(
+    State(state): State<AppState>,
+    Path(project_id): Path<i32>,
+    Query(query): Query<HealthQuery>,
+    auth: Option<AuthUser>,
+) -> Result<Json<HealthChecksResponse>, ApiError> {
+    ensure_project_visible(&state, project_id, auth.as_ref()).await?;
+
+    let limit = query
+        .limit
+        .unwrap_or(DEFAULT_HEALTH_LIMIT)
+        .clamp(1, MAX_HEALTH_LIMIT);
+    let checks = list_health_checks(&state.db, project_id, limit).await?;
+
+    Ok(Json(HealthChecksResponse { checks }))
+}
+
+/// Handler que revisa la salud del repositorio en el momento
+///
+/// Hace la misma revisión que el servicio programado, la guarda a nombre
+/// del usuario y la devuelve. En repositorios grandes puede tardar varios
+/// segundos, por eso solo pueden lanzarla los administradores del proyecto.
+/// Si el repositorio no se puede leer la revisión se guarda como `failing`
+/// con un problema `repository_error`.
+///
+/// # Endpoint
+/// `POST /api/projects/:id/health`
+///
+/// # Response
+/// - **200 OK**: Revisión guardada (mismo formato que cada elemento de `GET /health`)
+/// - **401 Unauthorized**: No autenticado
+/// - **403 Forbidden**: No es administrador del proyecto
+/// - **404 Not Found**: Proyecto inexistente o no visible
+pub async fn check_project_health_handler(
+    State(state): State<AppState>,
+    Path(project_id): Path<i32>,
+    auth: AuthUser,
+) -> Result<Json<RepositoryHealthCheck>, ApiError> {
+    ensure_project_admin(&state, project_id, &auth).await?;
+    let project = find_project_by_id(&state.db, project_id).await?;
+
+    let check = health::check_project(&state.db, &state.health, &project, Some(auth.id)).await?;
+
+    tracing::info!(
+        "Revisión de salud del proyecto {} por {}: {:?}",
+        project_id,
+        auth.username,
+        check.status
+    );
+
+    Ok(Json(check))
+}
+
+/// Handler que busca miembros de un proyecto por prefijo de nombre
+///
+/// Pensado para autocompletar menciones `@usuario` en los comentarios:
+/// retorna los miembros activos cuyo nombre empieza por `q`, ordenados
+/// alfabéticamente. Sin `q` retorna los primeros miembros.
+///
+/// # Endpoint
+/// `GET /api/projects/:id/members?q=&limit=`
+///
+/// # Response
+/// - **200 OK**: Miembros que coinciden
+/// - **401 Unauthorized**: No autenticado
+/// - **404 Not Found**: Proyecto inexistente o no visible
+///
+/// # Example Response
+/// ```json
+/// {
+///   "members": [
+///     { "user_id": 3, "username": "jane", "role": "contributor" },
+///     { "user_id": 7, "username": "janet", "role": "viewer" }
+///   ]
+/// }
+/// ```
+pub async fn project_members_handler(
+    State(state): State<AppState>,
+    Path(project_id): Path<i32>,
+    Query(query): Query<MembersQuery>,
+    auth: AuthUser,
+) -> Result<Json<ProjectMembersResponse>, ApiError> {
+    ensure_project_visible(&state, project_id, Some(&auth)).await?;
+
+    let prefix = query.q.as_deref().map(str::trim).unwrap_or_default();
+    let prefix = prefix.strip_prefix('@').unwrap_or(prefix);
+    let limit = query
+        .limit
+        .unwrap_or(DEFAULT_MEMBERS_LIMIT)
+        .clamp(1, MAX_MEMBERS_LIMIT);
+    let members = search_project_members(&state.db, project_id, prefix, limit).await?;
+
+    Ok(Json(ProjectMembersResponse { members }))
+}
+
+/// Responde 404 si el proyecto no existe o no es visible para quien consulta
+///
+/// No se distingue entre ambos casos para no revelar qué proyectos privados existen.
+pub(crate) async fn ensure_project_visible(
+    state: &AppState,
+    project_id: i32,
+    auth: Option<&AuthUser>,
+) -> Result<(), ApiError> {
+    let user_id = auth.map(|user| user.id);
+    let is_admin = auth.is_some_and(AuthUser::is_admin);
+
+    if can_view_project(&state.db, project_id, user_id, is_admin).await? {
+        Ok(())
+    } else {
+        Err(ApiError::NotFound("Proyecto no encontrado".to_string()))
+    }
+}
+
+/// Responde 403 si quien consulta no administra el proyecto
+///
+/// Administran un proyecto los administradores globales y los miembros con
+/// rol `admin`. Si el proyecto no es visible se responde 404, como en
+/// [`ensure_project_visible`].
+pub(crate) async fn ensure_project_admin(
+    state: &AppState,
+    project_id: i32,
+    auth: &AuthUser,
+) -> Result<(), ApiError> {
+    ensure_project_visible(state, project_id, Some(auth)).await?;
+
+    if auth.is_admin() || is_project_admin(&state.db, project_id, auth.id).await? {
+        Ok(())
+    } else {
+        Err(ApiError::Forbidden(
+            "Se requiere ser administrador del proyecto".to_string(),
+        ))
+    }
+}
+
+/// Responde 403 si quien consulta no puede comentar en el proyecto
+///
+/// Pueden comentar los administradores globales y los miembros con rol
+/// `contributor` o `admin`. Si el proyecto no es visible se responde 404.
+pub(crate) async fn ensure_project_contributor(
+    state: &AppState,
+    project_id: i32,
+    auth: &AuthUser,
+) -> Result<(), ApiError> {
+    ensure_project_visible(state, project_id, Some(auth)).await?;
+
+    if auth.is_admin() || is_project_contributor(&state.db, project_id, auth.id).await? {
+        Ok(())
+    } else {
+        Err(ApiError::Forbidden(
+            "Se requiere ser colaborador del proyecto".to_string(),
+        ))
+    }
+}
@@ -0,0 +1,123 @@
+use axum::{
+    extract::{Path, State},
+    Json,
+};
+
+use super::projects::ensure_project_visible;
+use super::{ApiError, AppState};
+use crate::db::projects::find_project_by_id;
+use crate::middleware::AuthUser;
+use crate::models::{BranchesResponse, TagsResponse};
+use crate::services::git;
+
+/// Handler que lista las ramas locales de un proyecto
+///
+/// La rama por defecto (la de `HEAD`) va primera y el resto se ordena por
+/// fecha del último commit. Cada rama incluye cuántos commits lleva de
+/// ventaja y de retraso respecto a la rama por defecto y, si tiene una rama
+/// remota configurada, respecto a ella.
+///
+/// # Endpoint
+/// `GET /api/projects/:id/branches`
+///
+/// # Response
+/// - **200 OK**: Rama por defecto y lista de ramas
+/// - **404 Not Found**: Proyecto inexistente o no visible
+///
+/// # Example Response
+/// ```json
+/// {
+///   "default_branch": "main",
+///   "branches": [
+///     {
+///       "name": "feature/login",
+///       "is_default": false,
+///       "commit": {
+///         "hash": "9fceb02d0ae598e95dc970b74767f19372d61af8",
+///         "summary": "Add login form",
+///         "author_name": "Jane Doe",
+///         "author_email": "jane@example.com",
+///         "authored_at": "2025-10-21T03:15:42Z"
+///       },
+///       "last_commit_at": "2025-10-21T03:15:42Z",
+///       "ahead_by": 3,
+///       "behind_by": 1,
+///       "upstream": { "name": "origin/feature/login", "ahead_by": 1, "behind_by": 0 }
+///     }
+///   ]
+/// }
+/// ```
+pub async fn branches_handler(
+    State(state): State<AppState>,
+    Path(project_id): Path<i32>,
+    auth: Option<AuthUser>,
+) -> Result<Json<BranchesResponse>, ApiError> {
+    ensure_project_visible(&state, project_id, auth.as_ref()).await?;
+    let project = find_project_by_id(&state.db, project_id).await?;
+
+    let response = tokio::task::spawn_blocking(move || -> git::Result<BranchesResponse> {
+        let repo = git::open_repository(&project.repository_path)?;
+
+        Ok(BranchesResponse {
+            default_branch: git::default_branch(&repo)?,
+            branches: git::list_branches(&repo)?,
+        })
+    })
+    .await??;
+
+    Ok(Json(response))
+}
+
+/// Handler que lista los tags de un proyecto
+///
+/// Ordenados del más reciente al más antiguo. Los tags anotados incluyen
+/// su mensaje (notas de la versión) y quién lo creó.
+///
+/// # Endpoint
+/// `GET /api/projects/:id/tags`
+///
+/// # Response
+/// - **200 OK**: Lista de tags
+/// - **404 Not Found**: Proyecto inexistente o no visible
+///
+/// # Example Response
+/// ```json
+/// {
+///   "tags": [
+///     {
+///       "name": "v1.0.0",
+///       "commit": {
+///         "hash": "9fceb02d0ae598e95dc970b74767f19372d61af8",
+///         "summary": "Release 1.0.0",
+///         "author_name": "Jane Doe",
+///         "author_email": "jane@example.com",
+///         "authored_at": "2025-10-21T03:15:42Z"
+///       },
+///       "annotated": true,
+///       "message": "Primera versión estable\n",
+///       "tagger_name": "Jane Doe",
+///       "tagger_email": "jane@example.com",
+///       "tagged_at": "2025-10-21T03:20:00Z"
+///     }
+///   ]
+/// }
+/// ```
+pub async fn tags_handler(
+    State(state): State<AppState>,
+    Path(project_id): Path<i32>,
+    auth: Option<AuthUser>,
+) -> Result<Json<TagsResponse>, ApiError> {
+    ensure_project_visible(&state, project_id, auth.as_ref()).await?;
+    let project = find_project_by_id(&state.db, project_id).await?;
+
+    let response = tokio::task::spawn_blocking(move || -> git::Result<TagsResponse> {
+        let repo = git::open_repository(&project.repository_path)?;
+
+        Ok(TagsResponse {
+            tags: git::list_tags(&repo)?,
+        })
+    })
+    .await??;
+
+    Ok(Json(response))
+}
@@ -0,0 +1,776 @@
+use axum::{
+    body::Body,
+    extract::{Path, Query, State},
+    http::{header, HeaderMap, HeaderValue, StatusCode},
+    response::{IntoResponse, Response},
+    Json,
+};
+use serde::Deserialize;
+use serde_json::json;
+use std::io::{self, Write};
+use std::sync::Arc;
+use tokio::sync::mpsc;
+use tokio_stream::wrappers::ReceiverStream;
+
+use super::projects::ensure_project_visible;
+use super::{ApiError, AppState};
+use crate::db::audit::record_audit;
+use crate::db::projects::{find_project_by_id, list_visible_projects};
+use crate::middleware::AuthUser;
+use crate::models::{
+    ArchiveFormat, AuditAction, BlameResponse, BlobResponse, CreateAuditEntry, Project,
+    TreeEntry, TreeResponse,
+};
+use crate::services::{git, highlight, language, markdown};
+use crate::utils::stream::{ChannelWriter, Chunk};
+use crate::utils::url::{percent_encode, raw_url};
+
+/// Bloques del archivo en vuelo antes de frenar su generación
+const ARCHIVE_STREAM_BUFFER: usize = 16;
+
+/// Parámetros de consulta para navegar el árbol de un repositorio
+#[derive(Debug, Deserialize)]
+pub struct TreeQuery {
+    /// Rama, tag o hash; `HEAD` por defecto
+    #[serde(rename = "ref")]
+    pub git_ref: Option<String>,
+    /// Directorio relativo a la raíz; raíz por defecto
+    pub path: Option<String>,
+}
+
+/// Parámetros de consulta para leer un archivo
+#[derive(Debug, Deserialize)]
+pub struct BlobQuery {
+    /// Rama, tag o hash; `HEAD` por defecto
+    #[serde(rename = "ref")]
+    pub git_ref: Option<String>,
+    /// Ruta del archivo relativa a la raíz
+    pub path: String,
+    /// Fuerza `Content-Disposition: attachment` (solo `/raw`)
+    #[serde(default)]
+    pub download: bool,
+    /// Incluye `highlighted_html` con resaltado de sintaxis (solo `/blob`)
+    #[serde(default)]
+    pub highlight: bool,
+    /// Incluye `rendered_html` para archivos Markdown (solo `/blob`)
+    #[serde(default)]
+    pub render: bool,
+}
+
+/// Parámetros de consulta para descargar un directorio
+#[derive(Debug, Deserialize)]
+pub struct ArchiveQuery {
+    /// Rama, tag o hash; `HEAD` por defecto
+    #[serde(rename = "ref")]
+    pub git_ref: Option<String>,
+    /// Directorio relativo a la raíz; raíz por defecto
+    pub path: Option<String>,
+    /// `tar.gz` (por defecto) o `zip`
+    #[serde(default)]
+    pub format: ArchiveFormat,
+}
+
+/// Parámetros de consulta para el blame de un archivo
+#[derive(Debug, Deserialize)]
+pub struct BlameQuery {
+    /// Rama, tag o hash; `HEAD` por defecto
+    #[serde(rename = "ref")]
+    pub git_ref: Option<String>,
+    /// Ruta del archivo relativa a la raíz
+    pub path: String,
+}
+
+/// Handler que lista el contenido de un directorio en una revisión
+///
+/// Los submódulos incluyen la URL de `.gitmodules`, el commit fijado y, si
+/// el repositorio también está monitoreado y es visible para quien
+/// consulta, el ID de ese proyecto. Los punteros de Git LFS incluyen el
+/// objeto y si está descargado en el servidor.
+///
+/// # Endpoint
+/// `GET /api/projects/:id/tree?ref=&path=`
+///
+/// # Response
+/// - **200 OK**: Entradas del directorio con tipo, tamaño, modo y último commit
+/// - **400 Bad Request**: Ruta inválida (p. ej. con `..`) o que no es un directorio
+/// - **404 Not Found**: Proyecto inexistente o no visible, referencia o ruta inexistente
+///
+/// # Example Response
+/// ```json
+/// {
+///   "ref": "main",
+///   "commit": "9fceb02d0ae598e95dc970b74767f19372d61af8",
+///   "path": "src",
+///   "entries": [
+///     {
+///       "name": "main.rs",
+///       "path": "src/main.rs",
+///       "type": "file",
+///       "size": 2048,
+///       "mode": "100644",
+///       "oid": "e69de29bb2d1d6434b8b29ae775ad8c2e48c5391",
+///       "last_commit": {
+///         "hash": "9fceb02d0ae598e95dc970b74767f19372d61af8",
+///         "summary": "Add health endpoint",
+///         "author_name": "Jane Doe",
+///         "author_email": "jane@example.com",
+///         "authored_at": "2025-10-21T03:15:42Z"
+///       }
+///     },
+///     {
+///       "name": "vendor-core",
+///       "path": "src/vendor-core",
+///       "type": "submodule",
+///       "size": null,
+///       "mode": "160000",
+///       "oid": "1a410efbd13591db07496601ebc7a059dd55cfe9",
+///       "submodule": {
+///         "url": "https://github.com/acme/core.git",
+///         "commit": "1a410efbd13591db07496601ebc7a059dd55cfe9",
+///         "project_id": 4
+///       },
+///       "last_commit": null
+///     }
+///   ]
+/// }
+/// ```
+pub async fn tree_handler(
+    State(state): State<AppState>,
+    Path(project_id): Path<i32>,
+    Query(query): Query<TreeQuery>,
+    auth: Option<AuthUser>,
+) -> Result<Json<TreeResponse>, ApiError> {
+    ensure_project_visible(&state, project_id, auth.as_ref()).await?;
+    let project = find_project_by_id(&state.db, project_id).await?;
+
+    let mut response = tokio::task::spawn_blocking(move || -> git::Result<TreeResponse> {
+        let repo = git::open_repository(&project.repository_path)?;
+        let commit = git::resolve_commit(&repo, query.git_ref.as_deref())?;
+        let path = git::normalize_path(query.path.as_deref())?;
+        let entries = git::list_tree(&repo, &commit, &path)?;
+
+        Ok(TreeResponse {
+            git_ref: query.git_ref.unwrap_or_else(|| "HEAD".to_string()),
+            commit: commit.id().to_string(),
+            path,
+            entries,
+        })
+    })
+    .await??;
+
+    let has_submodules = response
+        .entries
+        .iter()
+        .any(|e| e.submodule.as_ref().is_some_and(|s| s.url.is_some()));
+    if has_submodules {
+        let user_id = auth.as_ref().map(|user| user.id);
+        let is_admin = auth.as_ref().is_some_and(AuthUser::is_admin);
+        let mut projects = list_visible_projects(&state.db, user_id, is_admin).await?;
+        projects.retain(|p| p.id != project_id);
+
+        response.entries = tokio::task::spawn_blocking(move || {
+            let mut entries = response.entries;
+            link_submodules(&mut entries, &projects);
+            entries
+        })
+        .await?;
+    }
+
+    Ok(Json(response))
+}
+
+/// Enlaza los submódulos con los proyectos que monitorean su repositorio
+///
+/// Un proyecto coincide si su ruta en disco o la URL de alguno de sus
+/// remotos es la URL del submódulo.
+fn link_submodules(entries: &mut [TreeEntry], projects: &[Project]) {
+    let locations = git::project_locations(projects);
+    for submodule in entries.iter_mut().filter_map(|e| e.submodule.as_mut()) {
+        if let Some(url) = &submodule.url {
+            submodule.project_id = git::match_project(url, &locations);
+        }
+    }
+}
+
+/// Handler que retorna el contenido de un archivo como texto
+///
+/// Detecta si el archivo es binario, su codificación y su lenguaje. El
+/// texto se devuelve convertido a UTF-8 y recortado a `GIT_MAX_BLOB_SIZE`
+/// bytes; los binarios no incluyen contenido y deben pedirse a `raw_url`.
+/// Con `highlight=true` se añade el HTML resaltado y con `render=true` el
+/// Markdown renderizado, ambos solo para archivos de hasta
+/// `GIT_MAX_HIGHLIGHT_SIZE` bytes que no se hayan truncado. Los punteros
+/// de Git LFS se sustituyen por el archivo real si está en el almacén LFS
+/// del servidor; `lfs` indica el objeto y si estaba disponible.
+///
+/// # Endpoint
+/// `GET /api/projects/:id/blob?ref=&path=&highlight=&render=`
+///
+/// # Response
+/// - **200 OK**: Metadatos y contenido del archivo
+/// - **400 Bad Request**: Ruta inválida o que no es un archivo
+/// - **404 Not Found**: Proyecto inexistente o no visible, referencia o ruta inexistente
+/// - **413 Payload Too Large**: El archivo supera `GIT_MAX_RAW_SIZE`
+///
+/// # Example Response
+/// ```json
+/// {
+///   "ref": "main",
+///   "commit": "9fceb02d0ae598e95dc970b74767f19372d61af8",
+///   "path": "src/main.rs",
+///   "name": "main.rs",
+///   "oid": "e69de29bb2d1d6434b8b29ae775ad8c2e48c5391",
+///   "size": 2048,
+///   "mode": "100644",
+///   "mime_type": "text/x-rust",
+///   "binary": false,
+///   "is_symlink": false,
+///   "encoding": "UTF-8",
+///   "language": "Rust",
+///   "content": "fn main() {}\n",
+///   "truncated": false,
+///   "raw_url": "/api/projects/1/raw?ref=9fceb02d0ae598e95dc970b74767f19372d61af8&path=src%2Fmain.rs"
+/// }
+/// ```
+pub async fn blob_handler(
+    State(state): State<AppState>,
+    Path(project_id): Path<i32>,
+    Query(query): Query<BlobQuery>,
+    auth: Option<AuthUser>,
+) -> Result<Json<BlobResponse>, ApiError> {
+    ensure_project_visible(&state, project_id, auth.as_ref()).await?;
+    let project = find_project_by_id(&state.db, project_id).await?;
+    let config = state.git.clone();
+
+    let response = tokio::task::spawn_blocking(move || -> git::Result<BlobResponse> {
+        let repo = git::open_repository(&project.repository_path)?;
+        let commit = git::resolve_commit(&repo, query.git_ref.as_deref())?;
+        let path = git::normalize_path(Some(&query.path))?;
+        let blob = git::read_blob(&repo, &commit, &path, config.max_raw_size)?;
+        let (blob, lfs) = git::smudge_lfs(&repo, blob, config.max_raw_size)?;
+
+        let decoded = git::decode_text(&blob.data, config.max_blob_size);
+        let name = path.rsplit('/').next().unwrap_or(&path).to_string();
+        let commit_hash = commit.id().to_string();
+
+        let renderable = decoded
+            .as_ref()
+            .filter(|d| !d.truncated && blob.size() <= config.max_highlight_size && !blob.is_symlink())
+            .map(|d| d.content.as_str());
+        let highlighted_html = renderable
+            .filter(|_| query.highlight)
+            .and_then(|content| highlight::highlight_html(&path, content));
+        let rendered_html = renderable
+            .filter(|_| query.render && markdown::is_markdown(&path))
+            .map(|content| {
+                let base_dir = path.rsplit_once('/').map(|(dir, _)| dir).unwrap_or_default();
+                let links = markdown::RepositoryLinks {
+                    project_id: project.id,
+                    commit: &commit_hash,
+                    base_dir,
+                };
+                markdown::render_markdown(content, Some(&links))
+            });
+
+        Ok(BlobResponse {
+            git_ref: query.git_ref.unwrap_or_else(|| "HEAD".to_string()),
+            raw_url: raw_url(project.id, &commit_hash, &path),
+            highlighted_html,
+            rendered_html,
+            commit: commit_hash,
+            name,
+            oid: blob.oid.to_string(),
+            size: blob.size(),
+            mode: format!("{:06o}", blob.mode),
+            mime_type: mime_guess::from_path(&path)
+                .first_or_octet_stream()
+                .essence_str()
+                .to_string(),
+            binary: decoded.is_none(),
+            is_symlink: blob.is_symlink(),
+            encoding: decoded.as_ref().map(|d| d.encoding.to_string()),
+            language: language::detect_language_with_content(&path, &blob.data).map(str::to_string),
+            truncated: decoded.as_ref().is_some_and(|d| d.truncated),
+            content: decoded.map(|d| d.content),
+            lfs,
+            path,
+        })
+    })
+    .await??;
+
+    Ok(Json(response))
+}
+
+/// Handler que sirve el contenido original de un archivo
+///
+/// Imágenes (excepto SVG), PDF, audio y vídeo se sirven `inline` con su
+/// tipo MIME para permitir la vista previa; el texto se sirve como
+/// `text/plain` y el resto como descarga. Todas las respuestas llevan
+/// `X-Content-Type-Options: nosniff` y una CSP `sandbox` para que ningún
+/// contenido del repositorio pueda ejecutar scripts en el origen de la API.
+/// Soporta peticiones `Range` de un único rango y revalidación por `ETag`.
+/// Los punteros de Git LFS se sirven con el archivo real cuando está en el
+/// almacén LFS del servidor.
+///
+/// # Endpoint
+/// `GET /api/projects/:id/raw?ref=&path=&download=`
+///
+/// # Response
+/// - **200 OK**: Contenido completo
+/// - **206 Partial Content**: Rango solicitado con `Range: bytes=...`
+/// - **304 Not Modified**: El `If-None-Match` coincide con el OID del archivo
+/// - **400 Bad Request**: Ruta inválida o que no es un archivo
+/// - **404 Not Found**: Proyecto inexistente o no visible, referencia o ruta inexistente
+/// - **413 Payload Too Large**: El archivo supera `GIT_MAX_RAW_SIZE`
+/// - **416 Range Not Satisfiable**: El rango queda fuera del archivo
+pub async fn raw_handler(
+    State(state): State<AppState>,
+    Path(project_id): Path<i32>,
+    Query(query): Query<BlobQuery>,
+    headers: HeaderMap,
+    auth: Option<AuthUser>,
+) -> Result<Response, ApiError> {
+    ensure_project_visible(&state, project_id, auth.as_ref()).await?;
+    let project = find_project_by_id(&state.db, project_id).await?;
+    let limit = state.git.max_raw_size;
+    let download = query.download;
+
+    let (path, blob) = tokio::task::spawn_blocking(move || -> git::Result<_> {
+        let repo = git::open_repository(&project.repository_path)?;
+        let commit = git::resolve_commit(&repo, query.git_ref.as_deref())?;
+        let path = git::normalize_path(Some(&query.path))?;
+        let blob = git::read_blob(&repo, &commit, &path, limit)?;
+        let (blob, _) = git::smudge_lfs(&repo, blob, limit)?;
+        Ok((path, blob))
+    })
+    .await??;
+
+    let etag = format!("\"{}\"", blob.oid);
+    let size = blob.size();
+    let (content_type, inline) = raw_content_type(&path, &blob.data);
+    let name = path.rsplit('/').next().unwrap_or(&path);
+    let disposition = content_disposition(name, inline && !download);
+
+    let mut response_headers = HeaderMap::new();
+    response_headers.insert(header::ETAG, header_value(&etag));
+    response_headers.insert(header::ACCEPT_RANGES, HeaderValue::from_static("bytes"));
+    response_headers.insert(header::CACHE_CONTROL, HeaderValue::from_static("private, no-cache"));
+    response_headers.insert(header::X_CONTENT_TYPE_OPTIONS, HeaderValue::from_static("nosniff"));
+    response_headers.insert(
+        header::CONTENT_SECURITY_POLICY,
+        HeaderValue::from_static("default-src 'none'; style-src 'unsafe-inline'; sandbox"),
+    );
+
+    let not_modified = headers
+        .get(header::IF_NONE_MATCH)
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|v| v.split(',').any(|tag| tag.trim() == etag || tag.trim() == "*"));
+    if not_modified {
+        return Ok((StatusCode::NOT_MODIFIED, response_headers).into_response());
+    }
+
+    response_headers.insert(header::CONTENT_TYPE, header_value(&content_type));
+    response_headers.insert(header::CONTENT_DISPOSITION, header_value(&disposition));
+
+    let range = headers
+        .get(header::RANGE)
+        .and_then(|v| v.to_str().ok())
+        .map(|v| parse_range(v, size))
+        .unwrap_or(ByteRange::Full);
+
+    let response = match range {
+        ByteRange::Full => (StatusCode::OK, response_headers, Body::from(blob.data)).into_response(),
+        ByteRange::Partial(start, end) => {
+            response_headers.insert(
+                header::CONTENT_RANGE,
+                header_value(&format!("bytes {}-{}/{}", start, end, size)),
+            );
+            let data = blob.data[start as usize..=end as usize].to_vec();
+            (StatusCode::PARTIAL_CONTENT, response_headers, Body::from(data)).into_response()
+        }
+        ByteRange::Unsatisfiable => {
+            response_headers.remove(header::CONTENT_DISPOSITION);
+            response_headers.insert(header::CONTENT_RANGE, header_value(&format!("bytes */{}", size)));
+            (StatusCode::RANGE_NOT_SATISFIABLE, response_headers).into_response()
+        }
+    };
+
+    Ok(response)
+}
+
+/// Handler que retorna el blame de un archivo en una revisión
+///
+/// Devuelve rangos de líneas consecutivas con el commit que las introdujo.
+/// Los autores se resuelven con el `.mailmap` del repositorio. Como el
+/// cálculo es costoso, los resultados se guardan en una caché LRU en
+/// memoria por proyecto, commit resuelto y blob (`GIT_BLAME_CACHE_SIZE`).
+///
+/// # Endpoint
+/// `GET /api/projects/:id/blame?ref=&path=`
+///
+/// # Response
+/// - **200 OK**: Rangos de líneas con su commit
+/// - **400 Bad Request**: Ruta inválida o que no es un archivo
+/// - **404 Not Found**: Proyecto inexistente o no visible, referencia o ruta inexistente
+/// - **413 Payload Too Large**: El archivo supera `GIT_MAX_BLOB_SIZE`
+///
+/// # Example Response
+/// ```json
+/// {
+///   "ref": "main",
+///   "commit": "9fceb02d0ae598e95dc970b74767f19372d61af8",
+///   "path": "src/main.rs",
+///   "oid": "e69de29bb2d1d6434b8b29ae775ad8c2e48c5391",
+///   "ranges": [
+///     {
+///       "start_line": 1,
+///       "end_line": 12,
+///       "commit": {
+///         "hash": "1a410efbd13591db07496601ebc7a059dd55cfe9",
+///         "summary": "Initial commit",
+///         "author_name": "Jane Doe",
+///         "author_email": "jane@example.com",
+///         "authored_at": "2025-10-20T18:02:11Z"
+///       },
+///       "original_start_line": 1
+///     }
+///   ]
+/// }
+/// ```
+pub async fn blame_handler(
+    State(state): State<AppState>,
+    Path(project_id): Path<i32>,
+    Query(query): Query<BlameQuery>,
+    auth: Option<AuthUser>,
+) -> Result<Json<BlameResponse>, ApiError> {
+    ensure_project_visible(&state, project_id, auth.as_ref()).await?;
+    let project = find_project_by_id(&state.db, project_id).await?;
+    let limit = state.git.max_blob_size;
+    let cache = state.blame_cache.clone();
+
+    let response = tokio::task::spawn_blocking(move || -> git::Result<BlameResponse> {
+        let repo = git::open_repository(&project.repository_path)?;
+        let commit = git::resolve_commit(&repo, query.git_ref.as_deref())?;
+        let path = git::normalize_path(Some(&query.path))?;
+        let blob = git::read_blob(&repo, &commit, &path, limit)?;
+
+        let key = git::BlameKey {
+            project_id,
+            commit: commit.id(),
+            blob: blob.oid,
+            path: path.clone(),
+        };
+        let ranges = match cache.get(&key) {
+            Some(ranges) => ranges,
+            None => {
+                let ranges = Arc::new(git::blame_file(&repo, &commit, &path)?);
+                cache.insert(key, ranges.clone());
+                ranges
+            }
+        };
+
+        Ok(BlameResponse {
+            git_ref: query.git_ref.unwrap_or_else(|| "HEAD".to_string()),
+            commit: commit.id().to_string(),
+            path,
+            oid: blob.oid.to_string(),
+            ranges: ranges.as_ref().clone(),
+        })
+    })
+    .await??;
+
+    Ok(Json(response))
+}
+
+/// Handler que descarga un directorio de una revisión como `.tar.gz` o `.zip`
+///
+/// El archivo se genera a partir de la base de objetos (sin tocar el
+/// working copy) y se envía a medida que se escribe, sin montarlo en
+/// memoria. Todas las entradas cuelgan de un directorio
+/// `<proyecto>-<ref>`; los submódulos aparecen como directorios vacíos.
+/// Antes de empezar se suma el tamaño de los archivos y se rechaza la
+/// descarga si supera `GIT_MAX_ARCHIVE_SIZE`, o si es un `.zip` con algún
+/// archivo mayor que `GIT_MAX_RAW_SIZE`. Cada descarga aceptada queda
+/// registrada en el log de auditoría. Sin token solo se pueden descargar
+/// proyectos públicos.
+///
+/// # Endpoint
+/// `GET /api/projects/:id/archive?ref=&path=&format=tar.gz|zip`
+///
+/// # Response
+/// - **200 OK**: Archivo como adjunto (`application/gzip` o `application/zip`)
+/// - **400 Bad Request**: Ruta inválida o que no es un directorio
+/// - **404 Not Found**: Proyecto inexistente o no visible, referencia o ruta inexistente
+/// - **413 Payload Too Large**: El contenido supera `GIT_MAX_ARCHIVE_SIZE`, o un
+///   archivo del `.zip` supera `GIT_MAX_RAW_SIZE`
+pub async fn archive_handler(
+    State(state): State<AppState>,
+    Path(project_id): Path<i32>,
+    Query(query): Query<ArchiveQuery>,
+    auth: Option<AuthUser>,
+) -> Result<Response, ApiError> {
+    ensure_project_visible(&state, project_id, auth.as_ref()).await?;
+    let project = find_project_by_id(&state.db, project_id).await?;
+    let limit = state.git.max_archive_size;
+    let format = query.format;
+
+    let repository_path = project.repository_path.clone();
+    let git_ref = query.git_ref.clone();
+    let (commit_id, path, plan) = tokio::task::spawn_blocking(move || -> git::Result<_> {
+        let repo = git::open_repository(&repository_path)?;
+        let commit = git::resolve_commit(&repo, git_ref.as_deref())?;
+        let path = git::normalize_path(query.path.as_deref())?;
+        let plan = git::plan_archive(&repo, &commit, &path)?;
+        Ok((commit.id(), path, plan))
+    })
+    .await??;
+
+    if plan.size > limit {
+        return Err(ApiError::PayloadTooLarge(format!(
+            "El contenido ocupa {} bytes sin comprimir y supera el límite de {} bytes",
+            plan.size, limit
+        )));
+    }
+    // El zip retiene en memoria cada archivo hasta completar su cabecera
+    if format == ArchiveFormat::Zip && plan.largest > state.git.max_raw_size {
+        return Err(ApiError::PayloadTooLarge(format!(
+            "Un archivo ocupa {} bytes y supera el límite de {} bytes por archivo de un .zip; usa format=tar.gz",
+            plan.largest, state.git.max_raw_size
+        )));
+    }
+
+    let prefix = archive_prefix(&project.name, query.git_ref.as_deref(), &commit_id.to_string(), &path);
+    record_audit(
+        &state.db,
+        CreateAuditEntry {
+            project_id: Some(project_id),
+            user_id: auth.as_ref().map(|user| user.id),
+            action: AuditAction::ArchiveDownload,
+            details: json!({
+                "ref": query.git_ref,
+                "commit": commit_id.to_string(),
+                "path": path,
+                "format": format,
+                "files": plan.files,
+                "size": plan.size,
+            }),
+        },
+    )
+    .await?;
+
+    let (tx, rx) = mpsc::channel::<Chunk>(ARCHIVE_STREAM_BUFFER);
+    let error_tx = tx.clone();
+    let repository_path = project.repository_path;
+    let name = prefix.clone();
+
+    tokio::task::spawn_blocking(move || {
+        let result = git::open_repository(&repository_path).and_then(|repo| match format {
+            ArchiveFormat::TarGz => git::write_tar_gz(&repo, &plan, &name, ChannelWriter::streaming(tx)),
+            ArchiveFormat::Zip => git::write_zip(&repo, &plan, &name, ChannelWriter::spooled(tx)),
+        });
+
+        let result = result.and_then(|mut writer| Ok(writer.flush()?));
+        match result {
+            Ok(()) => {}
+            Err(git::GitError::Io(e)) if e.kind() == io::ErrorKind::BrokenPipe => {
+                tracing::debug!("Descarga del proyecto {} cancelada por el cliente", project_id);
+            }
+            Err(e) => {
+                tracing::error!("Error al generar el archivo del proyecto {}: {}", project_id, e);
+                // Corta la respuesta para que no parezca un archivo completo
+                let _ = error_tx.blocking_send(Err(io::Error::other("Error al generar el archivo")));
+            }
+        }
+    });
+
+    let filename = format!("{}.{}", prefix, format.extension());
+    let mut headers = HeaderMap::new();
+    headers.insert(header::CONTENT_TYPE, HeaderValue::from_static(format.content_type()));
+    headers.insert(header::CONTENT_DISPOSITION, header_value(&content_disposition(&filename, false)));
+    headers.insert(header::CACHE_CONTROL, HeaderValue::from_static("private, no-cache"));
+    headers.insert(header::X_CONTENT_TYPE_OPTIONS, HeaderValue::from_static("nosniff"));
+
+    Ok((headers, Body::from_stream(ReceiverStream::new(rx))).into_response())
+}
+
+/// Resultado de interpretar una cabecera `Range`
+#[derive(Debug, PartialEq, Eq)]
+enum ByteRange {
+    /// Sin rango válido: se sirve el contenido completo
+    Full,
+    /// Rango inclusivo `[inicio, fin]`
+    Partial(u64, u64),
+    Unsatisfiable,
+}
+
+/// Interpreta una cabecera `Range: bytes=...` para un contenido de `len` bytes
+///
+/// Solo se soporta un único rango; las cabeceras con varios rangos o mal
+/// formadas se ignoran y se sirve el contenido completo (RFC 9110 §14.2).
+fn parse_range(value: &str, len: u64) -> ByteRange {
+    let Some(spec) = value.trim().strip_prefix("bytes=") else {
+        return ByteRange::Full;
+    };
+    if spec.contains(',') {
+        return ByteRange::Full;
+    }
+    let Some((start, end)) = spec.trim().split_once('-') else {
+        return ByteRange::Full;
+    };
+
+    match (start.parse::<u64>().ok(), end.parse::<u64>().ok()) {
+        // bytes=-N: los últimos N bytes
+        (None, Some(suffix)) if start.is_empty() => {
+            if suffix == 0 || len == 0 {
+                ByteRange::Unsatisfiable
+            } else {
+                ByteRange::Partial(len - suffix.min(len), len - 1)
+            }
+        }
+        // bytes=N-
+        (Some(start), None) if end.is_empty() => {
+            if start >= len {
+                ByteRange::Unsatisfiable
+            } else {
+                ByteRange::Partial(start, len - 1)
+            }
+        }
+        // bytes=N-M
+        (Some(start), Some(end)) if start <= end => {
+            if start >= len {
+                ByteRange::Unsatisfiable
+            } else {
+                ByteRange::Partial(start, end.min(len - 1))
+            }
+        }
+        _ => ByteRange::Full,
+    }
+}
+
+/// Decide el `Content-Type` de `/raw` y si el contenido puede mostrarse inline
+fn raw_content_type(path: &str, data: &[u8]) -> (String, bool) {
+    let guessed = mime_guess::from_path(path).first_or_octet_stream();
+
+    let previewable = match guessed.type_() {
+        mime_guess::mime::IMAGE => guessed.subtype() != mime_guess::mime::SVG,
+        mime_guess::mime::AUDIO | mime_guess::mime::VIDEO => true,
+        _ => guessed == mime_guess::mime::APPLICATION_PDF,
+    };
+    if previewable {
+        return (guessed.essence_str().to_string(), true);
+    }
+
+    match git::blob::detect_encoding(data) {
+        Some(encoding) => (format!("text/plain; charset={}", encoding.name()), true),
+        None => ("application/octet-stream".to_string(), false),
+    }
+}
+
+/// Construye `Content-Disposition` con nombre ASCII de respaldo y `filename*` UTF-8
+fn content_disposition(name: &str, inline: bool) -> String {
+    let fallback: String = name
+        .chars()
+        .map(|c| match c {
+            ' '..='~' if c != '"' && c != '\\' => c,
+            _ => '_',
+        })
+        .collect();
+
+    format!(
+        "{}; filename=\"{}\"; filename*=UTF-8''{}",
+        if inline { "inline" } else { "attachment" },
+        fallback,
+        percent_encode(name)
+    )
+}
+
+/// Directorio raíz del archivo: `<proyecto>-<ref>[-<ruta>]`
+///
+/// Sin `ref` se usa el hash abreviado. Todo lo que no sea alfanumérico,
+/// `.`, `_` o `-` se sustituye por `-`.
+fn archive_prefix(project: &str, git_ref: Option<&str>, commit: &str, path: &str) -> String {
+    let git_ref = git_ref
+        .map(str::trim)
+        .filter(|r| !r.is_empty())
+        .unwrap_or(&commit[..commit.len().min(7)]);
+
+    let mut prefix = format!("{}-{}", project, git_ref);
+    if !path.is_empty() {
+        prefix.push('-');
+        prefix.push_str(path);
+    }
+
+    prefix
+        .chars()
+        .map(|c| match c {
+            'A'..='Z' | 'a'..='z' | '0'..='9' | '.' | '_' | '-' => c,
+            _ => '-',
+        })
+        .collect()
+}
+
+/// Convierte una cadena ya saneada en valor de cabecera
+fn header_value(value: &str) -> HeaderValue {
+    HeaderValue::from_str(value).unwrap_or_else(|_| HeaderValue::from_static(""))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_range() {
+        assert_eq!(parse_range("bytes=0-9", 100), ByteRange::Partial(0, 9));
+        assert_eq!(parse_range("bytes=90-", 100), ByteRange::Partial(90, 99));
+        assert_eq!(parse_range("bytes=-10", 100), ByteRange::Partial(90, 99));
+        assert_eq!(parse_range("bytes=-500", 100), ByteRange::Partial(0, 99));
+        assert_eq!(parse_range("bytes=50-500", 100), ByteRange::Partial(50, 99));
+        assert_eq!(parse_range("bytes=100-", 100), ByteRange::Unsatisfiable);
+        assert_eq!(parse_range("bytes=-0", 100), ByteRange::Unsatisfiable);
+    }
+
+    #[test]
+    fn test_parse_range_ignores_unsupported() {
+        assert_eq!(parse_range("bytes=0-1,5-6", 100), ByteRange::Full);
+        assert_eq!(parse_range("items=0-1", 100), ByteRange::Full);
+        assert_eq!(parse_range("bytes=9-1", 100), ByteRange::Full);
+        assert_eq!(parse_range("bytes=abc", 100), ByteRange::Full);
+    }
+
+    #[test]
+    fn test_raw_content_type() {
+        assert_eq!(raw_content_type("logo.png", b"\x89PNG\0"), ("image/png".to_string(), true));
+        assert_eq!(raw_content_type("doc.pdf", b"%PDF-1.7"), ("application/pdf".to_string(), true));
+        assert_eq!(
+            raw_content_type("index.html", b"<script>alert(1)</script>"),
+            ("text/plain; charset=UTF-8".to_string(), true)
+        );
+        assert_eq!(
+            raw_content_type("icon.svg", b"<svg onload=alert(1)>"),
+            ("text/plain; charset=UTF-8".to_string(), true)
+        );
+        assert_eq!(
+            raw_content_type("app.bin", b"\0\x01\x02"),
+            ("application/octet-stream".to_string(), false)
+        );
+    }
+
+    #[test]
+    fn test_content_disposition() {
+        assert_eq!(
+            content_disposition("diseño \"final\".png", true),
+            "inline; filename=\"dise_o _final_.png\"; filename*=UTF-8''dise%C3%B1o%20%22final%22.png"
+        );
+        assert!(content_disposition("a.zip", false).starts_with("attachment;"));
+    }
+
+    #[test]
+    fn test_archive_prefix() {
+        let commit = "9fceb02d0ae598e95dc970b74767f19372d61af8";
+        assert_eq!(archive_prefix("lumastack", None, commit, ""), "lumastack-9fceb02");
+        assert_eq!(
+            archive_prefix("luma stack", Some("feature/búsqueda"), commit, "src/api"),
+            "luma-stack-feature-b-squeda-src-api"
+        );
+    }
+}
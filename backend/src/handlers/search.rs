@@ -0,0 +1,310 @@
+use std::convert::Infallible;
+use std::time::Instant;
+
+use axum::{
+    body::Body,
+    extract::{Path, Query, State},
+    http::header,
+    response::{IntoResponse, Response},
+    Json,
+};
+use regex::{Regex, RegexBuilder};
+use serde::Deserialize;
+use tokio::sync::mpsc;
+use tokio_stream::wrappers::ReceiverStream;
+
+use super::{ApiError, AppState};
+use super::projects::ensure_project_visible;
+use crate::db::projects::find_project_by_id;
+use crate::db::search::{search_commits, search_projects};
+use crate::middleware::AuthUser;
+use crate::models::{CodeSearchEvent, SearchResponse, SearchScope};
+use crate::services::git;
+
+/// Longitud máxima del texto de búsqueda
+const MAX_QUERY_LENGTH: usize = 200;
+
+/// Resultados devueltos por defecto en `/search`, por tipo
+const DEFAULT_SEARCH_LIMIT: i64 = 20;
+
+/// Máximo de resultados que se pueden pedir en `/search`, por tipo
+const MAX_SEARCH_LIMIT: i64 = 100;
+
+/// Tamaño máximo del autómata compilado de una expresión regular
+const MAX_REGEX_SIZE: usize = 1024 * 1024;
+
+/// Líneas NDJSON en vuelo antes de frenar el recorrido del árbol
+const STREAM_BUFFER: usize = 64;
+
+/// Parámetros de consulta de `GET /api/search`
+#[derive(Debug, Deserialize)]
+pub struct SearchQuery {
+    pub q: Option<String>,
+    /// `all` (default), `projects` o `commits`
+    #[serde(rename = "type", default)]
+    pub scope: SearchScope,
+    /// Limita la búsqueda de commits a un proyecto
+    pub project_id: Option<i32>,
+    /// Resultados por tipo (default: 20, máximo: 100)
+    pub limit: Option<i64>,
+}
+
+/// Parámetros de consulta de `GET /api/projects/:id/search/code`
+#[derive(Debug, Deserialize)]
+pub struct CodeSearchQuery {
+    pub q: Option<String>,
+    /// Interpreta `q` como expresión regular en lugar de texto literal
+    #[serde(default)]
+    pub regex: bool,
+    #[serde(default)]
+    pub case_sensitive: bool,
+    /// Globs separados por comas; `!` delante excluye (p. ej. `src/**,!*.lock`)
+    pub path: Option<String>,
+    /// Rama, tag o hash; `HEAD` por defecto
+    #[serde(rename = "ref")]
+    pub git_ref: Option<String>,
+}
+
+/// Handler de búsqueda de texto completo en proyectos y commits
+///
+/// Busca proyectos por nombre y descripción y commits de la caché por
+/// mensaje y autor. Cada término se busca como prefijo y todos deben
+/// aparecer. Solo se devuelven proyectos visibles para quien consulta:
+/// públicos, de los que es miembro o todos si es administrador. Sin token
+/// solo se buscan proyectos públicos. Con `project_id` no se buscan
+/// proyectos, solo commits de ese proyecto.
+///
+/// # Endpoint
+/// `GET /api/search?q=&type=&project_id=&limit=`
+///
+/// # Response
+/// - **200 OK**: Resultados ordenados por relevancia
+/// - **400 Bad Request**: `q` vacío o demasiado largo
+/// - **404 Not Found**: `project_id` no existe o no es visible
+///
+/// # Example Response
+/// ```json
+/// {
+///   "query": "login",
+///   "projects": [
+///     { "id": 1, "name": "lumastack", "description": "Monitor de repositorios", "is_public": true, "rank": 0.06 }
+///   ],
+///   "commits": [
+///     {
+///       "project_id": 1,
+///       "project_name": "lumastack",
+///       "hash": "9fceb02d0ae598e95dc970b74767f19372d61af8",
+///       "author_name": "Ada Lovelace",
+///       "author_email": "ada@example.com",
+///       "message": "Add login endpoint",
+///       "committed_at": "2025-10-21T03:15:40",
+///       "rank": 0.09
+///     }
+///   ]
+/// }
+/// ```
+pub async fn search_handler(
+    State(state): State<AppState>,
+    Query(query): Query<SearchQuery>,
+    auth: Option<AuthUser>,
+) -> Result<Json<SearchResponse>, ApiError> {
+    let text = validate_query(query.q.as_deref())?;
+    let limit = query
+        .limit
+        .unwrap_or(DEFAULT_SEARCH_LIMIT)
+        .clamp(1, MAX_SEARCH_LIMIT);
+    let user_id = auth.as_ref().map(|user| user.id);
+    let is_admin = auth.as_ref().is_some_and(AuthUser::is_admin);
+
+    if let Some(project_id) = query.project_id {
+        ensure_project_visible(&state, project_id, auth.as_ref()).await?;
+    }
+
+    let projects = if query.scope != SearchScope::Commits && query.project_id.is_none() {
+        search_projects(&state.db, text, user_id, is_admin, limit).await?
+    } else {
+        Vec::new()
+    };
+    let commits = if query.scope != SearchScope::Projects {
+        search_commits(&state.db, text, query.project_id, user_id, is_admin, limit).await?
+    } else {
+        Vec::new()
+    };
+
+    Ok(Json(SearchResponse {
+        query: text.to_string(),
+        projects,
+        commits,
+    }))
+}
+
+/// Handler que busca texto en el contenido de los archivos de una revisión
+///
+/// Lee los objetos del repositorio sin tocar el working copy y devuelve los
+/// resultados como NDJSON a medida que aparecen: una línea `start` con el
+/// commit resuelto, una línea `match` por cada línea coincidente y una
+/// línea final `summary` (o `error`). Sin `regex=true` el texto se busca
+/// literalmente; por defecto sin distinguir mayúsculas. La búsqueda se
+/// detiene al agotar `GIT_GREP_TIME_BUDGET_MS` (`timed_out`) o al llegar a
+/// `GIT_GREP_MAX_MATCHES` (`truncated`). Se omiten binarios y archivos
+/// mayores que `GIT_GREP_MAX_FILE_SIZE`.
+///
+/// # Endpoint
+/// `GET /api/projects/:id/search/code?q=&regex=&case_sensitive=&path=&ref=`
+///
+/// # Response
+/// - **200 OK**: Stream `application/x-ndjson`
+/// - **400 Bad Request**: `q` vacío, expresión regular o glob inválidos
+/// - **404 Not Found**: Proyecto inexistente o no visible, o referencia inexistente
+///
+/// # Example Response
+/// ```text
+/// {"type":"start","ref":"main","commit":"9fceb02d0ae598e95dc970b74767f19372d61af8"}
+/// {"type":"match","path":"src/main.rs","line_number":12,"line":"    // TODO: logging","ranges":[{"start":7,"end":11}]}
+/// {"type":"summary","files_scanned":84,"files_matched":1,"matches":1,"truncated":false,"timed_out":false,"elapsed_ms":31}
+/// ```
+pub async fn code_search_handler(
+    State(state): State<AppState>,
+    Path(project_id): Path<i32>,
+    Query(query): Query<CodeSearchQuery>,
+    auth: Option<AuthUser>,
+) -> Result<Response, ApiError> {
+    let deadline = Instant::now() + state.git.grep_time_budget;
+    ensure_project_visible(&state, project_id, auth.as_ref()).await?;
+    let project = find_project_by_id(&state.db, project_id).await?;
+
+    let text = validate_query(query.q.as_deref())?;
+    let pattern = build_pattern(text, query.regex, query.case_sensitive)?;
+    let paths = git::PathFilter::parse(query.path.as_deref().unwrap_or(""))?;
+    let git_ref = query
+        .git_ref
+        .clone()
+        .filter(|r| !r.trim().is_empty())
+        .unwrap_or_else(|| "HEAD".to_string());
+
+    // La referencia se resuelve antes de empezar el stream para poder
+    // responder 404 en lugar de un error a mitad de respuesta
+    let path = project.repository_path;
+    let rev = git_ref.clone();
+    let (commit_id, path) = tokio::task::spawn_blocking(move || -> git::Result<_> {
+        let repo = git::open_repository(&path)?;
+        let commit_id = git::resolve_commit(&repo, Some(&rev))?.id();
+        Ok((commit_id, path))
+    })
+    .await??;
+
+    let max_matches = state.git.grep_max_matches;
+    let max_file_size = state.git.grep_max_file_size;
+    let (tx, rx) = mpsc::channel::<Result<String, Infallible>>(STREAM_BUFFER);
+
+    tokio::task::spawn_blocking(move || {
+        // Si el cliente se desconecta el envío falla y el recorrido se detiene
+        let send = |event: CodeSearchEvent| match serde_json::to_string(&event) {
+            Ok(line) => tx.blocking_send(Ok(line + "\n")).is_ok(),
+            Err(_) => false,
+        };
+
+        if !send(CodeSearchEvent::Start {
+            git_ref,
+            commit: commit_id.to_string(),
+        }) {
+            return;
+        }
+
+        let options = git::GrepOptions {
+            pattern: &pattern,
+            paths: &paths,
+            max_matches,
+            max_file_size,
+            deadline,
+        };
+        let result = git::open_repository(&path).and_then(|repo| {
+            let tree = repo.find_commit(commit_id)?.tree()?;
+            git::grep_tree(&repo, &tree, &options, |found| {
+                send(CodeSearchEvent::Match(found))
+            })
+        });
+
+        match result {
+            Ok(summary) => {
+                send(CodeSearchEvent::Summary(summary));
+            }
+            Err(e) => {
+                tracing::error!(
+                    "Error en la búsqueda de código del proyecto {}: {}",
+                    project_id,
+                    e
+                );
+                send(CodeSearchEvent::Error {
+                    error: "Error al recorrer el repositorio".to_string(),
+                });
+            }
+        }
+    });
+
+    Ok((
+        [(header::CONTENT_TYPE, "application/x-ndjson")],
+        Body::from_stream(ReceiverStream::new(rx)),
+    )
+        .into_response())
+}
+
+/// Valida el texto de búsqueda y lo retorna sin espacios sobrantes
+fn validate_query(q: Option<&str>) -> Result<&str, ApiError> {
+    let text = q.map(str::trim).unwrap_or_default();
+
+    if text.is_empty() {
+        return Err(ApiError::BadRequest(
+            "El parámetro q es obligatorio".to_string(),
+        ));
+    }
+    if text.chars().count() > MAX_QUERY_LENGTH {
+        return Err(ApiError::BadRequest(format!(
+            "La búsqueda no puede superar {} caracteres",
+            MAX_QUERY_LENGTH
+        )));
+    }
+
+    Ok(text)
+}
+
+/// Compila el patrón de la búsqueda de código
+fn build_pattern(text: &str, regex: bool, case_sensitive: bool) -> Result<Regex, ApiError> {
+    let source = if regex {
+        text.to_string()
+    } else {
+        regex::escape(text)
+    };
+
+    RegexBuilder::new(&source)
+        .case_insensitive(!case_sensitive)
+        .size_limit(MAX_REGEX_SIZE)
+        .build()
+        .map_err(|e| ApiError::BadRequest(format!("Expresión regular inválida: {}", e)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_query() {
+        assert_eq!(validate_query(Some("  login ")).unwrap(), "login");
+        assert!(validate_query(None).is_err());
+        assert!(validate_query(Some("   ")).is_err());
+        assert!(validate_query(Some(&"a".repeat(MAX_QUERY_LENGTH + 1))).is_err());
+    }
+
+    #[test]
+    fn test_build_pattern() {
+        let literal = build_pattern("a.b(", false, false).unwrap();
+        assert!(literal.is_match("x A.B( y"));
+        assert!(!literal.is_match("axb("));
+
+        let regex = build_pattern(r"fn \w+", true, true).unwrap();
+        assert!(regex.is_match("pub fn main"));
+        assert!(!regex.is_match("pub FN main"));
+
+        assert!(build_pattern("(", true, false).is_err());
+    }
+}
@@ -0,0 +1,264 @@
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    Json,
+};
+use serde::Deserialize;
+
+use super::{ApiError, AppState};
+use crate::db::signing_keys::{delete_signing_key, insert_signing_key, list_signing_keys};
+use crate::middleware::AuthUser;
+use crate::models::{CreateSigningKey, SignatureKind, SigningKey, SigningKeysResponse};
+use crate::services::signature::parse_ssh_key;
+
+/// Tamaño máximo de una clave pública
+const MAX_KEY_LENGTH: usize = 64 * 1024;
+
+/// Principals máximos de una clave SSH
+const MAX_PRINCIPALS: usize = 50;
+
+/// Parámetros de consulta del listado de claves
+#[derive(Debug, Deserialize)]
+pub struct SigningKeysQuery {
+    /// `ssh` o `gpg`; sin filtro por defecto
+    pub kind: Option<SignatureKind>,
+}
+
+/// Handler que lista las claves de confianza para verificar firmas
+/// (administradores)
+///
+/// # Endpoint
+/// `GET /api/signing-keys?kind=ssh|gpg`
+///
+/// # Response
+/// - **200 OK**: Claves con su huella y principals
+/// - **401 Unauthorized** / **403 Forbidden**: No autenticado o no administrador
+pub async fn signing_keys_handler(
+    State(state): State<AppState>,
+    Query(query): Query<SigningKeysQuery>,
+    auth: AuthUser,
+) -> Result<Json<SigningKeysResponse>, ApiError> {
+    auth.require_admin()?;
+    let keys = list_signing_keys(&state.db, query.kind).await?;
+
+    Ok(Json(SigningKeysResponse { keys }))
+}
+
+/// Handler que añade una clave de confianza (administradores)
+///
+/// Las claves SSH forman la lista `allowed_signers`: cada una autoriza a
+/// firmar commits de los emails de `principals` (admiten `*` y `?`). También
+/// se acepta una línea de `allowed_signers` completa en `public_key` sin
+/// `principals`. Las claves GPG se importan al keyring y autorizan los
+/// emails de sus user IDs.
+///
+/// # Endpoint
+/// `POST /api/signing-keys`
+///
+/// # Request Body
+/// ```json
+/// { "kind": "ssh", "public_key": "ssh-ed25519 AAAAC3Nza... jane@laptop", "principals": ["jane@example.com"] }
+/// ```
+/// o
+/// ```json
+/// { "kind": "gpg", "public_key": "-----BEGIN PGP PUBLIC KEY BLOCK-----\n...", "comment": "Jane" }
+/// ```
+///
+/// # Response
+/// - **201 Created**: Clave guardada
+/// - **400 Bad Request**: Clave inválida o sin principals
+/// - **401 Unauthorized** / **403 Forbidden**: No autenticado o no administrador
+/// - **409 Conflict**: Ya existe una clave con la misma huella
+/// - **503 Service Unavailable**: Clave GPG sin el programa `gpg` disponible
+pub async fn create_signing_key_handler(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Json(body): Json<CreateSigningKey>,
+) -> Result<(StatusCode, Json<SigningKey>), ApiError> {
+    auth.require_admin()?;
+
+    let comment = body
+        .comment
+        .as_deref()
+        .map(str::trim)
+        .filter(|c| !c.is_empty());
+    if comment.is_some_and(|c| c.chars().count() > 255) {
+        return Err(ApiError::BadRequest(
+            "El comentario supera los 255 caracteres".to_string(),
+        ));
+    }
+    if body.public_key.len() > MAX_KEY_LENGTH {
+        return Err(ApiError::BadRequest(format!(
+            "La clave supera los {} bytes",
+            MAX_KEY_LENGTH
+        )));
+    }
+
+    let (fingerprint, principals, public_key) = match body.kind {
+        SignatureKind::Ssh => {
+            let (principals, key_text) = ssh_principals(&body.public_key, &body.principals)?;
+            let (key, fingerprint) = parse_ssh_key(key_text)?;
+            let public_key = key
+                .to_openssh()
+                .map_err(|e| ApiError::BadRequest(e.to_string()))?;
+            (fingerprint, principals, public_key)
+        }
+        SignatureKind::Gpg => {
+            let armored = body.public_key.trim().to_string();
+            let signatures = state.signatures.clone();
+            let inspected = {
+                let armored = armored.clone();
+                tokio::task::spawn_blocking(move || signatures.inspect_gpg_key(&armored)).await??
+            };
+            if inspected.emails.is_empty() {
+                return Err(ApiError::BadRequest(
+                    "La clave GPG no tiene ningún user ID con email".to_string(),
+                ));
+            }
+            (inspected.fingerprint, inspected.emails, armored)
+        }
+        SignatureKind::X509 => {
+            return Err(ApiError::BadRequest(
+                "Solo se admiten claves ssh o gpg".to_string(),
+            ))
+        }
+    };
+
+    let key = match insert_signing_key(
+        &state.db,
+        body.kind,
+        &fingerprint,
+        &principals,
+        &public_key,
+        comment,
+        auth.id,
+    )
+    .await
+    {
+        Ok(key) => key,
+        Err(sqlx::Error::Database(db_err)) if db_err.is_unique_violation() => {
+            return Err(ApiError::Conflict(format!(
+                "Ya existe una clave con la huella {}",
+                fingerprint
+            )))
+        }
+        Err(e) => return Err(e.into()),
+    };
+    state.signatures.reload(&state.db).await?;
+
+    tracing::info!(
+        "Clave de firma {:?} {} añadida por {}",
+        key.kind,
+        key.fingerprint,
+        auth.username
+    );
+
+    Ok((StatusCode::CREATED, Json(key)))
+}
+
+/// Handler que elimina una clave de confianza (administradores)
+///
+/// Las firmas hechas con ella dejan de considerarse verificadas.
+///
+/// # Endpoint
+/// `DELETE /api/signing-keys/:key_id`
+///
+/// # Response
+/// - **204 No Content**: Clave eliminada
+/// - **401 Unauthorized** / **403 Forbidden**: No autenticado o no administrador
+/// - **404 Not Found**: La clave no existe
+pub async fn delete_signing_key_handler(
+    State(state): State<AppState>,
+    Path(key_id): Path<i32>,
+    auth: AuthUser,
+) -> Result<StatusCode, ApiError> {
+    auth.require_admin()?;
+
+    if !delete_signing_key(&state.db, key_id).await? {
+        return Err(ApiError::NotFound("La clave no existe".to_string()));
+    }
+    state.signatures.reload(&state.db).await?;
+
+    tracing::info!("Clave de firma {} eliminada por {}", key_id, auth.username);
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Obtiene los principals de una clave SSH y el texto de la clave
+///
+/// Sin `principals` explícitos, `public_key` se interpreta como una línea de
+/// `allowed_signers` (`principal1,principal2 tipo clave [comentario]`).
+fn ssh_principals<'a>(
+    public_key: &'a str,
+    principals: &[String],
+) -> Result<(Vec<String>, &'a str), ApiError> {
+    let public_key = public_key.trim();
+    let (list, key_text): (Vec<&str>, &str) = if principals.is_empty() {
+        match public_key.split_once(char::is_whitespace) {
+            Some((first, rest)) if !first.starts_with("ssh-") && !first.starts_with("ecdsa-") => {
+                (first.split(',').collect(), rest.trim_start())
+            }
+            _ => {
+                return Err(ApiError::BadRequest(
+                    "Las claves SSH necesitan al menos un principal".to_string(),
+                ))
+            }
+        }
+    } else {
+        (principals.iter().map(String::as_str).collect(), public_key)
+    };
+
+    let mut result: Vec<String> = Vec::new();
+    for principal in list.iter().map(|p| p.trim()).filter(|p| !p.is_empty()) {
+        if principal.len() > 255 || principal.contains(char::is_whitespace) {
+            return Err(ApiError::BadRequest(format!(
+                "Principal inválido: {}",
+                principal
+            )));
+        }
+        if !result.iter().any(|p| p == principal) {
+            result.push(principal.to_string());
+        }
+    }
+    if result.is_empty() {
+        return Err(ApiError::BadRequest(
+            "Las claves SSH necesitan al menos un principal".to_string(),
+        ));
+    }
+    if result.len() > MAX_PRINCIPALS {
+        return Err(ApiError::BadRequest(format!(
+            "Una clave admite como mucho {} principals",
+            MAX_PRINCIPALS
+        )));
+    }
+
+    Ok((result, key_text))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const KEY: &str = "ssh-ed25519 AAAAC3NzaC1lZDI1NTE5AAAAIGj2ke92FLKWBma+/AMXzh8wvNGnxRl7Nev+mws9wDwY test@example.com";
+
+    #[test]
+    fn test_ssh_principals() {
+        let line = format!("jane@example.com,*@ops.example.com  {}", KEY);
+        let (principals, key) = ssh_principals(&line, &[]).unwrap();
+        assert_eq!(principals, vec!["jane@example.com", "*@ops.example.com"]);
+        assert_eq!(key, KEY);
+        assert!(parse_ssh_key(key).is_ok());
+
+        let explicit = vec![
+            " ada@example.com ".to_string(),
+            "ada@example.com".to_string(),
+        ];
+        let (principals, key) = ssh_principals(KEY, &explicit).unwrap();
+        assert_eq!(principals, vec!["ada@example.com"]);
+        assert_eq!(key, KEY);
+
+        assert!(ssh_principals(KEY, &[]).is_err());
+        assert!(ssh_principals(KEY, &["a b".to_string()]).is_err());
+        assert!(ssh_principals(KEY, &[" ".to_string()]).is_err());
+    }
+}
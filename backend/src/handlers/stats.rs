@@ -0,0 +1,511 @@
+use std::collections::HashMap;
+
+use axum::{
+    extract::{Path, Query, State},
+    Json,
+};
+use chrono::{Days, NaiveDate, Utc};
+use serde::Deserialize;
+
+use super::projects::ensure_project_visible;
+use super::{ApiError, AppState};
+use crate::db::projects::find_project_by_id;
+use crate::db::stats::{
+    activity_totals, daily_activity, project_activity, project_author_rows, project_heatmap,
+    top_authors, top_projects,
+};
+use crate::middleware::AuthUser;
+use crate::models::{
+    ActivityResponse, AuthorStats, AuthorStatsResponse, AuthorStatsRow, DashboardSummary,
+    HeatmapResponse, StatsInterval,
+};
+use crate::services::git;
+
+/// Días incluidos por defecto en la serie diaria
+const DEFAULT_ACTIVITY_DAYS: u64 = 30;
+
+/// Semanas incluidas por defecto en la serie semanal
+const DEFAULT_ACTIVITY_WEEKS: u64 = 26;
+
+/// Máximo de puntos de una serie de actividad
+const MAX_ACTIVITY_POINTS: u64 = 366;
+
+/// Autores devueltos por defecto en `/stats/authors`
+const DEFAULT_AUTHORS_LIMIT: usize = 50;
+
+/// Máximo de autores que se pueden pedir en `/stats/authors`
+const MAX_AUTHORS_LIMIT: usize = 500;
+
+/// Desplazamiento horario máximo admitido en el heatmap (UTC±14:00)
+const MAX_TZ_OFFSET_MINUTES: i32 = 14 * 60;
+
+/// Días que cubre el resumen del dashboard, incluido hoy
+const SUMMARY_DAYS: u64 = 7;
+
+/// Proyectos y autores destacados en el resumen del dashboard
+const SUMMARY_TOP_LIMIT: i64 = 5;
+
+/// Parámetros de consulta de `GET /api/projects/:id/stats/activity`
+#[derive(Debug, Deserialize)]
+pub struct ActivityQuery {
+    /// `day` (default) o `week`
+    #[serde(default)]
+    pub interval: StatsInterval,
+    /// Primer día incluido (`YYYY-MM-DD`)
+    pub since: Option<NaiveDate>,
+    /// Último día incluido (`YYYY-MM-DD`); hoy por defecto
+    pub until: Option<NaiveDate>,
+}
+
+/// Parámetros de consulta de `GET /api/projects/:id/stats/authors`
+#[derive(Debug, Deserialize)]
+pub struct AuthorStatsQuery {
+    /// Primer día incluido; sin él cuenta toda la historia
+    pub since: Option<NaiveDate>,
+    /// Último día incluido
+    pub until: Option<NaiveDate>,
+    /// Autores a devolver (default: 50, máximo: 500)
+    pub limit: Option<usize>,
+}
+
+/// Parámetros de consulta de `GET /api/projects/:id/stats/heatmap`
+#[derive(Debug, Deserialize)]
+pub struct HeatmapQuery {
+    /// Primer día incluido; sin él cuenta toda la historia
+    pub since: Option<NaiveDate>,
+    /// Último día incluido
+    pub until: Option<NaiveDate>,
+    /// Minutos a sumar a las fechas UTC (p. ej. `-300` para UTC-05:00)
+    #[serde(default)]
+    pub tz_offset: i32,
+}
+
+/// Handler que retorna los commits y las líneas cambiadas por día o semana
+///
+/// Incluye los periodos sin commits. Por defecto cubre los últimos 30 días
+/// (o 26 semanas con `interval=week`) hasta hoy. Las líneas no incluyen los
+/// merges ni los commits cacheados antes de guardar estadísticas.
+///
+/// # Endpoint
+/// `GET /api/projects/:id/stats/activity?interval=&since=&until=`
+///
+/// # Response
+/// - **200 OK**: Serie de actividad
+/// - **400 Bad Request**: Rango inválido o con demasiados periodos
+/// - **404 Not Found**: Proyecto inexistente o no visible
+///
+/// # Example Response
+/// ```json
+/// {
+///   "interval": "day",
+///   "since": "2025-10-20",
+///   "until": "2025-10-21",
+///   "points": [
+///     { "period": "2025-10-20", "commits": 0, "additions": 0, "deletions": 0 },
+///     { "period": "2025-10-21", "commits": 3, "additions": 120, "deletions": 14 }
+///   ]
+/// }
+/// ```
+pub async fn activity_stats_handler(
+    State(state): State<AppState>,
+    Path(project_id): Path<i32>,
+    Query(query): Query<ActivityQuery>,
+    auth: Option<AuthUser>,
+) -> Result<Json<ActivityResponse>, ApiError> {
+    ensure_project_visible(&state, project_id, auth.as_ref()).await?;
+
+    let today = Utc::now().date_naive();
+    let (since, until) = activity_range(query.interval, query.since, query.until, today)?;
+    let points = project_activity(&state.db, project_id, query.interval, since, until).await?;
+
+    Ok(Json(ActivityResponse {
+        interval: query.interval,
+        since,
+        until,
+        points,
+    }))
+}
+
+/// Handler que retorna los autores de un proyecto ordenados por commits
+///
+/// Las identidades se unifican con el `.mailmap` del repositorio y después
+/// por email sin distinguir mayúsculas, así que un autor con varios nombres
+/// o emails aparece una sola vez. Si el repositorio no está accesible se
+/// agrupa solo por email.
+///
+/// # Endpoint
+/// `GET /api/projects/:id/stats/authors?since=&until=&limit=`
+///
+/// # Response
+/// - **200 OK**: Autores con sus contribuciones
+/// - **400 Bad Request**: Rango inválido
+/// - **404 Not Found**: Proyecto inexistente o no visible
+///
+/// # Example Response
+/// ```json
+/// {
+///   "total_authors": 12,
+///   "authors": [
+///     {
+///       "name": "Ada Lovelace",
+///       "email": "ada@example.com",
+///       "commits": 42,
+///       "additions": 3120,
+///       "deletions": 870,
+///       "first_commit_at": "2025-01-03T10:12:00",
+///       "last_commit_at": "2025-10-21T03:15:40"
+///     }
+///   ]
+/// }
+/// ```
+pub async fn author_stats_handler(
+    State(state): State<AppState>,
+    Path(project_id): Path<i32>,
+    Query(query): Query<AuthorStatsQuery>,
+    auth: Option<AuthUser>,
+) -> Result<Json<AuthorStatsResponse>, ApiError> {
+    ensure_project_visible(&state, project_id, auth.as_ref()).await?;
+    validate_range(query.since, query.until)?;
+    let limit = query
+        .limit
+        .unwrap_or(DEFAULT_AUTHORS_LIMIT)
+        .clamp(1, MAX_AUTHORS_LIMIT);
+
+    let project = find_project_by_id(&state.db, project_id).await?;
+    let rows = project_author_rows(&state.db, project_id, query.since, query.until).await?;
+
+    let identities: Vec<(String, String)> = rows
+        .iter()
+        .map(|row| (row.author_name.clone(), row.email.clone()))
+        .collect();
+    let path = project.repository_path;
+    let resolved = tokio::task::spawn_blocking(move || {
+        let repo = git::open_repository(&path)?;
+        git::resolve_identities(&repo, &identities)
+    })
+    .await?
+    .unwrap_or_else(|e| {
+        tracing::warn!(
+            "No se pudo aplicar el .mailmap del proyecto {}: {}",
+            project_id,
+            e
+        );
+        Vec::new()
+    });
+
+    let mut authors = merge_authors(rows, resolved);
+    let total_authors = authors.len();
+    authors.truncate(limit);
+
+    Ok(Json(AuthorStatsResponse {
+        total_authors,
+        authors,
+    }))
+}
+
+/// Handler que retorna los commits por día de la semana y hora
+///
+/// `cells[d][h]` cuenta los commits del día `d` (0 = lunes) a la hora `h`,
+/// tras sumar `tz_offset` minutos a las fechas UTC de la caché.
+///
+/// # Endpoint
+/// `GET /api/projects/:id/stats/heatmap?since=&until=&tz_offset=`
+///
+/// # Response
+/// - **200 OK**: Matriz de 7×24
+/// - **400 Bad Request**: Rango o `tz_offset` inválidos
+/// - **404 Not Found**: Proyecto inexistente o no visible
+///
+/// # Example Response
+/// ```json
+/// {
+///   "tz_offset": 60,
+///   "total": 214,
+///   "cells": [[0, 0, 0, 0, 0, 0, 0, 0, 0, 4, 7, 9, 2, 1, 5, 8, 6, 3, 1, 0, 0, 0, 0, 0], "..."]
+/// }
+/// ```
+pub async fn heatmap_handler(
+    State(state): State<AppState>,
+    Path(project_id): Path<i32>,
+    Query(query): Query<HeatmapQuery>,
+    auth: Option<AuthUser>,
+) -> Result<Json<HeatmapResponse>, ApiError> {
+    ensure_project_visible(&state, project_id, auth.as_ref()).await?;
+    validate_range(query.since, query.until)?;
+    if query.tz_offset.abs() > MAX_TZ_OFFSET_MINUTES {
+        return Err(ApiError::BadRequest(format!(
+            "tz_offset debe estar entre -{0} y {0} minutos",
+            MAX_TZ_OFFSET_MINUTES
+        )));
+    }
+
+    let counts = project_heatmap(
+        &state.db,
+        project_id,
+        query.since,
+        query.until,
+        query.tz_offset,
+    )
+    .await?;
+
+    let mut cells = vec![vec![0i64; 24]; 7];
+    let mut total = 0;
+    for (weekday, hour, commits) in counts {
+        if let Some(cell) = cells
+            .get_mut(weekday as usize)
+            .and_then(|row| row.get_mut(hour as usize))
+        {
+            *cell += commits;
+            total += commits;
+        }
+    }
+
+    Ok(Json(HeatmapResponse {
+        tz_offset: query.tz_offset,
+        total,
+        cells,
+    }))
+}
+
+/// Handler que retorna el resumen de actividad de los últimos 7 días
+///
+/// Solo cuenta proyectos visibles para quien consulta: públicos, de los que
+/// es miembro o todos si es administrador. Sin token solo cuenta proyectos
+/// públicos. Los autores se unifican por email.
+///
+/// # Endpoint
+/// `GET /api/stats/summary`
+///
+/// # Response
+/// - **200 OK**: Totales, serie diaria y proyectos y autores más activos
+///
+/// # Example Response
+/// ```json
+/// {
+///   "since": "2025-10-15",
+///   "until": "2025-10-21",
+///   "commits": 58,
+///   "active_projects": 4,
+///   "active_authors": 9,
+///   "additions": 4210,
+///   "deletions": 1377,
+///   "daily": [
+///     { "period": "2025-10-15", "commits": 6, "additions": 310, "deletions": 95 }
+///   ],
+///   "top_projects": [
+///     { "project_id": 1, "project_name": "lumastack", "commits": 31 }
+///   ],
+///   "top_authors": [
+///     { "name": "Ada Lovelace", "email": "ada@example.com", "commits": 17 }
+///   ]
+/// }
+/// ```
+pub async fn dashboard_summary_handler(
+    State(state): State<AppState>,
+    auth: Option<AuthUser>,
+) -> Result<Json<DashboardSummary>, ApiError> {
+    let user_id = auth.as_ref().map(|user| user.id);
+    let is_admin = auth.as_ref().is_some_and(AuthUser::is_admin);
+    let until = Utc::now().date_naive();
+    let since = until - Days::new(SUMMARY_DAYS - 1);
+
+    let totals = activity_totals(&state.db, user_id, is_admin, since, until).await?;
+    let daily = daily_activity(&state.db, user_id, is_admin, since, until).await?;
+    let top_projects = top_projects(
+        &state.db,
+        user_id,
+        is_admin,
+        since,
+        until,
+        SUMMARY_TOP_LIMIT,
+    )
+    .await?;
+    let top_authors = top_authors(
+        &state.db,
+        user_id,
+        is_admin,
+        since,
+        until,
+        SUMMARY_TOP_LIMIT,
+    )
+    .await?;
+
+    Ok(Json(DashboardSummary {
+        since,
+        until,
+        totals,
+        daily,
+        top_projects,
+        top_authors,
+    }))
+}
+
+/// Completa y valida el rango de una serie de actividad
+fn activity_range(
+    interval: StatsInterval,
+    since: Option<NaiveDate>,
+    until: Option<NaiveDate>,
+    today: NaiveDate,
+) -> Result<(NaiveDate, NaiveDate), ApiError> {
+    let days_per_point = match interval {
+        StatsInterval::Day => 1,
+        StatsInterval::Week => 7,
+    };
+    let default_points = match interval {
+        StatsInterval::Day => DEFAULT_ACTIVITY_DAYS,
+        StatsInterval::Week => DEFAULT_ACTIVITY_WEEKS,
+    };
+
+    let until = until.unwrap_or(today);
+    let since = since.unwrap_or_else(|| until - Days::new(default_points * days_per_point - 1));
+    validate_range(Some(since), Some(until))?;
+
+    let points = (until - since).num_days() as u64 / days_per_point + 1;
+    if points > MAX_ACTIVITY_POINTS {
+        return Err(ApiError::BadRequest(format!(
+            "El rango no puede superar {} periodos",
+            MAX_ACTIVITY_POINTS
+        )));
+    }
+
+    Ok((since, until))
+}
+
+fn validate_range(since: Option<NaiveDate>, until: Option<NaiveDate>) -> Result<(), ApiError> {
+    match (since, until) {
+        (Some(since), Some(until)) if since > until => Err(ApiError::BadRequest(
+            "since no puede ser posterior a until".to_string(),
+        )),
+        _ => Ok(()),
+    }
+}
+
+/// Unifica las filas de autores tras aplicar el `.mailmap`
+///
+/// `resolved` trae la identidad canónica de cada fila, en el mismo orden;
+/// si está vacío se usan las identidades originales. Los autores se agrupan
+/// por email en minúsculas (o por nombre si no hay email) y toman el nombre
+/// de la identidad con más commits.
+fn merge_authors(rows: Vec<AuthorStatsRow>, resolved: Vec<(String, String)>) -> Vec<AuthorStats> {
+    let mut resolved = resolved.into_iter();
+    let mut authors: Vec<AuthorStats> = Vec::new();
+    // Commits de la identidad que da nombre a cada autor
+    let mut name_commits: Vec<i64> = Vec::new();
+    let mut index: HashMap<String, usize> = HashMap::new();
+
+    for row in rows {
+        let (name, email) = resolved
+            .next()
+            .unwrap_or_else(|| (row.author_name.clone(), row.email.clone()));
+        let email = email.trim().to_lowercase();
+        let key = if email.is_empty() {
+            format!("name:{}", name.trim().to_lowercase())
+        } else {
+            email.clone()
+        };
+
+        match index.get(&key) {
+            Some(&i) => {
+                let author = &mut authors[i];
+                if row.commits > name_commits[i] {
+                    author.name = name;
+                    name_commits[i] = row.commits;
+                }
+                author.commits += row.commits;
+                author.additions += row.additions;
+                author.deletions += row.deletions;
+                author.first_commit_at = author.first_commit_at.min(row.first_commit_at);
+                author.last_commit_at = author.last_commit_at.max(row.last_commit_at);
+            }
+            None => {
+                index.insert(key, authors.len());
+                name_commits.push(row.commits);
+                authors.push(AuthorStats {
+                    name,
+                    email,
+                    commits: row.commits,
+                    additions: row.additions,
+                    deletions: row.deletions,
+                    first_commit_at: row.first_commit_at,
+                    last_commit_at: row.last_commit_at,
+                });
+            }
+        }
+    }
+
+    authors.sort_by(|a, b| b.commits.cmp(&a.commits).then_with(|| a.name.cmp(&b.name)));
+    authors
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(s: &str) -> NaiveDate {
+        s.parse().unwrap()
+    }
+
+    fn row(name: &str, email: &str, commits: i64, day: u32) -> AuthorStatsRow {
+        let at = NaiveDate::from_ymd_opt(2025, 1, day)
+            .unwrap()
+            .and_hms_opt(12, 0, 0)
+            .unwrap();
+        AuthorStatsRow {
+            author_name: name.to_string(),
+            email: email.to_string(),
+            commits,
+            additions: commits * 10,
+            deletions: commits,
+            first_commit_at: at,
+            last_commit_at: at,
+        }
+    }
+
+    #[test]
+    fn test_activity_range() {
+        let today = date("2025-03-31");
+
+        let (since, until) = activity_range(StatsInterval::Day, None, None, today).unwrap();
+        assert_eq!((since, until), (date("2025-03-02"), today));
+
+        let (since, _) = activity_range(StatsInterval::Week, None, None, today).unwrap();
+        assert_eq!(since, date("2024-10-01"));
+
+        assert!(activity_range(StatsInterval::Day, Some(date("2025-04-01")), None, today).is_err());
+        assert!(activity_range(StatsInterval::Day, Some(date("2020-01-01")), None, today).is_err());
+        assert!(activity_range(StatsInterval::Week, Some(date("2020-01-01")), None, today).is_ok());
+    }
+
+    #[test]
+    fn test_merge_authors() {
+        let rows = vec![
+            row("ana", "ana@old.example.com", 1, 2),
+            row("Ana García", "ana@example.com", 3, 5),
+            row("Bob", "", 2, 3),
+            row("bob ", "", 1, 4),
+            row("Eve", "eve@example.com", 1, 1),
+        ];
+        let resolved = vec![
+            ("Ana García".to_string(), "Ana@Example.com".to_string()),
+            ("Ana García".to_string(), "ana@example.com".to_string()),
+            ("Bob".to_string(), String::new()),
+            ("bob ".to_string(), String::new()),
+            ("Eve".to_string(), "eve@example.com".to_string()),
+        ];
+
+        let authors = merge_authors(rows, resolved);
+        assert_eq!(authors.len(), 3);
+        assert_eq!(authors[0].name, "Ana García");
+        assert_eq!(authors[0].email, "ana@example.com");
+        assert_eq!(authors[0].commits, 4);
+        assert_eq!(authors[0].additions, 40);
+        assert_eq!(authors[0].first_commit_at.date(), date("2025-01-02"));
+        assert_eq!(authors[0].last_commit_at.date(), date("2025-01-05"));
+        assert_eq!((authors[1].name.as_str(), authors[1].commits), ("Bob", 3));
+        assert_eq!(authors[2].name, "Eve");
+
+        // Sin mailmap se agrupa solo por email
+        let authors = merge_authors(vec![row("ana", "ana@old.example.com", 1, 2)], Vec::new());
+        assert_eq!(authors[0].email, "ana@old.example.com");
+    }
+}
@@ -0,0 +1,92 @@
+use axum::{
+    extract::{Path, Query, State},
+    Json,
+};
+use serde::Deserialize;
+
+use super::commits::diff_limits;
+use super::projects::ensure_project_admin;
+use super::{ApiError, AppState};
+use crate::db::projects::find_project_by_id;
+use crate::middleware::AuthUser;
+use crate::models::{DiffFormat, WorkingCopyResponse};
+use crate::services::git;
+
+/// Parámetros de consulta del estado del working copy
+#[derive(Debug, Deserialize)]
+pub struct StatusQuery {
+    /// `structured` (por defecto) o `unified`
+    #[serde(default)]
+    pub format: DiffFormat,
+}
+
+/// Handler que retorna los cambios sin commitear del working copy
+///
+/// Agrupa los archivos como `git status` (en el índice, sin añadir al
+/// índice, sin seguimiento y con conflictos) e incluye el diff del working
+/// copy contra `HEAD`, como `git diff HEAD`, con los mismos límites que el
+/// diff de un commit. Solo para administradores del proyecto: el working
+/// copy puede contener secretos que nunca se commitearon.
+///
+/// # Endpoint
+/// `GET /api/projects/:id/status?format=`
+///
+/// # Response
+/// - **200 OK**: Estado y diff
+/// - **401 Unauthorized**: No autenticado
+/// - **403 Forbidden**: No es administrador del proyecto
+/// - **404 Not Found**: Proyecto inexistente o no visible
+/// - **409 Conflict**: El repositorio es bare
+///
+/// # Example Response
+/// ```json
+/// {
+///   "branch": "main",
+///   "head": "9fceb02d0ae598e95dc970b74767f19372d61af8",
+///   "is_clean": false,
+///   "staged": [],
+///   "unstaged": [{ "path": "config/app.toml", "old_path": null, "status": "modified" }],
+///   "untracked": ["tmp/"],
+///   "conflicted": [],
+///   "status_truncated": false,
+///   "stats": { "files_changed": 1, "additions": 1, "deletions": 1 },
+///   "files": [ ... ],
+///   "truncated": false
+/// }
+/// ```
+pub async fn status_handler(
+    State(state): State<AppState>,
+    Path(project_id): Path<i32>,
+    Query(query): Query<StatusQuery>,
+    auth: AuthUser,
+) -> Result<Json<WorkingCopyResponse>, ApiError> {
+    ensure_project_admin(&state, project_id, &auth).await?;
+    let project = find_project_by_id(&state.db, project_id).await?;
+    let limits = diff_limits(&state.git);
+
+    let response = tokio::task::spawn_blocking(move || -> git::Result<_> {
+        let repo = git::open_repository(&project.repository_path)?;
+        let changes = git::working_copy_status(&repo)?;
+        let diff = git::diff_workdir(&repo, query.format, limits)?;
+        let head = match repo.head() {
+            Ok(head) => head.target().map(|oid| oid.to_string()),
+            Err(e) if e.code() == git2::ErrorCode::UnbornBranch => None,
+            Err(e) => return Err(e.into()),
+        };
+
+        Ok(WorkingCopyResponse {
+            branch: git::default_branch(&repo)?,
+            head,
+            is_clean: changes.is_clean(),
+            staged: changes.staged,
+            unstaged: changes.unstaged,
+            untracked: changes.untracked,
+            conflicted: changes.conflicted,
+            status_truncated: changes.truncated,
+            diff,
+        })
+    })
+    .await??;
+
+    Ok(Json(response))
+}
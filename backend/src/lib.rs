@@ -1,3 +1,11 @@
+// El listado de endpoints de `root_handler` supera la recursión por defecto de `json!`
+#![recursion_limit = "256"]
+
+pub mod config;
 pub mod db;
 pub mod handlers;
+pub mod middleware;
 pub mod models;
+pub mod routes;
+pub mod services;
+pub mod utils;
@@ -1,16 +1,25 @@
-use axum::{routing::get, Router};
 use clap::Parser;
 use std::net::SocketAddr;
-use tower_http::trace::TraceLayer;
+use std::sync::Arc;
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 
 mod cli;
-mod db;
-mod handlers;
-mod models;
 
 use cli::{Cli, Commands};
-use handlers::{health_handler, root_handler, AppState};
+use lumastack_backend::{
+    config::{
+        AnalyzerConfig, AuthConfig, GitConfig, HealthConfig, SignatureConfig, VaultConfig,
+        WatcherConfig,
+    },
+    db,
+    handlers::AppState,
+    routes,
+    services::{
+        analyzer::RepositoryAnalyzer, comments::CommentAnchorTracker, events::EventBus,
+        git::BlameCache, health::HealthChecker, notifications::NotificationDispatcher,
+        signature::SignatureVerifier, vault::CredentialVault, watcher::PullWatcher,
+    },
+};
 
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
@@ -55,14 +64,52 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     }
 
     // Create application state
-    let state = AppState { db: pool };
+    let git = GitConfig::from_env();
+    let events = EventBus::default();
+
+    // Start pull detection on monitored repositories
+    let watcher_config = WatcherConfig::from_env();
+    if watcher_config.enabled {
+        PullWatcher::new(pool.clone(), events.clone(), watcher_config).spawn();
+    }
+
+    // Analyze language mix and repository size of each default branch
+    let analyzer_config = AnalyzerConfig::from_env();
+    if analyzer_config.enabled {
+        RepositoryAnalyzer::new(pool.clone(), events.clone(), analyzer_config).spawn();
+    }
+
+    // Check integrity, storage and working copy of every repository, and alert on dirty checkouts
+    let health = HealthConfig::from_env();
+    if health.enabled || health.working_copy_enabled {
+        HealthChecker::new(pool.clone(), events.clone(), health.clone()).spawn();
+    }
+
+    // Keep file comments attached to their lines as the default branches move
+    CommentAnchorTracker::new(pool.clone(), events.clone()).spawn();
+
+    // Notify project admins and requesters about locks, lock requests and dirty working copies
+    NotificationDispatcher::new(pool.clone(), events.clone()).spawn();
+
+    // Load trusted signing keys; without them every signature is reported as unverified
+    let signatures = Arc::new(SignatureVerifier::new(&SignatureConfig::from_env()));
+    if let Err(e) = signatures.reload(&pool).await {
+        tracing::error!("No se pudieron cargar las claves de firma: {}", e);
+    }
+
+    let state = AppState {
+        db: pool,
+        auth: AuthConfig::from_env(),
+        blame_cache: Arc::new(BlameCache::new(git.blame_cache_size)),
+        signatures,
+        events,
+        vault: CredentialVault::new(&VaultConfig::from_env()),
+        git,
+        health,
+    };
 
     // Build application router with routes
-    let app = Router::new()
-        .route("/", get(root_handler))
-        .route("/health", get(health_handler))
-        .layer(TraceLayer::new_for_http())
-        .with_state(state);
+    let app = routes::create_router(state);
 
     // Get server configuration from environment
     let host = std::env::var("HOST").unwrap_or_else(|_| "0.0.0.0".to_string());
@@ -0,0 +1,81 @@
+use axum::{
+    async_trait,
+    extract::FromRequestParts,
+    http::{header, request::Parts},
+};
+
+use crate::db::users::{find_user_by_id, UserError};
+use crate::handlers::{ApiError, AppState};
+use crate::models::UserRole;
+use crate::services::auth::verify_token;
+
+/// Usuario autenticado mediante `Authorization: Bearer <token>`
+///
+/// Usarlo como extractor en un handler exige autenticación. Además de
+/// validar el token se comprueba que el usuario siga existiendo y activo, y
+/// el rol se toma de la base de datos para que los cambios de rol apliquen
+/// sin esperar a que expire el token.
+#[derive(Debug, Clone)]
+pub struct AuthUser {
+    pub id: i32,
+    pub username: String,
+    pub role: UserRole,
+}
+
+impl AuthUser {
+    pub fn is_admin(&self) -> bool {
+        self.role == UserRole::Admin
+    }
+
+    /// Exige rol de administrador
+    ///
+    /// # Errors
+    /// - `ApiError::Forbidden` si el usuario no es administrador
+    pub fn require_admin(&self) -> Result<(), ApiError> {
+        if self.is_admin() {
+            Ok(())
+        } else {
+            Err(ApiError::Forbidden(
+                "Se requiere rol de administrador".to_string(),
+            ))
+        }
+    }
+}
+
+#[async_trait]
+impl FromRequestParts<AppState> for AuthUser {
+    type Rejection = ApiError;
+
+    async fn from_request_parts(
+        parts: &mut Parts,
+        state: &AppState,
+    ) -> Result<Self, Self::Rejection> {
+        let token = parts
+            .headers
+            .get(header::AUTHORIZATION)
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| v.strip_prefix("Bearer "))
+            .map(str::trim)
+            .filter(|t| !t.is_empty())
+            .ok_or_else(|| ApiError::Unauthorized("Se requiere autenticación".to_string()))?;
+
+        let claims =
+            verify_token(&state.auth, token).map_err(|e| ApiError::Unauthorized(e.to_string()))?;
+
+        let user = match find_user_by_id(&state.db, claims.sub).await {
+            Ok(user) if user.is_active => user,
+            Ok(_) | Err(UserError::NotFound) => {
+                return Err(ApiError::Unauthorized(
+                    "Token inválido o expirado".to_string(),
+                ))
+            }
+            Err(e) => return Err(ApiError::Internal(e.to_string())),
+        };
+
+        Ok(AuthUser {
+            id: user.id,
+            username: user.username,
+            role: user.role,
+        })
+    }
+}
@@ -0,0 +1,41 @@
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+
+/// Acción registrada en el log de auditoría
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type)]
+#[sqlx(type_name = "VARCHAR", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum AuditAction {
+    /// Descarga de un `.tar.gz` o `.zip` con `/archive`
+    ArchiveDownload,
+}
+
+/// Entrada del log de auditoría
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct AuditEntry {
+    pub id: i32,
+    pub project_id: Option<i32>,
+    /// `None` si el acceso fue anónimo o el usuario ya no existe
+    pub user_id: Option<i32>,
+    pub username: Option<String>,
+    pub action: AuditAction,
+    /// Datos propios de cada acción
+    pub details: serde_json::Value,
+    pub created_at: NaiveDateTime,
+}
+
+/// Datos para registrar una entrada de auditoría
+#[derive(Debug, Clone)]
+pub struct CreateAuditEntry {
+    pub project_id: Option<i32>,
+    pub user_id: Option<i32>,
+    pub action: AuditAction,
+    pub details: serde_json::Value,
+}
+
+/// Respuesta de `GET /api/projects/:id/audit`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuditLogResponse {
+    pub entries: Vec<AuditEntry>,
+}
@@ -0,0 +1,33 @@
+use serde::{Deserialize, Serialize};
+
+use super::CommitSummary;
+
+/// Rango de líneas consecutivas atribuidas al mismo commit
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BlameRange {
+    /// Primera línea del rango (base 1)
+    pub start_line: usize,
+    /// Última línea del rango (inclusive)
+    pub end_line: usize,
+    /// Commit que introdujo las líneas, con el autor resuelto vía `.mailmap`
+    pub commit: CommitSummary,
+    /// Ruta del archivo en ese commit, solo si difiere de la actual (renombrados)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub original_path: Option<String>,
+    /// Línea inicial del rango en ese commit
+    pub original_start_line: usize,
+}
+
+/// Respuesta de `GET /api/projects/:id/blame`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BlameResponse {
+    /// Referencia solicitada (`HEAD` si no se indicó)
+    #[serde(rename = "ref")]
+    pub git_ref: String,
+    /// Commit al que resolvió la referencia
+    pub commit: String,
+    pub path: String,
+    /// OID del blob anotado
+    pub oid: String,
+    pub ranges: Vec<BlameRange>,
+}
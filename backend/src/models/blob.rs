@@ -0,0 +1,52 @@
+use serde::{Deserialize, Serialize};
+
+/// Respuesta de `GET /api/projects/:id/blob`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BlobResponse {
+    /// Referencia solicitada (`HEAD` si no se indicó)
+    #[serde(rename = "ref")]
+    pub git_ref: String,
+    /// Commit al que resolvió la referencia
+    pub commit: String,
+    pub path: String,
+    pub name: String,
+    pub oid: String,
+    /// Tamaño real del archivo en bytes (el del objeto LFS si es un puntero)
+    pub size: u64,
+    /// Modo Git en octal
+    pub mode: String,
+    /// Tipo MIME deducido de la extensión
+    pub mime_type: String,
+    pub binary: bool,
+    pub is_symlink: bool,
+    /// Codificación detectada; `None` para archivos binarios
+    pub encoding: Option<String>,
+    pub language: Option<String>,
+    /// Contenido decodificado a UTF-8; `None` para archivos binarios
+    pub content: Option<String>,
+    /// `true` si el contenido se recortó por superar el límite configurado
+    pub truncated: bool,
+    /// URL del contenido original (descarga o vista previa de imágenes/PDF)
+    pub raw_url: String,
+    /// Puntero de Git LFS; si el objeto está en el almacén local, el
+    /// contenido es el del archivo real y no el del puntero
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub lfs: Option<LfsInfo>,
+    /// HTML con resaltado de sintaxis, solo si se pidió con `highlight=true`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub highlighted_html: Option<String>,
+    /// HTML saneado de archivos Markdown, solo si se pidió con `render=true`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rendered_html: Option<String>,
+}
+
+/// Archivo guardado con Git LFS
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LfsInfo {
+    /// SHA-256 del contenido
+    pub oid: String,
+    /// Tamaño real del archivo
+    pub size: u64,
+    /// `true` si el objeto está descargado en el almacén LFS del servidor
+    pub available: bool,
+}
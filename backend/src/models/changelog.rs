@@ -0,0 +1,78 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Trailer del pie de un mensaje (`Co-authored-by: Ada <ada@example.com>`)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Trailer {
+    pub key: String,
+    pub value: String,
+}
+
+/// Mensaje de commit interpretado según Conventional Commits
+///
+/// Si la cabecera no sigue el formato `tipo(ámbito)!: descripción`,
+/// `commit_type` es `None` y `description` es la primera línea; los
+/// trailers y referencias a issues se extraen igualmente.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ConventionalCommit {
+    /// Tipo en minúsculas (`feat`, `fix`...)
+    pub commit_type: Option<String>,
+    pub scope: Option<String>,
+    /// `!` en la cabecera o trailer `BREAKING CHANGE`
+    pub breaking: bool,
+    pub description: String,
+    /// Texto del trailer `BREAKING CHANGE`, si lo hay
+    pub breaking_note: Option<String>,
+    pub trailers: Vec<Trailer>,
+    /// Referencias a issues (`#12`, `org/repo#12`, `PROJ-12` en trailers)
+    pub issues: Vec<String>,
+}
+
+/// Formato de la respuesta de `/changelog`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ChangelogFormat {
+    #[default]
+    Json,
+    Markdown,
+}
+
+/// Commit de un changelog
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChangelogEntry {
+    pub hash: String,
+    pub scope: Option<String>,
+    pub description: String,
+    pub breaking: bool,
+    pub breaking_note: Option<String>,
+    pub author_name: String,
+    pub authored_at: DateTime<Utc>,
+    /// Valores de los trailers `Co-authored-by`
+    pub co_authors: Vec<String>,
+    pub issues: Vec<String>,
+}
+
+/// Grupo de commits de un mismo tipo
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChangelogSection {
+    /// Tipo del grupo (`feat`, `fix`...), `breaking` u `other`
+    pub kind: String,
+    pub title: String,
+    pub entries: Vec<ChangelogEntry>,
+}
+
+/// Respuesta JSON de `GET /api/projects/:id/changelog`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChangelogResponse {
+    /// Referencia de origen (excluida); `None` si se parte del primer commit
+    pub from: Option<String>,
+    pub from_commit: Option<String>,
+    pub to: String,
+    pub to_commit: String,
+    pub date: DateTime<Utc>,
+    /// Commits incluidos (sin merges)
+    pub total_commits: usize,
+    /// `true` si se alcanzó el máximo de commits
+    pub truncated: bool,
+    pub sections: Vec<ChangelogSection>,
+}
@@ -0,0 +1,126 @@
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+
+use super::UserResponse;
+
+/// Comentario de un proyecto o de un commit
+///
+/// Las respuestas cuelgan de un comentario raíz (`parent_comment_id`) y
+/// solo hay un nivel de anidamiento. Un comentario eliminado conserva su
+/// lugar en el hilo mientras tenga respuestas, pero sin contenido.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Comment {
+    pub id: i32,
+    pub project_id: i32,
+    /// `None` si el autor ya no existe
+    pub user_id: Option<i32>,
+    /// Commit comentado; `None` en los comentarios del proyecto
+    pub commit_hash: Option<String>,
+    /// Markdown original; vacío si el comentario fue eliminado
+    pub content: String,
+    /// `content` renderizado a HTML saneado, con los commits y `#comentarios` enlazados
+    pub content_html: String,
+    /// Archivo comentado en `commit_hash`; `None` si el comentario no es de un archivo
+    pub file_path: Option<String>,
+    /// Líneas comentadas en `commit_hash` (empezando en 1, inclusive)
+    pub line_start: Option<i32>,
+    pub line_end: Option<i32>,
+    /// Posición en la punta de la rama por defecto, si se pudo seguir
+    pub position: Option<CommentPosition>,
+    /// `true` si las líneas comentadas cambiaron o el archivo se borró;
+    /// `position` conserva entonces la última posición conocida
+    pub is_outdated: bool,
+    /// Usuarios mencionados con `@usuario` que pueden ver el proyecto
+    pub mentions: Vec<String>,
+    pub parent_comment_id: Option<i32>,
+    /// Número de ediciones; las versiones anteriores están en `/revisions`
+    pub revision_count: i64,
+    /// Reacciones agrupadas por emoji, en el orden en que aparecieron
+    pub reactions: Vec<ReactionCount>,
+    /// Momento en que se resolvió el hilo; `None` mientras está abierto
+    /// (solo se resuelven comentarios raíz)
+    pub resolved_at: Option<NaiveDateTime>,
+    /// Usuario que resolvió el hilo; `None` si está abierto o el usuario ya no existe
+    pub resolved_by: Option<i32>,
+    pub resolved_by_username: Option<String>,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+    pub deleted_at: Option<NaiveDateTime>,
+    /// Autor del comentario
+    pub user: Option<UserResponse>,
+}
+
+/// Posición de un comentario de archivo trasladada a un commit posterior
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CommentPosition {
+    /// Commit en el que se calculó la posición
+    pub commit: String,
+    pub path: String,
+    pub line_start: Option<i32>,
+    pub line_end: Option<i32>,
+}
+
+/// Reacciones de un comentario con un mismo emoji
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReactionCount {
+    pub emoji: String,
+    pub count: i64,
+    /// Usuarios que reaccionaron, del primero al último
+    pub users: Vec<String>,
+}
+
+/// Datos para reaccionar a un comentario
+#[derive(Debug, Clone, Deserialize)]
+pub struct CreateReaction {
+    pub emoji: String,
+}
+
+/// Datos para crear un comentario
+#[derive(Debug, Clone, Deserialize)]
+pub struct CreateComment {
+    pub content: String,
+    /// Hash, rama o tag del commit comentado; se guarda el hash completo
+    pub commit_hash: Option<String>,
+    /// Archivo comentado; sin `commit_hash` se usa `HEAD`
+    pub file_path: Option<String>,
+    /// Líneas comentadas; sin ellas el comentario es sobre el archivo completo
+    pub line_start: Option<i32>,
+    pub line_end: Option<i32>,
+    /// Comentario raíz al que se responde
+    pub parent_comment_id: Option<i32>,
+}
+
+/// Datos para editar un comentario
+#[derive(Debug, Clone, Deserialize)]
+pub struct UpdateComment {
+    pub content: String,
+}
+
+/// Respuesta de `GET /api/projects/:id/comments`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CommentsResponse {
+    /// Del más antiguo al más reciente; las respuestas se agrupan por `parent_comment_id`
+    pub comments: Vec<Comment>,
+}
+
+/// Versión anterior de un comentario editado
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CommentRevision {
+    pub id: i32,
+    pub comment_id: i32,
+    /// Contenido antes de la edición
+    pub content: String,
+    pub content_html: String,
+    /// Usuario que hizo la edición; `None` si ya no existe
+    pub edited_by: Option<i32>,
+    pub edited_by_username: Option<String>,
+    /// Momento en que esta versión fue reemplazada
+    pub edited_at: NaiveDateTime,
+}
+
+/// Respuesta de `GET /api/projects/:id/comments/:comment_id/revisions`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CommentRevisionsResponse {
+    /// De la más antigua (el contenido original) a la más reciente
+    pub revisions: Vec<CommentRevision>,
+}
@@ -0,0 +1,35 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::{CommitSignature, ConventionalCommit};
+
+/// Resumen de un commit leído directamente de la base de objetos de Git
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CommitSummary {
+    pub hash: String,
+    /// Primera línea del mensaje del commit
+    pub summary: String,
+    pub author_name: String,
+    pub author_email: String,
+    pub authored_at: DateTime<Utc>,
+    /// Estado de la firma; solo en las respuestas que la verifican
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signature: Option<CommitSignature>,
+}
+
+/// Commit leído de Git para guardarlo en la caché de commits
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CommitRecord {
+    pub hash: String,
+    pub author_name: String,
+    pub author_email: String,
+    /// Mensaje completo del commit
+    pub message: String,
+    pub committed_at: DateTime<Utc>,
+    /// Líneas añadidas respecto al primer padre; `None` en los merges
+    pub additions: Option<i32>,
+    /// Líneas eliminadas respecto al primer padre; `None` en los merges
+    pub deletions: Option<i32>,
+    /// Tipo, ámbito, trailers e issues interpretados del mensaje
+    pub conventional: ConventionalCommit,
+}
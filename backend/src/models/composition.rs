@@ -0,0 +1,88 @@
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+
+/// Bytes y archivos de un lenguaje en un árbol
+#[derive(Debug, Clone, PartialEq, Eq, FromRow, Serialize, Deserialize)]
+pub struct LanguageBytes {
+    pub language: String,
+    pub files: i32,
+    pub bytes: i64,
+}
+
+/// Composición guardada de la punta de la rama por defecto
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct RepositorySnapshot {
+    pub id: i32,
+    pub project_id: i32,
+    pub commit_hash: String,
+    pub branch: String,
+    pub committed_at: NaiveDateTime,
+    /// Archivos contados (sin vendorizados, generados ni documentación)
+    pub total_files: i32,
+    /// Suma de sus tamaños
+    pub total_bytes: i64,
+    /// Archivos excluidos por las reglas de tipo linguist
+    pub excluded_files: i32,
+    /// Tamaño del directorio Git en disco
+    pub disk_size: i64,
+    pub pack_count: i32,
+    pub pack_size: i64,
+    pub loose_objects: i32,
+    pub loose_size: i64,
+    pub analyzed_at: Option<NaiveDateTime>,
+}
+
+/// Datos para guardar una composición
+#[derive(Debug, Clone)]
+pub struct CreateRepositorySnapshot {
+    pub project_id: i32,
+    pub commit_hash: String,
+    pub branch: String,
+    pub committed_at: NaiveDateTime,
+    pub total_files: i32,
+    pub total_bytes: i64,
+    pub excluded_files: i32,
+    pub disk_size: i64,
+    pub pack_count: i32,
+    pub pack_size: i64,
+    pub loose_objects: i32,
+    pub loose_size: i64,
+    /// Ordenados de más a menos bytes
+    pub languages: Vec<LanguageBytes>,
+}
+
+/// Lenguaje con su porcentaje sobre los bytes con lenguaje detectado
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LanguageShare {
+    pub language: String,
+    pub files: i32,
+    pub bytes: i64,
+    /// 0–100, con dos decimales
+    pub percentage: f64,
+}
+
+/// Respuesta de `GET /api/projects/:id/languages`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CompositionResponse {
+    pub snapshot: RepositorySnapshot,
+    pub languages: Vec<LanguageShare>,
+}
+
+/// Composición de un commit en la serie histórica
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CompositionPoint {
+    pub commit_hash: String,
+    pub committed_at: NaiveDateTime,
+    pub total_files: i32,
+    pub total_bytes: i64,
+    pub disk_size: i64,
+    pub languages: Vec<LanguageBytes>,
+}
+
+/// Respuesta de `GET /api/projects/:id/languages/history`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CompositionHistoryResponse {
+    /// Del commit más antiguo al más reciente
+    pub points: Vec<CompositionPoint>,
+}
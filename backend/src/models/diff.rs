@@ -0,0 +1,139 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::{CodeOwner, CommitSignature, CommitSummary};
+
+/// Formato de los cambios de cada archivo en las respuestas de diff
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DiffFormat {
+    /// Hunks con líneas estructuradas (origen y números de línea)
+    #[default]
+    Structured,
+    /// Texto del parche en formato unified diff
+    Unified,
+}
+
+/// Tipo de cambio de un archivo
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FileStatus {
+    Added,
+    Modified,
+    Deleted,
+    Renamed,
+    Copied,
+    /// Cambio de tipo (p. ej. archivo regular a symlink)
+    TypeChange,
+}
+
+/// Origen de una línea dentro de un hunk
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DiffLineOrigin {
+    Context,
+    Addition,
+    Deletion,
+}
+
+/// Línea de un hunk
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DiffLine {
+    pub origin: DiffLineOrigin,
+    pub old_lineno: Option<u32>,
+    pub new_lineno: Option<u32>,
+    /// Contenido sin el salto de línea final
+    pub content: String,
+}
+
+/// Bloque de cambios contiguos de un archivo
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DiffHunk {
+    /// Cabecera `@@ -a,b +c,d @@ contexto`
+    pub header: String,
+    pub old_start: u32,
+    pub old_lines: u32,
+    pub new_start: u32,
+    pub new_lines: u32,
+    pub lines: Vec<DiffLine>,
+}
+
+/// Cambios de un archivo
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FileDiff {
+    /// Ruta en la revisión nueva (o la antigua si se eliminó)
+    pub path: String,
+    /// Ruta anterior, solo en renombrados y copias
+    pub old_path: Option<String>,
+    pub status: FileStatus,
+    pub old_mode: Option<String>,
+    pub new_mode: Option<String>,
+    pub binary: bool,
+    pub additions: usize,
+    pub deletions: usize,
+    /// `true` si el contenido del diff se omitió por superar los límites
+    pub truncated: bool,
+    /// Hunks estructurados (formato `structured`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hunks: Option<Vec<DiffHunk>>,
+    /// Parche en texto (formato `unified`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub patch: Option<String>,
+}
+
+/// Totales de un diff
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DiffStats {
+    pub files_changed: usize,
+    pub additions: usize,
+    pub deletions: usize,
+}
+
+/// Diff entre dos árboles
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DiffResult {
+    pub stats: DiffStats,
+    pub files: Vec<FileDiff>,
+    /// `true` si se omitieron archivos o contenido por los límites configurados
+    pub truncated: bool,
+}
+
+/// Respuesta de `GET /api/projects/:id/commits/:hash`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CommitDetailResponse {
+    pub hash: String,
+    pub tree: String,
+    pub parents: Vec<String>,
+    pub summary: String,
+    /// Mensaje completo del commit
+    pub message: String,
+    pub author_name: String,
+    pub author_email: String,
+    pub authored_at: DateTime<Utc>,
+    pub committer_name: String,
+    pub committer_email: String,
+    pub committed_at: DateTime<Utc>,
+    pub signature: CommitSignature,
+    /// Dueños de los archivos tocados según el `CODEOWNERS` de `HEAD`
+    pub owners: Vec<CodeOwner>,
+    /// Diff contra el primer padre (o contra el árbol vacío si es el commit raíz)
+    #[serde(flatten)]
+    pub diff: DiffResult,
+}
+
+/// Respuesta de `GET /api/projects/:id/compare`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CompareResponse {
+    pub base: String,
+    pub head: String,
+    /// Ancestro común usado como origen del diff (comparación de tres puntos)
+    pub merge_base: Option<String>,
+    /// Commits en `head` que no están en `base`
+    pub ahead_by: usize,
+    /// Commits en `base` que no están en `head`
+    pub behind_by: usize,
+    /// Commits de `base..head`, del más reciente al más antiguo (limitado)
+    pub commits: Vec<CommitSummary>,
+    #[serde(flatten)]
+    pub diff: DiffResult,
+}
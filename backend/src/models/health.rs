@@ -0,0 +1,121 @@
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use sqlx::types::Json;
+use sqlx::FromRow;
+
+/// Resultado global de una revisión de salud
+///
+/// El orden importa: una revisión toma el estado de su problema más grave.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize, sqlx::Type,
+)]
+#[sqlx(type_name = "VARCHAR", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum HealthStatus {
+    Healthy,
+    /// Requiere mantenimiento (p. ej. `git gc`) pero el repositorio es usable
+    Warning,
+    /// Objetos corruptos, conflictos sin resolver o repositorio inaccesible
+    Failing,
+}
+
+/// Tipo de problema detectado en una revisión
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HealthIssueKind {
+    /// No se pudo abrir o leer el repositorio
+    RepositoryError,
+    /// Referencia rota u objeto alcanzable ausente o corrupto
+    Integrity,
+    /// Demasiados objetos que nada alcanza
+    UnreachableObjects,
+    /// Demasiados objetos sueltos sin empaquetar
+    LooseObjects,
+    /// Demasiados packs
+    PackCount,
+    /// Los packs ocupan demasiado
+    PackSize,
+    /// El working copy tiene cambios sin commitear
+    DirtyWorkingCopy,
+    /// `HEAD` no apunta a una rama
+    DetachedHead,
+    /// Hay archivos con conflictos sin resolver
+    UnmergedFiles,
+}
+
+/// Problema detectado en una revisión
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HealthIssue {
+    pub kind: HealthIssueKind,
+    pub severity: HealthStatus,
+    pub message: String,
+}
+
+/// Revisión de salud guardada de un repositorio
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct RepositoryHealthCheck {
+    pub id: i32,
+    pub project_id: i32,
+    pub status: HealthStatus,
+    /// `false` si faltan objetos alcanzables o alguna referencia está rota
+    pub integrity_ok: bool,
+    /// `false` si el recorrido se detuvo en el límite de objetos
+    pub integrity_complete: bool,
+    pub objects_checked: i32,
+    /// Objetos colgando; `None` si el recorrido no se completó
+    pub unreachable_objects: Option<i32>,
+    pub loose_objects: i32,
+    pub loose_size: i64,
+    pub pack_count: i32,
+    pub pack_size: i64,
+    pub is_bare: bool,
+    /// Archivos modificados, en el índice o sin seguimiento
+    pub dirty_files: i32,
+    pub detached_head: bool,
+    pub unmerged_files: Vec<String>,
+    pub issues: Json<Vec<HealthIssue>>,
+    /// Usuario que pidió la revisión; `None` si fue programada
+    pub triggered_by: Option<i32>,
+    pub triggered_by_username: Option<String>,
+    pub duration_ms: i32,
+    pub checked_at: NaiveDateTime,
+}
+
+/// Datos para guardar una revisión de salud
+#[derive(Debug, Clone)]
+pub struct CreateRepositoryHealthCheck {
+    pub project_id: i32,
+    pub status: HealthStatus,
+    pub integrity_ok: bool,
+    pub integrity_complete: bool,
+    pub objects_checked: i32,
+    pub unreachable_objects: Option<i32>,
+    pub loose_objects: i32,
+    pub loose_size: i64,
+    pub pack_count: i32,
+    pub pack_size: i64,
+    pub is_bare: bool,
+    pub dirty_files: i32,
+    pub detached_head: bool,
+    pub unmerged_files: Vec<String>,
+    pub issues: Vec<HealthIssue>,
+    pub triggered_by: Option<i32>,
+    pub duration_ms: i32,
+}
+
+/// Respuesta de `GET /api/projects/:id/health`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HealthChecksResponse {
+    /// De la más reciente a la más antigua
+    pub checks: Vec<RepositoryHealthCheck>,
+}
+
+/// Proyectos por estado de su última revisión, para `GET /health`
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RepositoryHealthSummary {
+    pub healthy: i64,
+    pub warning: i64,
+    pub failing: i64,
+    /// Proyectos aún sin revisar
+    pub unchecked: i64,
+}
@@ -0,0 +1,109 @@
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+
+/// Cambio de estado registrado en el historial de bloqueos
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type)]
+#[sqlx(type_name = "VARCHAR", rename_all = "lowercase")]
+#[serde(rename_all = "lowercase")]
+pub enum LockAction {
+    Locked,
+    Unlocked,
+}
+
+/// Estado de una solicitud de bloqueo
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type)]
+#[sqlx(type_name = "VARCHAR", rename_all = "lowercase")]
+#[serde(rename_all = "lowercase")]
+pub enum LockRequestStatus {
+    Pending,
+    Approved,
+    Rejected,
+}
+
+/// Estado de bloqueo de un proyecto
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct LockStatus {
+    pub project_id: i32,
+    pub is_blocked: bool,
+    pub reason: Option<String>,
+    pub locked_by: Option<i32>,
+    pub locked_by_username: Option<String>,
+    pub locked_at: Option<NaiveDateTime>,
+}
+
+/// Entrada del historial de bloqueos de un proyecto
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct LockHistoryEntry {
+    pub id: i32,
+    pub project_id: i32,
+    pub action: LockAction,
+    pub reason: Option<String>,
+    pub actor_id: Option<i32>,
+    pub actor_username: Option<String>,
+    /// Solicitud aprobada que originó el bloqueo, si la hubo
+    pub request_id: Option<i32>,
+    pub created_at: NaiveDateTime,
+}
+
+/// Solicitud de bloqueo hecha por un usuario
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct LockRequest {
+    pub id: i32,
+    pub project_id: i32,
+    pub requested_by: Option<i32>,
+    pub requested_by_username: Option<String>,
+    pub reason: String,
+    pub status: LockRequestStatus,
+    pub reviewed_by: Option<i32>,
+    pub review_comment: Option<String>,
+    pub created_at: NaiveDateTime,
+    pub reviewed_at: Option<NaiveDateTime>,
+}
+
+/// Datos para bloquear un proyecto (administradores)
+#[derive(Debug, Clone, Deserialize)]
+pub struct LockProject {
+    pub reason: String,
+}
+
+/// Datos para desbloquear un proyecto (administradores)
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct UnlockProject {
+    pub reason: Option<String>,
+}
+
+/// Datos para solicitar el bloqueo de un proyecto
+#[derive(Debug, Clone, Deserialize)]
+pub struct CreateLockRequest {
+    pub reason: String,
+}
+
+/// Datos para aprobar o rechazar una solicitud de bloqueo
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct ReviewLockRequest {
+    pub comment: Option<String>,
+}
+
+/// Respuesta de `GET /api/projects/:id/lock/history`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LockHistoryResponse {
+    pub history: Vec<LockHistoryEntry>,
+}
+
+/// Respuesta de `GET /api/projects/:id/lock/requests`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LockRequestsResponse {
+    pub requests: Vec<LockRequest>,
+}
+
+/// Estado del hook Git que impide pulls mientras el proyecto está bloqueado
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LockHookStatus {
+    /// Ruta del hook (`reference-transaction`)
+    pub path: String,
+    /// `true` si el hook de LumaStack está instalado
+    pub installed: bool,
+    /// `true` si en esa ruta hay un hook ajeno que LumaStack no modificará
+    pub foreign_hook: bool,
+}
@@ -1,3 +1,88 @@
+pub mod archive;
+pub mod audit;
+pub mod blame;
+pub mod blob;
+pub mod changelog;
+pub mod comment;
+pub mod commit;
+pub mod composition;
+pub mod diff;
+pub mod health;
+pub mod lock;
+pub mod notification;
+pub mod owners;
+pub mod project;
+pub mod pull;
+pub mod refs;
+pub mod search;
+pub mod signature;
+pub mod stats;
+pub mod status;
+pub mod sync;
+pub mod tree;
 pub mod user;
 
+pub use archive::ArchiveFormat;
+pub use audit::{AuditAction, AuditEntry, AuditLogResponse, CreateAuditEntry};
+pub use blame::{BlameRange, BlameResponse};
+pub use blob::{BlobResponse, LfsInfo};
+pub use changelog::{
+    ChangelogEntry, ChangelogFormat, ChangelogResponse, ChangelogSection, ConventionalCommit,
+    Trailer,
+};
+pub use comment::{
+    Comment, CommentPosition, CommentRevision, CommentRevisionsResponse, CommentsResponse,
+    CreateComment, CreateReaction, ReactionCount, UpdateComment,
+};
+pub use commit::{CommitRecord, CommitSummary};
+pub use composition::{
+    CompositionHistoryResponse, CompositionPoint, CompositionResponse, CreateRepositorySnapshot,
+    LanguageBytes, LanguageShare, RepositorySnapshot,
+};
+pub use diff::{
+    CommitDetailResponse, CompareResponse, DiffFormat, DiffHunk, DiffLine, DiffLineOrigin,
+    DiffResult, DiffStats, FileDiff, FileStatus,
+};
+pub use health::{
+    CreateRepositoryHealthCheck, HealthChecksResponse, HealthIssue, HealthIssueKind, HealthStatus,
+    RepositoryHealthCheck, RepositoryHealthSummary,
+};
+pub use lock::{
+    CreateLockRequest, LockAction, LockHistoryEntry, LockHistoryResponse, LockHookStatus,
+    LockProject, LockRequest, LockRequestStatus, LockRequestsResponse, LockStatus,
+    ReviewLockRequest, UnlockProject,
+};
+pub use notification::{
+    CreateNotification, MarkAllReadResponse, Notification, NotificationType,
+    NotificationsResponse, UnreadCountResponse,
+};
+pub use owners::{
+    CodeOwner, OwnerMapping, OwnerMappingsResponse, OwnerUser, OwnersResponse, OwnershipRule,
+    SetOwnerMapping,
+};
+pub use project::{
+    CreateProject, Project, ProjectMember, ProjectMembersResponse, ProjectResponse, ProjectRole,
+    ProjectScanResponse, ReadmeResponse, ScanProjects, ScanSkipReason, SkippedRepository,
+};
+pub use pull::{CreatePullEvent, PullEvent, PullEventsResponse};
+pub use refs::{BranchInfo, BranchesResponse, TagInfo, TagsResponse, UpstreamStatus};
+pub use search::{
+    CodeMatch, CodeSearchEvent, CodeSearchSummary, CommitSearchHit, MatchRange, ProjectSearchHit,
+    SearchResponse, SearchScope,
+};
+pub use signature::{
+    CommitSignature, CreateSigningKey, SignatureKind, SignatureStatus, SigningKey,
+    SigningKeysResponse, UnverifiedReason,
+};
+pub use stats::{
+    ActivityPoint, ActivityResponse, ActivityTotals, AuthorActivity, AuthorStats,
+    AuthorStatsResponse, AuthorStatsRow, DashboardSummary, HeatmapResponse, ProjectActivity,
+    StatsInterval,
+};
+pub use status::{WorkingCopyFile, WorkingCopyResponse, WorkingCopySummary};
+pub use sync::{
+    CreateSyncOperation, CredentialKind, CredentialResponse, ProjectCredential, RemoteCredentials,
+    SetCredential, SyncOperation, SyncOperationsResponse, SyncStatus,
+};
+pub use tree::{SubmoduleInfo, TreeEntry, TreeEntryType, TreeResponse};
 pub use user::{User, UserResponse, UserRole, CreateUser, UpdateUser};
@@ -0,0 +1,66 @@
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+
+/// Categoría de una notificación
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type)]
+#[sqlx(type_name = "VARCHAR", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum NotificationType {
+    Commit,
+    Comment,
+    /// Se mencionó al usuario con `@usuario`
+    Mention,
+    /// Bloqueos del proyecto y solicitudes de bloqueo
+    Lock,
+    /// El working copy del proyecto tiene cambios sin commitear
+    WorkingCopy,
+}
+
+/// Notificación de un usuario dentro de la aplicación
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct Notification {
+    pub id: i32,
+    pub user_id: i32,
+    pub project_id: Option<i32>,
+    #[serde(rename = "type")]
+    pub kind: NotificationType,
+    pub title: String,
+    pub message: String,
+    /// Ruta de la aplicación a la que lleva la notificación
+    pub link: Option<String>,
+    pub is_read: bool,
+    pub created_at: NaiveDateTime,
+}
+
+/// Datos para crear una notificación, iguales para todos sus destinatarios
+#[derive(Debug, Clone)]
+pub struct CreateNotification {
+    pub project_id: Option<i32>,
+    pub kind: NotificationType,
+    pub title: String,
+    pub message: String,
+    pub link: Option<String>,
+}
+
+/// Respuesta de `GET /api/notifications`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NotificationsResponse {
+    /// De la más reciente a la más antigua
+    pub notifications: Vec<Notification>,
+    /// Valor de `cursor` para pedir la página siguiente; `None` si no hay más
+    pub next_cursor: Option<i32>,
+}
+
+/// Respuesta de `GET /api/notifications/unread-count`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UnreadCountResponse {
+    pub unread: i64,
+}
+
+/// Respuesta de `POST /api/notifications/read-all`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MarkAllReadResponse {
+    /// Notificaciones que estaban sin leer
+    pub updated: u64,
+}
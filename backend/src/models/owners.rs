@@ -0,0 +1,72 @@
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+
+/// Usuario de LumaStack al que corresponde un dueño de `CODEOWNERS`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OwnerUser {
+    pub id: i32,
+    pub username: String,
+}
+
+/// Dueño de una ruta resuelto a un usuario y/o a un usuario de Telegram
+///
+/// Sin mapeo explícito, `@usuario` se resuelve por nombre de usuario y un
+/// email por el email de la cuenta. Los equipos (`@org/equipo`) solo se
+/// resuelven con un mapeo.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CodeOwner {
+    /// Dueño tal como aparece en `CODEOWNERS`
+    pub owner: String,
+    pub user: Option<OwnerUser>,
+    /// Usuario de Telegram, sin `@`
+    pub telegram_handle: Option<String>,
+}
+
+/// Regla de `CODEOWNERS` que asigna los dueños de una ruta
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OwnershipRule {
+    /// Línea del archivo (empezando en 1)
+    pub line: usize,
+    pub pattern: String,
+}
+
+/// Respuesta de `GET /api/projects/:id/owners`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OwnersResponse {
+    pub commit: String,
+    /// Archivo `CODEOWNERS` usado; `None` si el repositorio no tiene
+    pub source: Option<String>,
+    pub path: String,
+    /// Última regla que coincide con la ruta
+    pub rule: Option<OwnershipRule>,
+    pub owners: Vec<CodeOwner>,
+}
+
+/// Mapeo de un dueño de `CODEOWNERS` a un usuario o a Telegram
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct OwnerMapping {
+    pub id: i32,
+    pub owner: String,
+    pub user_id: Option<i32>,
+    pub username: Option<String>,
+    pub telegram_handle: Option<String>,
+    pub created_by: Option<i32>,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+/// Datos para crear o reemplazar el mapeo de un dueño
+#[derive(Debug, Clone, Deserialize)]
+pub struct SetOwnerMapping {
+    /// `@usuario`, `@org/equipo` o email
+    pub owner: String,
+    pub user_id: Option<i32>,
+    pub telegram_handle: Option<String>,
+}
+
+/// Respuesta de `GET /api/owner-mappings`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OwnerMappingsResponse {
+    pub mappings: Vec<OwnerMapping>,
+}
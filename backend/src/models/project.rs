@@ -0,0 +1,150 @@
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+
+use super::RepositoryHealthCheck;
+
+/// Repositorio Git monitoreado por el sistema
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct Project {
+    pub id: i32,
+    pub name: String,
+    /// Ruta absoluta al directorio `.git` (o al repositorio bare)
+    pub repository_path: String,
+    pub description: Option<String>,
+    pub is_public: bool,
+    pub last_scanned_at: Option<NaiveDateTime>,
+    pub last_pull_at: Option<NaiveDateTime>,
+    /// Si es `true`, el hook instalado rechaza los pulls
+    pub is_blocked: bool,
+    pub lock_reason: Option<String>,
+    pub locked_by: Option<i32>,
+    pub locked_at: Option<NaiveDateTime>,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+/// Proyecto para respuestas API
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProjectResponse {
+    pub id: i32,
+    pub name: String,
+    pub path: String,
+    pub description: Option<String>,
+    pub is_public: bool,
+    /// Commit al que apunta `HEAD`; `None` si el repositorio está vacío
+    pub last_commit_hash: Option<String>,
+    /// Rama a la que apunta `HEAD`; `None` si está desacoplado
+    pub default_branch: Option<String>,
+    pub last_scanned_at: Option<NaiveDateTime>,
+    /// Último pull detectado por el watcher
+    pub last_pull_at: Option<NaiveDateTime>,
+    pub is_blocked: bool,
+    pub lock_reason: Option<String>,
+    pub locked_by: Option<i32>,
+    pub locked_at: Option<NaiveDateTime>,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+    /// README de la raíz renderizado a HTML saneado
+    pub readme: Option<ReadmeResponse>,
+    /// Última revisión de salud del repositorio
+    pub health: Option<RepositoryHealthCheck>,
+}
+
+impl From<Project> for ProjectResponse {
+    fn from(project: Project) -> Self {
+        Self {
+            id: project.id,
+            name: project.name,
+            path: project.repository_path,
+            description: project.description,
+            is_public: project.is_public,
+            last_commit_hash: None,
+            default_branch: None,
+            last_scanned_at: project.last_scanned_at,
+            last_pull_at: project.last_pull_at,
+            is_blocked: project.is_blocked,
+            lock_reason: project.lock_reason,
+            locked_by: project.locked_by,
+            locked_at: project.locked_at,
+            created_at: project.created_at,
+            updated_at: project.updated_at,
+            readme: None,
+            health: None,
+        }
+    }
+}
+
+/// README renderizado de un proyecto
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReadmeResponse {
+    pub path: String,
+    pub html: String,
+}
+
+/// Rol de un usuario en un proyecto (`project_members.role`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type)]
+#[sqlx(type_name = "VARCHAR", rename_all = "lowercase")]
+#[serde(rename_all = "lowercase")]
+pub enum ProjectRole {
+    /// Solo lectura
+    Viewer,
+    /// Puede comentar
+    Contributor,
+    /// Gestiona el proyecto
+    Admin,
+}
+
+/// Miembro de un proyecto, para autocompletar menciones
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct ProjectMember {
+    pub user_id: i32,
+    pub username: String,
+    pub role: ProjectRole,
+}
+
+/// Respuesta de `GET /api/projects/:id/members`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProjectMembersResponse {
+    pub members: Vec<ProjectMember>,
+}
+
+/// Datos para registrar un proyecto
+#[derive(Debug, Clone)]
+pub struct CreateProject {
+    pub name: String,
+    pub repository_path: String,
+}
+
+/// Body de `POST /api/projects/scan`
+#[derive(Debug, Clone, Deserialize)]
+pub struct ScanProjects {
+    /// Directorio absoluto en el que buscar repositorios
+    pub path: String,
+}
+
+/// Motivo por el que un repositorio encontrado no se registró
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ScanSkipReason {
+    /// Ya hay un proyecto sobre ese repositorio
+    AlreadyRegistered,
+    /// Es un worktree enlazado; se vigila a través de su repositorio principal
+    LinkedWorktree,
+}
+
+/// Repositorio encontrado que no se registró como proyecto
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SkippedRepository {
+    pub path: String,
+    pub reason: ScanSkipReason,
+    /// Proyecto del repositorio principal, si está registrado
+    pub project_id: Option<i32>,
+}
+
+/// Respuesta de `POST /api/projects/scan`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProjectScanResponse {
+    pub created: Vec<ProjectResponse>,
+    pub skipped: Vec<SkippedRepository>,
+}
@@ -0,0 +1,34 @@
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+
+/// Actualización de una rama detectada por el watcher de repositorios
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct PullEvent {
+    pub id: i32,
+    pub project_id: i32,
+    /// Referencia completa (p. ej. `refs/heads/main`)
+    pub ref_name: String,
+    /// Punta anterior; `None` si la rama se creó
+    pub old_commit: Option<String>,
+    pub new_commit: String,
+    /// Commits nuevos en la rama (limitado por el watcher)
+    pub commit_count: i32,
+    pub detected_at: NaiveDateTime,
+}
+
+/// Datos para registrar un pull
+#[derive(Debug, Clone)]
+pub struct CreatePullEvent {
+    pub project_id: i32,
+    pub ref_name: String,
+    pub old_commit: Option<String>,
+    pub new_commit: String,
+    pub commit_count: i32,
+}
+
+/// Respuesta de `GET /api/projects/:id/pulls`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PullEventsResponse {
+    pub pulls: Vec<PullEvent>,
+}
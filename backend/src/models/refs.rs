@@ -0,0 +1,63 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::CommitSummary;
+
+/// Estado de una rama respecto a su rama remota de seguimiento
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UpstreamStatus {
+    /// Nombre corto de la rama remota (p. ej. `origin/main`)
+    pub name: String,
+    /// Commits locales que no están en la rama remota
+    pub ahead_by: usize,
+    /// Commits de la rama remota que no están en la local
+    pub behind_by: usize,
+}
+
+/// Rama local de un repositorio
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BranchInfo {
+    pub name: String,
+    /// `true` si es la rama a la que apunta `HEAD`
+    pub is_default: bool,
+    /// Commit en la punta de la rama
+    pub commit: CommitSummary,
+    /// Fecha del último commit (committer), útil para detectar ramas abandonadas
+    pub last_commit_at: DateTime<Utc>,
+    /// Commits de la rama que no están en la rama por defecto
+    pub ahead_by: Option<usize>,
+    /// Commits de la rama por defecto que no están en la rama
+    pub behind_by: Option<usize>,
+    /// Rama remota configurada, si existe
+    pub upstream: Option<UpstreamStatus>,
+}
+
+/// Tag de un repositorio
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TagInfo {
+    pub name: String,
+    /// Commit al que apunta el tag (tras resolver tags anidados)
+    pub commit: CommitSummary,
+    /// `true` para tags anotados, `false` para tags ligeros
+    pub annotated: bool,
+    /// Mensaje del tag anotado
+    pub message: Option<String>,
+    pub tagger_name: Option<String>,
+    pub tagger_email: Option<String>,
+    /// Fecha del tag anotado o, en tags ligeros, del commit
+    pub tagged_at: DateTime<Utc>,
+}
+
+/// Respuesta de `GET /api/projects/:id/branches`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BranchesResponse {
+    /// Rama a la que apunta `HEAD`; `None` si `HEAD` está desacoplado
+    pub default_branch: Option<String>,
+    pub branches: Vec<BranchInfo>,
+}
+
+/// Respuesta de `GET /api/projects/:id/tags`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TagsResponse {
+    pub tags: Vec<TagInfo>,
+}
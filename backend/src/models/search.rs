@@ -0,0 +1,95 @@
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+
+/// Qué buscar en `GET /api/search`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SearchScope {
+    #[default]
+    All,
+    Projects,
+    Commits,
+}
+
+/// Proyecto que coincide con una búsqueda
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct ProjectSearchHit {
+    pub id: i32,
+    pub name: String,
+    pub description: Option<String>,
+    pub is_public: bool,
+    /// Relevancia según `ts_rank`; mayor es mejor
+    pub rank: f32,
+}
+
+/// Commit de la caché que coincide con una búsqueda
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct CommitSearchHit {
+    pub project_id: i32,
+    pub project_name: String,
+    pub hash: String,
+    pub author_name: String,
+    pub author_email: Option<String>,
+    pub message: String,
+    pub committed_at: NaiveDateTime,
+    pub rank: f32,
+}
+
+/// Respuesta de `GET /api/search`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SearchResponse {
+    pub query: String,
+    pub projects: Vec<ProjectSearchHit>,
+    pub commits: Vec<CommitSearchHit>,
+}
+
+/// Rango de una coincidencia dentro de una línea, en caracteres
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MatchRange {
+    pub start: usize,
+    pub end: usize,
+}
+
+/// Línea de un archivo que coincide con una búsqueda de código
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CodeMatch {
+    pub path: String,
+    /// Número de línea, empezando en 1
+    pub line_number: usize,
+    /// Contenido de la línea (recortado si es muy larga)
+    pub line: String,
+    pub ranges: Vec<MatchRange>,
+}
+
+/// Resumen final de una búsqueda de código
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CodeSearchSummary {
+    pub files_scanned: usize,
+    pub files_matched: usize,
+    pub matches: usize,
+    /// Se alcanzó el máximo de coincidencias
+    pub truncated: bool,
+    /// Se agotó el tiempo de búsqueda antes de recorrer todo el árbol
+    pub timed_out: bool,
+    pub elapsed_ms: u64,
+}
+
+/// Línea del stream NDJSON de `GET /api/projects/:id/search/code`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum CodeSearchEvent {
+    /// Primera línea: commit sobre el que se busca
+    Start {
+        #[serde(rename = "ref")]
+        git_ref: String,
+        commit: String,
+    },
+    Match(CodeMatch),
+    /// Última línea
+    Summary(CodeSearchSummary),
+    /// Error durante el recorrido; es la última línea
+    Error {
+        error: String,
+    },
+}
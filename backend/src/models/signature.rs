@@ -0,0 +1,115 @@
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+
+/// Resultado de verificar la firma de un commit
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SignatureStatus {
+    /// Firma válida de una clave de confianza cuya identidad coincide con el committer
+    Verified,
+    /// El commit está firmado pero la firma no se pudo verificar (ver `reason`)
+    Unverified,
+    /// El commit no tiene firma
+    Unsigned,
+}
+
+/// Formato de la firma
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type)]
+#[sqlx(type_name = "VARCHAR", rename_all = "lowercase")]
+#[serde(rename_all = "lowercase")]
+pub enum SignatureKind {
+    Gpg,
+    Ssh,
+    /// Firmas S/MIME (`gpgsm`); se detectan pero no se verifican
+    X509,
+}
+
+/// Motivo por el que una firma no se considera verificada
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum UnverifiedReason {
+    /// La clave que firmó no está entre las claves de confianza
+    UnknownKey,
+    /// La firma no corresponde al contenido del commit
+    BadSignature,
+    /// La clave es de confianza pero no está autorizada para el email del committer
+    IdentityMismatch,
+    /// La clave o la firma han expirado
+    ExpiredKey,
+    /// La clave fue revocada
+    RevokedKey,
+    /// La firma no se pudo interpretar
+    Malformed,
+    /// Formato sin verificación disponible (X.509 o GPG sin el programa `gpg`)
+    Unsupported,
+}
+
+/// Estado de la firma de un commit
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CommitSignature {
+    pub status: SignatureStatus,
+    pub kind: Option<SignatureKind>,
+    /// Huella de la clave que firmó, si se pudo determinar
+    pub key_fingerprint: Option<String>,
+    /// Identidad del firmante: email autorizado (SSH) o user ID de la clave (GPG)
+    pub signer: Option<String>,
+    pub reason: Option<UnverifiedReason>,
+}
+
+impl CommitSignature {
+    pub fn unsigned() -> Self {
+        Self {
+            status: SignatureStatus::Unsigned,
+            kind: None,
+            key_fingerprint: None,
+            signer: None,
+            reason: None,
+        }
+    }
+
+    pub fn unverified(kind: SignatureKind, reason: UnverifiedReason) -> Self {
+        Self {
+            status: SignatureStatus::Unverified,
+            kind: Some(kind),
+            key_fingerprint: None,
+            signer: None,
+            reason: Some(reason),
+        }
+    }
+}
+
+/// Clave de confianza para verificar firmas
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct SigningKey {
+    pub id: i32,
+    pub kind: SignatureKind,
+    pub fingerprint: String,
+    /// SSH: principals autorizados (admiten `*` y `?`); GPG: emails de los user IDs
+    pub principals: Vec<String>,
+    /// Clave pública en formato OpenSSH o GPG con armadura ASCII
+    pub public_key: String,
+    pub comment: Option<String>,
+    pub created_by: Option<i32>,
+    pub created_at: NaiveDateTime,
+}
+
+/// Datos para dar de alta una clave de confianza
+///
+/// Para SSH `public_key` es la clave en formato OpenSSH y `principals` los
+/// emails autorizados, como en un archivo `allowed_signers`. Para GPG es la
+/// clave pública con armadura ASCII y los principals se toman de sus user IDs.
+#[derive(Debug, Clone, Deserialize)]
+pub struct CreateSigningKey {
+    pub kind: SignatureKind,
+    pub public_key: String,
+    #[serde(default)]
+    pub principals: Vec<String>,
+    pub comment: Option<String>,
+}
+
+/// Respuesta de `GET /api/signing-keys`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SigningKeysResponse {
+    pub keys: Vec<SigningKey>,
+}
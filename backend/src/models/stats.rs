@@ -0,0 +1,126 @@
+use chrono::{NaiveDate, NaiveDateTime};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+
+/// Agrupación temporal de las series de actividad
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum StatsInterval {
+    #[default]
+    Day,
+    /// Semanas ISO (empiezan en lunes)
+    Week,
+}
+
+impl StatsInterval {
+    /// Unidad de `date_trunc` en PostgreSQL
+    pub fn as_sql(self) -> &'static str {
+        match self {
+            StatsInterval::Day => "day",
+            StatsInterval::Week => "week",
+        }
+    }
+}
+
+/// Commits y líneas cambiadas en un periodo
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct ActivityPoint {
+    /// Primer día del periodo
+    pub period: NaiveDate,
+    pub commits: i64,
+    /// Líneas añadidas; los merges y los commits sin estadísticas no cuentan
+    pub additions: i64,
+    pub deletions: i64,
+}
+
+/// Respuesta de `GET /api/projects/:id/stats/activity`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ActivityResponse {
+    pub interval: StatsInterval,
+    pub since: NaiveDate,
+    pub until: NaiveDate,
+    /// Un punto por periodo, incluidos los periodos sin commits
+    pub points: Vec<ActivityPoint>,
+}
+
+/// Commits de un par nombre/email tal como están en la caché
+#[derive(Debug, Clone, FromRow)]
+pub struct AuthorStatsRow {
+    pub author_name: String,
+    /// Email en minúsculas y sin espacios; vacío si el commit no tenía
+    pub email: String,
+    pub commits: i64,
+    pub additions: i64,
+    pub deletions: i64,
+    pub first_commit_at: NaiveDateTime,
+    pub last_commit_at: NaiveDateTime,
+}
+
+/// Contribuciones de un autor, unificado con `.mailmap` y por email
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AuthorStats {
+    pub name: String,
+    pub email: String,
+    pub commits: i64,
+    pub additions: i64,
+    pub deletions: i64,
+    pub first_commit_at: NaiveDateTime,
+    pub last_commit_at: NaiveDateTime,
+}
+
+/// Respuesta de `GET /api/projects/:id/stats/authors`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuthorStatsResponse {
+    /// Autores distintos en el periodo (antes de aplicar `limit`)
+    pub total_authors: usize,
+    pub authors: Vec<AuthorStats>,
+}
+
+/// Respuesta de `GET /api/projects/:id/stats/heatmap`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HeatmapResponse {
+    /// Desplazamiento aplicado a las fechas UTC, en minutos
+    pub tz_offset: i32,
+    pub total: i64,
+    /// 7 filas (lunes a domingo) × 24 columnas (horas)
+    pub cells: Vec<Vec<i64>>,
+}
+
+/// Proyecto con más commits en el resumen del dashboard
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct ProjectActivity {
+    pub project_id: i32,
+    pub project_name: String,
+    pub commits: i64,
+}
+
+/// Autor con más commits en el resumen del dashboard
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct AuthorActivity {
+    pub name: String,
+    pub email: String,
+    pub commits: i64,
+}
+
+/// Totales de actividad del resumen del dashboard
+#[derive(Debug, Clone, Default, FromRow, Serialize, Deserialize)]
+pub struct ActivityTotals {
+    pub commits: i64,
+    pub active_projects: i64,
+    pub active_authors: i64,
+    pub additions: i64,
+    pub deletions: i64,
+}
+
+/// Respuesta de `GET /api/stats/summary`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DashboardSummary {
+    pub since: NaiveDate,
+    pub until: NaiveDate,
+    #[serde(flatten)]
+    pub totals: ActivityTotals,
+    /// Un punto por día
+    pub daily: Vec<ActivityPoint>,
+    pub top_projects: Vec<ProjectActivity>,
+    pub top_authors: Vec<AuthorActivity>,
+}
@@ -0,0 +1,41 @@
+use serde::{Deserialize, Serialize};
+
+use super::{DiffResult, FileStatus};
+
+/// Archivo con cambios sin commitear
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WorkingCopyFile {
+    pub path: String,
+    /// Ruta anterior, solo en renombrados
+    pub old_path: Option<String>,
+    pub status: FileStatus,
+}
+
+/// Número de archivos de cada grupo de `git status`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WorkingCopySummary {
+    pub staged: usize,
+    pub unstaged: usize,
+    pub untracked: usize,
+    pub conflicted: usize,
+}
+
+/// Respuesta de `GET /api/projects/:id/status`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WorkingCopyResponse {
+    /// Rama a la que apunta `HEAD`; `None` si está desacoplado
+    pub branch: Option<String>,
+    /// Commit de `HEAD`; `None` si el repositorio no tiene commits
+    pub head: Option<String>,
+    pub is_clean: bool,
+    pub staged: Vec<WorkingCopyFile>,
+    pub unstaged: Vec<WorkingCopyFile>,
+    /// Los directorios sin seguimiento aparecen una vez, con `/` final
+    pub untracked: Vec<String>,
+    pub conflicted: Vec<String>,
+    /// `true` si algún grupo se cortó en el máximo de entradas
+    pub status_truncated: bool,
+    /// Diff del working copy (índice incluido) contra `HEAD`
+    #[serde(flatten)]
+    pub diff: DiffResult,
+}
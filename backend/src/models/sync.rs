@@ -0,0 +1,154 @@
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+
+/// Tipo de credencial para autenticarse contra el remoto
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type)]
+#[sqlx(type_name = "VARCHAR", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum CredentialKind {
+    /// Clave privada SSH (PEM u OpenSSH), opcionalmente con passphrase
+    SshKey,
+    /// Token o contraseña para HTTPS
+    Token,
+}
+
+/// Credencial de un proyecto tal como se guarda en la base de datos
+///
+/// El secreto va cifrado; nunca se serializa.
+#[derive(Clone, FromRow)]
+pub struct ProjectCredential {
+    pub project_id: i32,
+    pub kind: CredentialKind,
+    pub username: Option<String>,
+    pub secret_encrypted: Vec<u8>,
+    pub created_by: Option<i32>,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+/// Datos públicos de una credencial (sin el secreto)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CredentialResponse {
+    pub project_id: i32,
+    pub kind: CredentialKind,
+    pub username: Option<String>,
+    pub created_by: Option<i32>,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+impl From<ProjectCredential> for CredentialResponse {
+    fn from(credential: ProjectCredential) -> Self {
+        Self {
+            project_id: credential.project_id,
+            kind: credential.kind,
+            username: credential.username,
+            created_by: credential.created_by,
+            created_at: credential.created_at,
+            updated_at: credential.updated_at,
+        }
+    }
+}
+
+/// Datos para guardar la credencial de un proyecto
+#[derive(Deserialize)]
+pub struct SetCredential {
+    pub kind: CredentialKind,
+    /// Usuario SSH o HTTPS; si falta se usa el de la URL del remoto
+    pub username: Option<String>,
+    /// Clave privada o token
+    pub secret: String,
+    /// Passphrase de la clave SSH
+    pub passphrase: Option<String>,
+}
+
+/// Credencial descifrada, lista para usarse en un fetch
+#[derive(Clone, Serialize, Deserialize)]
+pub struct RemoteCredentials {
+    pub kind: CredentialKind,
+    pub username: Option<String>,
+    pub secret: String,
+    pub passphrase: Option<String>,
+}
+
+// Los secretos nunca deben acabar en los logs
+impl std::fmt::Debug for ProjectCredential {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ProjectCredential")
+            .field("project_id", &self.project_id)
+            .field("kind", &self.kind)
+            .field("username", &self.username)
+            .finish_non_exhaustive()
+    }
+}
+
+impl std::fmt::Debug for SetCredential {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SetCredential")
+            .field("kind", &self.kind)
+            .field("username", &self.username)
+            .finish_non_exhaustive()
+    }
+}
+
+impl std::fmt::Debug for RemoteCredentials {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RemoteCredentials")
+            .field("kind", &self.kind)
+            .field("username", &self.username)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Resultado de una operación de sincronización
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type)]
+#[sqlx(type_name = "VARCHAR", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum SyncStatus {
+    /// La rama local ya contenía la punta remota
+    UpToDate,
+    /// La rama local avanzó hasta la punta remota
+    FastForwarded,
+    Failed,
+}
+
+/// Operación de fetch + fast-forward lanzada desde la API
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct SyncOperation {
+    pub id: i32,
+    pub project_id: i32,
+    pub triggered_by: Option<i32>,
+    pub triggered_by_username: Option<String>,
+    /// Remoto del que se hizo fetch (p. ej. `origin`)
+    pub remote: Option<String>,
+    /// Rama local actualizada (p. ej. `main`)
+    pub branch: Option<String>,
+    pub status: SyncStatus,
+    pub old_commit: Option<String>,
+    pub new_commit: Option<String>,
+    /// Motivo del fallo si `status` es `failed`
+    pub message: Option<String>,
+    pub duration_ms: i32,
+    pub created_at: NaiveDateTime,
+}
+
+/// Datos para registrar una operación de sincronización
+#[derive(Debug, Clone)]
+pub struct CreateSyncOperation {
+    pub project_id: i32,
+    pub triggered_by: i32,
+    pub remote: Option<String>,
+    pub branch: Option<String>,
+    pub status: SyncStatus,
+    pub old_commit: Option<String>,
+    pub new_commit: Option<String>,
+    pub message: Option<String>,
+    pub duration_ms: i32,
+}
+
+/// Respuesta de `GET /api/projects/:id/sync/history`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SyncOperationsResponse {
+    pub operations: Vec<SyncOperation>,
+}
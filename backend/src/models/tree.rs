@@ -0,0 +1,64 @@
+use serde::{Deserialize, Serialize};
+
+use super::{CommitSummary, LfsInfo};
+
+/// Tipo de una entrada del árbol de un repositorio
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TreeEntryType {
+    File,
+    Directory,
+    Symlink,
+    Submodule,
+}
+
+/// Entrada de un árbol (directorio) en una revisión concreta
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TreeEntry {
+    pub name: String,
+    /// Ruta relativa a la raíz del repositorio
+    pub path: String,
+    #[serde(rename = "type")]
+    pub entry_type: TreeEntryType,
+    /// Tamaño en bytes (solo archivos y symlinks); el del archivo real en punteros LFS
+    pub size: Option<u64>,
+    /// Modo Git en octal (`100644`, `100755`, `040000`, `120000`, `160000`)
+    pub mode: String,
+    /// OID del objeto; para submódulos es el commit fijado
+    pub oid: String,
+    /// Destino del enlace, solo para symlinks (nunca se sigue)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub symlink_target: Option<String>,
+    /// Repositorio y commit fijado, solo para submódulos
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub submodule: Option<SubmoduleInfo>,
+    /// Objeto de Git LFS, solo para archivos que son punteros LFS
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub lfs: Option<LfsInfo>,
+    /// Último commit que modificó la entrada, si se encontró dentro del límite de búsqueda
+    pub last_commit: Option<CommitSummary>,
+}
+
+/// Submódulo de un árbol según `.gitmodules`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SubmoduleInfo {
+    /// URL configurada; las relativas se resuelven contra el remoto `origin`.
+    /// `None` si el submódulo no aparece en `.gitmodules`
+    pub url: Option<String>,
+    /// Commit fijado por el repositorio contenedor
+    pub commit: String,
+    /// Proyecto de LumaStack que monitorea ese repositorio, si es visible
+    pub project_id: Option<i32>,
+}
+
+/// Respuesta de `GET /api/projects/:id/tree`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TreeResponse {
+    /// Referencia solicitada (`HEAD` si no se indicó)
+    #[serde(rename = "ref")]
+    pub git_ref: String,
+    /// Commit al que resolvió la referencia
+    pub commit: String,
+    pub path: String,
+    pub entries: Vec<TreeEntry>,
+}
@@ -3,21 +3,16 @@ use serde::{Deserialize, Serialize};
 use sqlx::FromRow;
 
 /// Rol de usuario en el sistema
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, sqlx::Type)]
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, sqlx::Type)]
 #[sqlx(type_name = "VARCHAR", rename_all = "lowercase")]
 pub enum UserRole {
+    #[default]
     #[serde(rename = "user")]
     User,
     #[serde(rename = "admin")]
     Admin,
 }
 
-impl Default for UserRole {
-    fn default() -> Self {
-        Self::User
-    }
-}
-
 impl std::fmt::Display for UserRole {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
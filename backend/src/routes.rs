@@ -0,0 +1,160 @@
+use axum::{
+    routing::{delete, get, patch, post},
+    Router,
+};
+use tower_http::trace::TraceLayer;
+
+use crate::handlers::{
+    activity_stats_handler, add_reaction_handler,
+    approve_lock_request_handler, archive_handler, audit_log_handler,
+    author_stats_handler, blame_handler, blob_handler, branches_handler, changelog_handler,
+    check_project_health_handler, code_search_handler, comment_revisions_handler, comments_handler,
+    commit_handler, compare_handler, create_comment_handler, create_lock_request_handler,
+    create_signing_key_handler, credential_handler, dashboard_summary_handler,
+    delete_comment_handler, delete_credential_handler,
+    delete_notification_handler, delete_owner_mapping_handler, delete_project_handler,
+    delete_signing_key_handler, health_handler, heatmap_handler, install_lock_hook_handler,
+    languages_handler, languages_history_handler, list_lock_requests_handler, lock_handler,
+    lock_history_handler, lock_hook_status_handler, lock_status_handler,
+    login_handler, mark_all_notifications_read_handler,
+    mark_notification_read_handler, me_handler, notifications_handler,
+    owner_mappings_handler, owners_handler, project_handler, project_health_handler,
+    project_members_handler, pulls_handler, raw_handler, reject_lock_request_handler,
+    remove_reaction_handler, resolve_comment_handler, root_handler, scan_projects_handler,
+    search_handler, set_credential_handler, set_owner_mapping_handler, signing_keys_handler,
+    status_handler, sync_handler, sync_history_handler, tags_handler, tree_handler,
+    uninstall_lock_hook_handler, unlock_handler, unread_count_handler,
+    unresolve_comment_handler, update_comment_handler, AppState,
+};
+
+/// Construye el router de la aplicación con todas las rutas
+///
+/// Las rutas de la API REST se montan bajo `/api`.
+pub fn create_router(state: AppState) -> Router {
+    let api = Router::new()
+        .route("/auth/login", post(login_handler))
+        .route("/auth/me", get(me_handler))
+        .route("/search", get(search_handler))
+        .route("/stats/summary", get(dashboard_summary_handler))
+        .route("/notifications", get(notifications_handler))
+        .route("/notifications/unread-count", get(unread_count_handler))
+        .route(
+            "/notifications/read-all",
+            post(mark_all_notifications_read_handler),
+        )
+        .route(
+            "/notifications/:id/read",
+            post(mark_notification_read_handler),
+        )
+        .route("/notifications/:id", delete(delete_notification_handler))
+        .route(
+            "/signing-keys",
+            get(signing_keys_handler).post(create_signing_key_handler),
+        )
+        .route("/signing-keys/:key_id", delete(delete_signing_key_handler))
+        .route(
+            "/owner-mappings",
+            get(owner_mappings_handler).put(set_owner_mapping_handler),
+        )
+        .route(
+            "/owner-mappings/:mapping_id",
+            delete(delete_owner_mapping_handler),
+        )
+        .route("/projects/scan", post(scan_projects_handler))
+        .route("/projects/:id", get(project_handler).delete(delete_project_handler))
+        .route("/projects/:id/tree", get(tree_handler))
+        .route("/projects/:id/blob", get(blob_handler))
+        .route("/projects/:id/raw", get(raw_handler))
+        .route("/projects/:id/blame", get(blame_handler))
+        .route("/projects/:id/archive", get(archive_handler))
+        .route("/projects/:id/pulls", get(pulls_handler))
+        .route("/projects/:id/audit", get(audit_log_handler))
+        .route("/projects/:id/status", get(status_handler))
+        .route(
+            "/projects/:id/health",
+            get(project_health_handler).post(check_project_health_handler),
+        )
+        .route("/projects/:id/branches", get(branches_handler))
+        .route("/projects/:id/tags", get(tags_handler))
+        .route("/projects/:id/commits/:hash", get(commit_handler))
+        .route("/projects/:id/compare", get(compare_handler))
+        .route("/projects/:id/changelog", get(changelog_handler))
+        .route("/projects/:id/owners", get(owners_handler))
+        .route("/projects/:id/members", get(project_members_handler))
+        .route(
+            "/projects/:id/comments",
+            get(comments_handler).post(create_comment_handler),
+        )
+        .route(
+            "/projects/:id/comments/:comment_id",
+            patch(update_comment_handler).delete(delete_comment_handler),
+        )
+        .route(
+            "/projects/:id/comments/:comment_id/revisions",
+            get(comment_revisions_handler),
+        )
+        .route(
+            "/projects/:id/comments/:comment_id/resolve",
+            post(resolve_comment_handler),
+        )
+        .route(
+            "/projects/:id/comments/:comment_id/unresolve",
+            post(unresolve_comment_handler),
+        )
+        .route(
+            "/projects/:id/comments/:comment_id/reactions",
+            post(add_reaction_handler),
+        )
+        .route(
+            "/projects/:id/comments/:comment_id/reactions/:emoji",
+            delete(remove_reaction_handler),
+        )
+        .route("/projects/:id/search/code", get(code_search_handler))
+        .route("/projects/:id/languages", get(languages_handler))
+        .route(
+            "/projects/:id/languages/history",
+            get(languages_history_handler),
+        )
+        .route("/projects/:id/stats/activity", get(activity_stats_handler))
+        .route("/projects/:id/stats/authors", get(author_stats_handler))
+        .route("/projects/:id/stats/heatmap", get(heatmap_handler))
+        .route(
+            "/projects/:id/lock",
+            get(lock_status_handler).post(lock_handler),
+        )
+        .route("/projects/:id/unlock", post(unlock_handler))
+        .route("/projects/:id/lock/history", get(lock_history_handler))
+        .route(
+            "/projects/:id/lock/requests",
+            get(list_lock_requests_handler).post(create_lock_request_handler),
+        )
+        .route(
+            "/projects/:id/lock/requests/:request_id/approve",
+            post(approve_lock_request_handler),
+        )
+        .route(
+            "/projects/:id/lock/requests/:request_id/reject",
+            post(reject_lock_request_handler),
+        )
+        .route(
+            "/projects/:id/lock/hook",
+            get(lock_hook_status_handler)
+                .post(install_lock_hook_handler)
+                .delete(uninstall_lock_hook_handler),
+        )
+        .route("/projects/:id/sync", post(sync_handler))
+        .route("/projects/:id/sync/history", get(sync_history_handler))
+        .route(
+            "/projects/:id/credentials",
+            get(credential_handler)
+                .put(set_credential_handler)
+                .delete(delete_credential_handler),
+        );
+
+    Router::new()
+        .route("/", get(root_handler))
+        .route("/health", get(health_handler))
+        .nest("/api", api)
+        .layer(TraceLayer::new_for_http())
+        .with_state(state)
+}
@@ -0,0 +1,179 @@
+//! Análisis de la composición de los repositorios en segundo plano
+//!
+//! Calcula lenguajes, número de archivos y tamaño en disco de la punta de
+//! la rama por defecto de cada proyecto y lo guarda por commit, de modo que
+//! se puede seguir la evolución en el tiempo. Se analiza al arrancar, tras
+//! cada [`ProjectEvent::Pull`] y periódicamente (para los proyectos que no
+//! se vigilan o cuando se pierden eventos). Los commits ya analizados no se
+//! repiten.
+
+use sqlx::PgPool;
+use tokio::sync::broadcast::error::RecvError;
+use tokio::task::JoinHandle;
+use tokio::time::MissedTickBehavior;
+
+use crate::config::AnalyzerConfig;
+use crate::db;
+use crate::models::{CreateRepositorySnapshot, Project};
+use crate::services::events::{EventBus, ProjectEvent};
+use crate::services::git;
+
+/// Servicio que analiza la composición de los repositorios
+pub struct RepositoryAnalyzer {
+    db: PgPool,
+    events: EventBus,
+    config: AnalyzerConfig,
+}
+
+impl RepositoryAnalyzer {
+    pub fn new(db: PgPool, events: EventBus, config: AnalyzerConfig) -> Self {
+        Self { db, events, config }
+    }
+
+    /// Arranca el analizador en una tarea en segundo plano
+    pub fn spawn(self) -> JoinHandle<()> {
+        tokio::spawn(self.run())
+    }
+
+    async fn run(self) {
+        // Suscribirse antes del primer barrido para no perder pulls
+        let mut rx = self.events.subscribe();
+        let mut sweep = tokio::time::interval(self.config.interval);
+        sweep.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+        tracing::info!(
+            "Analizador de repositorios activo (intervalo {:?})",
+            self.config.interval
+        );
+
+        loop {
+            tokio::select! {
+                _ = sweep.tick() => self.analyze_all().await,
+                event = rx.recv() => match event {
+                    Ok(ProjectEvent::Pull { event, .. }) => {
+                        match db::projects::find_project_by_id(&self.db, event.project_id).await {
+                            Ok(project) => self.analyze(&project).await,
+                            Err(e) => tracing::warn!(
+                                "No se analizará el proyecto {}: {}",
+                                event.project_id,
+                                e
+                            ),
+                        }
+                    }
+                    Ok(_) => {}
+                    // El próximo barrido recupera lo perdido
+                    Err(RecvError::Lagged(_)) => {}
+                    Err(RecvError::Closed) => break,
+                },
+            }
+        }
+    }
+
+    async fn analyze_all(&self) {
+        let projects = match db::projects::list_projects(&self.db).await {
+            Ok(projects) => projects,
+            Err(e) => {
+                tracing::error!("No se pudieron leer los proyectos a analizar: {}", e);
+                return;
+            }
+        };
+
+        for project in &projects {
+            self.analyze(project).await;
+        }
+    }
+
+    async fn analyze(&self, project: &Project) {
+        match analyze_project(&self.db, project).await {
+            Ok(Some(commit)) => tracing::info!(
+                "Composición del proyecto {} analizada en {}",
+                project.id,
+                commit
+            ),
+            Ok(None) => {}
+            Err(e) => tracing::warn!("No se pudo analizar el proyecto {}: {}", project.id, e),
+        }
+    }
+}
+
+/// Errores del análisis de un proyecto
+#[derive(Debug, thiserror::Error)]
+pub enum AnalyzerError {
+    #[error(transparent)]
+    Git(#[from] git::GitError),
+
+    #[error(transparent)]
+    Database(#[from] sqlx::Error),
+
+    #[error("Tarea de análisis abortada: {0}")]
+    Join(#[from] tokio::task::JoinError),
+}
+
+/// Analiza la punta de la rama por defecto de un proyecto si aún no lo está
+///
+/// Retorna el hash analizado, o `None` si ya existía o el repositorio no
+/// tiene rama por defecto (p. ej. `HEAD` desacoplado o repositorio vacío).
+///
+/// # Errors
+/// - `AnalyzerError::Git` si falla la lectura del repositorio
+/// - `AnalyzerError::Database` si falla el guardado
+pub async fn analyze_project(
+    pool: &PgPool,
+    project: &Project,
+) -> Result<Option<String>, AnalyzerError> {
+    let path = project.repository_path.clone();
+    let tip = tokio::task::spawn_blocking(move || -> git::Result<_> {
+        let repo = git::open_repository(&path)?;
+        let Some(branch) = git::default_branch(&repo)? else {
+            return Ok(None);
+        };
+        let tip = match repo.find_branch(&branch, git2::BranchType::Local) {
+            Ok(reference) => reference.get().peel_to_commit()?.id(),
+            Err(e) if e.code() == git2::ErrorCode::NotFound => return Ok(None),
+            Err(e) => return Err(e.into()),
+        };
+        Ok(Some((branch, tip)))
+    })
+    .await??;
+
+    let Some((branch, commit_id)) = tip else {
+        return Ok(None);
+    };
+    let commit_hash = commit_id.to_string();
+    if db::composition::snapshot_exists(pool, project.id, &commit_hash).await? {
+        return Ok(None);
+    }
+
+    let path = project.repository_path.clone();
+    let project_id = project.id;
+    let snapshot = tokio::task::spawn_blocking(move || -> git::Result<_> {
+        let repo = git::open_repository(&path)?;
+        let commit = repo.find_commit(commit_id)?;
+        let composition = git::analyze_tree(&repo, &commit.tree()?)?;
+        let storage = git::storage_stats(&repo)?;
+        let int = |n: u64| i32::try_from(n).unwrap_or(i32::MAX);
+        let bigint = |n: u64| i64::try_from(n).unwrap_or(i64::MAX);
+        let committed_at = git::git_time_to_utc(commit.committer().when()).naive_utc();
+
+        Ok(CreateRepositorySnapshot {
+            project_id,
+            commit_hash: commit_id.to_string(),
+            branch,
+            committed_at,
+            total_files: int(composition.total_files),
+            total_bytes: bigint(composition.total_bytes),
+            excluded_files: int(composition.excluded_files),
+            disk_size: bigint(storage.disk_size),
+            pack_count: int(storage.pack_count),
+            pack_size: bigint(storage.pack_size),
+            loose_objects: int(storage.loose_objects),
+            loose_size: bigint(storage.loose_size),
+            languages: composition.languages,
+        })
+    })
+    .await??;
+
+    Ok(db::composition::insert_snapshot(pool, &snapshot)
+        .await?
+        .map(|_| commit_hash))
+}
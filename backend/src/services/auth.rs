@@ -0,0 +1,161 @@
+//! Emisión y validación de tokens JWT
+
+use std::sync::OnceLock;
+
+use chrono::{DateTime, Utc};
+use jsonwebtoken::{decode, encode, DecodingKey, EncodingKey, Header, Validation};
+use serde::{Deserialize, Serialize};
+
+use crate::config::AuthConfig;
+use crate::models::{User, UserRole};
+
+/// Hash bcrypt con el que se compara cuando el usuario no existe, para que
+/// el tiempo de respuesta no revele qué usuarios están registrados
+static DUMMY_PASSWORD_HASH: OnceLock<String> = OnceLock::new();
+
+/// Errores de autenticación
+#[derive(Debug, thiserror::Error)]
+pub enum AuthError {
+    #[error("Token inválido o expirado")]
+    InvalidToken,
+
+    #[error("Error al firmar el token: {0}")]
+    Signing(#[from] jsonwebtoken::errors::Error),
+}
+
+/// Contenido de los tokens emitidos por la API
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Claims {
+    /// ID del usuario
+    pub sub: i32,
+    pub username: String,
+    pub role: UserRole,
+    pub iat: i64,
+    pub exp: i64,
+}
+
+/// Emite un token para un usuario
+///
+/// Retorna el token y su fecha de expiración.
+///
+/// # Errors
+/// - `AuthError::Signing` si no se puede firmar el token
+pub fn issue_token(config: &AuthConfig, user: &User) -> Result<(String, DateTime<Utc>), AuthError> {
+    let now = Utc::now();
+    let expires_at = now
+        + chrono::Duration::from_std(config.jwt_expiration).unwrap_or(chrono::Duration::days(1));
+
+    let claims = Claims {
+        sub: user.id,
+        username: user.username.clone(),
+        role: user.role.clone(),
+        iat: now.timestamp(),
+        exp: expires_at.timestamp(),
+    };
+
+    let token = encode(
+        &Header::default(),
+        &claims,
+        &EncodingKey::from_secret(config.jwt_secret.as_bytes()),
+    )?;
+
+    Ok((token, expires_at))
+}
+
+/// Valida la firma y la expiración de un token
+///
+/// # Errors
+/// - `AuthError::InvalidToken` si el token no es válido o expiró
+pub fn verify_token(config: &AuthConfig, token: &str) -> Result<Claims, AuthError> {
+    decode::<Claims>(
+        token,
+        &DecodingKey::from_secret(config.jwt_secret.as_bytes()),
+        &Validation::default(),
+    )
+    .map(|data| data.claims)
+    .map_err(|_| AuthError::InvalidToken)
+}
+
+/// Comprueba una contraseña contra su hash bcrypt
+///
+/// Sin hash (usuario inexistente) se verifica igualmente contra un hash
+/// ficticio para igualar los tiempos. Es una operación costosa: llamar
+/// desde `spawn_blocking`.
+pub fn verify_password(password: &str, hash: Option<&str>) -> bool {
+    let hash = hash.ok_or_else(|| {
+        DUMMY_PASSWORD_HASH
+            .get_or_init(|| bcrypt::hash("lumastack", bcrypt::DEFAULT_COST).unwrap_or_default())
+    });
+
+    match hash {
+        Ok(hash) => bcrypt::verify(password, hash).unwrap_or(false),
+        Err(dummy) => {
+            let _ = bcrypt::verify(password, dummy);
+            false
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    fn config() -> AuthConfig {
+        AuthConfig {
+            jwt_secret: "test-secret".to_string(),
+            jwt_expiration: Duration::from_secs(3600),
+        }
+    }
+
+    fn user() -> User {
+        let now = Utc::now().naive_utc();
+        User {
+            id: 7,
+            username: "ana".to_string(),
+            email: "ana@example.com".to_string(),
+            password_hash: String::new(),
+            role: UserRole::Admin,
+            is_active: true,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    #[test]
+    fn test_issue_and_verify_token() {
+        let (token, expires_at) = issue_token(&config(), &user()).unwrap();
+        assert!(expires_at > Utc::now());
+
+        let claims = verify_token(&config(), &token).unwrap();
+        assert_eq!(claims.sub, 7);
+        assert_eq!(claims.username, "ana");
+        assert_eq!(claims.role, UserRole::Admin);
+    }
+
+    #[test]
+    fn test_verify_token_rejects_other_secret() {
+        let (token, _) = issue_token(&config(), &user()).unwrap();
+        let other = AuthConfig {
+            jwt_secret: "other-secret".to_string(),
+            ..config()
+        };
+
+        assert!(matches!(
+            verify_token(&other, &token),
+            Err(AuthError::InvalidToken)
+        ));
+        assert!(matches!(
+            verify_token(&config(), "not-a-token"),
+            Err(AuthError::InvalidToken)
+        ));
+    }
+
+    #[test]
+    fn test_verify_password() {
+        let hash = bcrypt::hash("secreta", 4).unwrap();
+        assert!(verify_password("secreta", Some(&hash)));
+        assert!(!verify_password("otra", Some(&hash)));
+        assert!(!verify_password("secreta", None));
+    }
+}
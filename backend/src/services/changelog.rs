@@ -0,0 +1,180 @@
+//! Generación de changelogs agrupados por tipo de Conventional Commit
+
+use std::fmt::Write;
+
+use crate::models::{ChangelogEntry, ChangelogResponse, ChangelogSection, ConventionalCommit};
+
+/// Secciones en el orden en que se muestran; los tipos no listados van a `other`
+const SECTIONS: &[(&str, &str)] = &[
+    ("breaking", "⚠ Breaking Changes"),
+    ("feat", "Features"),
+    ("fix", "Bug Fixes"),
+    ("perf", "Performance Improvements"),
+    ("revert", "Reverts"),
+    ("refactor", "Code Refactoring"),
+    ("docs", "Documentation"),
+    ("test", "Tests"),
+    ("build", "Build System"),
+    ("ci", "Continuous Integration"),
+    ("style", "Styles"),
+    ("chore", "Chores"),
+    ("other", "Other Changes"),
+];
+
+/// Agrupa los commits por sección, conservando su orden dentro de cada una
+///
+/// Los cambios incompatibles aparecen en `breaking` además de en su tipo.
+/// Las secciones vacías se omiten.
+pub fn group_entries(commits: Vec<(ConventionalCommit, ChangelogEntry)>) -> Vec<ChangelogSection> {
+    let mut sections: Vec<ChangelogSection> = SECTIONS
+        .iter()
+        .map(|(kind, title)| ChangelogSection {
+            kind: kind.to_string(),
+            title: title.to_string(),
+            entries: Vec::new(),
+        })
+        .collect();
+    let index = |kind: &str| {
+        SECTIONS
+            .iter()
+            .position(|(k, _)| *k == kind)
+            .unwrap_or(SECTIONS.len() - 1)
+    };
+
+    for (parsed, entry) in commits {
+        if entry.breaking {
+            sections[0].entries.push(entry.clone());
+        }
+        let kind = parsed.commit_type.as_deref().unwrap_or("other");
+        sections[index(kind)].entries.push(entry);
+    }
+
+    sections.retain(|s| !s.entries.is_empty());
+    sections
+}
+
+/// Renderiza el changelog como Markdown
+pub fn render_markdown(changelog: &ChangelogResponse) -> String {
+    let mut out = String::new();
+    let _ = writeln!(
+        out,
+        "## {} ({})\n",
+        changelog.to,
+        changelog.date.format("%Y-%m-%d")
+    );
+
+    if changelog.sections.is_empty() {
+        out.push_str("No hay cambios.\n");
+    }
+
+    for section in &changelog.sections {
+        let _ = writeln!(out, "### {}\n", section.title);
+        for entry in &section.entries {
+            out.push_str("- ");
+            if let Some(scope) = &entry.scope {
+                let _ = write!(out, "**{}:** ", escape_markdown(scope));
+            }
+            out.push_str(&escape_markdown(&entry.description));
+            // Las referencias ya escritas en la descripción no se repiten
+            let issues: Vec<&str> = entry
+                .issues
+                .iter()
+                .map(String::as_str)
+                .filter(|issue| !entry.description.contains(issue))
+                .collect();
+            if !issues.is_empty() {
+                let _ = write!(out, " ({})", issues.join(", "));
+            }
+            let _ = writeln!(out, " ({})", &entry.hash[..entry.hash.len().min(7)]);
+
+            if section.kind == "breaking" {
+                if let Some(note) = &entry.breaking_note {
+                    for line in note.lines() {
+                        let _ = writeln!(out, "  {}", escape_markdown(line));
+                    }
+                }
+            }
+        }
+        out.push('\n');
+    }
+
+    if changelog.truncated {
+        let _ = writeln!(
+            out,
+            "_Changelog truncado: se muestran los {} commits más recientes._",
+            changelog.total_commits
+        );
+    }
+
+    out
+}
+
+/// Escapa los caracteres que Markdown interpretaría en texto de commits
+fn escape_markdown(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        if matches!(c, '\\' | '*' | '_' | '`' | '[' | ']' | '<' | '>' | '|') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::conventional::parse_commit_message;
+    use chrono::{TimeZone, Utc};
+
+    fn commit(hash: &str, message: &str) -> (ConventionalCommit, ChangelogEntry) {
+        let parsed = parse_commit_message(message);
+        let entry = ChangelogEntry {
+            hash: hash.to_string(),
+            scope: parsed.scope.clone(),
+            description: parsed.description.clone(),
+            breaking: parsed.breaking,
+            breaking_note: parsed.breaking_note.clone(),
+            author_name: "Ada".to_string(),
+            authored_at: Utc.timestamp_opt(0, 0).unwrap(),
+            co_authors: Vec::new(),
+            issues: parsed.issues.clone(),
+        };
+        (parsed, entry)
+    }
+
+    #[test]
+    fn test_group_and_render() {
+        let sections = group_entries(vec![
+            commit("aaaaaaaaaa", "fix(ui): align *buttons* (#3)"),
+            commit(
+                "bbbbbbbbbb",
+                "feat!: new config\n\nBREAKING CHANGE: rename keys",
+            ),
+            commit("cccccccccc", "Update deps"),
+            commit("dddddddddd", "wip: experiment"),
+        ]);
+
+        let kinds: Vec<_> = sections.iter().map(|s| s.kind.as_str()).collect();
+        assert_eq!(kinds, vec!["breaking", "feat", "fix", "other"]);
+        assert_eq!(sections[3].entries.len(), 2);
+
+        let changelog = ChangelogResponse {
+            from: Some("v1.0.0".to_string()),
+            from_commit: None,
+            to: "v1.1.0".to_string(),
+            to_commit: "bbbbbbbbbb".to_string(),
+            date: Utc.with_ymd_and_hms(2025, 10, 21, 12, 0, 0).unwrap(),
+            total_commits: 4,
+            truncated: false,
+            sections,
+        };
+        let markdown = render_markdown(&changelog);
+
+        assert!(markdown.starts_with("## v1.1.0 (2025-10-21)\n\n### ⚠ Breaking Changes\n\n"));
+        assert!(markdown.contains("- new config (bbbbbbb)\n  rename keys\n"));
+        assert!(markdown.contains("- **ui:** align \\*buttons\\* (#3) (aaaaaaa)\n"));
+        assert!(markdown
+            .contains("### Other Changes\n\n- Update deps (ccccccc)\n- experiment (ddddddd)\n"));
+    }
+}
@@ -0,0 +1,363 @@
+//! Seguimiento de los comentarios: posiciones en archivos y menciones
+//!
+//! Un comentario de archivo se ancla a una ruta y un rango de líneas en un
+//! commit. Cuando la rama por defecto avanza, el [`CommentAnchorTracker`]
+//! traslada cada posición a la nueva punta a través del diff: sigue los
+//! renombrados y los desplazamientos, y marca el comentario como
+//! desactualizado cuando cambian sus líneas o se borra el archivo. Se
+//! revisa al arrancar y tras cada [`ProjectEvent::Pull`].
+//!
+//! Las menciones `@usuario` de un comentario se resuelven al crearlo o
+//! editarlo con [`record_mentions`], que notifica a los mencionados. El
+//! contenido se renderiza a HTML en el servidor con [`render_comments`].
+
+use std::collections::{BTreeMap, HashMap};
+
+use git2::Oid;
+use sqlx::PgPool;
+use tokio::sync::broadcast::error::RecvError;
+use tokio::task::JoinHandle;
+
+use crate::db;
+use crate::db::comments::{AnchorUpdate, TrackedAnchor};
+use crate::models::{Comment, CommentRevision, CreateNotification, NotificationType, Project};
+use crate::services::events::{EventBus, ProjectEvent};
+use crate::services::git::{self, LineAnchor};
+use crate::services::markdown::{self, extract_mentions, Reference};
+use crate::services::notifications;
+use crate::utils::url::{comment_link, commit_link};
+
+/// Servicio que mantiene las posiciones de los comentarios de archivo
+pub struct CommentAnchorTracker {
+    db: PgPool,
+    events: EventBus,
+}
+
+impl CommentAnchorTracker {
+    pub fn new(db: PgPool, events: EventBus) -> Self {
+        Self { db, events }
+    }
+
+    /// Arranca el seguimiento en una tarea en segundo plano
+    pub fn spawn(self) -> JoinHandle<()> {
+        tokio::spawn(self.run())
+    }
+
+    async fn run(self) {
+        // Suscribirse antes del primer barrido para no perder pulls
+        let mut rx = self.events.subscribe();
+        self.remap_all().await;
+
+        loop {
+            match rx.recv().await {
+                Ok(ProjectEvent::Pull { event, .. }) => {
+                    match db::projects::find_project_by_id(&self.db, event.project_id).await {
+                        Ok(project) => self.remap(&project).await,
+                        Err(e) => tracing::warn!(
+                            "No se trasladarán los comentarios del proyecto {}: {}",
+                            event.project_id,
+                            e
+                        ),
+                    }
+                }
+                Ok(_) => {}
+                Err(RecvError::Lagged(_)) => self.remap_all().await,
+                Err(RecvError::Closed) => break,
+            }
+        }
+    }
+
+    async fn remap_all(&self) {
+        let projects = match db::projects::list_projects(&self.db).await {
+            Ok(projects) => projects,
+            Err(e) => {
+                tracing::error!("No se pudieron leer los proyectos: {}", e);
+                return;
+            }
+        };
+
+        for project in &projects {
+            self.remap(project).await;
+        }
+    }
+
+    async fn remap(&self, project: &Project) {
+        match remap_project_anchors(&self.db, project).await {
+            Ok(0) => {}
+            Ok(moved) => tracing::debug!(
+                "{} comentarios del proyecto {} trasladados",
+                moved,
+                project.id
+            ),
+            Err(e) => tracing::warn!(
+                "No se pudieron trasladar los comentarios del proyecto {}: {}",
+                project.id,
+                e
+            ),
+        }
+    }
+}
+
+/// Errores al trasladar las posiciones de los comentarios
+#[derive(Debug, thiserror::Error)]
+pub enum AnchorError {
+    #[error(transparent)]
+    Git(#[from] git::GitError),
+
+    #[error(transparent)]
+    Database(#[from] sqlx::Error),
+
+    #[error("Tarea de seguimiento abortada: {0}")]
+    Join(#[from] tokio::task::JoinError),
+}
+
+/// Traslada los comentarios de archivo de un proyecto a la punta de su rama por defecto
+///
+/// Retorna cuántos comentarios cambiaron de commit (trasladados o
+/// desactualizados). No hace nada si el repositorio no tiene rama por
+/// defecto.
+///
+/// # Errors
+/// - `AnchorError::Git` si falla la lectura del repositorio
+/// - `AnchorError::Database` si falla la consulta o el guardado
+pub async fn remap_project_anchors(pool: &PgPool, project: &Project) -> Result<usize, AnchorError> {
+    let anchors = db::comments::list_tracked_anchors(pool, project.id).await?;
+    if anchors.is_empty() {
+        return Ok(0);
+    }
+
+    let path = project.repository_path.clone();
+    let remapped = tokio::task::spawn_blocking(move || -> git::Result<_> {
+        let repo = git::open_repository(&path)?;
+        let Some(branch) = git::default_branch(&repo)? else {
+            return Ok(None);
+        };
+        let tip = match repo.find_branch(&branch, git2::BranchType::Local) {
+            Ok(reference) => reference.get().peel_to_commit()?.id(),
+            Err(e) if e.code() == git2::ErrorCode::NotFound => return Ok(None),
+            Err(e) => return Err(e.into()),
+        };
+
+        let mut by_commit: BTreeMap<&str, Vec<&TrackedAnchor>> = BTreeMap::new();
+        for anchor in &anchors {
+            by_commit.entry(&anchor.commit).or_default().push(anchor);
+        }
+
+        let mut updates: Vec<AnchorUpdate> = Vec::new();
+        for (commit, group) in by_commit {
+            let Ok(from) = Oid::from_str(commit) else {
+                updates.extend(group.iter().map(|anchor| (anchor.id, None)));
+                continue;
+            };
+            if from == tip {
+                continue;
+            }
+
+            let line_anchors: Vec<LineAnchor> = group.iter().map(|a| line_anchor(a)).collect();
+            let positions = git::remap_anchors(&repo, from, tip, &line_anchors)?;
+            updates.extend(group.iter().zip(positions).map(|(anchor, position)| {
+                let position = position.map(|p| {
+                    let lines = p.lines.map(|(start, end)| (to_i32(start), to_i32(end)));
+                    (p.path, lines)
+                });
+                (anchor.id, position)
+            }));
+        }
+
+        Ok(Some((tip.to_string(), updates)))
+    })
+    .await??;
+
+    let Some((tip, updates)) = remapped else {
+        return Ok(0);
+    };
+    if !updates.is_empty() {
+        db::comments::update_anchors(pool, &tip, &updates).await?;
+    }
+
+    Ok(updates.len())
+}
+
+fn line_anchor(anchor: &TrackedAnchor) -> LineAnchor {
+    let lines = anchor.line_start.zip(anchor.line_end).map(|(start, end)| {
+        (
+            u32::try_from(start).unwrap_or(1),
+            u32::try_from(end).unwrap_or(1),
+        )
+    });
+
+    LineAnchor {
+        path: anchor.path.clone(),
+        lines,
+    }
+}
+
+fn to_i32(line: u32) -> i32 {
+    i32::try_from(line).unwrap_or(i32::MAX)
+}
+
+/// Guarda las menciones de un comentario y notifica a los nuevos mencionados
+///
+/// Solo se registran los usuarios activos que pueden ver el proyecto; las
+/// menciones a cualquier otro nombre se ignoran. El autor no se notifica
+/// a sí mismo y, al editar, solo se notifica a quien no estaba ya
+/// mencionado. Retorna cuántos usuarios se notificaron.
+///
+/// # Errors
+/// Retorna error si falla alguna consulta
+pub async fn record_mentions(
+    pool: &PgPool,
+    project: &Project,
+    comment: &Comment,
+) -> Result<usize, sqlx::Error> {
+    let usernames = extract_mentions(&comment.content);
+    let users = if usernames.is_empty() {
+        Vec::new()
+    } else {
+        db::comments::find_mentionable_users(pool, project.id, &usernames).await?
+    };
+    let user_ids: Vec<i32> = users
+        .iter()
+        .map(|user| user.id)
+        .filter(|&id| Some(id) != comment.user_id)
+        .collect();
+
+    let added = db::comments::replace_mentions(pool, comment.id, &user_ids).await?;
+    if added.is_empty() {
+        return Ok(0);
+    }
+
+    let link = db::comments::find_comment_locations(pool, project.id, &[comment.id])
+        .await?
+        .into_iter()
+        .next()
+        .map(|location| {
+            comment_link(
+                project.id,
+                comment.id,
+                location.commit_hash.as_deref(),
+                location.path.as_deref(),
+            )
+        });
+
+    let author = comment
+        .user
+        .as_ref()
+        .map_or("Alguien", |user| user.username.as_str());
+    let notification = CreateNotification {
+        project_id: Some(project.id),
+        kind: NotificationType::Mention,
+        title: format!("{} te mencionó en {}", author, project.name),
+        message: notifications::excerpt(&comment.content),
+        link,
+    };
+
+    Ok(notifications::notify_users(pool, &added, &notification)
+        .await?
+        .len())
+}
+
+/// Renderiza el contenido de los comentarios a HTML saneado
+///
+/// Se enlazan los hashes de commits del repositorio y las referencias
+/// `#id` a comentarios no eliminados del proyecto; el resto se deja como
+/// texto. Si el repositorio no se puede leer, tampoco se enlazan los hashes.
+///
+/// # Errors
+/// Retorna error si falla la consulta de los comentarios referenciados
+pub async fn render_comments(
+    pool: &PgPool,
+    project: &Project,
+    comments: &mut [Comment],
+) -> Result<(), sqlx::Error> {
+    let sources: Vec<&str> = comments.iter().map(|c| c.content.as_str()).collect();
+    let links = resolve_references(pool, project, &sources).await?;
+
+    for comment in comments.iter_mut() {
+        comment.content_html =
+            markdown::render_comment(&comment.content, |reference| links.get(reference).cloned());
+    }
+    Ok(())
+}
+
+/// Renderiza las versiones anteriores de un comentario como [`render_comments`]
+///
+/// # Errors
+/// Retorna error si falla la consulta de los comentarios referenciados
+pub async fn render_revisions(
+    pool: &PgPool,
+    project: &Project,
+    revisions: &mut [CommentRevision],
+) -> Result<(), sqlx::Error> {
+    let sources: Vec<&str> = revisions.iter().map(|r| r.content.as_str()).collect();
+    let links = resolve_references(pool, project, &sources).await?;
+
+    for revision in revisions.iter_mut() {
+        revision.content_html =
+            markdown::render_comment(&revision.content, |reference| links.get(reference).cloned());
+    }
+    Ok(())
+}
+
+/// URLs de las referencias que existen en el proyecto
+async fn resolve_references(
+    pool: &PgPool,
+    project: &Project,
+    sources: &[&str],
+) -> Result<HashMap<Reference, String>, sqlx::Error> {
+    let mut commits: Vec<String> = Vec::new();
+    let mut comment_ids: Vec<i32> = Vec::new();
+    for reference in sources.iter().flat_map(|source| markdown::find_references(source)) {
+        match reference {
+            Reference::Commit(hash) if !commits.contains(&hash) => commits.push(hash),
+            Reference::Comment(id) if !comment_ids.contains(&id) => comment_ids.push(id),
+            _ => {}
+        }
+    }
+
+    let mut links = HashMap::new();
+
+    if !commits.is_empty() {
+        let path = project.repository_path.clone();
+        let resolved = tokio::task::spawn_blocking(move || -> git::Result<Vec<_>> {
+            let repo = git::open_repository(&path)?;
+            // Los prefijos ambiguos o que no son commits no se enlazan
+            Ok(commits
+                .into_iter()
+                .filter_map(|hash| {
+                    let commit = repo.find_commit_by_prefix(&hash).ok()?;
+                    Some((hash, commit.id().to_string()))
+                })
+                .collect())
+        })
+        .await;
+
+        match resolved {
+            Ok(Ok(resolved)) => {
+                for (hash, full) in resolved {
+                    links.insert(Reference::Commit(hash), commit_link(project.id, &full));
+                }
+            }
+            Ok(Err(e)) => tracing::warn!(
+                "No se enlazarán los commits del proyecto {}: {}",
+                project.id,
+                e
+            ),
+            Err(e) => tracing::warn!("Tarea de enlazado de commits abortada: {}", e),
+        }
+    }
+
+    if !comment_ids.is_empty() {
+        for location in db::comments::find_comment_locations(pool, project.id, &comment_ids).await? {
+            let link = comment_link(
+                project.id,
+                location.id,
+                location.commit_hash.as_deref(),
+                location.path.as_deref(),
+            );
+            links.insert(Reference::Comment(location.id), link);
+        }
+    }
+
+    Ok(links)
+}
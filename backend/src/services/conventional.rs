@@ -0,0 +1,202 @@
+//! Interpretación de mensajes de commit según Conventional Commits 1.0
+//!
+//! `tipo(ámbito)!: descripción`, cuerpo opcional y un pie con trailers
+//! (`Token: valor` o `Token #valor`) en el último párrafo.
+
+use std::sync::OnceLock;
+
+use regex::Regex;
+
+use crate::models::{ConventionalCommit, Trailer};
+
+/// Cabecera `tipo(ámbito)!: descripción`
+fn header_regex() -> &'static Regex {
+    static HEADER: OnceLock<Regex> = OnceLock::new();
+    HEADER.get_or_init(|| {
+        Regex::new(
+            r"^(?P<type>[A-Za-z]+)(?:\((?P<scope>[^()\r\n]*)\))?(?P<bang>!)?: +(?P<desc>\S.*)$",
+        )
+        .expect("regex de cabecera inválida")
+    })
+}
+
+/// Línea de trailer: `Token: valor`, `Token #valor` o `BREAKING CHANGE: valor`
+fn trailer_regex() -> &'static Regex {
+    static TRAILER: OnceLock<Regex> = OnceLock::new();
+    TRAILER.get_or_init(|| {
+        Regex::new(r"^(?P<key>BREAKING CHANGE|[A-Za-z][A-Za-z0-9-]*)(?:: +| +#)(?P<value>.*)$")
+            .expect("regex de trailer inválida")
+    })
+}
+
+/// Referencias a issues en cualquier parte del mensaje (`#12`, `org/repo#12`)
+fn issue_regex() -> &'static Regex {
+    static ISSUE: OnceLock<Regex> = OnceLock::new();
+    ISSUE.get_or_init(|| {
+        Regex::new(r"(?:^|[\s(\[,])(?P<issue>(?:[\w.-]+/[\w.-]+)?#\d+)\b")
+            .expect("regex de issues inválida")
+    })
+}
+
+/// Claves de gestores de incidencias (`PROJ-12`); solo se buscan en trailers
+/// para no confundirlas con texto como `UTF-8`
+fn tracker_key_regex() -> &'static Regex {
+    static KEY: OnceLock<Regex> = OnceLock::new();
+    KEY.get_or_init(|| Regex::new(r"\b[A-Z][A-Z0-9]+-\d+\b").expect("regex de claves inválida"))
+}
+
+/// Interpreta un mensaje de commit
+pub fn parse_commit_message(message: &str) -> ConventionalCommit {
+    let message = message.trim();
+    let (header, rest) = message.split_once('\n').unwrap_or((message, ""));
+    let header = header.trim_end();
+
+    let mut parsed = match header_regex().captures(header) {
+        Some(captures) => ConventionalCommit {
+            commit_type: Some(captures["type"].to_ascii_lowercase()),
+            scope: captures
+                .name("scope")
+                .map(|s| s.as_str().trim().to_string())
+                .filter(|s| !s.is_empty()),
+            breaking: captures.name("bang").is_some(),
+            description: captures["desc"].trim().to_string(),
+            ..ConventionalCommit::default()
+        },
+        None => ConventionalCommit {
+            description: header.to_string(),
+            ..ConventionalCommit::default()
+        },
+    };
+
+    parsed.trailers = parse_trailers(rest);
+    for trailer in &parsed.trailers {
+        if trailer.key == "BREAKING CHANGE" || trailer.key.eq_ignore_ascii_case("BREAKING-CHANGE") {
+            parsed.breaking = true;
+            parsed
+                .breaking_note
+                .get_or_insert_with(|| trailer.value.clone());
+        }
+    }
+
+    let mut issues = Vec::new();
+    for captures in issue_regex().captures_iter(message) {
+        push_unique(&mut issues, &captures["issue"]);
+    }
+    for trailer in &parsed.trailers {
+        if trailer.key.eq_ignore_ascii_case("co-authored-by")
+            || trailer.key.eq_ignore_ascii_case("signed-off-by")
+        {
+            continue;
+        }
+        // `Refs #12` se interpreta como trailer con valor `12`
+        if trailer.value.chars().all(|c| c.is_ascii_digit()) && !trailer.value.is_empty() {
+            push_unique(&mut issues, &format!("#{}", trailer.value));
+        }
+        for key in tracker_key_regex().find_iter(&trailer.value) {
+            push_unique(&mut issues, key.as_str());
+        }
+    }
+    parsed.issues = issues;
+
+    parsed
+}
+
+/// Extrae los trailers del último párrafo del cuerpo
+///
+/// El párrafo solo se considera pie si empieza por un trailer. Las líneas
+/// que no lo son continúan el valor del trailer anterior.
+fn parse_trailers(body: &str) -> Vec<Trailer> {
+    let body = body.trim();
+    if body.is_empty() {
+        return Vec::new();
+    }
+
+    let footer = body
+        .rsplit_once("\n\n")
+        .map_or(body, |(_, last)| last)
+        .trim_matches('\n');
+    let mut trailers: Vec<Trailer> = Vec::new();
+
+    for line in footer.lines() {
+        match trailer_regex().captures(line.trim_end()) {
+            Some(captures) => trailers.push(Trailer {
+                key: captures["key"].to_string(),
+                value: captures["value"].trim().to_string(),
+            }),
+            None => match trailers.last_mut() {
+                Some(last) => {
+                    last.value.push('\n');
+                    last.value.push_str(line.trim());
+                }
+                None => return Vec::new(),
+            },
+        }
+    }
+
+    trailers
+}
+
+fn push_unique(list: &mut Vec<String>, value: &str) {
+    if !list.iter().any(|v| v == value) {
+        list.push(value.to_string());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_header() {
+        let parsed = parse_commit_message("feat(api)!: drop v1 endpoints");
+        assert_eq!(parsed.commit_type.as_deref(), Some("feat"));
+        assert_eq!(parsed.scope.as_deref(), Some("api"));
+        assert!(parsed.breaking);
+        assert_eq!(parsed.description, "drop v1 endpoints");
+
+        let parsed = parse_commit_message("Fix: typo");
+        assert_eq!(parsed.commit_type.as_deref(), Some("fix"));
+        assert_eq!(parsed.scope, None);
+        assert!(!parsed.breaking);
+
+        let parsed = parse_commit_message("Merge branch 'main' into dev");
+        assert_eq!(parsed.commit_type, None);
+        assert_eq!(parsed.description, "Merge branch 'main' into dev");
+
+        assert_eq!(parse_commit_message("feat:no space").commit_type, None);
+        assert_eq!(parse_commit_message("feat(a)(b): x").commit_type, None);
+    }
+
+    #[test]
+    fn test_parse_footer() {
+        let parsed = parse_commit_message(
+            "fix(auth): expire sessions (#40)\n\n\
+             Sessions were never expiring. Refs UTF-8 handling.\n\n\
+             BREAKING CHANGE: tokens issued before the upgrade\n  must be renewed\n\
+             Co-authored-by: Ada Lovelace <ada@example.com>\n\
+             Refs: PROJ-7, org/app#3\n\
+             Closes #41\n",
+        );
+
+        assert!(parsed.breaking);
+        assert_eq!(
+            parsed.breaking_note.as_deref(),
+            Some("tokens issued before the upgrade\nmust be renewed")
+        );
+        let keys: Vec<_> = parsed.trailers.iter().map(|t| t.key.as_str()).collect();
+        assert_eq!(
+            keys,
+            vec!["BREAKING CHANGE", "Co-authored-by", "Refs", "Closes"]
+        );
+        assert_eq!(parsed.trailers[1].value, "Ada Lovelace <ada@example.com>");
+        assert_eq!(parsed.issues, vec!["#40", "org/app#3", "#41", "PROJ-7"]);
+    }
+
+    #[test]
+    fn test_body_without_footer() {
+        let parsed =
+            parse_commit_message("docs: readme\n\nExplain setup.\nSee: the wiki is outdated");
+        assert!(parsed.trailers.is_empty());
+        assert!(parsed.issues.is_empty());
+    }
+}
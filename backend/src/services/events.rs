@@ -0,0 +1,132 @@
+//! Bus de eventos en memoria entre subsistemas
+//!
+//! Los productores (p. ej. el watcher de repositorios) publican eventos y
+//! cualquier subsistema interesado (WebSockets, notificaciones, Telegram)
+//! se suscribe con [`EventBus::subscribe`]. Un suscriptor lento puede perder
+//! eventos antiguos si el buffer se llena (`RecvError::Lagged`).
+
+use serde::Serialize;
+use tokio::sync::broadcast;
+
+use crate::models::{
+    CommitRecord, LockAction, LockRequest, LockStatus, Project, PullEvent, WorkingCopySummary,
+};
+
+/// Eventos del dominio emitidos por los servicios
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ProjectEvent {
+    /// Se movió la punta de una rama de un proyecto
+    Pull {
+        event: PullEvent,
+        /// Commits nuevos, del más reciente al más antiguo (limitado)
+        commits: Vec<CommitRecord>,
+    },
+    /// Un proyecto se bloqueó o desbloqueó
+    LockChanged {
+        action: LockAction,
+        status: LockStatus,
+        actor_id: i32,
+    },
+    /// Un usuario solicitó bloquear un proyecto
+    LockRequested { request: LockRequest },
+    /// Un administrador aprobó o rechazó una solicitud de bloqueo
+    LockRequestReviewed { request: LockRequest },
+    /// El working copy de un proyecto pasó de limpio a tener cambios sin commitear
+    WorkingCopyDirty {
+        project_id: i32,
+        summary: WorkingCopySummary,
+    },
+    /// Se registró un proyecto nuevo
+    ProjectCreated { project: Project },
+    /// Se eliminó un proyecto
+    ProjectDeleted { project_id: i32 },
+}
+
+impl ProjectEvent {
+    /// Proyecto al que pertenece el evento
+    pub fn project_id(&self) -> i32 {
+        match self {
+            ProjectEvent::Pull { event, .. } => event.project_id,
+            ProjectEvent::LockChanged { status, .. } => status.project_id,
+            ProjectEvent::LockRequested { request }
+            | ProjectEvent::LockRequestReviewed { request } => request.project_id,
+            ProjectEvent::WorkingCopyDirty { project_id, .. }
+            | ProjectEvent::ProjectDeleted { project_id } => *project_id,
+            ProjectEvent::ProjectCreated { project } => project.id,
+        }
+    }
+}
+
+/// Canal de difusión de [`ProjectEvent`]
+#[derive(Debug, Clone)]
+pub struct EventBus {
+    sender: broadcast::Sender<ProjectEvent>,
+}
+
+impl EventBus {
+    /// Crea un bus que retiene hasta `capacity` eventos por suscriptor
+    pub fn new(capacity: usize) -> Self {
+        let (sender, _) = broadcast::channel(capacity.max(1));
+        Self { sender }
+    }
+
+    pub fn subscribe(&self) -> broadcast::Receiver<ProjectEvent> {
+        self.sender.subscribe()
+    }
+
+    /// Publica un evento; si no hay suscriptores se descarta
+    pub fn publish(&self, event: ProjectEvent) {
+        let _ = self.sender.send(event);
+    }
+}
+
+impl Default for EventBus {
+    fn default() -> Self {
+        Self::new(256)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pull_event(project_id: i32) -> ProjectEvent {
+        ProjectEvent::Pull {
+            event: PullEvent {
+                id: 1,
+                project_id,
+                ref_name: "refs/heads/main".to_string(),
+                old_commit: None,
+                new_commit: "9fceb02d0ae598e95dc970b74767f19372d61af8".to_string(),
+                commit_count: 0,
+                detected_at: chrono::NaiveDateTime::default(),
+            },
+            commits: Vec::new(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_subscribers_receive_events() {
+        let bus = EventBus::new(8);
+        let mut first = bus.subscribe();
+        let mut second = bus.subscribe();
+
+        bus.publish(pull_event(7));
+
+        assert_eq!(first.recv().await.unwrap().project_id(), 7);
+        assert_eq!(second.recv().await.unwrap().project_id(), 7);
+    }
+
+    #[test]
+    fn test_publish_without_subscribers() {
+        EventBus::default().publish(pull_event(1));
+    }
+
+    #[test]
+    fn test_event_serialization() {
+        let json = serde_json::to_value(pull_event(3)).unwrap();
+        assert_eq!(json["type"], "pull");
+        assert_eq!(json["event"]["project_id"], 3);
+    }
+}
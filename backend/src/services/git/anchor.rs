@@ -0,0 +1,289 @@
+use std::collections::{HashMap, HashSet};
+
+use git2::{Commit, Delta, DiffFindOptions, DiffOptions, ErrorCode, Oid, Patch, Repository};
+
+use super::blob::detect_encoding;
+use super::{read_blob, Result};
+
+/// Posición de un comentario en un archivo
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LineAnchor {
+    pub path: String,
+    /// Primera y última línea (empezando en 1, inclusive); `None` si el
+    /// comentario es sobre el archivo completo
+    pub lines: Option<(u32, u32)>,
+}
+
+/// Cabecera de un hunk de un diff sin contexto
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct HunkRange {
+    old_start: u32,
+    old_lines: u32,
+    new_lines: u32,
+}
+
+/// Qué le pasó a un archivo entre dos commits
+enum FileChange {
+    /// Se borró o se reescribió de forma que sus líneas no se pueden seguir
+    Lost,
+    /// Se modificó y/o se renombró
+    Changed {
+        new_path: String,
+        hunks: Vec<HunkRange>,
+    },
+}
+
+/// Número de líneas de un archivo en la revisión de `commit`
+///
+/// Retorna `None` si el archivo es binario.
+///
+/// # Errors
+/// Los mismos que [`read_blob`]
+pub fn line_count(
+    repo: &Repository,
+    commit: &Commit,
+    path: &str,
+    limit: u64,
+) -> Result<Option<u32>> {
+    let blob = read_blob(repo, commit, path, limit)?;
+    if detect_encoding(&blob.data).is_none() {
+        return Ok(None);
+    }
+
+    let newlines = blob.data.iter().filter(|&&b| b == b'\n').count();
+    let unterminated = usize::from(blob.data.last().is_some_and(|&b| b != b'\n'));
+    Ok(Some((newlines + unterminated) as u32))
+}
+
+/// Traslada posiciones de comentarios del commit `from` al commit `to`
+///
+/// Sigue los renombrados y desplaza los rangos según las líneas añadidas o
+/// quitadas antes de ellos. Una posición pasa a `None` (comentario
+/// desactualizado) si se modifica alguna de sus líneas, si el archivo se
+/// borra o si deja de poder compararse (p. ej. se vuelve binario). Si
+/// `from` ya no existe en el repositorio todas quedan desactualizadas.
+///
+/// # Errors
+/// - `GitError::Git` si falla la lectura de los commits o el diff
+pub fn remap_anchors(
+    repo: &Repository,
+    from: Oid,
+    to: Oid,
+    anchors: &[LineAnchor],
+) -> Result<Vec<Option<LineAnchor>>> {
+    if from == to {
+        return Ok(anchors.iter().cloned().map(Some).collect());
+    }
+
+    let old_tree = match repo.find_commit(from) {
+        Ok(commit) => commit.tree()?,
+        Err(e) if e.code() == ErrorCode::NotFound => return Ok(vec![None; anchors.len()]),
+        Err(e) => return Err(e.into()),
+    };
+    let new_tree = repo.find_commit(to)?.tree()?;
+
+    let mut options = DiffOptions::new();
+    options.context_lines(0);
+    let mut diff = repo.diff_tree_to_tree(Some(&old_tree), Some(&new_tree), Some(&mut options))?;
+    diff.find_similar(Some(DiffFindOptions::new().renames(true)))?;
+
+    let wanted: HashSet<&str> = anchors.iter().map(|a| a.path.as_str()).collect();
+    let mut changes: HashMap<String, FileChange> = HashMap::new();
+
+    for (idx, delta) in diff.deltas().enumerate() {
+        let Some(old_path) = delta
+            .old_file()
+            .path()
+            .map(|p| p.to_string_lossy().into_owned())
+        else {
+            continue;
+        };
+        if !wanted.contains(old_path.as_str()) {
+            continue;
+        }
+
+        let change = match delta.status() {
+            Delta::Modified | Delta::Renamed => {
+                let new_path = delta
+                    .new_file()
+                    .path()
+                    .map(|p| p.to_string_lossy().into_owned())
+                    .unwrap_or_else(|| old_path.clone());
+
+                if delta.old_file().id() == delta.new_file().id() {
+                    FileChange::Changed {
+                        new_path,
+                        hunks: Vec::new(),
+                    }
+                } else {
+                    match Patch::from_diff(&diff, idx)? {
+                        Some(patch) if !delta.flags().is_binary() => {
+                            let hunks = (0..patch.num_hunks())
+                                .map(|h| {
+                                    patch.hunk(h).map(|(hunk, _)| HunkRange {
+                                        old_start: hunk.old_start(),
+                                        old_lines: hunk.old_lines(),
+                                        new_lines: hunk.new_lines(),
+                                    })
+                                })
+                                .collect::<std::result::Result<Vec<_>, _>>()?;
+                            FileChange::Changed { new_path, hunks }
+                        }
+                        _ => FileChange::Lost,
+                    }
+                }
+            }
+            Delta::Deleted | Delta::Typechange => FileChange::Lost,
+            _ => continue,
+        };
+        changes.insert(old_path, change);
+    }
+
+    Ok(anchors
+        .iter()
+        .map(|anchor| match changes.get(&anchor.path) {
+            None => Some(anchor.clone()),
+            Some(FileChange::Lost) => None,
+            Some(FileChange::Changed { new_path, hunks }) => {
+                let lines = match anchor.lines {
+                    Some((start, end)) => Some(remap_range(hunks, start, end)?),
+                    None => None,
+                };
+                Some(LineAnchor {
+                    path: new_path.clone(),
+                    lines,
+                })
+            }
+        })
+        .collect())
+}
+
+/// Desplaza un rango de líneas según los hunks (ordenados) de un diff sin contexto
+///
+/// Retorna `None` si algún hunk toca el rango, incluidas las líneas
+/// insertadas entre dos líneas del rango.
+fn remap_range(hunks: &[HunkRange], start: u32, end: u32) -> Option<(u32, u32)> {
+    let mut offset: i64 = 0;
+
+    for hunk in hunks {
+        if hunk.old_lines == 0 {
+            // Inserción tras la línea `old_start`
+            if hunk.old_start >= end {
+                break;
+            }
+            if hunk.old_start >= start {
+                return None;
+            }
+        } else {
+            let old_end = hunk.old_start + hunk.old_lines - 1;
+            if hunk.old_start > end {
+                break;
+            }
+            if old_end >= start {
+                return None;
+            }
+        }
+        offset += i64::from(hunk.new_lines) - i64::from(hunk.old_lines);
+    }
+
+    let shift = |line: u32| u32::try_from(i64::from(line) + offset).ok();
+    Some((shift(start)?, shift(end)?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::git::test_support::{commit_files, init_repo};
+
+    fn hunk(old_start: u32, old_lines: u32, new_lines: u32) -> HunkRange {
+        HunkRange {
+            old_start,
+            old_lines,
+            new_lines,
+        }
+    }
+
+    #[test]
+    fn test_remap_range() {
+        // Sin cambios
+        assert_eq!(remap_range(&[], 5, 7), Some((5, 7)));
+        // Dos líneas insertadas al principio y una borrada antes del rango
+        assert_eq!(
+            remap_range(&[hunk(0, 0, 2), hunk(3, 1, 0)], 5, 7),
+            Some((6, 8))
+        );
+        // Inserción justo antes y justo después del rango
+        assert_eq!(remap_range(&[hunk(4, 0, 3)], 5, 7), Some((8, 10)));
+        assert_eq!(remap_range(&[hunk(7, 0, 3)], 5, 7), Some((5, 7)));
+        // Cambios posteriores no afectan
+        assert_eq!(remap_range(&[hunk(8, 2, 5)], 5, 7), Some((5, 7)));
+        // Línea del rango modificada o borrada
+        assert_eq!(remap_range(&[hunk(7, 1, 1)], 5, 7), None);
+        assert_eq!(remap_range(&[hunk(3, 3, 0)], 5, 7), None);
+        // Inserción entre dos líneas del rango
+        assert_eq!(remap_range(&[hunk(5, 0, 1)], 5, 7), None);
+    }
+
+    #[test]
+    fn test_remap_anchors_across_commits() {
+        let (_dir, repo) = init_repo();
+        let base = "uno\ndos\ntres\ncuatro\ncinco\nseis\n";
+        let first = commit_files(
+            &repo,
+            &[("src/a.txt", base.as_bytes()), ("b.txt", b"b1\nb2\nb3\n")],
+            "first",
+        );
+        // Dos líneas nuevas arriba en a.txt (renombrado) y b.txt modificado en la línea 2
+        let moved = format!("cero\ncero bis\n{}", base);
+        let second = commit_files(
+            &repo,
+            &[("lib/a.txt", moved.as_bytes()), ("b.txt", b"b1\nB2\nb3\n")],
+            "second",
+        );
+
+        let anchors = vec![
+            LineAnchor {
+                path: "src/a.txt".to_string(),
+                lines: Some((3, 4)),
+            },
+            LineAnchor {
+                path: "b.txt".to_string(),
+                lines: Some((1, 1)),
+            },
+            LineAnchor {
+                path: "b.txt".to_string(),
+                lines: Some((2, 3)),
+            },
+            LineAnchor {
+                path: "b.txt".to_string(),
+                lines: None,
+            },
+        ];
+        let remapped = remap_anchors(&repo, first, second, &anchors).unwrap();
+        assert_eq!(
+            remapped,
+            vec![
+                Some(LineAnchor {
+                    path: "lib/a.txt".to_string(),
+                    lines: Some((5, 6)),
+                }),
+                Some(anchors[1].clone()),
+                None,
+                Some(anchors[3].clone()),
+            ]
+        );
+
+        // Archivo borrado
+        let third = commit_files(&repo, &[("lib/a.txt", moved.as_bytes())], "third");
+        assert_eq!(
+            remap_anchors(&repo, second, third, &anchors[3..]).unwrap(),
+            vec![None]
+        );
+
+        let commit = repo.find_commit(second).unwrap();
+        assert_eq!(
+            line_count(&repo, &commit, "lib/a.txt", 1024).unwrap(),
+            Some(8)
+        );
+    }
+}
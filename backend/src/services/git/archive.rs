@@ -0,0 +1,328 @@
+use std::io::{self, Read, Seek, Write};
+
+use flate2::{write::GzEncoder, Compression};
+use git2::{Commit, Oid, Repository, TreeWalkMode, TreeWalkResult};
+use zip::{write::SimpleFileOptions, CompressionMethod, ZipWriter};
+
+use super::tree::{entry_type, subtree};
+use super::{git_time_to_utc, join_path, GitError, Result};
+use crate::models::TreeEntryType;
+
+/// Entrada de un árbol a incluir en un archivo
+#[derive(Debug, Clone)]
+struct ArchiveItem {
+    /// Ruta relativa al directorio archivado
+    path: String,
+    kind: TreeEntryType,
+    executable: bool,
+    oid: Oid,
+}
+
+/// Contenido de un archivo `.tar.gz` o `.zip` antes de generarlo
+///
+/// Solo guarda rutas y OIDs; los blobs se leen uno a uno al escribir para
+/// no tener el árbol completo en memoria.
+#[derive(Debug, Clone)]
+pub struct ArchivePlan {
+    items: Vec<ArchiveItem>,
+    /// Fecha del commit en segundos Unix; se usa como fecha de todas las entradas
+    mtime: i64,
+    /// Archivos y symlinks incluidos
+    pub files: usize,
+    /// Suma de los tamaños sin comprimir de los blobs
+    pub size: u64,
+    /// Tamaño del blob más grande
+    pub largest: u64,
+}
+
+/// Prepara el archivo de un directorio (la raíz si `path` está vacío) en un commit
+///
+/// Lee solo las cabeceras de los objetos, así que sirve para comprobar el
+/// tamaño total antes de empezar a enviar. Los submódulos se incluyen
+/// como directorios vacíos, igual que `git archive`.
+///
+/// # Errors
+/// - `GitError::PathNotFound` si la ruta no existe en el commit
+/// - `GitError::NotADirectory` si la ruta no es un directorio
+/// - `GitError::Git` si falla la lectura de objetos
+pub fn plan_archive(repo: &Repository, commit: &Commit, path: &str) -> Result<ArchivePlan> {
+    let tree =
+        subtree(repo, commit, path)?.ok_or_else(|| GitError::PathNotFound(path.to_string()))?;
+    let odb = repo.odb()?;
+
+    let mut items = Vec::new();
+    tree.walk(TreeWalkMode::PreOrder, |root, entry| {
+        items.push(ArchiveItem {
+            path: join_path(
+                root.trim_end_matches('/'),
+                &String::from_utf8_lossy(entry.name_bytes()),
+            ),
+            kind: entry_type(entry),
+            executable: entry.filemode() == 0o100755,
+            oid: entry.id(),
+        });
+        TreeWalkResult::Ok
+    })?;
+
+    let mut plan = ArchivePlan {
+        items,
+        mtime: commit.time().seconds(),
+        files: 0,
+        size: 0,
+        largest: 0,
+    };
+    for item in &plan.items {
+        if matches!(item.kind, TreeEntryType::File | TreeEntryType::Symlink) {
+            let size = odb.read_header(item.oid)?.0 as u64;
+            plan.files += 1;
+            plan.size += size;
+            plan.largest = plan.largest.max(size);
+        }
+    }
+
+    Ok(plan)
+}
+
+/// Escribe el archivo como `.tar.gz`
+///
+/// Todas las entradas cuelgan de `prefix` (p. ej. `lumastack-main`). El
+/// writer solo necesita `Write`: la salida se genera secuencialmente.
+///
+/// # Errors
+/// - `GitError::Io` si falla la escritura (p. ej. el cliente se desconectó)
+/// - `GitError::Git` si falla la lectura de objetos
+pub fn write_tar_gz<W: Write>(
+    repo: &Repository,
+    plan: &ArchivePlan,
+    prefix: &str,
+    writer: W,
+) -> Result<W> {
+    let mut builder = tar::Builder::new(GzEncoder::new(writer, Compression::default()));
+    let mtime = u64::try_from(plan.mtime).unwrap_or(0);
+
+    let header = |entry_type: tar::EntryType, mode: u32, size: u64| {
+        let mut header = tar::Header::new_gnu();
+        header.set_entry_type(entry_type);
+        header.set_mode(mode);
+        header.set_size(size);
+        header.set_mtime(mtime);
+        header
+    };
+
+    let root = format!("{}/", prefix);
+    builder.append_data(
+        &mut header(tar::EntryType::Directory, 0o755, 0),
+        &root,
+        io::empty(),
+    )?;
+
+    for item in &plan.items {
+        let path = format!("{}{}", root, item.path);
+        match item.kind {
+            TreeEntryType::Directory | TreeEntryType::Submodule => {
+                let mut header = header(tar::EntryType::Directory, 0o755, 0);
+                builder.append_data(&mut header, format!("{}/", path), io::empty())?;
+            }
+            TreeEntryType::Symlink => {
+                let blob = repo.find_blob(item.oid)?;
+                let target = String::from_utf8_lossy(blob.content()).into_owned();
+                let mut header = header(tar::EntryType::Symlink, 0o777, 0);
+                builder.append_link(&mut header, &path, target)?;
+            }
+            TreeEntryType::File => {
+                let blob = repo.find_blob(item.oid)?;
+                let mode = if item.executable { 0o755 } else { 0o644 };
+                let mut header = header(tar::EntryType::Regular, mode, blob.size() as u64);
+                builder.append_data(&mut header, &path, blob.content())?;
+            }
+        }
+    }
+
+    Ok(builder.into_inner()?.finish()?)
+}
+
+/// Escribe el archivo como `.zip`
+///
+/// El formato zip exige volver atrás para completar la cabecera de cada
+/// archivo, así que el writer necesita `Seek`; nunca se retrocede más allá
+/// del archivo en curso y el writer se vacía (`flush`) al terminar cada
+/// uno, de modo que puede descartar lo ya enviado. `Read` solo lo exige
+/// la API de `zip` para activar ese vaciado; nunca se lee.
+///
+/// Mientras tanto el writer retiene el archivo en curso comprimido, además
+/// del blob leído: quien llama debe limitar `plan.largest`.
+///
+/// # Errors
+/// - `GitError::Io` si falla la escritura (p. ej. el cliente se desconectó)
+/// - `GitError::Git` si falla la lectura de objetos
+pub fn write_zip<W: Read + Write + Seek>(
+    repo: &Repository,
+    plan: &ArchivePlan,
+    prefix: &str,
+    writer: W,
+) -> Result<W> {
+    let mut zip = ZipWriter::new(writer);
+    zip.set_flush_on_finish_file(true);
+
+    let options = SimpleFileOptions::default()
+        .compression_method(CompressionMethod::Deflated)
+        .last_modified_time(zip_time(plan.mtime));
+
+    let root = format!("{}/", prefix);
+    zip.add_directory(&root, options.unix_permissions(0o755))
+        .map_err(zip_error)?;
+
+    for item in &plan.items {
+        let path = format!("{}{}", root, item.path);
+        match item.kind {
+            TreeEntryType::Directory | TreeEntryType::Submodule => {
+                zip.add_directory(path, options.unix_permissions(0o755))
+                    .map_err(zip_error)?;
+            }
+            TreeEntryType::Symlink => {
+                let blob = repo.find_blob(item.oid)?;
+                let target = String::from_utf8_lossy(blob.content()).into_owned();
+                zip.add_symlink(path, target, options.unix_permissions(0o777))
+                    .map_err(zip_error)?;
+            }
+            TreeEntryType::File => {
+                let blob = repo.find_blob(item.oid)?;
+                let mode = if item.executable { 0o755 } else { 0o644 };
+                let file_options = options
+                    .unix_permissions(mode)
+                    .large_file(blob.size() as u64 >= u64::from(u32::MAX));
+                zip.start_file(path, file_options).map_err(zip_error)?;
+                zip.write_all(blob.content())?;
+            }
+        }
+    }
+
+    zip.finish().map_err(zip_error)
+}
+
+/// Fecha de las entradas zip; el formato solo admite años entre 1980 y 2107
+fn zip_time(seconds: i64) -> zip::DateTime {
+    let time = git_time_to_utc(git2::Time::new(seconds, 0)).naive_utc();
+    zip::DateTime::try_from(time).unwrap_or_default()
+}
+
+fn zip_error(err: zip::result::ZipError) -> GitError {
+    match err {
+        zip::result::ZipError::Io(e) => GitError::Io(e),
+        other => GitError::Io(io::Error::other(other)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::git::test_support::{commit_entries, init_repo};
+    use flate2::read::GzDecoder;
+    use std::io::{Cursor, Read};
+
+    fn sample_repo() -> (tempfile::TempDir, Repository) {
+        let (dir, repo) = init_repo();
+        commit_entries(
+            &repo,
+            &[
+                ("README.md", 0o100644, b"# Demo\n"),
+                ("bin/run.sh", 0o100755, b"#!/bin/sh\necho hi\n"),
+                ("src/lib.rs", 0o100644, b"pub fn lib() {}\n"),
+                ("link", 0o120000, b"README.md"),
+            ],
+            "init",
+        );
+        (dir, repo)
+    }
+
+    #[test]
+    fn test_plan_archive() {
+        let (_dir, repo) = sample_repo();
+        let commit = repo.head().unwrap().peel_to_commit().unwrap();
+
+        let plan = plan_archive(&repo, &commit, "").unwrap();
+        assert_eq!(plan.files, 4);
+        assert_eq!(plan.size, 7 + 18 + 16 + 9);
+        assert_eq!(plan.largest, 18);
+
+        let plan = plan_archive(&repo, &commit, "src").unwrap();
+        assert_eq!(plan.files, 1);
+        assert_eq!(plan.items[0].path, "lib.rs");
+
+        assert!(matches!(
+            plan_archive(&repo, &commit, "missing"),
+            Err(GitError::PathNotFound(_))
+        ));
+        assert!(matches!(
+            plan_archive(&repo, &commit, "README.md"),
+            Err(GitError::NotADirectory(_))
+        ));
+    }
+
+    #[test]
+    fn test_write_tar_gz() {
+        let (_dir, repo) = sample_repo();
+        let commit = repo.head().unwrap().peel_to_commit().unwrap();
+        let plan = plan_archive(&repo, &commit, "").unwrap();
+
+        let data = write_tar_gz(&repo, &plan, "demo-main", Vec::new()).unwrap();
+        let mut archive = tar::Archive::new(GzDecoder::new(data.as_slice()));
+
+        let mut entries = Vec::new();
+        for entry in archive.entries().unwrap() {
+            let mut entry = entry.unwrap();
+            let path = entry.path().unwrap().to_string_lossy().into_owned();
+            let mode = entry.header().mode().unwrap();
+            let link = entry
+                .link_name()
+                .unwrap()
+                .map(|l| l.to_string_lossy().into_owned());
+            let mut content = String::new();
+            entry.read_to_string(&mut content).unwrap();
+            entries.push((path, mode, link, content));
+        }
+
+        let paths: Vec<_> = entries.iter().map(|e| e.0.as_str()).collect();
+        assert_eq!(
+            paths,
+            vec![
+                "demo-main/",
+                "demo-main/README.md",
+                "demo-main/bin/",
+                "demo-main/bin/run.sh",
+                "demo-main/link",
+                "demo-main/src/",
+                "demo-main/src/lib.rs",
+            ]
+        );
+        assert_eq!(entries[1].3, "# Demo\n");
+        assert_eq!(entries[3].1, 0o755);
+        assert_eq!(entries[4].2.as_deref(), Some("README.md"));
+    }
+
+    #[test]
+    fn test_write_zip() {
+        let (_dir, repo) = sample_repo();
+        let commit = repo.head().unwrap().peel_to_commit().unwrap();
+        let plan = plan_archive(&repo, &commit, "").unwrap();
+
+        let data = write_zip(&repo, &plan, "demo-main", Cursor::new(Vec::new()))
+            .unwrap()
+            .into_inner();
+        let mut archive = zip::ZipArchive::new(Cursor::new(data)).unwrap();
+
+        assert_eq!(archive.len(), 7);
+        let mut content = String::new();
+        archive
+            .by_name("demo-main/src/lib.rs")
+            .unwrap()
+            .read_to_string(&mut content)
+            .unwrap();
+        assert_eq!(content, "pub fn lib() {}\n");
+
+        let script = archive.by_name("demo-main/bin/run.sh").unwrap();
+        assert_eq!(script.unix_mode().map(|m| m & 0o777), Some(0o755));
+        drop(script);
+        assert!(archive.by_name("demo-main/link").unwrap().is_symlink());
+    }
+}
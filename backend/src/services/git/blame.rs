@@ -0,0 +1,193 @@
+use std::collections::HashMap;
+use std::num::NonZeroUsize;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+
+use git2::{BlameOptions, Commit, Oid, Repository};
+use lru::LruCache;
+
+use super::{commit_summary, Result};
+use crate::models::{BlameRange, CommitSummary};
+
+/// Calcula el blame de un archivo en la revisión de `commit`
+///
+/// Agrupa las líneas consecutivas atribuidas al mismo commit en rangos.
+/// Los autores se resuelven con el `.mailmap` del repositorio (el del
+/// directorio de trabajo o, en repositorios bare, el de `HEAD`), igual que
+/// `git blame`.
+///
+/// # Errors
+/// - `GitError::Git` si la ruta no existe en la revisión o falla el cálculo
+pub fn blame_file(repo: &Repository, commit: &Commit, path: &str) -> Result<Vec<BlameRange>> {
+    let mailmap = repo.mailmap()?;
+
+    let mut options = BlameOptions::new();
+    options.newest_commit(commit.id());
+    let blame = repo.blame_file(Path::new(path), Some(&mut options))?;
+
+    let mut summaries: HashMap<Oid, CommitSummary> = HashMap::new();
+    let mut ranges = Vec::with_capacity(blame.len());
+
+    for hunk in blame.iter() {
+        let lines = hunk.lines_in_hunk();
+        if lines == 0 {
+            continue;
+        }
+
+        let commit_id = hunk.final_commit_id();
+        let summary = match summaries.get(&commit_id) {
+            Some(summary) => summary.clone(),
+            None => {
+                let blamed = repo.find_commit(commit_id)?;
+                let author = mailmap.resolve_signature(&blamed.author())?;
+                let mut summary = commit_summary(&blamed);
+                summary.author_name = String::from_utf8_lossy(author.name_bytes()).into_owned();
+                summary.author_email = String::from_utf8_lossy(author.email_bytes()).into_owned();
+                summaries.insert(commit_id, summary.clone());
+                summary
+            }
+        };
+
+        let original_path = hunk
+            .path()
+            .map(|p| p.to_string_lossy().into_owned())
+            .filter(|p| p != path);
+        let start_line = hunk.final_start_line();
+
+        ranges.push(BlameRange {
+            start_line,
+            end_line: start_line + lines - 1,
+            commit: summary,
+            original_path,
+            original_start_line: hunk.orig_start_line(),
+        });
+    }
+
+    Ok(ranges)
+}
+
+/// Clave de la caché de blame
+///
+/// El resultado depende de la historia alcanzable desde el commit y no solo
+/// del contenido, por eso la clave incluye el commit además del blob.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct BlameKey {
+    pub project_id: i32,
+    pub commit: Oid,
+    pub blob: Oid,
+    pub path: String,
+}
+
+/// Caché LRU en memoria de resultados de blame
+///
+/// Con capacidad 0 la caché queda desactivada.
+pub struct BlameCache {
+    entries: Option<Mutex<LruCache<BlameKey, Arc<Vec<BlameRange>>>>>,
+}
+
+impl BlameCache {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            entries: NonZeroUsize::new(capacity).map(|cap| Mutex::new(LruCache::new(cap))),
+        }
+    }
+
+    pub fn get(&self, key: &BlameKey) -> Option<Arc<Vec<BlameRange>>> {
+        let entries = self.entries.as_ref()?;
+        let mut entries = entries.lock().unwrap_or_else(|e| e.into_inner());
+        entries.get(key).cloned()
+    }
+
+    pub fn insert(&self, key: BlameKey, ranges: Arc<Vec<BlameRange>>) {
+        if let Some(entries) = &self.entries {
+            let mut entries = entries.lock().unwrap_or_else(|e| e.into_inner());
+            entries.put(key, ranges);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::git::test_support::{commit_files, init_repo};
+
+    #[test]
+    fn test_blame_file_groups_ranges() {
+        let (_dir, repo) = init_repo();
+        let first = commit_files(&repo, &[("a.txt", b"uno\ndos\ntres\n")], "first");
+        let second = commit_files(&repo, &[("a.txt", b"uno\nDOS\ntres\ncuatro\n")], "second");
+        let commit = repo.find_commit(second).unwrap();
+
+        let ranges = blame_file(&repo, &commit, "a.txt").unwrap();
+        let spans: Vec<_> = ranges
+            .iter()
+            .map(|r| (r.start_line, r.end_line, r.commit.hash.clone()))
+            .collect();
+        assert_eq!(
+            spans,
+            vec![
+                (1, 1, first.to_string()),
+                (2, 2, second.to_string()),
+                (3, 3, first.to_string()),
+                (4, 4, second.to_string()),
+            ]
+        );
+        assert_eq!(ranges[1].commit.summary, "second");
+        assert_eq!(ranges[0].original_path, None);
+    }
+
+    #[test]
+    fn test_blame_file_at_older_revision() {
+        let (_dir, repo) = init_repo();
+        let first = commit_files(&repo, &[("a.txt", b"uno\n")], "first");
+        commit_files(&repo, &[("a.txt", b"otro\n")], "second");
+        let commit = repo.find_commit(first).unwrap();
+
+        let ranges = blame_file(&repo, &commit, "a.txt").unwrap();
+        assert_eq!(ranges.len(), 1);
+        assert_eq!(ranges[0].commit.hash, first.to_string());
+    }
+
+    #[test]
+    fn test_blame_file_uses_mailmap() {
+        let (dir, repo) = init_repo();
+        std::fs::write(
+            dir.path().join(".mailmap"),
+            "Real Name <real@example.com> <test@example.com>\n",
+        )
+        .unwrap();
+        let oid = commit_files(&repo, &[("a.txt", b"uno\n")], "first");
+        let commit = repo.find_commit(oid).unwrap();
+
+        let ranges = blame_file(&repo, &commit, "a.txt").unwrap();
+        assert_eq!(ranges[0].commit.author_name, "Real Name");
+        assert_eq!(ranges[0].commit.author_email, "real@example.com");
+    }
+
+    #[test]
+    fn test_blame_cache() {
+        let key = BlameKey {
+            project_id: 1,
+            commit: Oid::zero(),
+            blob: Oid::zero(),
+            path: "a.txt".to_string(),
+        };
+
+        let cache = BlameCache::new(1);
+        assert!(cache.get(&key).is_none());
+        cache.insert(key.clone(), Arc::new(Vec::new()));
+        assert!(cache.get(&key).is_some());
+
+        let other = BlameKey {
+            path: "b.txt".to_string(),
+            ..key.clone()
+        };
+        cache.insert(other.clone(), Arc::new(Vec::new()));
+        assert!(cache.get(&key).is_none());
+        assert!(cache.get(&other).is_some());
+
+        let disabled = BlameCache::new(0);
+        disabled.insert(key.clone(), Arc::new(Vec::new()));
+        assert!(disabled.get(&key).is_none());
+    }
+}
@@ -0,0 +1,256 @@
+use std::path::Path;
+
+use encoding_rs::{Encoding, UTF_8};
+use git2::{Commit, ErrorCode, ObjectType, Oid, Repository};
+
+use super::{GitError, Result};
+use crate::services::markdown::pick_readme;
+
+/// Bytes inspeccionados para decidir si un archivo es binario (mismo criterio que Git)
+const BINARY_SNIFF_LENGTH: usize = 8000;
+
+/// Contenido de un archivo en una revisión concreta
+#[derive(Debug, Clone)]
+pub struct BlobContent {
+    pub oid: Oid,
+    /// Modo Git de la entrada (`0o100644`, `0o100755` o `0o120000`)
+    pub mode: i32,
+    pub data: Vec<u8>,
+}
+
+impl BlobContent {
+    pub fn size(&self) -> u64 {
+        self.data.len() as u64
+    }
+
+    pub fn is_symlink(&self) -> bool {
+        self.mode == i32::from(git2::FileMode::Link)
+    }
+}
+
+/// Texto decodificado de un archivo
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DecodedText {
+    pub content: String,
+    /// Nombre WHATWG de la codificación detectada (p. ej. `UTF-8`, `windows-1252`)
+    pub encoding: &'static str,
+    pub truncated: bool,
+}
+
+/// Lee un archivo de la base de objetos en la revisión de `commit`
+///
+/// El tamaño se consulta en la cabecera del objeto antes de cargarlo, de
+/// modo que los archivos que superan `limit` se rechazan sin leerlos.
+/// Los symlinks devuelven su destino como contenido.
+///
+/// # Errors
+/// - `GitError::PathNotFound` si la ruta no existe en la revisión
+/// - `GitError::NotAFile` si la ruta es un directorio o un submódulo
+/// - `GitError::BlobTooLarge` si el archivo supera `limit` bytes
+pub fn read_blob(repo: &Repository, commit: &Commit, path: &str, limit: u64) -> Result<BlobContent> {
+    if path.is_empty() {
+        return Err(GitError::NotAFile(path.to_string()));
+    }
+
+    let tree = repo.find_tree(commit.tree_id())?;
+    let entry = match tree.get_path(Path::new(path)) {
+        Ok(entry) => entry,
+        Err(e) if e.code() == ErrorCode::NotFound => {
+            return Err(GitError::PathNotFound(path.to_string()))
+        }
+        Err(e) => return Err(e.into()),
+    };
+
+    if entry.kind() != Some(ObjectType::Blob) {
+        return Err(GitError::NotAFile(path.to_string()));
+    }
+
+    let (size, _) = repo.odb()?.read_header(entry.id())?;
+    let size = size as u64;
+    if size > limit {
+        return Err(GitError::BlobTooLarge { size, limit });
+    }
+
+    let blob = repo.find_blob(entry.id())?;
+
+    Ok(BlobContent {
+        oid: entry.id(),
+        mode: entry.filemode(),
+        data: blob.content().to_vec(),
+    })
+}
+
+/// Busca el README en la raíz del repositorio en la revisión de `commit`
+///
+/// Retorna la ruta y el contenido, o `None` si no hay README o supera `limit`.
+///
+/// # Errors
+/// - `GitError::Git` si falla la lectura de objetos
+pub fn find_readme(repo: &Repository, commit: &Commit, limit: u64) -> Result<Option<(String, BlobContent)>> {
+    let tree = repo.find_tree(commit.tree_id())?;
+    let names: Vec<String> = tree
+        .iter()
+        .filter(|e| e.kind() == Some(ObjectType::Blob))
+        .filter_map(|e| e.name().map(str::to_string))
+        .collect();
+
+    let Some(name) = pick_readme(names.iter().map(String::as_str)) else {
+        return Ok(None);
+    };
+
+    match read_blob(repo, commit, name, limit) {
+        Ok(blob) => Ok(Some((name.to_string(), blob))),
+        Err(GitError::BlobTooLarge { .. }) => Ok(None),
+        Err(e) => Err(e),
+    }
+}
+
+/// Detecta la codificación de un contenido de texto
+///
+/// Retorna `None` si el contenido parece binario. El orden de detección es:
+/// BOM, presencia de bytes nulos (binario), UTF-8 válido y, por último,
+/// la heurística de `chardetng` para codificaciones heredadas.
+pub fn detect_encoding(data: &[u8]) -> Option<&'static Encoding> {
+    if let Some((encoding, _)) = Encoding::for_bom(data) {
+        return Some(encoding);
+    }
+
+    let sniff = &data[..data.len().min(BINARY_SNIFF_LENGTH)];
+    if sniff.contains(&0) {
+        return None;
+    }
+
+    match std::str::from_utf8(data) {
+        Ok(_) => return Some(UTF_8),
+        // Secuencia incompleta al final: contenido UTF-8 truncado
+        Err(e) if e.error_len().is_none() => return Some(UTF_8),
+        Err(_) => {}
+    }
+
+    let mut detector = chardetng::EncodingDetector::new();
+    detector.feed(data, true);
+    Some(detector.guess(None, true))
+}
+
+/// Decodifica un contenido de texto, truncándolo a `max_bytes`
+///
+/// Retorna `None` si el contenido es binario. El corte nunca parte un
+/// carácter UTF-8 a la mitad.
+pub fn decode_text(data: &[u8], max_bytes: u64) -> Option<DecodedText> {
+    let encoding = detect_encoding(data)?;
+
+    let max_bytes = usize::try_from(max_bytes).unwrap_or(usize::MAX);
+    let truncated = data.len() > max_bytes;
+    let mut slice = &data[..data.len().min(max_bytes)];
+
+    if encoding == UTF_8 {
+        if let Err(e) = std::str::from_utf8(slice) {
+            if e.error_len().is_none() {
+                slice = &slice[..e.valid_up_to()];
+            }
+        }
+    }
+
+    let (content, _) = encoding.decode_with_bom_removal(slice);
+
+    Some(DecodedText {
+        content: content.into_owned(),
+        encoding: encoding.name(),
+        truncated,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::git::test_support::{commit_entries, commit_files, init_repo};
+
+    #[test]
+    fn test_read_blob() {
+        let (_dir, repo) = init_repo();
+        let oid = commit_entries(
+            &repo,
+            &[
+                ("src/main.rs", 0o100644, b"fn main() {}\n"),
+                ("link", 0o120000, b"src/main.rs"),
+            ],
+            "initial",
+        );
+        let commit = repo.find_commit(oid).unwrap();
+
+        let blob = read_blob(&repo, &commit, "src/main.rs", 1024).unwrap();
+        assert_eq!(blob.data, b"fn main() {}\n");
+        assert_eq!(blob.size(), 13);
+        assert!(!blob.is_symlink());
+
+        let link = read_blob(&repo, &commit, "link", 1024).unwrap();
+        assert!(link.is_symlink());
+        assert_eq!(link.data, b"src/main.rs");
+    }
+
+    #[test]
+    fn test_read_blob_errors() {
+        let (_dir, repo) = init_repo();
+        let oid = commit_files(&repo, &[("src/main.rs", b"fn main() {}\n")], "initial");
+        let commit = repo.find_commit(oid).unwrap();
+
+        assert!(matches!(
+            read_blob(&repo, &commit, "src", 1024),
+            Err(GitError::NotAFile(_))
+        ));
+        assert!(matches!(
+            read_blob(&repo, &commit, "missing.rs", 1024),
+            Err(GitError::PathNotFound(_))
+        ));
+        assert!(matches!(
+            read_blob(&repo, &commit, "src/main.rs", 4),
+            Err(GitError::BlobTooLarge { size: 13, limit: 4 })
+        ));
+    }
+
+    #[test]
+    fn test_find_readme() {
+        let (_dir, repo) = init_repo();
+        let oid = commit_files(
+            &repo,
+            &[("README", b"plain"), ("Readme.md", b"# Demo"), ("docs/README.md", b"no")],
+            "initial",
+        );
+        let commit = repo.find_commit(oid).unwrap();
+
+        let (path, blob) = find_readme(&repo, &commit, 1024).unwrap().unwrap();
+        assert_eq!(path, "Readme.md");
+        assert_eq!(blob.data, b"# Demo");
+
+        assert!(find_readme(&repo, &commit, 2).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_detect_encoding() {
+        assert_eq!(detect_encoding(b"hola mundo"), Some(UTF_8));
+        assert_eq!(detect_encoding("añadir".as_bytes()), Some(UTF_8));
+        assert_eq!(detect_encoding(b"\x89PNG\r\n\x1a\n\0\0\0\rIHDR"), None);
+        assert_eq!(
+            detect_encoding(b"\xff\xfeh\0o\0l\0a\0"),
+            Some(encoding_rs::UTF_16LE)
+        );
+        assert_eq!(
+            detect_encoding(b"Configuraci\xf3n del a\xf1o"),
+            Some(encoding_rs::WINDOWS_1252)
+        );
+    }
+
+    #[test]
+    fn test_decode_text_truncates_on_char_boundary() {
+        let decoded = decode_text("añadir".as_bytes(), 2).unwrap();
+        assert_eq!(decoded.content, "a");
+        assert!(decoded.truncated);
+
+        let full = decode_text(b"\xef\xbb\xbfhola", 1024).unwrap();
+        assert_eq!(full.content, "hola");
+        assert_eq!(full.encoding, "UTF-8");
+        assert!(!full.truncated);
+
+        assert!(decode_text(b"\0\x01\x02", 1024).is_none());
+    }
+}
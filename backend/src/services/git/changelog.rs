@@ -0,0 +1,130 @@
+use std::collections::HashMap;
+
+use chrono::{DateTime, Utc};
+use git2::{Oid, Repository, Sort};
+
+use super::{git_time_to_utc, list_tags, Result};
+
+/// Commits recorridos como máximo al buscar el tag anterior
+const MAX_TAG_SEARCH: usize = 100_000;
+
+/// Commit a incluir en un changelog
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChangelogCommit {
+    pub hash: String,
+    pub message: String,
+    pub author_name: String,
+    pub authored_at: DateTime<Utc>,
+}
+
+/// Busca el tag más cercano entre los ancestros de `to`, sin contar `to`
+///
+/// Sirve como origen por defecto del changelog de una versión. Si varios
+/// tags apuntan al mismo commit se usa el más reciente.
+///
+/// # Errors
+/// - `GitError::Git` si falla la lectura de referencias u objetos
+pub fn previous_tag(repo: &Repository, to: Oid) -> Result<Option<(String, Oid)>> {
+    let mut tagged: HashMap<Oid, String> = HashMap::new();
+    for tag in list_tags(repo)? {
+        if let Ok(oid) = Oid::from_str(&tag.commit.hash) {
+            tagged.entry(oid).or_insert(tag.name);
+        }
+    }
+    if tagged.is_empty() {
+        return Ok(None);
+    }
+
+    let mut revwalk = repo.revwalk()?;
+    revwalk.set_sorting(Sort::TOPOLOGICAL | Sort::TIME)?;
+    revwalk.push(to)?;
+
+    for oid in revwalk.take(MAX_TAG_SEARCH) {
+        let oid = oid?;
+        if oid == to {
+            continue;
+        }
+        if let Some(name) = tagged.remove(&oid) {
+            return Ok(Some((name, oid)));
+        }
+    }
+
+    Ok(None)
+}
+
+/// Commits alcanzables desde `to` pero no desde `from`, del más reciente al más antiguo
+///
+/// Omite los merges. Retorna como mucho `max` commits y si se truncó la lista.
+///
+/// # Errors
+/// - `GitError::Git` si falla el recorrido
+pub fn changelog_commits(
+    repo: &Repository,
+    to: Oid,
+    from: Option<Oid>,
+    max: usize,
+) -> Result<(Vec<ChangelogCommit>, bool)> {
+    let mut revwalk = repo.revwalk()?;
+    revwalk.set_sorting(Sort::TOPOLOGICAL | Sort::TIME)?;
+    revwalk.push(to)?;
+    if let Some(from) = from {
+        revwalk.hide(from)?;
+    }
+
+    let mut commits = Vec::new();
+    for oid in revwalk {
+        let commit = repo.find_commit(oid?)?;
+        if commit.parent_count() > 1 {
+            continue;
+        }
+        if commits.len() == max {
+            return Ok((commits, true));
+        }
+
+        let author = commit.author();
+        commits.push(ChangelogCommit {
+            hash: commit.id().to_string(),
+            message: String::from_utf8_lossy(commit.message_bytes()).into_owned(),
+            author_name: String::from_utf8_lossy(author.name_bytes()).into_owned(),
+            authored_at: git_time_to_utc(author.when()),
+        });
+    }
+
+    Ok((commits, false))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::git::test_support::{commit_files, init_repo};
+
+    #[test]
+    fn test_changelog_range() {
+        let (_dir, repo) = init_repo();
+        let first = commit_files(&repo, &[("a", b"1")], "chore: init");
+        let tagged = commit_files(&repo, &[("a", b"2")], "feat: one");
+        repo.tag_lightweight("v1.0.0", &repo.find_object(tagged, None).unwrap(), false)
+            .unwrap();
+        let fix = commit_files(&repo, &[("a", b"3")], "fix: two");
+        let head = commit_files(&repo, &[("a", b"4")], "feat: three");
+        repo.tag_lightweight("v1.1.0", &repo.find_object(head, None).unwrap(), false)
+            .unwrap();
+
+        // El tag que apunta a `to` no cuenta como origen
+        assert_eq!(
+            previous_tag(&repo, head).unwrap(),
+            Some(("v1.0.0".to_string(), tagged))
+        );
+        assert_eq!(previous_tag(&repo, first).unwrap(), None);
+
+        let (commits, truncated) = changelog_commits(&repo, head, Some(tagged), 10).unwrap();
+        let messages: Vec<_> = commits.iter().map(|c| c.message.as_str()).collect();
+        assert_eq!(messages, vec!["feat: three", "fix: two"]);
+        assert!(!truncated);
+        assert_eq!(commits[1].hash, fix.to_string());
+
+        let (commits, truncated) = changelog_commits(&repo, head, None, 3).unwrap();
+        assert_eq!(commits.len(), 3);
+        assert!(truncated);
+    }
+}
@@ -0,0 +1,267 @@
+use git2::{Commit, Repository};
+use globset::{GlobBuilder, GlobSet, GlobSetBuilder};
+
+use super::{read_blob, GitError, Result};
+
+/// Ubicaciones de `CODEOWNERS` en orden de precedencia (como en GitHub)
+pub const CODEOWNERS_PATHS: [&str; 3] = [".github/CODEOWNERS", "CODEOWNERS", "docs/CODEOWNERS"];
+
+/// Tamaño máximo de un archivo `CODEOWNERS`
+const MAX_CODEOWNERS_SIZE: u64 = 3 * 1024 * 1024;
+
+/// Regla de un archivo `CODEOWNERS`
+#[derive(Debug, Clone)]
+pub struct OwnerRule {
+    /// Línea del archivo (empezando en 1)
+    pub line: usize,
+    pub pattern: String,
+    /// `@usuario`, `@org/equipo` o email; vacío si la ruta queda sin dueño
+    pub owners: Vec<String>,
+    matcher: GlobSet,
+}
+
+/// Reglas de propiedad de un repositorio
+#[derive(Debug, Clone)]
+pub struct CodeOwners {
+    /// Ruta del archivo del que se leyeron las reglas
+    pub source: String,
+    rules: Vec<OwnerRule>,
+}
+
+impl CodeOwners {
+    /// Interpreta el contenido de un archivo `CODEOWNERS`
+    ///
+    /// Las líneas con patrones inválidos y las cabeceras de sección
+    /// (`[Sección]`, sintaxis de GitLab) se ignoran.
+    pub fn parse(source: &str, content: &str) -> Self {
+        let rules = content
+            .lines()
+            .enumerate()
+            .filter_map(|(i, line)| parse_line(i + 1, line))
+            .collect();
+
+        Self {
+            source: source.to_string(),
+            rules,
+        }
+    }
+
+    /// Regla que decide los dueños de `path`: la última que coincide
+    pub fn rule_for(&self, path: &str) -> Option<&OwnerRule> {
+        self.rules
+            .iter()
+            .rev()
+            .find(|rule| rule.matcher.is_match(path))
+    }
+
+    /// Dueños de `path`; vacío si ninguna regla coincide
+    pub fn owners_of(&self, path: &str) -> &[String] {
+        self.rule_for(path)
+            .map(|rule| rule.owners.as_slice())
+            .unwrap_or_default()
+    }
+
+    pub fn rules(&self) -> &[OwnerRule] {
+        &self.rules
+    }
+}
+
+/// Busca y lee el `CODEOWNERS` del repositorio en la revisión de `commit`
+///
+/// Se usa el primero que exista de `.github/`, la raíz y `docs/`. Retorna
+/// `None` si no hay ninguno o si supera el tamaño máximo.
+///
+/// # Errors
+/// - `GitError::Git` si falla la lectura de objetos
+pub fn find_codeowners(repo: &Repository, commit: &Commit) -> Result<Option<CodeOwners>> {
+    for path in CODEOWNERS_PATHS {
+        match read_blob(repo, commit, path, MAX_CODEOWNERS_SIZE) {
+            Ok(blob) => {
+                let content = String::from_utf8_lossy(&blob.data);
+                return Ok(Some(CodeOwners::parse(path, &content)));
+            }
+            Err(GitError::PathNotFound(_) | GitError::NotAFile(_)) => continue,
+            Err(GitError::BlobTooLarge { .. }) => {
+                tracing::warn!("{} supera el tamaño máximo; se ignora", path);
+                return Ok(None);
+            }
+            Err(e) => return Err(e),
+        }
+    }
+
+    Ok(None)
+}
+
+/// Interpreta una línea `patrón dueño1 dueño2...`
+fn parse_line(line: usize, text: &str) -> Option<OwnerRule> {
+    let text = text.trim();
+    if text.is_empty() || text.starts_with('#') || text.starts_with('[') || text.starts_with("^[") {
+        return None;
+    }
+
+    let mut tokens = text.split_whitespace();
+    let raw_pattern = tokens.next()?;
+    let pattern = raw_pattern
+        .strip_prefix('\\')
+        .filter(|p| p.starts_with('#'))
+        .unwrap_or(raw_pattern);
+    let owners = tokens
+        .take_while(|token| !token.starts_with('#'))
+        .filter(|token| is_owner(token))
+        .map(str::to_string)
+        .collect();
+
+    let matcher = pattern_matcher(pattern)?;
+
+    Some(OwnerRule {
+        line,
+        pattern: pattern.to_string(),
+        owners,
+        matcher,
+    })
+}
+
+/// Un dueño es `@usuario`, `@org/equipo` o un email
+pub fn is_owner(token: &str) -> bool {
+    match token.strip_prefix('@') {
+        Some(name) => !name.is_empty() && !name.starts_with('/') && !name.ends_with('/'),
+        None => token
+            .split_once('@')
+            .is_some_and(|(user, domain)| !user.is_empty() && domain.contains('.')),
+    }
+}
+
+/// Compila un patrón con la semántica de `.gitignore`
+///
+/// Sin `/` (salvo uno final) el patrón coincide a cualquier profundidad; con
+/// `/` se ancla a la raíz. Un `/` final limita el patrón a directorios y el
+/// resto coincide tanto con archivos como con todo lo que hay bajo un
+/// directorio del mismo nombre, salvo los que acaban en `*`, que como en
+/// GitHub no bajan a subdirectorios (`docs/*`).
+fn pattern_matcher(pattern: &str) -> Option<GlobSet> {
+    let (body, directory) = match pattern.strip_suffix('/') {
+        Some(body) => (body, true),
+        None => (pattern, false),
+    };
+    let anchored = body.contains('/');
+    let body = body.trim_start_matches('/');
+    if body.is_empty() {
+        return None;
+    }
+
+    let base = if anchored || body.starts_with("**") {
+        body.to_string()
+    } else {
+        format!("**/{}", body)
+    };
+
+    let mut globs = Vec::new();
+    if directory {
+        globs.push(format!("{}/**", base));
+    } else {
+        globs.push(base.clone());
+        if !base.ends_with('*') {
+            globs.push(format!("{}/**", base));
+        }
+    }
+
+    let mut builder = GlobSetBuilder::new();
+    for glob in globs {
+        builder.add(
+            GlobBuilder::new(&glob)
+                .literal_separator(true)
+                .build()
+                .ok()?,
+        );
+    }
+    builder.build().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::git::test_support::{commit_files, init_repo};
+
+    const CODEOWNERS: &str = "\
+# Dueños por defecto
+*       @global-owner1 @global-owner2
+
+*.js    @js-owner # comentario
+*.go    docs@example.com
+/build/logs/ @doctocat
+docs/*  docs@example.com
+apps/   @octocat
+/docs/  @doctocat
+/scripts/ @doctocat @octocat
+**/logs @octocat
+/apps/github
+\\#notes @hash-owner
+
+[Sección]
+/docs/generated/ not-an-owner
+";
+
+    #[test]
+    fn test_rule_precedence() {
+        let owners = CodeOwners::parse("CODEOWNERS", CODEOWNERS);
+        let of = |path| owners.owners_of(path).to_vec();
+
+        assert_eq!(of("README.md"), vec!["@global-owner1", "@global-owner2"]);
+        assert_eq!(of("web/app.js"), vec!["@js-owner"]);
+        assert_eq!(of("cmd/main.go"), vec!["docs@example.com"]);
+        assert_eq!(of("build/logs/out.txt"), vec!["@octocat"]);
+        assert_eq!(of("docs/build-app/troubleshooting.md"), vec!["@doctocat"]);
+        assert_eq!(of("scripts/deploy.sh"), vec!["@doctocat", "@octocat"]);
+        assert_eq!(of("deeply/nested/logs/a.txt"), vec!["@octocat"]);
+        assert_eq!(of("web/apps/index.html"), vec!["@octocat"]);
+        assert!(of("apps/github/main.rs").is_empty());
+        assert_eq!(of("#notes"), vec!["@hash-owner"]);
+
+        let rule = owners.rule_for("docs/generated/api.md").unwrap();
+        assert_eq!(rule.pattern, "/docs/generated/");
+        assert!(rule.owners.is_empty());
+    }
+
+    #[test]
+    fn test_star_does_not_descend() {
+        let owners = CodeOwners::parse("CODEOWNERS", "docs/* @docs\n");
+        assert_eq!(owners.owners_of("docs/index.md"), ["@docs"]);
+        assert!(owners.owners_of("docs/api/index.md").is_empty());
+        assert!(owners.owners_of("other/docs/index.md").is_empty());
+    }
+
+    #[test]
+    fn test_find_codeowners_precedence() {
+        let (_dir, repo) = init_repo();
+        commit_files(
+            &repo,
+            &[
+                ("CODEOWNERS", b"* @root"),
+                ("docs/CODEOWNERS", b"* @docs"),
+                ("src/main.rs", b"fn main() {}"),
+            ],
+            "root",
+        );
+        let head = repo.head().unwrap().peel_to_commit().unwrap();
+        let owners = find_codeowners(&repo, &head).unwrap().unwrap();
+        assert_eq!(owners.source, "CODEOWNERS");
+        assert_eq!(owners.owners_of("src/main.rs"), ["@root"]);
+
+        commit_files(
+            &repo,
+            &[
+                (".github/CODEOWNERS", b"*.rs @rust"),
+                ("CODEOWNERS", b"* @root"),
+            ],
+            "github",
+        );
+        let head = repo.head().unwrap().peel_to_commit().unwrap();
+        let owners = find_codeowners(&repo, &head).unwrap().unwrap();
+        assert_eq!(owners.source, ".github/CODEOWNERS");
+        assert_eq!(owners.rules().len(), 1);
+
+        commit_files(&repo, &[("README.md", b"hola")], "none");
+        let head = repo.head().unwrap().peel_to_commit().unwrap();
+        assert!(find_codeowners(&repo, &head).unwrap().is_none());
+    }
+}
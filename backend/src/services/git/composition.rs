@@ -0,0 +1,445 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+use git2::{Repository, Tree, TreeWalkMode, TreeWalkResult};
+use globset::{Glob, GlobBuilder, GlobMatcher, GlobSet, GlobSetBuilder};
+
+use super::{common_dir, Result};
+use crate::models::LanguageBytes;
+use crate::services::language::detect_language;
+
+/// Rutas de dependencias copiadas en el repositorio
+const VENDORED: &[&str] = &[
+    "**/node_modules/**",
+    "**/bower_components/**",
+    "**/vendor/**",
+    "**/vendors/**",
+    "**/third_party/**",
+    "**/third-party/**",
+    "**/3rdparty/**",
+    "**/Godeps/**",
+    "**/.yarn/**",
+    "**/dist/**",
+    "**/*.min.js",
+    "**/*.min.css",
+];
+
+/// Archivos generados por herramientas
+const GENERATED: &[&str] = &[
+    "**/Cargo.lock",
+    "**/package-lock.json",
+    "**/yarn.lock",
+    "**/pnpm-lock.yaml",
+    "**/poetry.lock",
+    "**/composer.lock",
+    "**/Gemfile.lock",
+    "**/go.sum",
+    "**/*.pb.go",
+    "**/*_pb2.py",
+    "**/*.generated.*",
+    "**/*.map",
+];
+
+/// Documentación
+const DOCUMENTATION: &[&str] = &[
+    "docs/**",
+    "doc/**",
+    "Documentation/**",
+    "**/README*",
+    "**/LICENSE*",
+    "**/COPYING*",
+    "**/CHANGELOG*",
+    "**/CONTRIBUTING*",
+];
+
+/// Tamaño máximo del `.gitattributes` que se interpreta
+const MAX_GITATTRIBUTES_SIZE: usize = 256 * 1024;
+
+/// Composición de un árbol: archivos, bytes y lenguajes
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TreeComposition {
+    /// Archivos contados (sin los excluidos)
+    pub total_files: u64,
+    pub total_bytes: u64,
+    /// Archivos vendorizados, generados o de documentación
+    pub excluded_files: u64,
+    /// De más a menos bytes; solo archivos con lenguaje detectado
+    pub languages: Vec<LanguageBytes>,
+}
+
+/// Uso de disco del directorio Git
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct StorageStats {
+    pub disk_size: u64,
+    pub pack_count: u64,
+    pub pack_size: u64,
+    pub loose_objects: u64,
+    pub loose_size: u64,
+}
+
+/// Regla de un `.gitattributes`; `None` deja el atributo como estaba
+#[derive(Debug, Clone)]
+struct AttributeRule {
+    matcher: GlobMatcher,
+    vendored: Option<bool>,
+    generated: Option<bool>,
+    documentation: Option<bool>,
+    language: Option<String>,
+}
+
+/// Reglas de exclusión y de lenguaje al estilo de linguist
+#[derive(Debug, Clone)]
+pub struct PathRules {
+    vendored: GlobSet,
+    generated: GlobSet,
+    documentation: GlobSet,
+    /// Reglas del `.gitattributes` raíz; las últimas tienen prioridad
+    attributes: Vec<AttributeRule>,
+}
+
+/// Clasificación de un archivo
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum PathClass {
+    Excluded,
+    Counted(Option<String>),
+}
+
+impl PathRules {
+    /// Reglas por defecto más los atributos `linguist-vendored`,
+    /// `linguist-generated`, `linguist-documentation` y `linguist-language`
+    /// de un `.gitattributes`
+    pub fn new(gitattributes: &str) -> Self {
+        Self {
+            vendored: glob_set(VENDORED),
+            generated: glob_set(GENERATED),
+            documentation: glob_set(DOCUMENTATION),
+            attributes: gitattributes
+                .lines()
+                .filter_map(parse_attribute_line)
+                .collect(),
+        }
+    }
+
+    fn classify(&self, path: &str) -> PathClass {
+        let mut vendored = self.vendored.is_match(path);
+        let mut generated = self.generated.is_match(path);
+        let mut documentation = self.documentation.is_match(path);
+        let mut language = None;
+
+        for rule in self.attributes.iter().filter(|r| r.matcher.is_match(path)) {
+            vendored = rule.vendored.unwrap_or(vendored);
+            generated = rule.generated.unwrap_or(generated);
+            documentation = rule.documentation.unwrap_or(documentation);
+            if rule.language.is_some() {
+                language = rule.language.clone();
+            }
+        }
+
+        if vendored || generated || documentation {
+            PathClass::Excluded
+        } else {
+            PathClass::Counted(language.or_else(|| detect_language(path).map(str::to_string)))
+        }
+    }
+}
+
+/// Calcula la composición de un árbol
+///
+/// Los tamaños salen de las cabeceras de los objetos, sin leer el contenido.
+/// Se ignoran enlaces simbólicos y submódulos. Las reglas salen del
+/// `.gitattributes` de la raíz del propio árbol.
+///
+/// # Errors
+/// - `GitError::Git` si falla la lectura de objetos
+pub fn analyze_tree(repo: &Repository, tree: &Tree) -> Result<TreeComposition> {
+    let gitattributes = match tree.get_name(".gitattributes") {
+        Some(entry) => {
+            let blob = repo.find_blob(entry.id())?;
+            let content = &blob.content()[..blob.size().min(MAX_GITATTRIBUTES_SIZE)];
+            String::from_utf8_lossy(content).into_owned()
+        }
+        None => String::new(),
+    };
+    let rules = PathRules::new(&gitattributes);
+    let odb = repo.odb()?;
+
+    let mut blobs = Vec::new();
+    tree.walk(TreeWalkMode::PreOrder, |root, entry| {
+        if entry.filemode() == 0o100644 || entry.filemode() == 0o100755 {
+            let name = String::from_utf8_lossy(entry.name_bytes());
+            blobs.push((format!("{}{}", root, name), entry.id()));
+        }
+        TreeWalkResult::Ok
+    })?;
+
+    let mut composition = TreeComposition::default();
+    let mut languages: HashMap<String, (u64, u64)> = HashMap::new();
+    for (path, oid) in blobs {
+        let language = match rules.classify(&path) {
+            PathClass::Excluded => {
+                composition.excluded_files += 1;
+                continue;
+            }
+            PathClass::Counted(language) => language,
+        };
+
+        let size = odb.read_header(oid)?.0 as u64;
+        composition.total_files += 1;
+        composition.total_bytes += size;
+        if let Some(language) = language {
+            let totals = languages.entry(language).or_default();
+            totals.0 += 1;
+            totals.1 += size;
+        }
+    }
+
+    composition.languages = languages
+        .into_iter()
+        .map(|(language, (files, bytes))| LanguageBytes {
+            language,
+            files: i32::try_from(files).unwrap_or(i32::MAX),
+            bytes: i64::try_from(bytes).unwrap_or(i64::MAX),
+        })
+        .collect();
+    composition.languages.sort_by(|a, b| {
+        b.bytes
+            .cmp(&a.bytes)
+            .then_with(|| a.language.cmp(&b.language))
+    });
+
+    Ok(composition)
+}
+
+/// Mide el directorio Git: tamaño total, packs y objetos sueltos
+///
+/// En un worktree enlazado se mide el directorio Git común, que es donde
+/// están los objetos.
+///
+/// # Errors
+/// - `GitError::Io` si no se puede recorrer el directorio
+pub fn storage_stats(repo: &Repository) -> Result<StorageStats> {
+    let git_dir = common_dir(repo);
+    let objects = git_dir.join("objects");
+    let mut stats = StorageStats {
+        disk_size: directory_size(&git_dir)?,
+        ..StorageStats::default()
+    };
+
+    if let Ok(entries) = fs::read_dir(objects.join("pack")) {
+        for entry in entries {
+            let entry = entry?;
+            if entry.path().extension().is_some_and(|ext| ext == "pack") {
+                stats.pack_count += 1;
+                stats.pack_size += entry.metadata()?.len();
+            }
+        }
+    }
+
+    if let Ok(entries) = fs::read_dir(&objects) {
+        for entry in entries {
+            let entry = entry?;
+            let name = entry.file_name();
+            let is_fanout = name.len() == 2
+                && name
+                    .to_str()
+                    .is_some_and(|n| n.bytes().all(|b| b.is_ascii_hexdigit()));
+            if !is_fanout || !entry.file_type()?.is_dir() {
+                continue;
+            }
+            for object in fs::read_dir(entry.path())? {
+                let metadata = object?.metadata()?;
+                if metadata.is_file() {
+                    stats.loose_objects += 1;
+                    stats.loose_size += metadata.len();
+                }
+            }
+        }
+    }
+
+    Ok(stats)
+}
+
+/// Suma el tamaño de los archivos de un directorio sin seguir enlaces simbólicos
+fn directory_size(path: &Path) -> Result<u64> {
+    let mut total = 0;
+    for entry in fs::read_dir(path)? {
+        let entry = entry?;
+        let file_type = entry.file_type()?;
+        if file_type.is_dir() {
+            total += directory_size(&entry.path())?;
+        } else if file_type.is_file() {
+            total += entry.metadata()?.len();
+        }
+    }
+    Ok(total)
+}
+
+fn glob_set(patterns: &[&str]) -> GlobSet {
+    let mut builder = GlobSetBuilder::new();
+    for pattern in patterns {
+        builder.add(Glob::new(pattern).expect("glob por defecto inválido"));
+    }
+    builder.build().expect("globs por defecto inválidos")
+}
+
+/// Interpreta una línea de `.gitattributes`; ignora las que no tocan linguist
+fn parse_attribute_line(line: &str) -> Option<AttributeRule> {
+    let line = line.trim();
+    if line.is_empty() || line.starts_with('#') {
+        return None;
+    }
+
+    let mut parts = line.split_whitespace();
+    let pattern = parts.next()?;
+    let mut rule = AttributeRule {
+        matcher: attribute_matcher(pattern)?,
+        vendored: None,
+        generated: None,
+        documentation: None,
+        language: None,
+    };
+
+    for attribute in parts {
+        let (name, value) = if let Some(name) = attribute.strip_prefix('-') {
+            (name, Some(false))
+        } else if let Some((name, value)) = attribute.split_once('=') {
+            match value {
+                "true" | "set" => (name, Some(true)),
+                "false" | "unset" => (name, Some(false)),
+                _ if name == "linguist-language" => {
+                    rule.language = Some(value.replace('-', " "));
+                    continue;
+                }
+                _ => continue,
+            }
+        } else {
+            (attribute, Some(true))
+        };
+
+        match name {
+            "linguist-vendored" => rule.vendored = value,
+            "linguist-generated" => rule.generated = value,
+            "linguist-documentation" => rule.documentation = value,
+            _ => {}
+        }
+    }
+
+    let touches_linguist = rule.vendored.is_some()
+        || rule.generated.is_some()
+        || rule.documentation.is_some()
+        || rule.language.is_some();
+    touches_linguist.then_some(rule)
+}
+
+/// Patrón de `.gitattributes`: sin `/` coincide a cualquier profundidad;
+/// con `/` se ancla a la raíz
+fn attribute_matcher(pattern: &str) -> Option<GlobMatcher> {
+    let glob = match pattern.trim_start_matches('/') {
+        p if !pattern.contains('/') => format!("**/{}", p),
+        p => p.to_string(),
+    };
+
+    GlobBuilder::new(&glob)
+        .literal_separator(true)
+        .build()
+        .ok()
+        .map(|g| g.compile_matcher())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::git::test_support::{commit_entries, init_repo};
+
+    #[test]
+    fn test_path_rules() {
+        let rules = PathRules::new(
+            "# comentario\n\
+             *.inc linguist-language=C++\n\
+             docs/api/*.md -linguist-documentation\n\
+             /generated/* linguist-generated\n\
+             src/vendor/** -linguist-vendored\n",
+        );
+
+        let class = |path| rules.classify(path);
+        assert_eq!(
+            class("src/main.rs"),
+            PathClass::Counted(Some("Rust".into()))
+        );
+        assert_eq!(class("web/node_modules/x/index.js"), PathClass::Excluded);
+        assert_eq!(class("Cargo.lock"), PathClass::Excluded);
+        assert_eq!(class("README.md"), PathClass::Excluded);
+        assert_eq!(class("docs/guide.md"), PathClass::Excluded);
+        assert_eq!(
+            class("docs/api/index.md"),
+            PathClass::Counted(Some("Markdown".into()))
+        );
+        assert_eq!(class("generated/schema.rs"), PathClass::Excluded);
+        assert_eq!(
+            class("src/generated/schema.rs"),
+            PathClass::Counted(Some("Rust".into()))
+        );
+        assert_eq!(
+            class("lib/tables.inc"),
+            PathClass::Counted(Some("C++".into()))
+        );
+        assert_eq!(
+            class("src/vendor/patched.c"),
+            PathClass::Counted(Some("C".into()))
+        );
+        assert_eq!(class("LICENSE-MIT"), PathClass::Excluded);
+        assert_eq!(class("data.bin"), PathClass::Counted(None));
+    }
+
+    #[test]
+    fn test_analyze_tree() {
+        let (_dir, repo) = init_repo();
+        commit_entries(
+            &repo,
+            &[
+                (".gitattributes", 0o100644, b"*.sql linguist-vendored\n"),
+                ("README.md", 0o100644, b"# Demo\n"),
+                ("src/main.rs", 0o100644, b"fn main() {}\n"),
+                ("src/lib.rs", 0o100644, b"pub fn a() {}\n"),
+                ("web/app.ts", 0o100644, b"export {}\n"),
+                ("db/init.sql", 0o100644, b"SELECT 1;\n"),
+                ("run.sh", 0o100755, b"#!/bin/sh\n"),
+                ("link", 0o120000, b"src/main.rs"),
+            ],
+            "init",
+        );
+        let tree = repo.head().unwrap().peel_to_tree().unwrap();
+
+        let composition = analyze_tree(&repo, &tree).unwrap();
+        assert_eq!(composition.total_files, 5);
+        assert_eq!(composition.excluded_files, 2);
+        assert_eq!(composition.total_bytes, 24 + 13 + 14 + 10 + 10);
+        assert_eq!(
+            composition.languages[0],
+            LanguageBytes {
+                language: "Rust".into(),
+                files: 2,
+                bytes: 27
+            }
+        );
+        let names: Vec<_> = composition
+            .languages
+            .iter()
+            .map(|l| l.language.as_str())
+            .collect();
+        assert_eq!(names, vec!["Rust", "Shell", "TypeScript"]);
+    }
+
+    #[test]
+    fn test_storage_stats() {
+        let (_dir, repo) = init_repo();
+        commit_entries(&repo, &[("a.txt", 0o100644, b"hola\n")], "init");
+
+        let stats = storage_stats(&repo).unwrap();
+        assert_eq!(stats.pack_count, 0);
+        // Blob, árbol y commit
+        assert_eq!(stats.loose_objects, 3);
+        assert!(stats.disk_size >= stats.loose_size && stats.loose_size > 0);
+    }
+}
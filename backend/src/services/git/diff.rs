@@ -0,0 +1,518 @@
+use git2::{
+    Commit, Delta, Diff, DiffFindOptions, DiffLineType, DiffOptions, Oid, Patch, Repository, Sort,
+    Tree,
+};
+
+use super::{commit_summary, Result};
+use crate::models::{
+    CommitSummary, DiffFormat, DiffHunk, DiffLine, DiffLineOrigin, DiffResult, DiffStats, FileDiff,
+    FileStatus,
+};
+
+/// Líneas de contexto alrededor de cada cambio
+const CONTEXT_LINES: u32 = 3;
+
+/// Límite de pares de archivos evaluados para detectar renombrados
+const RENAME_LIMIT: usize = 1000;
+
+/// Límites para evitar respuestas enormes en diffs grandes
+#[derive(Debug, Clone, Copy)]
+pub struct DiffLimits {
+    /// Archivos con detalle incluidos en la respuesta
+    pub max_files: usize,
+    /// Líneas de diff (contexto incluido) sumadas entre todos los archivos
+    pub max_lines: usize,
+}
+
+/// Resultado de comparar dos revisiones
+#[derive(Debug, Clone)]
+pub struct Comparison {
+    pub merge_base: Option<Oid>,
+    pub ahead_by: usize,
+    pub behind_by: usize,
+    pub commits: Vec<CommitSummary>,
+    pub diff: DiffResult,
+}
+
+/// Calcula el diff de un commit contra su primer padre
+///
+/// El commit raíz se compara contra el árbol vacío. Para los merges se usa
+/// el primer padre, como en la vista de commits de los hosts de código.
+///
+/// # Errors
+/// - `GitError::Git` si falla la lectura de objetos o el cálculo del diff
+pub fn diff_commit(
+    repo: &Repository,
+    commit: &Commit,
+    format: DiffFormat,
+    limits: DiffLimits,
+) -> Result<DiffResult> {
+    let new_tree = commit.tree()?;
+    let old_tree = match commit.parent_count() {
+        0 => None,
+        _ => Some(commit.parent(0)?.tree()?),
+    };
+
+    diff_trees(repo, old_tree.as_ref(), &new_tree, format, limits)
+}
+
+/// Cuenta las líneas añadidas y eliminadas por un commit
+///
+/// Se compara contra el primer padre (o el árbol vacío en el commit raíz)
+/// con detección de renombrados, sin generar el parche. Los merges retornan
+/// `None`: sus cambios ya se cuentan en los commits que integran.
+///
+/// # Errors
+/// - `GitError::Git` si falla la lectura de objetos o el cálculo del diff
+pub fn commit_line_stats(repo: &Repository, commit: &Commit) -> Result<Option<(usize, usize)>> {
+    let old_tree = match commit.parent_count() {
+        0 => None,
+        1 => Some(commit.parent(0)?.tree()?),
+        _ => return Ok(None),
+    };
+
+    let mut diff = repo.diff_tree_to_tree(old_tree.as_ref(), Some(&commit.tree()?), None)?;
+    let mut find = DiffFindOptions::new();
+    find.renames(true).rename_limit(RENAME_LIMIT);
+    diff.find_similar(Some(&mut find))?;
+
+    let stats = diff.stats()?;
+    Ok(Some((stats.insertions(), stats.deletions())))
+}
+
+/// Lista las rutas que toca un commit respecto a su primer padre
+///
+/// No se detectan renombrados, así que un archivo movido aporta la ruta
+/// antigua y la nueva. No se genera el parche.
+///
+/// # Errors
+/// - `GitError::Git` si falla la lectura de objetos o el cálculo del diff
+pub fn changed_paths(repo: &Repository, commit: &Commit) -> Result<Vec<String>> {
+    let old_tree = match commit.parent_count() {
+        0 => None,
+        _ => Some(commit.parent(0)?.tree()?),
+    };
+    let diff = repo.diff_tree_to_tree(old_tree.as_ref(), Some(&commit.tree()?), None)?;
+
+    let mut paths: Vec<String> = diff
+        .deltas()
+        .flat_map(|delta| [delta.old_file().path(), delta.new_file().path()])
+        .flatten()
+        .map(|path| path.to_string_lossy().into_owned())
+        .collect();
+    paths.sort();
+    paths.dedup();
+
+    Ok(paths)
+}
+
+/// Compara dos revisiones
+///
+/// Por defecto el diff parte del ancestro común (`base...head`), de modo
+/// que solo muestra lo introducido en `head`; con `straight` se compara
+/// directamente el árbol de `base` con el de `head`. La lista de commits
+/// corresponde a `base..head` y se corta en `max_commits`.
+///
+/// # Errors
+/// - `GitError::Git` si falla la lectura de objetos o el cálculo del diff
+pub fn compare(
+    repo: &Repository,
+    base: &Commit,
+    head: &Commit,
+    straight: bool,
+    format: DiffFormat,
+    limits: DiffLimits,
+    max_commits: usize,
+) -> Result<Comparison> {
+    let merge_base = match repo.merge_base(base.id(), head.id()) {
+        Ok(oid) => Some(oid),
+        Err(e) if e.code() == git2::ErrorCode::NotFound => None,
+        Err(e) => return Err(e.into()),
+    };
+    let (ahead_by, behind_by) = repo.graph_ahead_behind(head.id(), base.id())?;
+
+    let mut revwalk = repo.revwalk()?;
+    revwalk.push(head.id())?;
+    revwalk.hide(base.id())?;
+    revwalk.set_sorting(Sort::TOPOLOGICAL | Sort::TIME)?;
+    let commits = revwalk
+        .take(max_commits)
+        .map(|oid| Ok(commit_summary(&repo.find_commit(oid?)?)))
+        .collect::<Result<Vec<_>>>()?;
+
+    let old_tree = match (straight, merge_base) {
+        (false, Some(oid)) => repo.find_commit(oid)?.tree()?,
+        _ => base.tree()?,
+    };
+    let diff = diff_trees(repo, Some(&old_tree), &head.tree()?, format, limits)?;
+
+    Ok(Comparison {
+        merge_base,
+        ahead_by,
+        behind_by,
+        commits,
+        diff,
+    })
+}
+
+/// Calcula el diff entre dos árboles con detección de renombrados
+///
+/// Los totales de `stats` siempre cubren el diff completo. El detalle se
+/// limita a `max_files` archivos y a `max_lines` líneas en total; los
+/// archivos que no caben se devuelven con sus contadores pero sin hunks ni
+/// parche y marcados como `truncated`. Los binarios nunca incluyen contenido.
+///
+/// # Errors
+/// - `GitError::Git` si falla el cálculo del diff
+pub fn diff_trees(
+    repo: &Repository,
+    old: Option<&Tree>,
+    new: &Tree,
+    format: DiffFormat,
+    limits: DiffLimits,
+) -> Result<DiffResult> {
+    let mut options = DiffOptions::new();
+    options.context_lines(CONTEXT_LINES);
+    let diff = repo.diff_tree_to_tree(old, Some(new), Some(&mut options))?;
+
+    diff_result(diff, format, limits)
+}
+
+/// Calcula el diff del working copy (índice incluido) contra `HEAD`
+///
+/// Equivale a `git diff HEAD`: los archivos sin seguimiento no aparecen.
+/// En un repositorio sin commits se compara contra el árbol vacío. Aplica
+/// los mismos límites que [`diff_trees`].
+///
+/// # Errors
+/// - `GitError::Git` si falla la lectura del índice, del working copy o el cálculo del diff
+pub fn diff_workdir(repo: &Repository, format: DiffFormat, limits: DiffLimits) -> Result<DiffResult> {
+    let head_tree = match repo.head() {
+        Ok(head) => Some(head.peel_to_tree()?),
+        Err(e) if e.code() == git2::ErrorCode::UnbornBranch => None,
+        Err(e) => return Err(e.into()),
+    };
+
+    let mut options = DiffOptions::new();
+    options
+        .context_lines(CONTEXT_LINES)
+        .ignore_submodules(true);
+    let diff = repo.diff_tree_to_workdir_with_index(head_tree.as_ref(), Some(&mut options))?;
+
+    diff_result(diff, format, limits)
+}
+
+/// Detecta renombrados y convierte un diff de libgit2 al modelo de la API
+fn diff_result(mut diff: Diff, format: DiffFormat, limits: DiffLimits) -> Result<DiffResult> {
+    let mut find = DiffFindOptions::new();
+    find.renames(true).rename_limit(RENAME_LIMIT);
+    diff.find_similar(Some(&mut find))?;
+
+    let git_stats = diff.stats()?;
+    let stats = DiffStats {
+        files_changed: git_stats.files_changed(),
+        additions: git_stats.insertions(),
+        deletions: git_stats.deletions(),
+    };
+
+    let total_files = diff.deltas().len();
+    let mut truncated = total_files > limits.max_files;
+    let mut remaining_lines = limits.max_lines;
+    let mut files = Vec::with_capacity(total_files.min(limits.max_files));
+
+    for idx in 0..total_files.min(limits.max_files) {
+        let Some(mut patch) = Patch::from_diff(&diff, idx)? else {
+            continue;
+        };
+
+        let delta = patch.delta();
+        let binary = delta.flags().is_binary();
+        let (context, additions, deletions) = patch.line_stats()?;
+        let line_count = context + additions + deletions;
+
+        let fits = !binary && line_count <= remaining_lines;
+        if fits {
+            remaining_lines -= line_count;
+        } else if !binary {
+            truncated = true;
+        }
+
+        let status = file_status(delta.status());
+        let new_path = delta
+            .new_file()
+            .path()
+            .map(|p| p.to_string_lossy().into_owned());
+        let old_path = delta
+            .old_file()
+            .path()
+            .map(|p| p.to_string_lossy().into_owned());
+        let mode = |file: git2::DiffFile| {
+            file.exists()
+                .then(|| format!("{:06o}", u32::from(file.mode())))
+        };
+
+        let mut file = FileDiff {
+            path: new_path
+                .clone()
+                .or_else(|| old_path.clone())
+                .unwrap_or_default(),
+            old_path: match status {
+                FileStatus::Renamed | FileStatus::Copied => old_path,
+                _ => None,
+            },
+            status,
+            old_mode: mode(delta.old_file()),
+            new_mode: mode(delta.new_file()),
+            binary,
+            additions,
+            deletions,
+            truncated: !binary && !fits,
+            hunks: None,
+            patch: None,
+        };
+
+        if fits {
+            match format {
+                DiffFormat::Structured => file.hunks = Some(structured_hunks(&patch)?),
+                DiffFormat::Unified => {
+                    let buf = patch.to_buf()?;
+                    file.patch = Some(String::from_utf8_lossy(&buf).into_owned());
+                }
+            }
+        }
+
+        files.push(file);
+    }
+
+    Ok(DiffResult {
+        stats,
+        files,
+        truncated,
+    })
+}
+
+/// Convierte los hunks de un parche a su forma estructurada
+///
+/// Se omiten los marcadores de "sin salto de línea al final".
+fn structured_hunks(patch: &Patch) -> Result<Vec<DiffHunk>> {
+    let mut hunks = Vec::with_capacity(patch.num_hunks());
+
+    for hunk_idx in 0..patch.num_hunks() {
+        let (hunk, line_count) = patch.hunk(hunk_idx)?;
+        let mut lines = Vec::with_capacity(line_count);
+
+        for line_idx in 0..line_count {
+            let line = patch.line_in_hunk(hunk_idx, line_idx)?;
+            let origin = match line.origin_value() {
+                DiffLineType::Context => DiffLineOrigin::Context,
+                DiffLineType::Addition => DiffLineOrigin::Addition,
+                DiffLineType::Deletion => DiffLineOrigin::Deletion,
+                _ => continue,
+            };
+
+            let content = String::from_utf8_lossy(line.content());
+            lines.push(DiffLine {
+                origin,
+                old_lineno: line.old_lineno(),
+                new_lineno: line.new_lineno(),
+                content: content.strip_suffix('\n').unwrap_or(&content).to_string(),
+            });
+        }
+
+        let header = String::from_utf8_lossy(hunk.header());
+        hunks.push(DiffHunk {
+            header: header.trim_end().to_string(),
+            old_start: hunk.old_start(),
+            old_lines: hunk.old_lines(),
+            new_start: hunk.new_start(),
+            new_lines: hunk.new_lines(),
+            lines,
+        });
+    }
+
+    Ok(hunks)
+}
+
+pub(crate) fn file_status(delta: Delta) -> FileStatus {
+    match delta {
+        Delta::Added => FileStatus::Added,
+        Delta::Deleted => FileStatus::Deleted,
+        Delta::Renamed => FileStatus::Renamed,
+        Delta::Copied => FileStatus::Copied,
+        Delta::Typechange => FileStatus::TypeChange,
+        _ => FileStatus::Modified,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::git::test_support::{commit_files, init_repo};
+
+    const LIMITS: DiffLimits = DiffLimits {
+        max_files: 100,
+        max_lines: 10_000,
+    };
+
+    const LOREM: &[u8] = b"linea 1\nlinea 2\nlinea 3\nlinea 4\nlinea 5\nlinea 6\nlinea 7\n";
+
+    #[test]
+    fn test_diff_root_commit() {
+        let (_dir, repo) = init_repo();
+        let oid = commit_files(&repo, &[("a.txt", b"uno\ndos\n")], "initial");
+        let commit = repo.find_commit(oid).unwrap();
+
+        let diff = diff_commit(&repo, &commit, DiffFormat::Structured, LIMITS).unwrap();
+        assert_eq!(
+            diff.stats,
+            DiffStats {
+                files_changed: 1,
+                additions: 2,
+                deletions: 0
+            }
+        );
+        assert_eq!(diff.files[0].status, FileStatus::Added);
+        assert_eq!(diff.files[0].old_mode, None);
+        assert_eq!(diff.files[0].new_mode.as_deref(), Some("100644"));
+
+        let hunk = &diff.files[0].hunks.as_ref().unwrap()[0];
+        assert_eq!(hunk.header, "@@ -0,0 +1,2 @@");
+        assert_eq!(hunk.lines[1].content, "dos");
+        assert_eq!(hunk.lines[1].new_lineno, Some(2));
+    }
+
+    #[test]
+    fn test_diff_detects_renames_and_binary() {
+        let (_dir, repo) = init_repo();
+        commit_files(
+            &repo,
+            &[("old.txt", LOREM), ("logo.png", b"\x89PNG\0\x01")],
+            "initial",
+        );
+        let oid = commit_files(
+            &repo,
+            &[("new.txt", LOREM), ("logo.png", b"\x89PNG\0\x02")],
+            "rename",
+        );
+        let commit = repo.find_commit(oid).unwrap();
+
+        let diff = diff_commit(&repo, &commit, DiffFormat::Unified, LIMITS).unwrap();
+        let renamed = diff.files.iter().find(|f| f.path == "new.txt").unwrap();
+        assert_eq!(renamed.status, FileStatus::Renamed);
+        assert_eq!(renamed.old_path.as_deref(), Some("old.txt"));
+
+        let binary = diff.files.iter().find(|f| f.path == "logo.png").unwrap();
+        assert!(binary.binary);
+        assert!(binary.patch.is_none());
+        assert!(!binary.truncated);
+    }
+
+    #[test]
+    fn test_diff_limits() {
+        let (_dir, repo) = init_repo();
+        commit_files(&repo, &[("a.txt", b"a\n"), ("b.txt", b"b\n")], "initial");
+        let oid = commit_files(&repo, &[("a.txt", b"a2\n"), ("b.txt", b"b2\n")], "edit");
+        let commit = repo.find_commit(oid).unwrap();
+
+        let few_lines = DiffLimits {
+            max_files: 100,
+            max_lines: 2,
+        };
+        let diff = diff_commit(&repo, &commit, DiffFormat::Structured, few_lines).unwrap();
+        assert!(diff.truncated);
+        assert!(diff.files[0].hunks.is_some());
+        assert!(diff.files[1].truncated);
+        assert!(diff.files[1].hunks.is_none());
+        assert_eq!(diff.files[1].additions, 1);
+
+        let one_file = DiffLimits {
+            max_files: 1,
+            max_lines: 10_000,
+        };
+        let diff = diff_commit(&repo, &commit, DiffFormat::Structured, one_file).unwrap();
+        assert!(diff.truncated);
+        assert_eq!(diff.files.len(), 1);
+        assert_eq!(diff.stats.files_changed, 2);
+    }
+
+    #[test]
+    fn test_compare_uses_merge_base() {
+        let (_dir, repo) = init_repo();
+        let base = commit_files(&repo, &[("a.txt", b"a\n")], "base");
+        let main_tip = commit_files(
+            &repo,
+            &[("a.txt", b"a\n"), ("main.txt", b"m\n")],
+            "main work",
+        );
+
+        let base_commit = repo.find_commit(base).unwrap();
+        repo.branch("feature", &base_commit, false).unwrap();
+        repo.set_head("refs/heads/feature").unwrap();
+        let feature_tip = commit_files(
+            &repo,
+            &[("a.txt", b"a\n"), ("feature.txt", b"f\n")],
+            "feature work",
+        );
+
+        let main = repo.find_commit(main_tip).unwrap();
+        let feature = repo.find_commit(feature_tip).unwrap();
+
+        let three_dot = compare(
+            &repo,
+            &main,
+            &feature,
+            false,
+            DiffFormat::Structured,
+            LIMITS,
+            10,
+        )
+        .unwrap();
+        assert_eq!(three_dot.merge_base, Some(base));
+        assert_eq!((three_dot.ahead_by, three_dot.behind_by), (1, 1));
+        assert_eq!(three_dot.commits.len(), 1);
+        assert_eq!(three_dot.commits[0].summary, "feature work");
+        assert_eq!(three_dot.diff.files.len(), 1);
+        assert_eq!(three_dot.diff.files[0].path, "feature.txt");
+
+        let straight = compare(
+            &repo,
+            &main,
+            &feature,
+            true,
+            DiffFormat::Structured,
+            LIMITS,
+            10,
+        )
+        .unwrap();
+        assert_eq!(straight.diff.files.len(), 2);
+    }
+
+    #[test]
+    fn test_commit_line_stats() {
+        let (_dir, repo) = init_repo();
+        let root = commit_files(&repo, &[("a.txt", b"uno\ndos\n")], "root");
+        let second = commit_files(&repo, &[("a.txt", b"uno\ntres\ncuatro\n")], "second");
+
+        let stats = |oid| commit_line_stats(&repo, &repo.find_commit(oid).unwrap()).unwrap();
+        assert_eq!(stats(root), Some((2, 0)));
+        assert_eq!(stats(second), Some((2, 1)));
+
+        let first = repo.find_commit(root).unwrap();
+        let head = repo.find_commit(second).unwrap();
+        let signature = head.author();
+        let merge = repo
+            .commit(None, &signature, &signature, "merge", &head.tree().unwrap(), &[&head, &first])
+            .unwrap();
+        assert_eq!(stats(merge), None);
+    }
+
+    #[test]
+    fn test_changed_paths() {
+        let (_dir, repo) = init_repo();
+        commit_files(&repo, &[("a.txt", LOREM), ("src/b.rs", b"b")], "root");
+        let oid = commit_files(&repo, &[("c.txt", LOREM), ("src/b.rs", b"bb")], "move");
+
+        let paths = changed_paths(&repo, &repo.find_commit(oid).unwrap()).unwrap();
+        assert_eq!(paths, vec!["a.txt", "c.txt", "src/b.rs"]);
+    }
+}
@@ -0,0 +1,310 @@
+use std::time::Instant;
+
+use git2::{ObjectType, Repository, Tree, TreeWalkMode, TreeWalkResult};
+use globset::{Glob, GlobSet, GlobSetBuilder};
+use regex::Regex;
+
+use super::{GitError, Result};
+use crate::models::{CodeMatch, CodeSearchSummary, MatchRange};
+
+/// Caracteres máximos devueltos por línea; evita respuestas enormes con
+/// archivos minificados
+const MAX_LINE_CHARS: usize = 500;
+
+/// Filtro de rutas a partir de globs separados por comas
+///
+/// Los globs con `!` delante excluyen. `*` también cruza directorios, así
+/// que `*.rs` coincide en cualquier nivel; `src/**` limita a un directorio.
+#[derive(Debug, Clone)]
+pub struct PathFilter {
+    include: Option<GlobSet>,
+    exclude: Option<GlobSet>,
+}
+
+impl PathFilter {
+    /// Construye el filtro; una cadena vacía acepta todas las rutas
+    ///
+    /// # Errors
+    /// - `GitError::InvalidPath` si algún glob es inválido
+    pub fn parse(globs: &str) -> Result<Self> {
+        let mut include = GlobSetBuilder::new();
+        let mut exclude = GlobSetBuilder::new();
+        let (mut has_include, mut has_exclude) = (false, false);
+
+        for glob in globs.split(',').map(str::trim).filter(|g| !g.is_empty()) {
+            let (builder, pattern) = match glob.strip_prefix('!') {
+                Some(pattern) => {
+                    has_exclude = true;
+                    (&mut exclude, pattern)
+                }
+                None => {
+                    has_include = true;
+                    (&mut include, glob)
+                }
+            };
+            builder.add(Glob::new(pattern).map_err(|e| GitError::InvalidPath(e.to_string()))?);
+        }
+
+        let build = |builder: GlobSetBuilder| {
+            builder
+                .build()
+                .map_err(|e| GitError::InvalidPath(e.to_string()))
+        };
+
+        Ok(Self {
+            include: has_include.then(|| build(include)).transpose()?,
+            exclude: has_exclude.then(|| build(exclude)).transpose()?,
+        })
+    }
+
+    pub fn matches(&self, path: &str) -> bool {
+        self.include.as_ref().is_none_or(|set| set.is_match(path))
+            && !self.exclude.as_ref().is_some_and(|set| set.is_match(path))
+    }
+}
+
+/// Límites de una búsqueda en el contenido de un árbol
+#[derive(Debug, Clone)]
+pub struct GrepOptions<'a> {
+    pub pattern: &'a Regex,
+    pub paths: &'a PathFilter,
+    /// Líneas coincidentes máximas; al alcanzarlas la búsqueda se detiene
+    pub max_matches: usize,
+    /// Los archivos mayores se omiten
+    pub max_file_size: u64,
+    /// Momento a partir del cual no se abren más archivos
+    pub deadline: Instant,
+}
+
+/// Busca `pattern` línea a línea en los archivos de texto de un árbol
+///
+/// Recorre el árbol en orden y entrega cada línea coincidente a `on_match`
+/// en cuanto la encuentra; si `on_match` retorna `false` (p. ej. el cliente
+/// se desconectó) la búsqueda se detiene. Se omiten binarios, enlaces
+/// simbólicos, submódulos y archivos mayores que `max_file_size`.
+///
+/// # Errors
+/// - `GitError::Git` si falla la lectura de objetos
+pub fn grep_tree(
+    repo: &Repository,
+    tree: &Tree,
+    options: &GrepOptions,
+    mut on_match: impl FnMut(CodeMatch) -> bool,
+) -> Result<CodeSearchSummary> {
+    let started = Instant::now();
+    let mut summary = CodeSearchSummary::default();
+    let mut error = None;
+
+    tree.walk(TreeWalkMode::PreOrder, |root, entry| {
+        if entry.kind() != Some(ObjectType::Blob) || entry.filemode() == 0o120000 {
+            return TreeWalkResult::Ok;
+        }
+        let Some(name) = entry.name() else {
+            return TreeWalkResult::Ok;
+        };
+        let path = format!("{}{}", root, name);
+        if !options.paths.matches(&path) {
+            return TreeWalkResult::Ok;
+        }
+        if Instant::now() >= options.deadline {
+            summary.timed_out = true;
+            return TreeWalkResult::Abort;
+        }
+
+        let blob = match repo.find_blob(entry.id()) {
+            Ok(blob) => blob,
+            Err(e) => {
+                error = Some(e);
+                return TreeWalkResult::Abort;
+            }
+        };
+        if blob.size() as u64 > options.max_file_size || blob.is_binary() {
+            return TreeWalkResult::Ok;
+        }
+        summary.files_scanned += 1;
+
+        let content = String::from_utf8_lossy(blob.content());
+        let mut file_matched = false;
+        for (index, line) in content.lines().enumerate() {
+            let Some(found) = match_line(options.pattern, &path, index + 1, line) else {
+                continue;
+            };
+            if !file_matched {
+                file_matched = true;
+                summary.files_matched += 1;
+            }
+            summary.matches += 1;
+
+            if !on_match(found) {
+                return TreeWalkResult::Abort;
+            }
+            if summary.matches >= options.max_matches {
+                summary.truncated = true;
+                return TreeWalkResult::Abort;
+            }
+        }
+
+        TreeWalkResult::Ok
+    })
+    .or_else(|e| {
+        // Abortar el recorrido desde el callback se reporta como error
+        if e.code() == git2::ErrorCode::User {
+            Ok(())
+        } else {
+            Err(e)
+        }
+    })?;
+
+    if let Some(e) = error {
+        return Err(e.into());
+    }
+
+    summary.elapsed_ms = u64::try_from(started.elapsed().as_millis()).unwrap_or(u64::MAX);
+    Ok(summary)
+}
+
+/// Retorna la coincidencia de una línea, con los rangos en caracteres
+fn match_line(pattern: &Regex, path: &str, line_number: usize, line: &str) -> Option<CodeMatch> {
+    let mut ranges = Vec::new();
+    for found in pattern.find_iter(line) {
+        if found.start() == found.end() {
+            continue;
+        }
+        let start = line[..found.start()].chars().count();
+        if start >= MAX_LINE_CHARS {
+            break;
+        }
+        let end = start + found.as_str().chars().count();
+        ranges.push(MatchRange {
+            start,
+            end: end.min(MAX_LINE_CHARS),
+        });
+    }
+
+    if ranges.is_empty() {
+        return None;
+    }
+
+    Some(CodeMatch {
+        path: path.to_string(),
+        line_number,
+        line: line.chars().take(MAX_LINE_CHARS).collect(),
+        ranges,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::git::test_support::{commit_entries, commit_files, init_repo};
+    use regex::RegexBuilder;
+    use std::time::Duration;
+
+    fn grep(
+        repo: &Repository,
+        pattern: &str,
+        paths: &str,
+        max_matches: usize,
+    ) -> (Vec<CodeMatch>, CodeSearchSummary) {
+        let pattern = RegexBuilder::new(pattern)
+            .case_insensitive(true)
+            .build()
+            .unwrap();
+        let paths = PathFilter::parse(paths).unwrap();
+        let tree = repo.head().unwrap().peel_to_tree().unwrap();
+        let options = GrepOptions {
+            pattern: &pattern,
+            paths: &paths,
+            max_matches,
+            max_file_size: 1024,
+            deadline: Instant::now() + Duration::from_secs(10),
+        };
+
+        let mut matches = Vec::new();
+        let summary = grep_tree(repo, &tree, &options, |m| {
+            matches.push(m);
+            true
+        })
+        .unwrap();
+        (matches, summary)
+    }
+
+    #[test]
+    fn test_grep_tree() {
+        let (_dir, repo) = init_repo();
+        commit_entries(
+            &repo,
+            &[
+                (
+                    "src/main.rs",
+                    0o100644,
+                    b"fn main() {\n    // TODO: logging\n}\n",
+                ),
+                ("src/lib.rs", 0o100644, "// todo añadir tests\n".as_bytes()),
+                ("docs/notes.md", 0o100644, b"todo list\n"),
+                ("bin/tool", 0o100644, b"todo\0binary"),
+                ("link", 0o120000, b"src/main.rs"),
+                ("big.txt", 0o100644, &[b'x'; 2048]),
+            ],
+            "init",
+        );
+
+        let (matches, summary) = grep(&repo, "todo", "", 100);
+        let paths: Vec<_> = matches.iter().map(|m| m.path.as_str()).collect();
+        assert_eq!(paths, vec!["docs/notes.md", "src/lib.rs", "src/main.rs"]);
+        assert_eq!(summary.files_scanned, 3);
+        assert_eq!(summary.files_matched, 3);
+        assert!(!summary.truncated && !summary.timed_out);
+
+        let main = &matches[2];
+        assert_eq!(main.line_number, 2);
+        assert_eq!(main.ranges, vec![MatchRange { start: 7, end: 11 }]);
+
+        let (matches, _) = grep(&repo, "todo", "src/**,!**/lib.rs", 100);
+        assert_eq!(matches.len(), 1);
+        assert_eq!(matches[0].path, "src/main.rs");
+
+        let (matches, _) = grep(&repo, "añadir", "*.rs", 100);
+        assert_eq!(matches[0].ranges, vec![MatchRange { start: 8, end: 14 }]);
+    }
+
+    #[test]
+    fn test_grep_limits() {
+        let (_dir, repo) = init_repo();
+        commit_files(&repo, &[("a.txt", b"match\nmatch\nmatch\n")], "init");
+
+        let (matches, summary) = grep(&repo, "match", "", 2);
+        assert_eq!(matches.len(), 2);
+        assert!(summary.truncated);
+
+        let pattern = Regex::new("match").unwrap();
+        let paths = PathFilter::parse("").unwrap();
+        let tree = repo.head().unwrap().peel_to_tree().unwrap();
+        let options = GrepOptions {
+            pattern: &pattern,
+            paths: &paths,
+            max_matches: 100,
+            max_file_size: 1024,
+            deadline: Instant::now(),
+        };
+        let summary = grep_tree(&repo, &tree, &options, |_| true).unwrap();
+        assert!(summary.timed_out);
+        assert_eq!(summary.matches, 0);
+
+        // El consumidor puede detener la búsqueda
+        let options = GrepOptions {
+            deadline: Instant::now() + Duration::from_secs(10),
+            ..options
+        };
+        let summary = grep_tree(&repo, &tree, &options, |_| false).unwrap();
+        assert_eq!(summary.matches, 1);
+    }
+
+    #[test]
+    fn test_invalid_glob() {
+        assert!(matches!(
+            PathFilter::parse("src/[a"),
+            Err(GitError::InvalidPath(_))
+        ));
+    }
+}
@@ -0,0 +1,282 @@
+use std::collections::HashSet;
+
+use git2::{ErrorCode, ObjectType, Oid, Repository, StatusOptions};
+
+use super::{storage_stats, Result, StorageStats};
+
+/// Errores de integridad que se conservan como máximo por revisión
+const MAX_INTEGRITY_ERRORS: usize = 20;
+
+/// Estado en disco de un repositorio
+#[derive(Debug, Clone, Default)]
+pub struct RepositoryHealth {
+    /// Objetos alcanzables revisados
+    pub objects_checked: u64,
+    /// `false` si el recorrido se detuvo al llegar al límite de objetos
+    pub integrity_complete: bool,
+    /// Referencias rotas y objetos ausentes o ilegibles (como mucho 20)
+    pub integrity_errors: Vec<String>,
+    /// Objetos guardados que nada alcanza; `None` si el recorrido no se completó
+    pub unreachable_objects: Option<u64>,
+    pub storage: StorageStats,
+    pub bare: bool,
+    pub detached_head: bool,
+    /// Archivos modificados, en el índice o sin seguimiento (sin contar ignorados)
+    pub dirty_files: u64,
+    /// Archivos con conflictos sin resolver
+    pub unmerged_files: Vec<String>,
+}
+
+/// Revisa la integridad, el almacenamiento y el working copy de un repositorio
+///
+/// La integridad se comprueba recorriendo todo lo alcanzable desde las
+/// referencias y `HEAD`: se leen los commits, árboles y tags (libgit2
+/// verifica su hash) y se comprueba que los blobs existen. Los reflogs y
+/// el índice solo cuentan para decidir qué objetos están colgando. El
+/// recorrido se detiene tras `max_objects` objetos.
+///
+/// En los repositorios bare no se revisa el working copy.
+///
+/// # Errors
+/// - `GitError::Git` si no se pueden listar las referencias o el estado del working copy
+/// - `GitError::Io` si falla la lectura del directorio de objetos
+pub fn check_repository(repo: &Repository, max_objects: u64) -> Result<RepositoryHealth> {
+    let mut health = RepositoryHealth {
+        storage: storage_stats(repo)?,
+        bare: repo.is_bare(),
+        detached_head: repo.head_detached()?,
+        ..RepositoryHealth::default()
+    };
+
+    check_integrity(repo, max_objects, &mut health)?;
+
+    if !health.bare {
+        let mut options = StatusOptions::new();
+        options
+            .include_untracked(true)
+            .recurse_untracked_dirs(false)
+            .include_ignored(false)
+            .exclude_submodules(true);
+        for entry in repo.statuses(Some(&mut options))?.iter() {
+            health.dirty_files += 1;
+            if entry.status().is_conflicted() {
+                let path = String::from_utf8_lossy(entry.path_bytes()).into_owned();
+                health.unmerged_files.push(path);
+            }
+        }
+        health.unmerged_files.sort();
+    }
+
+    Ok(health)
+}
+
+/// Recorre los objetos alcanzables y cuenta los que quedan colgando
+fn check_integrity(
+    repo: &Repository,
+    max_objects: u64,
+    health: &mut RepositoryHealth,
+) -> Result<()> {
+    let odb = repo.odb()?;
+    let mut errors = Vec::new();
+    // (objeto, tipo esperado): los blobs solo se comprueban por cabecera
+    let mut pending: Vec<(Oid, Option<ObjectType>)> = Vec::new();
+    // Raíces cuya ausencia no es un error (reflogs e índice)
+    let mut weak_roots: Vec<Oid> = Vec::new();
+
+    for reference in repo.references()? {
+        let reference = match reference {
+            Ok(reference) => reference,
+            Err(e) => {
+                errors.push(format!("Referencia ilegible: {}", e.message()));
+                continue;
+            }
+        };
+        let name = reference.name().unwrap_or("(nombre no UTF-8)").to_string();
+        match reference.resolve() {
+            Ok(resolved) => {
+                if let Some(oid) = resolved.target() {
+                    pending.push((oid, None));
+                }
+            }
+            Err(e) => errors.push(format!(
+                "La referencia {} no se puede resolver: {}",
+                name,
+                e.message()
+            )),
+        }
+        if let Ok(reflog) = repo.reflog(&name) {
+            weak_roots.extend(reflog.iter().map(|entry| entry.id_new()));
+        }
+    }
+
+    match repo.head() {
+        Ok(head) => pending.extend(head.target().map(|oid| (oid, None))),
+        Err(e) if matches!(e.code(), ErrorCode::UnbornBranch | ErrorCode::NotFound) => {}
+        Err(e) => errors.push(format!("HEAD no se puede resolver: {}", e.message())),
+    }
+
+    if !repo.is_bare() {
+        if let Ok(index) = repo.index() {
+            weak_roots.extend(
+                index
+                    .iter()
+                    .filter(|entry| entry.mode & 0o170000 != 0o160000)
+                    .map(|entry| entry.id),
+            );
+        }
+    }
+
+    let mut seen: HashSet<Oid> = HashSet::new();
+    let mut complete = true;
+    let mut strict = true;
+    loop {
+        let Some((oid, kind)) = pending.pop() else {
+            if !strict || weak_roots.is_empty() {
+                break;
+            }
+            // Los objetos que solo alcanzan reflogs e índice pueden faltar
+            strict = false;
+            pending.extend(
+                weak_roots
+                    .drain(..)
+                    .filter(|oid| odb.exists(*oid))
+                    .map(|oid| (oid, None)),
+            );
+            continue;
+        };
+        if !seen.insert(oid) {
+            continue;
+        }
+        if seen.len() as u64 > max_objects {
+            complete = false;
+            break;
+        }
+
+        if kind == Some(ObjectType::Blob) {
+            if let Err(e) = odb.read_header(oid) {
+                if strict {
+                    errors.push(format!("Blob {} ausente o ilegible: {}", oid, e.message()));
+                }
+            }
+            continue;
+        }
+
+        let object = match repo.find_object(oid, kind) {
+            Ok(object) => object,
+            Err(e) => {
+                if strict {
+                    errors.push(format!(
+                        "Objeto {} ausente o ilegible: {}",
+                        oid,
+                        e.message()
+                    ));
+                }
+                continue;
+            }
+        };
+        match object.kind() {
+            Some(ObjectType::Commit) => {
+                let commit = object.as_commit().expect("el objeto es un commit");
+                pending.push((commit.tree_id(), Some(ObjectType::Tree)));
+                pending.extend(commit.parent_ids().map(|id| (id, Some(ObjectType::Commit))));
+            }
+            Some(ObjectType::Tree) => {
+                let tree = object.as_tree().expect("el objeto es un árbol");
+                // Los submódulos (gitlinks) apuntan a commits de otro repositorio
+                pending.extend(
+                    tree.iter()
+                        .filter(|entry| entry.kind() != Some(ObjectType::Commit))
+                        .map(|entry| (entry.id(), entry.kind())),
+                );
+            }
+            Some(ObjectType::Tag) => {
+                let tag = object.as_tag().expect("el objeto es un tag");
+                pending.push((tag.target_id(), tag.target_type()));
+            }
+            _ => {}
+        }
+    }
+
+    health.objects_checked = seen.len().min(max_objects as usize) as u64;
+    health.integrity_complete = complete;
+    if complete {
+        let mut stored: HashSet<Oid> = HashSet::new();
+        odb.foreach(|oid| {
+            stored.insert(*oid);
+            true
+        })?;
+        health.unreachable_objects =
+            Some(stored.iter().filter(|oid| !seen.contains(oid)).count() as u64);
+    }
+
+    errors.truncate(MAX_INTEGRITY_ERRORS);
+    health.integrity_errors = errors;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::git::test_support::{commit_files, init_repo};
+    use std::fs;
+
+    #[test]
+    fn test_check_repository_healthy_and_dirty() {
+        let (dir, repo) = init_repo();
+        commit_files(
+            &repo,
+            &[("a.txt", b"a"), ("src/b.rs", b"fn b() {}")],
+            "first",
+        );
+        commit_files(
+            &repo,
+            &[("a.txt", b"a2"), ("src/b.rs", b"fn b() {}")],
+            "second",
+        );
+        repo.checkout_head(Some(git2::build::CheckoutBuilder::new().force()))
+            .unwrap();
+        // Blob escrito pero no referenciado por nada
+        repo.blob(b"colgando").unwrap();
+
+        let health = check_repository(&repo, 1000).unwrap();
+        assert!(health.integrity_errors.is_empty());
+        assert!(health.integrity_complete);
+        // 2 commits, 2 árboles raíz, 1 subárbol compartido y 3 blobs
+        assert_eq!(health.objects_checked, 8);
+        assert_eq!(health.unreachable_objects, Some(1));
+        assert!(!health.detached_head);
+        assert_eq!(health.dirty_files, 0);
+
+        fs::write(dir.path().join("nuevo.txt"), "x").unwrap();
+        let head = repo.head().unwrap().peel_to_commit().unwrap();
+        repo.set_head_detached(head.id()).unwrap();
+
+        let health = check_repository(&repo, 1000).unwrap();
+        assert!(health.detached_head);
+        assert_eq!(health.dirty_files, 1);
+        assert!(health.unmerged_files.is_empty());
+
+        let limited = check_repository(&repo, 3).unwrap();
+        assert!(!limited.integrity_complete);
+        assert_eq!(limited.objects_checked, 3);
+        assert_eq!(limited.unreachable_objects, None);
+    }
+
+    #[test]
+    fn test_check_repository_missing_object() {
+        let (dir, repo) = init_repo();
+        commit_files(&repo, &[("a.txt", b"contenido")], "first");
+        let blob = repo.blob(b"contenido").unwrap().to_string();
+        let object = dir
+            .path()
+            .join(".git/objects")
+            .join(&blob[..2])
+            .join(&blob[2..]);
+        fs::remove_file(object).unwrap();
+
+        let health = check_repository(&repo, 1000).unwrap();
+        assert_eq!(health.integrity_errors.len(), 1);
+        assert!(health.integrity_errors[0].contains(&blob));
+    }
+}
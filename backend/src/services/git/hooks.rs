@@ -0,0 +1,249 @@
+use std::fs;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+
+use git2::Repository;
+
+use super::{common_dir, GitError, Result};
+use crate::models::LockHookStatus;
+
+/// Archivo dentro del directorio Git común que indica que el proyecto está bloqueado
+///
+/// Contiene el motivo del bloqueo, que el hook muestra al rechazar la operación.
+pub const LOCK_MARKER: &str = "lumastack-lock";
+
+/// Hook que Git ejecuta antes de confirmar cualquier actualización de referencias (Git ≥ 2.28)
+const HOOK_NAME: &str = "reference-transaction";
+
+/// Línea que identifica el hook como instalado por LumaStack
+const HOOK_SIGNATURE: &str = "# LumaStack lock guard";
+
+/// Rechaza actualizaciones de ramas locales mientras exista el marcador.
+/// Solo actúa en el estado `prepared`, el único en que Git permite abortar.
+const HOOK_SCRIPT: &str = r#"#!/bin/sh
+# LumaStack lock guard
+# Instalado por LumaStack: rechaza pulls, commits y resets mientras el
+# proyecto esté bloqueado. Se puede desinstalar desde la API.
+[ "$1" = "prepared" ] || exit 0
+marker="$(git rev-parse --git-common-dir)/lumastack-lock"
+[ -f "$marker" ] || exit 0
+while read -r old new ref; do
+    case "$ref" in
+        refs/heads/*)
+            echo "LumaStack: el proyecto está bloqueado: $(cat "$marker")" >&2
+            exit 1
+            ;;
+    esac
+done
+exit 0
+"#;
+
+/// Crea o reemplaza el marcador de bloqueo con el motivo indicado
+///
+/// # Errors
+/// - `GitError::Io` si no se puede escribir el archivo
+pub fn write_lock_marker(repo: &Repository, reason: &str) -> Result<()> {
+    let marker = common_dir(repo).join(LOCK_MARKER);
+    let tmp = marker.with_extension("tmp");
+    fs::write(&tmp, format!("{}\n", reason.trim()))?;
+    fs::rename(&tmp, &marker)?;
+    Ok(())
+}
+
+/// Elimina el marcador de bloqueo; no falla si no existía
+///
+/// # Errors
+/// - `GitError::Io` si no se puede eliminar el archivo
+pub fn remove_lock_marker(repo: &Repository) -> Result<()> {
+    match fs::remove_file(common_dir(repo).join(LOCK_MARKER)) {
+        Err(e) if e.kind() != ErrorKind::NotFound => Err(e.into()),
+        _ => Ok(()),
+    }
+}
+
+/// Indica si existe el marcador de bloqueo
+pub fn lock_marker_exists(repo: &Repository) -> bool {
+    common_dir(repo).join(LOCK_MARKER).is_file()
+}
+
+/// Indica si el hook de bloqueo está instalado en el repositorio
+///
+/// # Errors
+/// - `GitError::Git` si no se puede leer la configuración del repositorio
+/// - `GitError::Io` si no se puede leer el hook
+pub fn lock_hook_status(repo: &Repository) -> Result<LockHookStatus> {
+    let path = hook_path(repo)?;
+    let installed = match fs::read_to_string(&path) {
+        Ok(content) => Some(is_lumastack_hook(&content)),
+        Err(e) if e.kind() == ErrorKind::NotFound => None,
+        // Un hook binario no es nuestro
+        Err(e) if e.kind() == ErrorKind::InvalidData => Some(false),
+        Err(e) => return Err(e.into()),
+    };
+
+    Ok(LockHookStatus {
+        path: path.to_string_lossy().into_owned(),
+        installed: installed == Some(true),
+        foreign_hook: installed == Some(false),
+    })
+}
+
+/// Instala el hook de bloqueo
+///
+/// Es idempotente: si el hook de LumaStack ya existe se reescribe con la
+/// versión actual. Nunca sobrescribe un hook ajeno.
+///
+/// # Errors
+/// - `GitError::HookConflict` si ya existe otro hook `reference-transaction`
+/// - `GitError::Io` si no se puede escribir el hook
+pub fn install_lock_hook(repo: &Repository) -> Result<LockHookStatus> {
+    let status = lock_hook_status(repo)?;
+    if status.foreign_hook {
+        return Err(GitError::HookConflict(status.path));
+    }
+
+    let path = PathBuf::from(&status.path);
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    fs::write(&path, HOOK_SCRIPT)?;
+    set_executable(&path)?;
+
+    lock_hook_status(repo)
+}
+
+/// Desinstala el hook de bloqueo; no falla si no estaba instalado
+///
+/// # Errors
+/// - `GitError::HookConflict` si el hook existente no es de LumaStack
+/// - `GitError::Io` si no se puede eliminar el hook
+pub fn uninstall_lock_hook(repo: &Repository) -> Result<LockHookStatus> {
+    let status = lock_hook_status(repo)?;
+    if status.foreign_hook {
+        return Err(GitError::HookConflict(status.path));
+    }
+    if status.installed {
+        fs::remove_file(&status.path)?;
+    }
+
+    lock_hook_status(repo)
+}
+
+/// Ruta del hook, respetando `core.hooksPath` si está configurado
+fn hook_path(repo: &Repository) -> Result<PathBuf> {
+    let configured = match repo.config()?.get_path("core.hooksPath") {
+        Ok(path) => Some(path),
+        Err(e) if e.code() == git2::ErrorCode::NotFound => None,
+        Err(e) => return Err(e.into()),
+    };
+
+    let dir = match configured {
+        // Las rutas relativas se resuelven como lo hace Git: desde el working
+        // copy o, en repositorios bare, desde el directorio Git
+        Some(dir) if dir.is_relative() => repo.workdir().unwrap_or_else(|| repo.path()).join(dir),
+        Some(dir) => dir,
+        None => common_dir(repo).join("hooks"),
+    };
+
+    Ok(dir.join(HOOK_NAME))
+}
+
+fn is_lumastack_hook(content: &str) -> bool {
+    content
+        .lines()
+        .take(3)
+        .any(|line| line.trim() == HOOK_SIGNATURE)
+}
+
+#[cfg(unix)]
+fn set_executable(path: &Path) -> std::io::Result<()> {
+    use std::os::unix::fs::PermissionsExt;
+    fs::set_permissions(path, fs::Permissions::from_mode(0o755))
+}
+
+#[cfg(not(unix))]
+fn set_executable(_path: &Path) -> std::io::Result<()> {
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::git::test_support::{commit_files, init_repo};
+    use std::process::Command;
+
+    fn git(repo: &Repository, args: &[&str]) -> std::process::Output {
+        Command::new("git")
+            .args(args)
+            .current_dir(repo.workdir().unwrap())
+            .env("GIT_AUTHOR_NAME", "Test")
+            .env("GIT_AUTHOR_EMAIL", "test@example.com")
+            .env("GIT_COMMITTER_NAME", "Test")
+            .env("GIT_COMMITTER_EMAIL", "test@example.com")
+            .output()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_install_and_uninstall_hook() {
+        let (_dir, repo) = init_repo();
+
+        let status = lock_hook_status(&repo).unwrap();
+        assert!(!status.installed && !status.foreign_hook);
+        assert!(status.path.ends_with("hooks/reference-transaction"));
+
+        assert!(install_lock_hook(&repo).unwrap().installed);
+        // Reinstalar es idempotente
+        assert!(install_lock_hook(&repo).unwrap().installed);
+
+        let status = uninstall_lock_hook(&repo).unwrap();
+        assert!(!status.installed);
+        assert!(!Path::new(&status.path).exists());
+    }
+
+    #[test]
+    fn test_foreign_hook_is_never_overwritten() {
+        let (_dir, repo) = init_repo();
+        let path = hook_path(&repo).unwrap();
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(&path, "#!/bin/sh\nexit 0\n").unwrap();
+
+        assert!(lock_hook_status(&repo).unwrap().foreign_hook);
+        assert!(matches!(
+            install_lock_hook(&repo),
+            Err(GitError::HookConflict(_))
+        ));
+        assert!(matches!(
+            uninstall_lock_hook(&repo),
+            Err(GitError::HookConflict(_))
+        ));
+        assert_eq!(fs::read_to_string(&path).unwrap(), "#!/bin/sh\nexit 0\n");
+    }
+
+    #[test]
+    fn test_hook_blocks_branch_updates_while_marker_exists() {
+        let (_dir, repo) = init_repo();
+        let first = commit_files(&repo, &[("a.txt", b"a")], "first");
+        commit_files(&repo, &[("a.txt", b"b")], "second");
+        install_lock_hook(&repo).unwrap();
+
+        write_lock_marker(&repo, "Migración en curso").unwrap();
+        let output = git(
+            &repo,
+            &["update-ref", "refs/heads/main", &first.to_string()],
+        );
+        assert!(!output.status.success());
+        assert!(String::from_utf8_lossy(&output.stderr).contains("Migración en curso"));
+        // Los tags no se ven afectados
+        assert!(git(&repo, &["tag", "v1"]).status.success());
+
+        remove_lock_marker(&repo).unwrap();
+        remove_lock_marker(&repo).unwrap();
+        let output = git(
+            &repo,
+            &["update-ref", "refs/heads/main", &first.to_string()],
+        );
+        assert!(output.status.success());
+        assert_eq!(repo.head().unwrap().target(), Some(first));
+    }
+}
@@ -0,0 +1,193 @@
+use std::fs;
+use std::io::ErrorKind;
+use std::path::PathBuf;
+
+use git2::Repository;
+
+use super::{common_dir, BlobContent, GitError, Result};
+use crate::models::LfsInfo;
+
+/// Tamaño máximo de un puntero LFS según la especificación
+pub const MAX_POINTER_SIZE: u64 = 1024;
+
+/// Primeras líneas válidas de un puntero LFS (la segunda es la de versiones antiguas)
+const POINTER_VERSIONS: [&str; 2] = [
+    "version https://git-lfs.github.com/spec/v1",
+    "version https://hawser.github.com/spec/v1",
+];
+
+/// Puntero de Git LFS guardado en el repositorio en lugar del archivo
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LfsPointer {
+    /// SHA-256 del contenido en hexadecimal
+    pub oid: String,
+    /// Tamaño real del archivo
+    pub size: u64,
+}
+
+/// Interpreta el contenido de un blob como puntero LFS
+///
+/// Retorna `None` si no sigue la especificación: primera línea `version`,
+/// claves `oid sha256:<64 hex>` y `size <n>`, como mucho 1024 bytes.
+pub fn parse_lfs_pointer(data: &[u8]) -> Option<LfsPointer> {
+    if data.len() as u64 > MAX_POINTER_SIZE {
+        return None;
+    }
+
+    let text = std::str::from_utf8(data).ok()?;
+    let mut lines = text.lines();
+    let version = lines.next()?;
+    if !POINTER_VERSIONS.contains(&version.trim_end()) {
+        return None;
+    }
+
+    let mut oid = None;
+    let mut size = None;
+    for line in lines.filter(|l| !l.is_empty()) {
+        let (key, value) = line.split_once(' ')?;
+        match key {
+            "oid" => {
+                let hash = value.strip_prefix("sha256:")?;
+                if hash.len() != 64 || !hash.bytes().all(|b| b.is_ascii_hexdigit()) {
+                    return None;
+                }
+                oid = Some(hash.to_ascii_lowercase());
+            }
+            "size" => size = Some(value.parse().ok()?),
+            _ => {}
+        }
+    }
+
+    Some(LfsPointer {
+        oid: oid?,
+        size: size?,
+    })
+}
+
+/// Ruta del objeto en el almacén local de LFS (`<git común>/lfs/objects/aa/bb/<oid>`)
+pub fn lfs_object_path(repo: &Repository, pointer: &LfsPointer) -> PathBuf {
+    common_dir(repo)
+        .join("lfs")
+        .join("objects")
+        .join(&pointer.oid[0..2])
+        .join(&pointer.oid[2..4])
+        .join(&pointer.oid)
+}
+
+/// Indica si el almacén local tiene el objeto completo de un puntero
+pub fn lfs_object_available(repo: &Repository, pointer: &LfsPointer) -> bool {
+    fs::metadata(lfs_object_path(repo, pointer))
+        .is_ok_and(|meta| meta.is_file() && meta.len() == pointer.size)
+}
+
+/// Sustituye un puntero LFS por el archivo real del almacén local
+///
+/// Si `blob` no es un puntero se devuelve tal cual y sin información LFS.
+/// Si el objeto no está descargado (o su tamaño no coincide) se devuelve
+/// el puntero con `available = false`. El OID y el modo siguen siendo los
+/// del puntero.
+///
+/// # Errors
+/// - `GitError::BlobTooLarge` si el archivo real supera `limit` bytes
+/// - `GitError::Io` si falla la lectura del almacén
+pub fn smudge_lfs(
+    repo: &Repository,
+    blob: BlobContent,
+    limit: u64,
+) -> Result<(BlobContent, Option<LfsInfo>)> {
+    if blob.is_symlink() {
+        return Ok((blob, None));
+    }
+    let Some(pointer) = parse_lfs_pointer(&blob.data) else {
+        return Ok((blob, None));
+    };
+
+    let mut info = LfsInfo {
+        oid: pointer.oid.clone(),
+        size: pointer.size,
+        available: false,
+    };
+    if !lfs_object_available(repo, &pointer) {
+        return Ok((blob, Some(info)));
+    }
+    if pointer.size > limit {
+        return Err(GitError::BlobTooLarge {
+            size: pointer.size,
+            limit,
+        });
+    }
+
+    let data = match fs::read(lfs_object_path(repo, &pointer)) {
+        Ok(data) => data,
+        Err(e) if e.kind() == ErrorKind::NotFound => return Ok((blob, Some(info))),
+        Err(e) => return Err(e.into()),
+    };
+    info.available = true;
+
+    Ok((BlobContent { data, ..blob }, Some(info)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::git::read_blob;
+    use crate::services::git::test_support::{commit_files, init_repo};
+
+    const OID: &str = "4d7a214614ab2935c943f9e0ff69d22eadbb8f32b1258daaa5e2ca24d17e2393";
+
+    fn pointer(size: u64) -> String {
+        format!(
+            "version https://git-lfs.github.com/spec/v1\noid sha256:{}\nsize {}\n",
+            OID, size
+        )
+    }
+
+    #[test]
+    fn test_parse_lfs_pointer() {
+        let parsed = parse_lfs_pointer(pointer(12345).as_bytes()).unwrap();
+        assert_eq!(parsed.oid, OID);
+        assert_eq!(parsed.size, 12345);
+
+        assert!(parse_lfs_pointer(b"hola mundo\n").is_none());
+        assert!(
+            parse_lfs_pointer(b"version https://git-lfs.github.com/spec/v1\nsize 3\n").is_none()
+        );
+        let bad_oid = pointer(1).replace("sha256:4d", "sha256:zz");
+        assert!(parse_lfs_pointer(bad_oid.as_bytes()).is_none());
+    }
+
+    #[test]
+    fn test_smudge_lfs() {
+        let (_dir, repo) = init_repo();
+        let content = b"contenido real";
+        let text = pointer(content.len() as u64);
+        commit_files(
+            &repo,
+            &[("big.bin", text.as_bytes()), ("a.txt", b"a")],
+            "lfs",
+        );
+        let head = repo.head().unwrap().peel_to_commit().unwrap();
+
+        let blob = read_blob(&repo, &head, "big.bin", 1024).unwrap();
+        let (missing, info) = smudge_lfs(&repo, blob.clone(), 1024).unwrap();
+        assert_eq!(missing.data, text.as_bytes());
+        assert!(!info.unwrap().available);
+
+        let pointer = parse_lfs_pointer(text.as_bytes()).unwrap();
+        let path = lfs_object_path(&repo, &pointer);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(&path, content).unwrap();
+
+        let (smudged, info) = smudge_lfs(&repo, blob.clone(), 1024).unwrap();
+        assert_eq!(smudged.data, content);
+        assert_eq!(smudged.oid, blob.oid);
+        assert!(info.unwrap().available);
+        assert!(matches!(
+            smudge_lfs(&repo, blob, 4),
+            Err(GitError::BlobTooLarge { size: 14, limit: 4 })
+        ));
+
+        let plain = read_blob(&repo, &head, "a.txt", 1024).unwrap();
+        assert!(smudge_lfs(&repo, plain, 1024).unwrap().1.is_none());
+    }
+}
@@ -0,0 +1,64 @@
+use git2::{Repository, Signature, Time};
+
+use super::Result;
+
+/// Aplica el `.mailmap` del repositorio a pares (nombre, email)
+///
+/// Retorna un par por cada entrada, en el mismo orden. Las identidades que
+/// libgit2 no acepta como firma (p. ej. nombre vacío) se devuelven sin
+/// cambios. Sin `.mailmap` cada par se devuelve tal cual.
+///
+/// # Errors
+/// - `GitError::Git` si el `.mailmap` no se puede leer
+pub fn resolve_identities(
+    repo: &Repository,
+    identities: &[(String, String)],
+) -> Result<Vec<(String, String)>> {
+    let mailmap = repo.mailmap()?;
+    let time = Time::new(0, 0);
+
+    Ok(identities
+        .iter()
+        .map(|(name, email)| {
+            Signature::new(name, email, &time)
+                .and_then(|signature| mailmap.resolve_signature(&signature))
+                .map(|resolved| {
+                    (
+                        String::from_utf8_lossy(resolved.name_bytes()).into_owned(),
+                        String::from_utf8_lossy(resolved.email_bytes()).into_owned(),
+                    )
+                })
+                .unwrap_or_else(|_| (name.clone(), email.clone()))
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::git::test_support::init_repo;
+
+    #[test]
+    fn test_resolve_identities() {
+        let (dir, repo) = init_repo();
+        std::fs::write(
+            dir.path().join(".mailmap"),
+            "Ana García <ana@example.com> <ana@old.example.com>\n",
+        )
+        .unwrap();
+
+        let identities = vec![
+            ("ana".to_string(), "ana@old.example.com".to_string()),
+            ("Bob".to_string(), "bob@example.com".to_string()),
+            (String::new(), String::new()),
+        ];
+        let resolved = resolve_identities(&repo, &identities).unwrap();
+
+        assert_eq!(
+            resolved[0],
+            ("Ana García".to_string(), "ana@example.com".to_string())
+        );
+        assert_eq!(resolved[1], identities[1]);
+        assert_eq!(resolved[2], identities[2]);
+    }
+}
@@ -0,0 +1,278 @@
+pub mod anchor;
+pub mod archive;
+pub mod blame;
+pub mod blob;
+pub mod changelog;
+pub mod codeowners;
+pub mod composition;
+pub mod diff;
+pub mod grep;
+pub mod health;
+pub mod hooks;
+pub mod lfs;
+pub mod mailmap;
+pub mod pull;
+pub mod refs;
+pub mod signature;
+pub mod status;
+pub mod submodule;
+pub mod sync;
+pub mod tree;
+pub mod worktree;
+
+#[cfg(test)]
+pub(crate) mod test_support;
+
+pub use anchor::{line_count, remap_anchors, LineAnchor};
+pub use archive::{plan_archive, write_tar_gz, write_zip, ArchivePlan};
+pub use blame::{blame_file, BlameCache, BlameKey};
+pub use blob::{decode_text, find_readme, read_blob, BlobContent, DecodedText};
+pub use changelog::{changelog_commits, previous_tag, ChangelogCommit};
+pub use codeowners::{find_codeowners, is_owner, CodeOwners, OwnerRule};
+pub use composition::{analyze_tree, storage_stats, PathRules, StorageStats, TreeComposition};
+pub use diff::{
+    changed_paths, commit_line_stats, compare, diff_commit, diff_trees, diff_workdir, Comparison,
+    DiffLimits,
+};
+pub use grep::{grep_tree, GrepOptions, PathFilter};
+pub use health::{check_repository, RepositoryHealth};
+pub use hooks::{
+    install_lock_hook, lock_hook_status, lock_marker_exists, remove_lock_marker, uninstall_lock_hook,
+    write_lock_marker,
+};
+pub use lfs::{parse_lfs_pointer, smudge_lfs, LfsPointer};
+pub use mailmap::resolve_identities;
+pub use pull::{detect_updates, snapshot_branches, RefSnapshot, RefUpdate};
+pub use refs::{default_branch, list_branches, list_tags};
+pub use signature::{commit_signature, SignedCommit};
+pub use status::{working_copy_status, WorkingCopyChanges};
+pub use submodule::{
+    match_project, normalize_repository_url, project_locations, submodules_at, SubmoduleConfig,
+};
+pub use sync::{fetch_and_fast_forward, sync_target, SyncOutcome, SyncTarget};
+pub use tree::list_tree;
+pub use worktree::{
+    common_dir, discover_repositories, repository_identity, DiscoveredRepository, RepositoryIdentity,
+};
+
+use chrono::{DateTime, Utc};
+use git2::{Commit, ErrorCode, Repository, RepositoryOpenFlags};
+
+use crate::models::CommitSummary;
+
+/// Errores de lectura de repositorios Git
+#[derive(Debug, thiserror::Error)]
+pub enum GitError {
+    #[error("Repositorio no encontrado o inaccesible")]
+    RepositoryNotFound,
+
+    #[error("Revisión no encontrada: {0}")]
+    RevisionNotFound(String),
+
+    #[error("Ruta no encontrada: {0}")]
+    PathNotFound(String),
+
+    #[error("Ruta inválida: {0}")]
+    InvalidPath(String),
+
+    #[error("La ruta no es un directorio: {0}")]
+    NotADirectory(String),
+
+    #[error("La ruta no es un archivo: {0}")]
+    NotAFile(String),
+
+    #[error("El archivo ocupa {size} bytes y supera el límite de {limit} bytes")]
+    BlobTooLarge { size: u64, limit: u64 },
+
+    #[error("HEAD no apunta a una rama")]
+    DetachedHead,
+
+    #[error("La rama {0} no tiene una rama remota configurada")]
+    NoUpstream(String),
+
+    #[error("La rama {0} divergió de la remota; no se puede hacer fast-forward")]
+    NotFastForward(String),
+
+    #[error("El working copy tiene cambios sin commitear")]
+    DirtyWorkingCopy,
+
+    #[error("El repositorio es bare y no tiene working copy")]
+    BareRepository,
+
+    #[error("El proyecto está bloqueado")]
+    RepositoryLocked,
+
+    #[error("Error al contactar con el remoto: {0}")]
+    Remote(String),
+
+    #[error("Ya existe un hook ajeno en {0}")]
+    HookConflict(String),
+
+    #[error("Error de Git: {0}")]
+    Git(#[from] git2::Error),
+
+    #[error("Error de E/S: {0}")]
+    Io(#[from] std::io::Error),
+}
+
+pub type Result<T> = std::result::Result<T, GitError>;
+
+/// Longitud máxima aceptada para una referencia (rama, tag o hash)
+const MAX_REF_LENGTH: usize = 255;
+
+/// Abre el repositorio de un proyecto
+///
+/// No busca repositorios en directorios padre: la ruta debe apuntar
+/// exactamente al directorio `.git` o al repositorio bare.
+///
+/// # Errors
+/// - `GitError::RepositoryNotFound` si la ruta no es un repositorio Git
+pub fn open_repository(path: &str) -> Result<Repository> {
+    Repository::open_ext(path, RepositoryOpenFlags::NO_SEARCH, &[] as &[&str])
+        .map_err(|_| GitError::RepositoryNotFound)
+}
+
+/// Resuelve una referencia (rama, tag, hash o expresión de revisión) a un commit
+///
+/// Si no se indica referencia se usa `HEAD`.
+///
+/// # Errors
+/// - `GitError::RevisionNotFound` si la referencia no existe o no apunta a un commit
+pub fn resolve_commit<'r>(repo: &'r Repository, rev: Option<&str>) -> Result<Commit<'r>> {
+    let rev = rev.map(str::trim).filter(|r| !r.is_empty()).unwrap_or("HEAD");
+
+    if rev.len() > MAX_REF_LENGTH || rev.starts_with('-') {
+        return Err(GitError::RevisionNotFound(rev.to_string()));
+    }
+
+    repo.revparse_single(rev)
+        .and_then(|object| object.peel_to_commit())
+        .map_err(|e| match e.code() {
+            ErrorCode::NotFound | ErrorCode::InvalidSpec | ErrorCode::Ambiguous | ErrorCode::Peel => {
+                GitError::RevisionNotFound(rev.to_string())
+            }
+            _ => GitError::Git(e),
+        })
+}
+
+/// Normaliza una ruta relativa a la raíz del repositorio
+///
+/// Elimina separadores repetidos y componentes `.`, y rechaza cualquier
+/// componente `..` o byte nulo para que la ruta nunca pueda salir del árbol
+/// del repositorio. La raíz se representa con la cadena vacía.
+///
+/// # Errors
+/// - `GitError::InvalidPath` si la ruta contiene componentes no permitidos
+pub fn normalize_path(path: Option<&str>) -> Result<String> {
+    let Some(path) = path else {
+        return Ok(String::new());
+    };
+
+    let mut components = Vec::new();
+    for component in path.split('/') {
+        match component {
+            "" | "." => continue,
+            ".." => return Err(GitError::InvalidPath(path.to_string())),
+            c if c.contains('\0') => return Err(GitError::InvalidPath(path.to_string())),
+            c => components.push(c),
+        }
+    }
+
+    Ok(components.join("/"))
+}
+
+/// Une una ruta de directorio normalizada con el nombre de una entrada
+pub(crate) fn join_path(dir: &str, name: &str) -> String {
+    if dir.is_empty() {
+        name.to_string()
+    } else {
+        format!("{}/{}", dir, name)
+    }
+}
+
+/// Construye el resumen serializable de un commit
+pub(crate) fn commit_summary(commit: &Commit) -> CommitSummary {
+    let author = commit.author();
+
+    CommitSummary {
+        hash: commit.id().to_string(),
+        summary: String::from_utf8_lossy(commit.summary_bytes().unwrap_or_default()).into_owned(),
+        author_name: String::from_utf8_lossy(author.name_bytes()).into_owned(),
+        author_email: String::from_utf8_lossy(author.email_bytes()).into_owned(),
+        authored_at: git_time_to_utc(author.when()),
+        signature: None,
+    }
+}
+
+/// Convierte una marca de tiempo de Git a UTC
+pub(crate) fn git_time_to_utc(time: git2::Time) -> DateTime<Utc> {
+    DateTime::from_timestamp(time.seconds(), 0).unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::git::test_support::{commit_files, init_repo};
+
+    #[test]
+    fn test_normalize_path() {
+        assert_eq!(normalize_path(None).unwrap(), "");
+        assert_eq!(normalize_path(Some("/")).unwrap(), "");
+        assert_eq!(normalize_path(Some("src//models/./")).unwrap(), "src/models");
+        assert_eq!(normalize_path(Some("/src/main.rs")).unwrap(), "src/main.rs");
+    }
+
+    #[test]
+    fn test_normalize_path_rejects_traversal() {
+        assert!(matches!(
+            normalize_path(Some("../etc/passwd")),
+            Err(GitError::InvalidPath(_))
+        ));
+        assert!(matches!(
+            normalize_path(Some("src/../../secret")),
+            Err(GitError::InvalidPath(_))
+        ));
+        assert!(matches!(
+            normalize_path(Some("src/\0")),
+            Err(GitError::InvalidPath(_))
+        ));
+    }
+
+    #[test]
+    fn test_resolve_commit() {
+        let (_dir, repo) = init_repo();
+        let first = commit_files(&repo, &[("README.md", b"hola")], "first");
+        let second = commit_files(&repo, &[("README.md", b"hola mundo")], "second");
+        repo.tag_lightweight("v1", &repo.find_object(first, None).unwrap(), false)
+            .unwrap();
+
+        assert_eq!(resolve_commit(&repo, None).unwrap().id(), second);
+        assert_eq!(resolve_commit(&repo, Some("main")).unwrap().id(), second);
+        assert_eq!(resolve_commit(&repo, Some("v1")).unwrap().id(), first);
+        assert_eq!(
+            resolve_commit(&repo, Some(&first.to_string()[..8])).unwrap().id(),
+            first
+        );
+        assert!(matches!(
+            resolve_commit(&repo, Some("no-such-branch")),
+            Err(GitError::RevisionNotFound(_))
+        ));
+        assert!(matches!(
+            resolve_commit(&repo, Some("--upload-pack=evil")),
+            Err(GitError::RevisionNotFound(_))
+        ));
+    }
+
+    #[test]
+    fn test_open_repository_does_not_search_parents() {
+        let (dir, _repo) = init_repo();
+        let nested = dir.path().join("nested");
+        std::fs::create_dir(&nested).unwrap();
+
+        assert!(open_repository(dir.path().to_str().unwrap()).is_ok());
+        assert!(matches!(
+            open_repository(nested.to_str().unwrap()),
+            Err(GitError::RepositoryNotFound)
+        ));
+    }
+}
@@ -0,0 +1,169 @@
+use std::collections::HashMap;
+
+use git2::{Oid, Repository, Sort};
+
+use super::{commit_line_stats, git_time_to_utc, Result};
+use crate::models::CommitRecord;
+use crate::services::conventional::parse_commit_message;
+
+/// Puntas de las ramas locales (`refs/heads/*`) en un momento dado
+pub type RefSnapshot = HashMap<String, Oid>;
+
+/// Rama cuya punta cambió entre dos snapshots
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RefUpdate {
+    /// Referencia completa (p. ej. `refs/heads/main`)
+    pub ref_name: String,
+    /// Punta anterior; `None` si la rama es nueva
+    pub old: Option<Oid>,
+    pub new: Oid,
+    /// Commits nuevos, del más reciente al más antiguo (limitado)
+    pub commits: Vec<CommitRecord>,
+}
+
+/// Lee la punta de todas las ramas locales
+///
+/// # Errors
+/// - `GitError::Git` si falla la lectura de referencias
+pub fn snapshot_branches(repo: &Repository) -> Result<RefSnapshot> {
+    let mut snapshot = RefSnapshot::new();
+
+    for reference in repo.references_glob("refs/heads/*")? {
+        let reference = reference?;
+        let (Some(name), Ok(commit)) = (reference.name(), reference.peel_to_commit()) else {
+            continue;
+        };
+        snapshot.insert(name.to_string(), commit.id());
+    }
+
+    Ok(snapshot)
+}
+
+/// Compara dos snapshots y retorna las ramas creadas o movidas
+///
+/// Los commits nuevos de una rama movida son los alcanzables desde la punta
+/// nueva pero no desde la anterior; en una rama creada, los que no eran
+/// alcanzables desde ninguna rama del snapshot anterior. Se leen como
+/// máximo `max_commits` por rama. Las ramas eliminadas se ignoran.
+///
+/// # Errors
+/// - `GitError::Git` si falla el recorrido de la historia
+pub fn detect_updates(
+    repo: &Repository,
+    before: &RefSnapshot,
+    after: &RefSnapshot,
+    max_commits: usize,
+) -> Result<Vec<RefUpdate>> {
+    let mut updates = Vec::new();
+
+    for (name, &new) in after {
+        let old = before.get(name).copied();
+        if old == Some(new) {
+            continue;
+        }
+
+        let mut revwalk = repo.revwalk()?;
+        revwalk.set_sorting(Sort::TOPOLOGICAL | Sort::TIME)?;
+        revwalk.push(new)?;
+        let hidden: Vec<Oid> = match old {
+            Some(old) => vec![old],
+            None => before.values().copied().collect(),
+        };
+        for oid in hidden {
+            // La punta anterior puede haber desaparecido tras un force push + gc
+            if repo.find_commit(oid).is_ok() {
+                revwalk.hide(oid)?;
+            }
+        }
+
+        let commits = revwalk
+            .take(max_commits)
+            .map(|oid| Ok(commit_record(repo, &repo.find_commit(oid?)?)))
+            .collect::<Result<Vec<_>>>()?;
+
+        updates.push(RefUpdate {
+            ref_name: name.clone(),
+            old,
+            new,
+            commits,
+        });
+    }
+
+    updates.sort_by(|a, b| a.ref_name.cmp(&b.ref_name));
+
+    Ok(updates)
+}
+
+fn commit_record(repo: &Repository, commit: &git2::Commit) -> CommitRecord {
+    let author = commit.author();
+    // Las estadísticas son opcionales: si el diff falla el commit se guarda sin ellas
+    let line_stats = commit_line_stats(repo, commit).ok().flatten();
+    let count = |lines: usize| i32::try_from(lines).unwrap_or(i32::MAX);
+    let message = String::from_utf8_lossy(commit.message_bytes()).into_owned();
+
+    CommitRecord {
+        hash: commit.id().to_string(),
+        author_name: String::from_utf8_lossy(author.name_bytes()).into_owned(),
+        author_email: String::from_utf8_lossy(author.email_bytes()).into_owned(),
+        committed_at: git_time_to_utc(commit.committer().when()),
+        additions: line_stats.map(|(added, _)| count(added)),
+        deletions: line_stats.map(|(_, removed)| count(removed)),
+        conventional: parse_commit_message(&message),
+        message,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::git::test_support::{commit_files, init_repo};
+
+    #[test]
+    fn test_detect_fast_forward() {
+        let (_dir, repo) = init_repo();
+        let first = commit_files(&repo, &[("a.txt", b"a")], "first");
+        let before = snapshot_branches(&repo).unwrap();
+
+        commit_files(&repo, &[("a.txt", b"b")], "second");
+        let third = commit_files(&repo, &[("a.txt", b"c")], "third");
+        let after = snapshot_branches(&repo).unwrap();
+
+        let updates = detect_updates(&repo, &before, &after, 100).unwrap();
+        assert_eq!(updates.len(), 1);
+        assert_eq!(updates[0].ref_name, "refs/heads/main");
+        assert_eq!(updates[0].old, Some(first));
+        assert_eq!(updates[0].new, third);
+        let messages: Vec<_> = updates[0]
+            .commits
+            .iter()
+            .map(|c| c.message.as_str())
+            .collect();
+        assert_eq!(messages, vec!["third", "second"]);
+
+        let limited = detect_updates(&repo, &before, &after, 1).unwrap();
+        assert_eq!(limited[0].commits.len(), 1);
+    }
+
+    #[test]
+    fn test_detect_new_branch_and_no_changes() {
+        let (_dir, repo) = init_repo();
+        let base = commit_files(&repo, &[("a.txt", b"a")], "base");
+        let before = snapshot_branches(&repo).unwrap();
+        assert!(detect_updates(&repo, &before, &before, 100)
+            .unwrap()
+            .is_empty());
+
+        repo.branch("feature", &repo.find_commit(base).unwrap(), false)
+            .unwrap();
+        repo.set_head("refs/heads/feature").unwrap();
+        commit_files(&repo, &[("a.txt", b"f")], "feature work");
+        let after = snapshot_branches(&repo).unwrap();
+
+        let updates = detect_updates(&repo, &before, &after, 100).unwrap();
+        assert_eq!(updates.len(), 1);
+        assert_eq!(updates[0].ref_name, "refs/heads/feature");
+        assert_eq!(updates[0].old, None);
+        assert_eq!(updates[0].commits.len(), 1);
+        assert_eq!(updates[0].commits[0].message, "feature work");
+    }
+}
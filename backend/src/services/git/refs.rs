@@ -0,0 +1,266 @@
+use git2::{BranchType, ErrorCode, Repository};
+
+use super::{commit_summary, git_time_to_utc, Result};
+use crate::models::{BranchInfo, TagInfo, UpstreamStatus};
+
+/// Nombre de la rama a la que apunta `HEAD`
+///
+/// Retorna `None` si `HEAD` está desacoplado o no apunta a una rama local.
+/// En repositorios recién creados la rama puede no existir todavía.
+///
+/// # Errors
+/// - `GitError::Git` si no se puede leer `HEAD`
+pub fn default_branch(repo: &Repository) -> Result<Option<String>> {
+    let head = match repo.find_reference("HEAD") {
+        Ok(head) => head,
+        Err(e) if e.code() == ErrorCode::NotFound => return Ok(None),
+        Err(e) => return Err(e.into()),
+    };
+
+    Ok(head
+        .symbolic_target()
+        .and_then(|target| target.strip_prefix("refs/heads/"))
+        .map(str::to_string))
+}
+
+/// Lista las ramas locales con su punta y su relación con la rama por defecto
+///
+/// La rama por defecto va primero; el resto se ordena por fecha del último
+/// commit, de la más reciente a la más antigua. `ahead_by`/`behind_by` son
+/// `None` si no hay rama por defecto o no comparten historia.
+///
+/// # Errors
+/// - `GitError::Git` si falla la lectura de referencias u objetos
+pub fn list_branches(repo: &Repository) -> Result<Vec<BranchInfo>> {
+    let default_name = default_branch(repo)?;
+    let default_tip = match &default_name {
+        Some(name) => match repo.find_branch(name, BranchType::Local) {
+            Ok(branch) => Some(branch.get().peel_to_commit()?.id()),
+            Err(e) if e.code() == ErrorCode::NotFound => None,
+            Err(e) => return Err(e.into()),
+        },
+        None => None,
+    };
+
+    let mut branches = Vec::new();
+    for item in repo.branches(Some(BranchType::Local))? {
+        let (branch, _) = item?;
+        let Some(name) = branch.name()?.map(str::to_string) else {
+            continue;
+        };
+        let Ok(tip) = branch.get().peel_to_commit() else {
+            continue;
+        };
+
+        let (ahead_by, behind_by) = match default_tip {
+            Some(base) if repo.merge_base(tip.id(), base).is_ok() => {
+                let (ahead, behind) = repo.graph_ahead_behind(tip.id(), base)?;
+                (Some(ahead), Some(behind))
+            }
+            _ => (None, None),
+        };
+
+        let upstream = match branch.upstream() {
+            Ok(upstream) => {
+                let upstream_name = upstream.name()?.unwrap_or_default().to_string();
+                let upstream_tip = upstream.get().peel_to_commit()?;
+                let (ahead, behind) = repo.graph_ahead_behind(tip.id(), upstream_tip.id())?;
+                Some(UpstreamStatus {
+                    name: upstream_name,
+                    ahead_by: ahead,
+                    behind_by: behind,
+                })
+            }
+            Err(e) if e.code() == ErrorCode::NotFound => None,
+            Err(e) => return Err(e.into()),
+        };
+
+        branches.push(BranchInfo {
+            is_default: default_name.as_deref() == Some(name.as_str()),
+            name,
+            commit: commit_summary(&tip),
+            last_commit_at: git_time_to_utc(tip.committer().when()),
+            ahead_by,
+            behind_by,
+            upstream,
+        });
+    }
+
+    branches.sort_by(|a, b| {
+        b.is_default
+            .cmp(&a.is_default)
+            .then(b.last_commit_at.cmp(&a.last_commit_at))
+            .then(a.name.cmp(&b.name))
+    });
+
+    Ok(branches)
+}
+
+/// Lista los tags que apuntan a commits, del más reciente al más antiguo
+///
+/// Los tags anotados incluyen mensaje y autor del tag. Los tags que apuntan
+/// a árboles o blobs se omiten.
+///
+/// # Errors
+/// - `GitError::Git` si falla la lectura de referencias u objetos
+pub fn list_tags(repo: &Repository) -> Result<Vec<TagInfo>> {
+    let mut tags = Vec::new();
+
+    for reference in repo.references_glob("refs/tags/*")? {
+        let reference = reference?;
+        let Some(name) = reference.shorthand().map(str::to_string) else {
+            continue;
+        };
+        let Ok(commit) = reference.peel_to_commit() else {
+            continue;
+        };
+
+        let object = match reference.target() {
+            Some(oid) => Some(repo.find_object(oid, None)?),
+            None => None,
+        };
+        let info = match object.as_ref().and_then(|o| o.as_tag()) {
+            Some(tag) => {
+                let tagger = tag.tagger();
+                TagInfo {
+                    name,
+                    annotated: true,
+                    message: tag
+                        .message_bytes()
+                        .map(|m| String::from_utf8_lossy(m).into_owned()),
+                    tagger_name: tagger
+                        .as_ref()
+                        .map(|t| String::from_utf8_lossy(t.name_bytes()).into_owned()),
+                    tagger_email: tagger
+                        .as_ref()
+                        .map(|t| String::from_utf8_lossy(t.email_bytes()).into_owned()),
+                    tagged_at: git_time_to_utc(tagger.as_ref().map_or(commit.time(), |t| t.when())),
+                    commit: commit_summary(&commit),
+                }
+            }
+            None => TagInfo {
+                name,
+                annotated: false,
+                message: None,
+                tagger_name: None,
+                tagger_email: None,
+                tagged_at: git_time_to_utc(commit.time()),
+                commit: commit_summary(&commit),
+            },
+        };
+
+        tags.push(info);
+    }
+
+    tags.sort_by(|a, b| b.tagged_at.cmp(&a.tagged_at).then(a.name.cmp(&b.name)));
+
+    Ok(tags)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::git::test_support::{commit_files, init_repo};
+    use git2::{Signature, Time};
+
+    #[test]
+    fn test_list_branches() {
+        let (_dir, repo) = init_repo();
+        let base = commit_files(&repo, &[("a.txt", b"a")], "base");
+        let base_commit = repo.find_commit(base).unwrap();
+        repo.branch("feature", &base_commit, false).unwrap();
+        commit_files(&repo, &[("a.txt", b"b")], "main work");
+
+        repo.set_head("refs/heads/feature").unwrap();
+        commit_files(&repo, &[("a.txt", b"c")], "feature 1");
+        commit_files(&repo, &[("a.txt", b"d")], "feature 2");
+        repo.set_head("refs/heads/main").unwrap();
+
+        let branches = list_branches(&repo).unwrap();
+        assert_eq!(branches.len(), 2);
+        assert_eq!(branches[0].name, "main");
+        assert!(branches[0].is_default);
+        assert_eq!(
+            (branches[0].ahead_by, branches[0].behind_by),
+            (Some(0), Some(0))
+        );
+
+        assert_eq!(branches[1].name, "feature");
+        assert_eq!(branches[1].commit.summary, "feature 2");
+        assert_eq!(
+            (branches[1].ahead_by, branches[1].behind_by),
+            (Some(2), Some(1))
+        );
+        assert!(branches[1].upstream.is_none());
+    }
+
+    #[test]
+    fn test_list_branches_with_upstream() {
+        let (_dir, repo) = init_repo();
+        let first = commit_files(&repo, &[("a.txt", b"a")], "first");
+        commit_files(&repo, &[("a.txt", b"b")], "second");
+
+        repo.remote("origin", "https://example.com/repo.git")
+            .unwrap();
+        repo.reference("refs/remotes/origin/main", first, false, "test")
+            .unwrap();
+        repo.find_branch("main", BranchType::Local)
+            .unwrap()
+            .set_upstream(Some("origin/main"))
+            .unwrap();
+
+        let branches = list_branches(&repo).unwrap();
+        let upstream = branches[0].upstream.as_ref().unwrap();
+        assert_eq!(upstream.name, "origin/main");
+        assert_eq!((upstream.ahead_by, upstream.behind_by), (1, 0));
+    }
+
+    #[test]
+    fn test_list_tags() {
+        let (_dir, repo) = init_repo();
+        let first = commit_files(&repo, &[("a.txt", b"a")], "first");
+        let second = commit_files(&repo, &[("a.txt", b"b")], "second");
+
+        repo.tag_lightweight("v0.1", &repo.find_object(first, None).unwrap(), false)
+            .unwrap();
+        let tagger = Signature::new(
+            "Release Bot",
+            "bot@example.com",
+            &Time::new(1_800_000_000, 0),
+        )
+        .unwrap();
+        repo.tag(
+            "v1.0",
+            &repo.find_object(second, None).unwrap(),
+            &tagger,
+            "Primera versión estable\n",
+            false,
+        )
+        .unwrap();
+
+        let tags = list_tags(&repo).unwrap();
+        assert_eq!(tags.len(), 2);
+        assert_eq!(tags[0].name, "v1.0");
+        assert!(tags[0].annotated);
+        assert_eq!(
+            tags[0].message.as_deref(),
+            Some("Primera versión estable\n")
+        );
+        assert_eq!(tags[0].tagger_name.as_deref(), Some("Release Bot"));
+        assert_eq!(tags[0].commit.hash, second.to_string());
+
+        assert_eq!(tags[1].name, "v0.1");
+        assert!(!tags[1].annotated);
+        assert_eq!(tags[1].commit.hash, first.to_string());
+    }
+
+    #[test]
+    fn test_default_branch() {
+        let (_dir, repo) = init_repo();
+        assert_eq!(default_branch(&repo).unwrap().as_deref(), Some("main"));
+
+        let oid = commit_files(&repo, &[("a.txt", b"a")], "first");
+        repo.set_head_detached(oid).unwrap();
+        assert_eq!(default_branch(&repo).unwrap(), None);
+    }
+}
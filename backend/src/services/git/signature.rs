@@ -0,0 +1,74 @@
+use git2::{ErrorCode, Oid, Repository};
+
+use super::Result;
+use crate::models::SignatureKind;
+
+/// Firma de un commit junto con el contenido firmado
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SignedCommit {
+    /// Cabecera `gpgsig` con la firma en armadura ASCII
+    pub signature: String,
+    /// Objeto commit sin la cabecera de firma, tal como se firmó
+    pub signed_data: Vec<u8>,
+}
+
+impl SignedCommit {
+    /// Formato de la firma según su cabecera PEM
+    pub fn kind(&self) -> SignatureKind {
+        let start = self.signature.trim_start();
+        if start.starts_with("-----BEGIN SSH SIGNATURE-----") {
+            SignatureKind::Ssh
+        } else if start.starts_with("-----BEGIN SIGNED MESSAGE-----") {
+            SignatureKind::X509
+        } else {
+            SignatureKind::Gpg
+        }
+    }
+}
+
+/// Extrae la firma de un commit; `None` si no está firmado
+///
+/// # Errors
+/// - `GitError::Git` si el commit no existe o no se puede leer
+pub fn commit_signature(repo: &Repository, oid: Oid) -> Result<Option<SignedCommit>> {
+    match repo.extract_signature(&oid, None) {
+        Ok((signature, signed_data)) => Ok(Some(SignedCommit {
+            signature: String::from_utf8_lossy(&signature).into_owned(),
+            signed_data: signed_data.to_vec(),
+        })),
+        Err(e) if e.code() == ErrorCode::NotFound => Ok(None),
+        Err(e) => Err(e.into()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::git::test_support::{commit_files, init_repo};
+
+    #[test]
+    fn test_commit_signature() {
+        let (_dir, repo) = init_repo();
+        let unsigned = commit_files(&repo, &[("a", b"1")], "unsigned");
+        assert_eq!(commit_signature(&repo, unsigned).unwrap(), None);
+
+        let commit = repo.find_commit(unsigned).unwrap();
+        let buffer = repo
+            .commit_create_buffer(
+                &commit.author(),
+                &commit.committer(),
+                "signed",
+                &commit.tree().unwrap(),
+                &[],
+            )
+            .unwrap();
+        let content = buffer.as_str().unwrap();
+        let armored = "-----BEGIN SSH SIGNATURE-----\nU1NIU0lH\n-----END SSH SIGNATURE-----";
+        let signed = repo.commit_signed(content, armored, None).unwrap();
+
+        let extracted = commit_signature(&repo, signed).unwrap().unwrap();
+        assert_eq!(extracted.signature, armored);
+        assert_eq!(extracted.signed_data, content.as_bytes());
+        assert_eq!(extracted.kind(), SignatureKind::Ssh);
+    }
+}
@@ -0,0 +1,193 @@
+use git2::{Delta, DiffDelta, Repository, Status, StatusOptions};
+
+use super::diff::file_status;
+use super::{GitError, Result};
+use crate::models::{FileStatus, WorkingCopyFile, WorkingCopySummary};
+
+/// Entradas que se listan como máximo en cada grupo
+const MAX_STATUS_ENTRIES: usize = 5000;
+
+/// Cambios sin commitear de un working copy, agrupados como en `git status`
+#[derive(Debug, Clone, Default)]
+pub struct WorkingCopyChanges {
+    /// Cambios en el índice respecto a `HEAD`
+    pub staged: Vec<WorkingCopyFile>,
+    /// Cambios en el working copy respecto al índice
+    pub unstaged: Vec<WorkingCopyFile>,
+    /// Archivos sin seguimiento; los directorios sin seguimiento aparecen una vez, con `/` final
+    pub untracked: Vec<String>,
+    /// Archivos con conflictos sin resolver
+    pub conflicted: Vec<String>,
+    /// `true` si algún grupo superaba el máximo de entradas
+    pub truncated: bool,
+}
+
+impl WorkingCopyChanges {
+    pub fn is_clean(&self) -> bool {
+        self.staged.is_empty()
+            && self.unstaged.is_empty()
+            && self.untracked.is_empty()
+            && self.conflicted.is_empty()
+    }
+
+    pub fn summary(&self) -> WorkingCopySummary {
+        WorkingCopySummary {
+            staged: self.staged.len(),
+            unstaged: self.unstaged.len(),
+            untracked: self.untracked.len(),
+            conflicted: self.conflicted.len(),
+        }
+    }
+}
+
+/// Lee el estado del working copy
+///
+/// Un mismo archivo puede aparecer en `staged` y en `unstaged` si tiene
+/// cambios en el índice y otros posteriores. Se detectan los renombrados
+/// en ambos lados; los archivos ignorados y los submódulos no se listan.
+///
+/// # Errors
+/// - `GitError::BareRepository` si el repositorio no tiene working copy
+/// - `GitError::Git` si falla la lectura del índice o del working copy
+pub fn working_copy_status(repo: &Repository) -> Result<WorkingCopyChanges> {
+    if repo.is_bare() {
+        return Err(GitError::BareRepository);
+    }
+
+    let mut options = StatusOptions::new();
+    options
+        .include_untracked(true)
+        .recurse_untracked_dirs(false)
+        .include_ignored(false)
+        .exclude_submodules(true)
+        .renames_head_to_index(true)
+        .renames_index_to_workdir(true);
+
+    let mut changes = WorkingCopyChanges::default();
+    for entry in repo.statuses(Some(&mut options))?.iter() {
+        let status = entry.status();
+        let path = String::from_utf8_lossy(entry.path_bytes()).into_owned();
+
+        if status.is_conflicted() {
+            push_limited(&mut changes.conflicted, path, &mut changes.truncated);
+            continue;
+        }
+        if status.is_wt_new() {
+            push_limited(&mut changes.untracked, path, &mut changes.truncated);
+            continue;
+        }
+        if status.intersects(
+            Status::INDEX_NEW
+                | Status::INDEX_MODIFIED
+                | Status::INDEX_DELETED
+                | Status::INDEX_RENAMED
+                | Status::INDEX_TYPECHANGE,
+        ) {
+            let file = changed_file(entry.head_to_index(), &path);
+            push_limited(&mut changes.staged, file, &mut changes.truncated);
+        }
+        if status.intersects(
+            Status::WT_MODIFIED | Status::WT_DELETED | Status::WT_RENAMED | Status::WT_TYPECHANGE,
+        ) {
+            let file = changed_file(entry.index_to_workdir(), &path);
+            push_limited(&mut changes.unstaged, file, &mut changes.truncated);
+        }
+    }
+
+    Ok(changes)
+}
+
+fn push_limited<T>(list: &mut Vec<T>, item: T, truncated: &mut bool) {
+    if list.len() < MAX_STATUS_ENTRIES {
+        list.push(item);
+    } else {
+        *truncated = true;
+    }
+}
+
+/// Convierte un delta de estado al modelo de la API
+fn changed_file(delta: Option<DiffDelta>, fallback: &str) -> WorkingCopyFile {
+    let Some(delta) = delta else {
+        return WorkingCopyFile {
+            path: fallback.to_string(),
+            old_path: None,
+            status: FileStatus::Modified,
+        };
+    };
+    let path_of = |file: git2::DiffFile| file.path().map(|p| p.to_string_lossy().into_owned());
+    let status = file_status(delta.status());
+
+    WorkingCopyFile {
+        path: path_of(delta.new_file())
+            .or_else(|| path_of(delta.old_file()))
+            .unwrap_or_else(|| fallback.to_string()),
+        old_path: (delta.status() == Delta::Renamed)
+            .then(|| path_of(delta.old_file()))
+            .flatten(),
+        status,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::git::test_support::{commit_files, init_repo};
+    use std::fs;
+    use std::path::Path;
+
+    #[test]
+    fn test_working_copy_status_groups() {
+        let (dir, repo) = init_repo();
+        commit_files(
+            &repo,
+            &[("a.txt", b"a\n"), ("b.txt", b"b\n"), ("c.txt", b"c\n")],
+            "first",
+        );
+        repo.checkout_head(Some(git2::build::CheckoutBuilder::new().force()))
+            .unwrap();
+        assert!(working_copy_status(&repo).unwrap().is_clean());
+
+        let root = dir.path();
+        fs::write(root.join("a.txt"), "a modificado\n").unwrap();
+        fs::write(root.join("b.txt"), "b en el índice\n").unwrap();
+        fs::remove_file(root.join("c.txt")).unwrap();
+        fs::create_dir(root.join("tmp")).unwrap();
+        fs::write(root.join("tmp/x.log"), "x").unwrap();
+        let mut index = repo.index().unwrap();
+        index.add_path(Path::new("b.txt")).unwrap();
+        index.write().unwrap();
+        fs::write(root.join("b.txt"), "b en el índice\ny después\n").unwrap();
+
+        let changes = working_copy_status(&repo).unwrap();
+        assert!(!changes.is_clean());
+        assert_eq!(changes.staged.len(), 1);
+        assert_eq!(changes.staged[0].path, "b.txt");
+        assert_eq!(changes.staged[0].status, FileStatus::Modified);
+
+        let unstaged: Vec<(&str, FileStatus)> = changes
+            .unstaged
+            .iter()
+            .map(|f| (f.path.as_str(), f.status))
+            .collect();
+        assert_eq!(
+            unstaged,
+            vec![
+                ("a.txt", FileStatus::Modified),
+                ("b.txt", FileStatus::Modified),
+                ("c.txt", FileStatus::Deleted),
+            ]
+        );
+        assert_eq!(changes.untracked, vec!["tmp/"]);
+        assert!(changes.conflicted.is_empty());
+    }
+
+    #[test]
+    fn test_working_copy_status_bare() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let repo = Repository::init_bare(dir.path()).unwrap();
+        assert!(matches!(
+            working_copy_status(&repo),
+            Err(GitError::BareRepository)
+        ));
+    }
+}
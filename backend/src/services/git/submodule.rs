@@ -0,0 +1,325 @@
+use git2::{Commit, Repository};
+
+use super::{read_blob, GitError, Result};
+use crate::models::Project;
+
+/// Tamaño máximo de `.gitmodules`
+const MAX_GITMODULES_SIZE: u64 = 1024 * 1024;
+
+/// Entrada de `.gitmodules`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SubmoduleConfig {
+    pub name: String,
+    pub path: Option<String>,
+    pub url: Option<String>,
+}
+
+/// Interpreta un archivo `.gitmodules`
+///
+/// Solo se leen las secciones `[submodule "nombre"]` y sus claves `path` y
+/// `url`; las claves no distinguen mayúsculas y los valores pueden ir entre
+/// comillas. Se ignoran los comentarios (`#` y `;`) y el resto de secciones.
+pub fn parse_gitmodules(content: &str) -> Vec<SubmoduleConfig> {
+    let mut modules: Vec<SubmoduleConfig> = Vec::new();
+    let mut in_submodule = false;
+
+    for line in content.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') || line.starts_with(';') {
+            continue;
+        }
+
+        if let Some(header) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+            let name = header
+                .trim()
+                .strip_prefix("submodule")
+                .map(str::trim)
+                .and_then(|n| n.strip_prefix('"'))
+                .and_then(|n| n.strip_suffix('"'));
+            in_submodule = name.is_some();
+            if let Some(name) = name {
+                modules.push(SubmoduleConfig {
+                    name: name.to_string(),
+                    path: None,
+                    url: None,
+                });
+            }
+            continue;
+        }
+
+        let (Some(module), true) = (modules.last_mut(), in_submodule) else {
+            continue;
+        };
+        let Some((key, value)) = line.split_once('=') else {
+            continue;
+        };
+        let value = value.trim();
+        let value = value
+            .strip_prefix('"')
+            .and_then(|v| v.strip_suffix('"'))
+            .unwrap_or(value)
+            .to_string();
+
+        match key.trim().to_ascii_lowercase().as_str() {
+            "path" => module.path = Some(value),
+            "url" => module.url = Some(value),
+            _ => {}
+        }
+    }
+
+    modules
+}
+
+/// Lee los submódulos declarados en `.gitmodules` en la revisión de `commit`
+///
+/// Las URL relativas (`./` o `../`) se resuelven contra el remoto `origin`
+/// o, sin remoto, contra la ruta del repositorio, como hace Git.
+///
+/// # Errors
+/// - `GitError::Git` si falla la lectura de objetos
+pub fn submodules_at(repo: &Repository, commit: &Commit) -> Result<Vec<SubmoduleConfig>> {
+    let blob = match read_blob(repo, commit, ".gitmodules", MAX_GITMODULES_SIZE) {
+        Ok(blob) => blob,
+        Err(GitError::PathNotFound(_) | GitError::NotAFile(_) | GitError::BlobTooLarge { .. }) => {
+            return Ok(Vec::new())
+        }
+        Err(e) => return Err(e),
+    };
+
+    let base = remote_url(repo).unwrap_or_else(|| repository_location(repo));
+    let mut modules = parse_gitmodules(&String::from_utf8_lossy(&blob.data));
+    for module in &mut modules {
+        if let Some(url) = &module.url {
+            module.url = Some(resolve_relative_url(&base, url));
+        }
+    }
+
+    Ok(modules)
+}
+
+/// URL del remoto `origin`, si existe
+pub fn remote_url(repo: &Repository) -> Option<String> {
+    repo.find_remote("origin")
+        .ok()
+        .and_then(|remote| remote.url().map(str::to_string))
+}
+
+/// Ruta del repositorio en disco: el working copy o el directorio Git si es bare
+fn repository_location(repo: &Repository) -> String {
+    let path = repo.workdir().unwrap_or_else(|| repo.path());
+    path.to_string_lossy().trim_end_matches('/').to_string()
+}
+
+/// Resuelve una URL relativa de submódulo contra la URL del repositorio contenedor
+///
+/// Cada `../` sube un nivel en la ruta de `base` y `./` se descarta.
+pub fn resolve_relative_url(base: &str, url: &str) -> String {
+    if !url.starts_with("./") && !url.starts_with("../") {
+        return url.to_string();
+    }
+
+    let mut base = base.trim_end_matches('/').to_string();
+    let mut rest = url;
+    loop {
+        if let Some(r) = rest.strip_prefix("./") {
+            rest = r;
+        } else if let Some(r) = rest.strip_prefix("../") {
+            rest = r;
+            match base.rfind(['/', ':']) {
+                Some(idx) if base[idx..].starts_with(':') => base.truncate(idx + 1),
+                Some(idx) => base.truncate(idx),
+                None => base.clear(),
+            }
+        } else {
+            break;
+        }
+    }
+
+    if base.ends_with(':') || base.is_empty() {
+        format!("{}{}", base, rest)
+    } else {
+        format!("{}/{}", base, rest)
+    }
+}
+
+/// Normaliza una URL de repositorio para comparar ubicaciones
+///
+/// Quita el esquema, el usuario, la barra final y el sufijo `.git` (o
+/// `/.git`), y convierte la sintaxis `host:ruta` de SSH a `host/ruta`, de
+/// modo que `git@host:org/repo.git` y `https://host/org/repo` coinciden.
+pub fn normalize_repository_url(url: &str) -> String {
+    let url = url.trim();
+    let without_scheme = match url.split_once("://") {
+        Some((_, rest)) => match rest.split_once('/') {
+            Some((authority, path)) => {
+                let host = authority.rsplit('@').next().unwrap_or(authority);
+                format!("{}/{}", host, path)
+            }
+            None => rest.rsplit('@').next().unwrap_or(rest).to_string(),
+        },
+        None => match url.split_once(':') {
+            Some((host, path)) if !host.contains('/') && !path.starts_with("//") => {
+                let host = host.rsplit('@').next().unwrap_or(host);
+                format!("{}/{}", host, path.trim_start_matches('/'))
+            }
+            _ => url.to_string(),
+        },
+    };
+
+    let trimmed = without_scheme.trim_end_matches('/');
+    let trimmed = trimmed.strip_suffix("/.git").unwrap_or(trimmed);
+    let trimmed = trimmed.strip_suffix(".git").unwrap_or(trimmed);
+    trimmed.to_lowercase()
+}
+
+/// Ubicaciones conocidas de cada proyecto: su ruta en disco y sus remotos
+///
+/// Los proyectos cuyo repositorio no se puede abrir solo aportan la ruta.
+pub fn project_locations(projects: &[Project]) -> Vec<(i32, Vec<String>)> {
+    projects
+        .iter()
+        .map(|project| {
+            let mut locations = vec![normalize_repository_url(&project.repository_path)];
+            if let Ok(repo) = super::open_repository(&project.repository_path) {
+                if let Ok(remotes) = repo.remotes() {
+                    for name in remotes.iter().flatten() {
+                        if let Some(url) = repo
+                            .find_remote(name)
+                            .ok()
+                            .and_then(|r| r.url().map(str::to_string))
+                        {
+                            locations.push(normalize_repository_url(&url));
+                        }
+                    }
+                }
+            }
+            (project.id, locations)
+        })
+        .collect()
+}
+
+/// Proyecto cuyo repositorio corresponde a la URL de un submódulo
+pub fn match_project(url: &str, locations: &[(i32, Vec<String>)]) -> Option<i32> {
+    let wanted = normalize_repository_url(url);
+    locations
+        .iter()
+        .find(|(_, known)| known.contains(&wanted))
+        .map(|(id, _)| *id)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::git::test_support::{commit_entries, init_repo};
+
+    #[test]
+    fn test_parse_gitmodules() {
+        let modules = parse_gitmodules(
+            "# comentario\n\
+             [submodule \"libs/core\"]\n\
+             \tpath = libs/core\n\
+             \tURL = \"git@github.com:acme/core.git\"\n\
+             [core]\n\
+             \turl = ignorada\n\
+             [submodule \"docs\"]\n\
+             \tpath = docs\n",
+        );
+
+        assert_eq!(
+            modules,
+            vec![
+                SubmoduleConfig {
+                    name: "libs/core".into(),
+                    path: Some("libs/core".into()),
+                    url: Some("git@github.com:acme/core.git".into()),
+                },
+                SubmoduleConfig {
+                    name: "docs".into(),
+                    path: Some("docs".into()),
+                    url: None,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_resolve_relative_url() {
+        let base = "https://github.com/acme/app.git";
+        assert_eq!(
+            resolve_relative_url(base, "../core.git"),
+            "https://github.com/acme/core.git"
+        );
+        assert_eq!(
+            resolve_relative_url(base, "./sub"),
+            "https://github.com/acme/app.git/sub"
+        );
+        assert_eq!(
+            resolve_relative_url("git@github.com:acme/app.git", "../../other/core"),
+            "git@github.com:other/core"
+        );
+        assert_eq!(resolve_relative_url(base, "/srv/core"), "/srv/core");
+    }
+
+    #[test]
+    fn test_normalize_repository_url() {
+        let expected = "github.com/acme/core";
+        assert_eq!(
+            normalize_repository_url("git@github.com:acme/core.git"),
+            expected
+        );
+        assert_eq!(
+            normalize_repository_url("https://github.com/acme/core"),
+            expected
+        );
+        assert_eq!(
+            normalize_repository_url("ssh://git@GitHub.com/acme/core.git/"),
+            expected
+        );
+        assert_eq!(
+            normalize_repository_url("/srv/repos/core/.git"),
+            "/srv/repos/core"
+        );
+        assert_eq!(
+            normalize_repository_url("file:///srv/repos/core.git"),
+            "/srv/repos/core"
+        );
+
+        let locations = vec![(7, vec!["/srv/repos/core".to_string(), expected.to_string()])];
+        assert_eq!(
+            match_project("https://github.com/acme/core.git", &locations),
+            Some(7)
+        );
+        assert_eq!(match_project("/srv/repos/core", &locations), Some(7));
+        assert_eq!(
+            match_project("https://github.com/acme/other", &locations),
+            None
+        );
+    }
+
+    #[test]
+    fn test_submodules_at_resolves_relative_urls() {
+        let (_dir, repo) = init_repo();
+        repo.remote("origin", "https://example.com/acme/app.git")
+            .unwrap();
+        let pinned = "1a410efbd13591db07496601ebc7a059dd55cfe9";
+        commit_entries(
+            &repo,
+            &[
+                (
+                    ".gitmodules",
+                    0o100644,
+                    b"[submodule \"core\"]\n\tpath = core\n\turl = ../core.git\n",
+                ),
+                ("core", 0o160000, pinned.as_bytes()),
+            ],
+            "submodule",
+        );
+        let head = repo.head().unwrap().peel_to_commit().unwrap();
+
+        let modules = submodules_at(&repo, &head).unwrap();
+        assert_eq!(
+            modules[0].url.as_deref(),
+            Some("https://example.com/acme/core.git")
+        );
+    }
+}
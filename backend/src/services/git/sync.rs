@@ -0,0 +1,378 @@
+use git2::{
+    build::CheckoutBuilder, AutotagOption, Cred, CredentialType, ErrorClass, ErrorCode,
+    FetchOptions, Oid, RemoteCallbacks, Repository, StatusOptions,
+};
+
+use super::{hooks, GitError, Result};
+use crate::models::{CredentialKind, RemoteCredentials};
+
+/// Intentos de autenticación antes de abandonar; libgit2 repite el callback
+/// mientras el remoto rechace la credencial
+const MAX_AUTH_ATTEMPTS: usize = 3;
+
+/// Rama local a sincronizar y su rama remota
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SyncTarget {
+    /// Nombre del remoto (p. ej. `origin`)
+    pub remote: String,
+    /// Nombre corto de la rama local (p. ej. `main`)
+    pub branch: String,
+    /// Referencia de la rama local (`refs/heads/main`)
+    pub branch_ref: String,
+    /// Referencia de seguimiento (`refs/remotes/origin/main`)
+    pub upstream_ref: String,
+    /// Punta local antes de sincronizar
+    pub head: Oid,
+}
+
+/// Resultado de una sincronización exitosa
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SyncOutcome {
+    pub old: Oid,
+    pub new: Oid,
+    /// `false` si la rama local ya contenía la punta remota
+    pub fast_forwarded: bool,
+}
+
+/// Determina la rama a sincronizar: la de `HEAD` y su upstream configurado
+///
+/// # Errors
+/// - `GitError::DetachedHead` si `HEAD` no apunta a una rama
+/// - `GitError::NoUpstream` si la rama no tiene upstream configurado
+pub fn sync_target(repo: &Repository) -> Result<SyncTarget> {
+    let head = match repo.head() {
+        Ok(head) if head.is_branch() => head,
+        Ok(_) => return Err(GitError::DetachedHead),
+        Err(e) if e.code() == ErrorCode::UnbornBranch => return Err(GitError::DetachedHead),
+        Err(e) => return Err(e.into()),
+    };
+    let branch_ref = head.name().ok_or(GitError::DetachedHead)?.to_string();
+    let branch = head.shorthand().unwrap_or(&branch_ref).to_string();
+
+    let upstream = |result: std::result::Result<git2::Buf, git2::Error>| match result {
+        Ok(buf) => buf
+            .as_str()
+            .map(str::to_string)
+            .ok_or_else(|| GitError::NoUpstream(branch.clone())),
+        Err(e) if e.code() == ErrorCode::NotFound => Err(GitError::NoUpstream(branch.clone())),
+        Err(e) => Err(e.into()),
+    };
+    let remote = upstream(repo.branch_upstream_remote(&branch_ref))?;
+    let upstream_ref = upstream(repo.branch_upstream_name(&branch_ref))?;
+
+    Ok(SyncTarget {
+        remote,
+        head: head.peel_to_commit()?.id(),
+        branch,
+        branch_ref,
+        upstream_ref,
+    })
+}
+
+/// Hace fetch del remoto y avanza la rama local solo si es fast-forward
+///
+/// Nunca crea merges ni sobrescribe cambios: si las ramas divergieron, si
+/// el working copy tiene cambios sin commitear o si el proyecto está
+/// bloqueado (marcador del hook presente) la rama no se toca. libgit2 no
+/// ejecuta hooks, por eso el bloqueo se comprueba aquí justo antes de
+/// mover la rama.
+///
+/// # Errors
+/// - `GitError::Remote` si el fetch falla (red, autenticación, remoto inexistente)
+/// - `GitError::NotFastForward` si la rama local y la remota divergieron
+/// - `GitError::DirtyWorkingCopy` si hay cambios locales en archivos versionados
+/// - `GitError::RepositoryLocked` si el proyecto está bloqueado
+pub fn fetch_and_fast_forward(
+    repo: &Repository,
+    target: &SyncTarget,
+    credentials: Option<&RemoteCredentials>,
+) -> Result<SyncOutcome> {
+    fetch(repo, &target.remote, credentials)?;
+
+    let remote_tip = repo
+        .find_reference(&target.upstream_ref)
+        .and_then(|r| r.peel_to_commit())
+        .map_err(|_| GitError::NoUpstream(target.branch.clone()))?
+        .id();
+    let up_to_date = SyncOutcome {
+        old: target.head,
+        new: target.head,
+        fast_forwarded: false,
+    };
+
+    if remote_tip == target.head || repo.graph_descendant_of(target.head, remote_tip)? {
+        return Ok(up_to_date);
+    }
+    if !repo.graph_descendant_of(remote_tip, target.head)? {
+        return Err(GitError::NotFastForward(target.branch.clone()));
+    }
+
+    if hooks::lock_marker_exists(repo) {
+        return Err(GitError::RepositoryLocked);
+    }
+
+    // La rama pudo moverse durante el fetch (p. ej. un pull manual)
+    let mut branch = repo.find_reference(&target.branch_ref)?;
+    if branch.peel_to_commit()?.id() != target.head {
+        return Err(GitError::NotFastForward(target.branch.clone()));
+    }
+
+    let is_checked_out = repo.head()?.name() == Some(target.branch_ref.as_str());
+    if is_checked_out && !repo.is_bare() {
+        ensure_clean(repo)?;
+        let commit = repo.find_commit(remote_tip)?;
+        repo.checkout_tree(commit.as_object(), Some(CheckoutBuilder::new().safe()))?;
+    }
+
+    branch.set_target(
+        remote_tip,
+        &format!(
+            "lumastack: fast-forward {} to {}",
+            target.branch, target.upstream_ref
+        ),
+    )?;
+
+    Ok(SyncOutcome {
+        old: target.head,
+        new: remote_tip,
+        fast_forwarded: true,
+    })
+}
+
+fn fetch(
+    repo: &Repository,
+    remote_name: &str,
+    credentials: Option<&RemoteCredentials>,
+) -> Result<()> {
+    let mut remote = repo
+        .find_remote(remote_name)
+        .map_err(|_| GitError::Remote(format!("el remoto '{}' no existe", remote_name)))?;
+
+    let mut attempts = 0;
+    let mut callbacks = RemoteCallbacks::new();
+    callbacks.credentials(move |_url, username_from_url, allowed| {
+        attempts += 1;
+        if attempts > MAX_AUTH_ATTEMPTS {
+            return Err(git2::Error::new(
+                ErrorCode::Auth,
+                ErrorClass::Net,
+                "el remoto rechazó las credenciales",
+            ));
+        }
+        remote_credentials(credentials, username_from_url, allowed)
+    });
+
+    let mut options = FetchOptions::new();
+    options
+        .remote_callbacks(callbacks)
+        .download_tags(AutotagOption::Auto);
+
+    remote
+        .fetch(&[] as &[&str], Some(&mut options), Some("lumastack: fetch"))
+        .map_err(|e| GitError::Remote(e.message().to_string()))
+}
+
+fn remote_credentials(
+    credentials: Option<&RemoteCredentials>,
+    username_from_url: Option<&str>,
+    allowed: CredentialType,
+) -> std::result::Result<Cred, git2::Error> {
+    let username = |fallback: &str| {
+        credentials
+            .and_then(|c| c.username.as_deref())
+            .or(username_from_url)
+            .unwrap_or(fallback)
+            .to_string()
+    };
+
+    match credentials {
+        Some(c)
+            if c.kind == CredentialKind::Token
+                && allowed.contains(CredentialType::USER_PASS_PLAINTEXT) =>
+        {
+            // GitHub y GitLab aceptan cualquier usuario no vacío junto a un token
+            Cred::userpass_plaintext(&username("x-access-token"), &c.secret)
+        }
+        Some(c)
+            if c.kind == CredentialKind::SshKey && allowed.contains(CredentialType::SSH_KEY) =>
+        {
+            Cred::ssh_key_from_memory(&username("git"), None, &c.secret, c.passphrase.as_deref())
+        }
+        _ if allowed.contains(CredentialType::USERNAME) => Cred::username(&username("git")),
+        _ => Err(git2::Error::new(
+            ErrorCode::Auth,
+            ErrorClass::Net,
+            "el remoto requiere credenciales y el proyecto no tiene ninguna compatible",
+        )),
+    }
+}
+
+/// Falla si hay cambios sin commitear en archivos versionados
+///
+/// Los archivos sin seguimiento se ignoran salvo que el fast-forward vaya a
+/// sobrescribirlos, en cuyo caso el checkout seguro falla por sí mismo.
+fn ensure_clean(repo: &Repository) -> Result<()> {
+    let mut options = StatusOptions::new();
+    options
+        .include_untracked(false)
+        .include_ignored(false)
+        .exclude_submodules(true);
+
+    if repo.statuses(Some(&mut options))?.is_empty() {
+        Ok(())
+    } else {
+        Err(GitError::DirtyWorkingCopy)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::git::test_support::commit_files;
+    use git2::RepositoryInitOptions;
+    use std::path::Path;
+    use tempfile::TempDir;
+
+    /// Remoto bare con un commit en `main` y un clon con `origin/main` como upstream
+    fn remote_and_clone() -> (TempDir, Repository, Repository) {
+        let dir = TempDir::new().unwrap();
+        let mut opts = RepositoryInitOptions::new();
+        opts.bare(true).initial_head("main");
+        let remote = Repository::init_opts(dir.path().join("remote.git"), &opts).unwrap();
+        commit_files(&remote, &[("a.txt", b"one")], "first");
+
+        let clone = Repository::clone(
+            dir.path().join("remote.git").to_str().unwrap(),
+            dir.path().join("clone"),
+        )
+        .unwrap();
+
+        (dir, remote, clone)
+    }
+
+    fn workdir_file(repo: &Repository, path: &str) -> String {
+        std::fs::read_to_string(repo.workdir().unwrap().join(path)).unwrap()
+    }
+
+    #[test]
+    fn test_sync_target() {
+        let (_dir, _remote, clone) = remote_and_clone();
+        let target = sync_target(&clone).unwrap();
+        assert_eq!(target.remote, "origin");
+        assert_eq!(target.branch, "main");
+        assert_eq!(target.branch_ref, "refs/heads/main");
+        assert_eq!(target.upstream_ref, "refs/remotes/origin/main");
+
+        clone.set_head_detached(target.head).unwrap();
+        assert!(matches!(sync_target(&clone), Err(GitError::DetachedHead)));
+    }
+
+    #[test]
+    fn test_no_upstream() {
+        let (_dir, _remote, clone) = remote_and_clone();
+        let head = clone.head().unwrap().peel_to_commit().unwrap();
+        clone.branch("local", &head, false).unwrap();
+        clone.set_head("refs/heads/local").unwrap();
+
+        assert!(matches!(sync_target(&clone), Err(GitError::NoUpstream(b)) if b == "local"));
+    }
+
+    #[test]
+    fn test_up_to_date() {
+        let (_dir, _remote, clone) = remote_and_clone();
+        let target = sync_target(&clone).unwrap();
+
+        let outcome = fetch_and_fast_forward(&clone, &target, None).unwrap();
+        assert!(!outcome.fast_forwarded);
+        assert_eq!(outcome.old, outcome.new);
+    }
+
+    #[test]
+    fn test_fast_forward_updates_branch_and_working_copy() {
+        let (_dir, remote, clone) = remote_and_clone();
+        commit_files(&remote, &[("a.txt", b"two")], "second");
+        let tip = commit_files(&remote, &[("a.txt", b"three"), ("b.txt", b"new")], "third");
+        let target = sync_target(&clone).unwrap();
+
+        let outcome = fetch_and_fast_forward(&clone, &target, None).unwrap();
+        assert!(outcome.fast_forwarded);
+        assert_eq!(outcome.old, target.head);
+        assert_eq!(outcome.new, tip);
+        assert_eq!(clone.head().unwrap().target(), Some(tip));
+        assert_eq!(workdir_file(&clone, "a.txt"), "three");
+        assert_eq!(workdir_file(&clone, "b.txt"), "new");
+        assert!(clone.statuses(None).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_diverged_branches_are_not_merged() {
+        let (_dir, remote, clone) = remote_and_clone();
+        commit_files(&remote, &[("a.txt", b"remote")], "remote work");
+        let local = commit_files(&clone, &[("a.txt", b"local")], "local work");
+        let target = sync_target(&clone).unwrap();
+
+        assert!(matches!(
+            fetch_and_fast_forward(&clone, &target, None),
+            Err(GitError::NotFastForward(b)) if b == "main"
+        ));
+        assert_eq!(clone.head().unwrap().target(), Some(local));
+    }
+
+    #[test]
+    fn test_dirty_working_copy_is_not_touched() {
+        let (_dir, remote, clone) = remote_and_clone();
+        commit_files(&remote, &[("a.txt", b"two")], "second");
+        std::fs::write(clone.workdir().unwrap().join("a.txt"), "local edit").unwrap();
+        let target = sync_target(&clone).unwrap();
+
+        assert!(matches!(
+            fetch_and_fast_forward(&clone, &target, None),
+            Err(GitError::DirtyWorkingCopy)
+        ));
+        assert_eq!(clone.head().unwrap().target(), Some(target.head));
+        assert_eq!(workdir_file(&clone, "a.txt"), "local edit");
+    }
+
+    #[test]
+    fn test_locked_repository_is_not_updated() {
+        let (_dir, remote, clone) = remote_and_clone();
+        commit_files(&remote, &[("a.txt", b"two")], "second");
+        hooks::write_lock_marker(&clone, "release").unwrap();
+        let target = sync_target(&clone).unwrap();
+
+        assert!(matches!(
+            fetch_and_fast_forward(&clone, &target, None),
+            Err(GitError::RepositoryLocked)
+        ));
+        assert_eq!(clone.head().unwrap().target(), Some(target.head));
+    }
+
+    #[test]
+    fn test_missing_remote() {
+        let (dir, _remote, clone) = remote_and_clone();
+        std::fs::remove_dir_all(dir.path().join("remote.git")).unwrap();
+        assert!(!Path::new(&dir.path().join("remote.git")).exists());
+        let target = sync_target(&clone).unwrap();
+
+        assert!(matches!(
+            fetch_and_fast_forward(&clone, &target, None),
+            Err(GitError::Remote(_))
+        ));
+    }
+
+    #[test]
+    fn test_credentials_callback() {
+        let token = RemoteCredentials {
+            kind: CredentialKind::Token,
+            username: None,
+            secret: "secret".to_string(),
+            passphrase: None,
+        };
+        assert!(
+            remote_credentials(Some(&token), None, CredentialType::USER_PASS_PLAINTEXT).is_ok()
+        );
+        // Un token no sirve para un remoto SSH
+        assert!(remote_credentials(Some(&token), Some("git"), CredentialType::SSH_KEY).is_err());
+        assert!(remote_credentials(None, None, CredentialType::USER_PASS_PLAINTEXT).is_err());
+    }
+}
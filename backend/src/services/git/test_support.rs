@@ -0,0 +1,72 @@
+//! Utilidades para construir repositorios de prueba sin tocar el working copy
+
+use git2::{Index, IndexEntry, IndexTime, Oid, Repository, RepositoryInitOptions, Signature, Time};
+use tempfile::TempDir;
+
+/// Marca de tiempo del primer commit de prueba; cada commit suma un minuto
+const BASE_TIME: i64 = 1_700_000_000;
+
+/// Crea un repositorio vacío en un directorio temporal con `main` como rama inicial
+pub(crate) fn init_repo() -> (TempDir, Repository) {
+    let dir = TempDir::new().unwrap();
+    let mut opts = RepositoryInitOptions::new();
+    opts.initial_head("main");
+    let repo = Repository::init_opts(dir.path(), &opts).unwrap();
+    (dir, repo)
+}
+
+/// Crea un commit en `HEAD` cuyo árbol contiene exactamente los archivos indicados
+pub(crate) fn commit_files(repo: &Repository, files: &[(&str, &[u8])], message: &str) -> Oid {
+    let entries: Vec<(&str, u32, &[u8])> =
+        files.iter().map(|(path, content)| (*path, 0o100644, *content)).collect();
+    commit_entries(repo, &entries, message)
+}
+
+/// Crea un commit en `HEAD` con entradas de modo arbitrario
+///
+/// Para submódulos (`0o160000`) el contenido es el hash del commit fijado.
+pub(crate) fn commit_entries(repo: &Repository, entries: &[(&str, u32, &[u8])], message: &str) -> Oid {
+    let mut index = Index::new().unwrap();
+
+    for (path, mode, content) in entries {
+        let id = if *mode == 0o160000 {
+            Oid::from_str(std::str::from_utf8(content).unwrap()).unwrap()
+        } else {
+            repo.blob(content).unwrap()
+        };
+        let entry = IndexEntry {
+            ctime: IndexTime::new(0, 0),
+            mtime: IndexTime::new(0, 0),
+            dev: 0,
+            ino: 0,
+            mode: *mode,
+            uid: 0,
+            gid: 0,
+            file_size: 0,
+            id,
+            flags: 0,
+            flags_extended: 0,
+            path: path.as_bytes().to_vec(),
+        };
+
+        index.add(&entry).unwrap();
+    }
+
+    let tree_id = index.write_tree_to(repo).unwrap();
+    commit_tree(repo, tree_id, message)
+}
+
+/// Crea un commit en `HEAD` a partir de un árbol ya escrito
+pub(crate) fn commit_tree(repo: &Repository, tree_id: Oid, message: &str) -> Oid {
+    let tree = repo.find_tree(tree_id).unwrap();
+    let parent = repo.head().ok().and_then(|h| h.peel_to_commit().ok());
+    let time = parent
+        .as_ref()
+        .map(|p| p.time().seconds() + 60)
+        .unwrap_or(BASE_TIME);
+    let signature = Signature::new("Test User", "test@example.com", &Time::new(time, 0)).unwrap();
+    let parents: Vec<&git2::Commit> = parent.iter().collect();
+
+    repo.commit(Some("HEAD"), &signature, &signature, message, &tree, &parents)
+        .unwrap()
+}
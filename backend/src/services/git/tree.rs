@@ -0,0 +1,303 @@
+use std::collections::HashMap;
+use std::path::Path;
+
+use git2::{Commit, ErrorCode, FileMode, ObjectType, Oid, Repository, Tree, TreeEntry as GitTreeEntry};
+
+use super::lfs::{lfs_object_available, parse_lfs_pointer, MAX_POINTER_SIZE};
+use super::{commit_summary, join_path, submodules_at, GitError, Result};
+use crate::models::{CommitSummary, LfsInfo, SubmoduleInfo, TreeEntry, TreeEntryType};
+
+/// Número máximo de commits recorridos para buscar el último commit de cada entrada
+const LAST_COMMIT_SEARCH_LIMIT: usize = 2000;
+
+/// Lista las entradas de un directorio en la revisión de `commit`
+///
+/// Lee los objetos directamente de la base de objetos, por lo que el
+/// resultado no depende del estado del working copy. Los symlinks no se
+/// siguen y los submódulos se reportan con el commit fijado y la URL de
+/// `.gitmodules`. Los punteros de Git LFS se reportan con el tamaño del
+/// archivo real. Las entradas se ordenan con los directorios primero y
+/// luego por nombre.
+///
+/// # Errors
+/// - `GitError::PathNotFound` si la ruta no existe en la revisión
+/// - `GitError::NotADirectory` si la ruta apunta a algo que no es un directorio
+/// - `GitError::Git` si falla la lectura de objetos
+pub fn list_tree(repo: &Repository, commit: &Commit, path: &str) -> Result<Vec<TreeEntry>> {
+    let tree = subtree(repo, commit, path)?.ok_or_else(|| GitError::PathNotFound(path.to_string()))?;
+    let odb = repo.odb()?;
+
+    let mut entries = Vec::with_capacity(tree.len());
+    for entry in tree.iter() {
+        let name = String::from_utf8_lossy(entry.name_bytes()).into_owned();
+        let entry_type = entry_type(&entry);
+
+        let mut size = match entry_type {
+            TreeEntryType::File | TreeEntryType::Symlink => {
+                Some(odb.read_header(entry.id())?.0 as u64)
+            }
+            TreeEntryType::Directory | TreeEntryType::Submodule => None,
+        };
+
+        let lfs = match (entry_type, size) {
+            (TreeEntryType::File, Some(blob_size)) if blob_size <= MAX_POINTER_SIZE => {
+                let blob = repo.find_blob(entry.id())?;
+                parse_lfs_pointer(blob.content()).map(|pointer| LfsInfo {
+                    available: lfs_object_available(repo, &pointer),
+                    oid: pointer.oid,
+                    size: pointer.size,
+                })
+            }
+            _ => None,
+        };
+        if let Some(lfs) = &lfs {
+            size = Some(lfs.size);
+        }
+
+        let symlink_target = match entry_type {
+            TreeEntryType::Symlink => {
+                let blob = repo.find_blob(entry.id())?;
+                Some(String::from_utf8_lossy(blob.content()).into_owned())
+            }
+            _ => None,
+        };
+
+        entries.push(TreeEntry {
+            path: join_path(path, &name),
+            name,
+            entry_type,
+            size,
+            mode: format!("{:06o}", entry.filemode()),
+            oid: entry.id().to_string(),
+            symlink_target,
+            submodule: None,
+            lfs,
+            last_commit: None,
+        });
+    }
+
+    if entries.iter().any(|e| e.entry_type == TreeEntryType::Submodule) {
+        let modules = submodules_at(repo, commit)?;
+        for entry in entries.iter_mut().filter(|e| e.entry_type == TreeEntryType::Submodule) {
+            let url = modules
+                .iter()
+                .find(|m| m.path.as_deref() == Some(entry.path.as_str()))
+                .or_else(|| modules.iter().find(|m| m.path.is_none() && m.name == entry.path))
+                .and_then(|m| m.url.clone());
+            entry.submodule = Some(SubmoduleInfo {
+                url,
+                commit: entry.oid.clone(),
+                project_id: None,
+            });
+        }
+    }
+
+    let wanted: Vec<(String, Oid)> = tree
+        .iter()
+        .map(|e| (String::from_utf8_lossy(e.name_bytes()).into_owned(), e.id()))
+        .collect();
+    let mut last_commits = find_last_commits(repo, commit, path, wanted)?;
+    for entry in &mut entries {
+        entry.last_commit = last_commits.remove(&entry.name);
+    }
+
+    entries.sort_by(|a, b| {
+        let a_dir = a.entry_type == TreeEntryType::Directory;
+        let b_dir = b.entry_type == TreeEntryType::Directory;
+        b_dir.cmp(&a_dir).then_with(|| a.name.cmp(&b.name))
+    });
+
+    Ok(entries)
+}
+
+/// Obtiene el árbol de un directorio en un commit
+///
+/// Retorna `None` si la ruta no existe en ese commit.
+///
+/// # Errors
+/// - `GitError::NotADirectory` si la ruta existe pero no es un directorio
+pub(crate) fn subtree<'r>(repo: &'r Repository, commit: &Commit, path: &str) -> Result<Option<Tree<'r>>> {
+    let root = repo.find_tree(commit.tree_id())?;
+    if path.is_empty() {
+        return Ok(Some(root));
+    }
+
+    let entry = match root.get_path(Path::new(path)) {
+        Ok(entry) => entry,
+        Err(e) if e.code() == ErrorCode::NotFound => return Ok(None),
+        Err(e) => return Err(e.into()),
+    };
+
+    match entry.kind() {
+        Some(ObjectType::Tree) => Ok(Some(repo.find_tree(entry.id())?)),
+        _ => Err(GitError::NotADirectory(path.to_string())),
+    }
+}
+
+/// Clasifica una entrada de árbol según su modo Git
+pub(crate) fn entry_type(entry: &GitTreeEntry) -> TreeEntryType {
+    match entry.filemode() {
+        m if m == i32::from(FileMode::Tree) => TreeEntryType::Directory,
+        m if m == i32::from(FileMode::Link) => TreeEntryType::Symlink,
+        m if m == i32::from(FileMode::Commit) => TreeEntryType::Submodule,
+        _ => TreeEntryType::File,
+    }
+}
+
+/// Busca el último commit que modificó cada entrada de un directorio
+///
+/// Recorre la historia desde `start` en orden topológico. Una entrada se
+/// atribuye al primer commit donde tiene el OID esperado y ninguno de sus
+/// padres lo tiene. Los commits que no modificaron el directorio se
+/// descartan comparando el OID del subárbol. La búsqueda se corta tras
+/// `LAST_COMMIT_SEARCH_LIMIT` commits; las entradas pendientes quedan sin
+/// último commit.
+fn find_last_commits(
+    repo: &Repository,
+    start: &Commit,
+    dir: &str,
+    wanted: Vec<(String, Oid)>,
+) -> Result<HashMap<String, CommitSummary>> {
+    let mut pending: HashMap<String, Oid> = wanted.into_iter().collect();
+    let mut found = HashMap::new();
+
+    let mut revwalk = repo.revwalk()?;
+    revwalk.push(start.id())?;
+    revwalk.set_sorting(git2::Sort::TOPOLOGICAL | git2::Sort::TIME)?;
+
+    for oid in revwalk.take(LAST_COMMIT_SEARCH_LIMIT) {
+        if pending.is_empty() {
+            break;
+        }
+
+        let commit = repo.find_commit(oid?)?;
+        let Some(tree) = subtree_or_none(repo, &commit, dir)? else {
+            continue;
+        };
+
+        let parent_trees = commit
+            .parents()
+            .map(|parent| subtree_or_none(repo, &parent, dir))
+            .collect::<Result<Vec<_>>>()?;
+
+        if parent_trees.iter().flatten().any(|t| t.id() == tree.id()) {
+            continue;
+        }
+
+        let introduced: Vec<String> = pending
+            .iter()
+            .filter(|(name, oid)| {
+                tree.get_name(name).map(|e| e.id()) == Some(**oid)
+                    && !parent_trees
+                        .iter()
+                        .flatten()
+                        .any(|t| t.get_name(name).map(|e| e.id()) == Some(**oid))
+            })
+            .map(|(name, _)| name.clone())
+            .collect();
+
+        if introduced.is_empty() {
+            continue;
+        }
+
+        let summary = commit_summary(&commit);
+        for name in introduced {
+            pending.remove(&name);
+            found.insert(name, summary.clone());
+        }
+    }
+
+    Ok(found)
+}
+
+/// Igual que `subtree`, pero trata como ausente una ruta que no es directorio
+fn subtree_or_none<'r>(repo: &'r Repository, commit: &Commit, path: &str) -> Result<Option<Tree<'r>>> {
+    match subtree(repo, commit, path) {
+        Err(GitError::NotADirectory(_)) => Ok(None),
+        other => other,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::git::test_support::{commit_entries, commit_files, init_repo};
+
+    #[test]
+    fn test_list_tree_root_entries() {
+        let (_dir, repo) = init_repo();
+        let submodule_commit = "0123456789abcdef0123456789abcdef01234567";
+        let oid = commit_entries(
+            &repo,
+            &[
+                ("README.md", 0o100644, b"# Demo\n"),
+                ("build.sh", 0o100755, b"#!/bin/sh\n"),
+                ("docs/guide.md", 0o100644, b"guide"),
+                ("latest", 0o120000, b"docs/guide.md"),
+                ("vendor/lib", 0o160000, submodule_commit.as_bytes()),
+            ],
+            "initial",
+        );
+        let commit = repo.find_commit(oid).unwrap();
+
+        let entries = list_tree(&repo, &commit, "").unwrap();
+        let names: Vec<&str> = entries.iter().map(|e| e.name.as_str()).collect();
+        assert_eq!(names, ["docs", "vendor", "README.md", "build.sh", "latest"]);
+
+        let readme = &entries[2];
+        assert_eq!(readme.entry_type, TreeEntryType::File);
+        assert_eq!(readme.size, Some(7));
+        assert_eq!(readme.mode, "100644");
+        assert_eq!(readme.last_commit.as_ref().unwrap().hash, oid.to_string());
+
+        assert_eq!(entries[3].mode, "100755");
+
+        let link = &entries[4];
+        assert_eq!(link.entry_type, TreeEntryType::Symlink);
+        assert_eq!(link.symlink_target.as_deref(), Some("docs/guide.md"));
+
+        let vendor = list_tree(&repo, &commit, "vendor").unwrap();
+        assert_eq!(vendor[0].entry_type, TreeEntryType::Submodule);
+        assert_eq!(vendor[0].oid, submodule_commit);
+        assert_eq!(vendor[0].size, None);
+        assert_eq!(vendor[0].path, "vendor/lib");
+    }
+
+    #[test]
+    fn test_list_tree_last_commit_per_entry() {
+        let (_dir, repo) = init_repo();
+        let first = commit_files(&repo, &[("a.txt", b"a"), ("src/b.rs", b"b")], "first");
+        let second = commit_files(&repo, &[("a.txt", b"a2"), ("src/b.rs", b"b")], "second");
+        let third = commit_files(&repo, &[("a.txt", b"a2"), ("src/b.rs", b"b2")], "third");
+
+        let head = repo.find_commit(third).unwrap();
+        let entries = list_tree(&repo, &head, "").unwrap();
+        let by_name: HashMap<_, _> = entries
+            .iter()
+            .map(|e| (e.name.as_str(), e.last_commit.as_ref().unwrap().hash.clone()))
+            .collect();
+        assert_eq!(by_name["a.txt"], second.to_string());
+        assert_eq!(by_name["src"], third.to_string());
+
+        let old = repo.find_commit(second).unwrap();
+        let src = list_tree(&repo, &old, "src").unwrap();
+        assert_eq!(src[0].last_commit.as_ref().unwrap().hash, first.to_string());
+        assert_eq!(src[0].last_commit.as_ref().unwrap().summary, "first");
+    }
+
+    #[test]
+    fn test_list_tree_errors() {
+        let (_dir, repo) = init_repo();
+        let oid = commit_files(&repo, &[("src/main.rs", b"fn main() {}")], "initial");
+        let commit = repo.find_commit(oid).unwrap();
+
+        assert!(matches!(
+            list_tree(&repo, &commit, "missing"),
+            Err(GitError::PathNotFound(_))
+        ));
+        assert!(matches!(
+            list_tree(&repo, &commit, "src/main.rs"),
+            Err(GitError::NotADirectory(_))
+        ));
+    }
+}
@@ -0,0 +1,156 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use git2::{Repository, RepositoryOpenFlags};
+
+/// Directorio Git compartido por todos los worktrees del repositorio
+///
+/// En un worktree secundario `repo.path()` apunta a `.git/worktrees/<nombre>`,
+/// que contiene un archivo `commondir` con la ruta del directorio común.
+/// Ahí viven las referencias, los objetos, los hooks y el almacén de LFS.
+pub fn common_dir(repo: &Repository) -> PathBuf {
+    let git_dir = repo.path();
+    match fs::read_to_string(git_dir.join("commondir")) {
+        Ok(content) => git_dir.join(content.trim()),
+        Err(_) => git_dir.to_path_buf(),
+    }
+}
+
+/// Identidad de un repositorio en disco
+///
+/// Dos proyectos con el mismo `common_dir` son el mismo repositorio aunque
+/// uno de ellos sea un worktree enlazado del otro.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RepositoryIdentity {
+    /// Directorio Git común, canónico si se pudo resolver
+    pub common_dir: PathBuf,
+    /// `true` si el proyecto apunta a un worktree enlazado (`git worktree add`)
+    pub linked_worktree: bool,
+}
+
+/// Calcula la identidad de un repositorio abierto
+pub fn repository_identity(repo: &Repository) -> RepositoryIdentity {
+    let common = common_dir(repo);
+
+    RepositoryIdentity {
+        common_dir: fs::canonicalize(&common).unwrap_or(common),
+        linked_worktree: repo.is_worktree(),
+    }
+}
+
+/// Repositorio encontrado al explorar un directorio de escaneo
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DiscoveredRepository {
+    /// Directorio Git (`.git`, o el propio repositorio si es bare), canónico
+    pub git_dir: PathBuf,
+    pub identity: RepositoryIdentity,
+}
+
+/// Busca repositorios Git bajo `root` (RF-006)
+///
+/// Baja como máximo `max_depth` niveles sin entrar en los repositorios
+/// encontrados, en directorios ocultos ni en enlaces simbólicos. Los
+/// worktrees enlazados también se devuelven, marcados en su identidad, para
+/// que quien registre los proyectos los asocie al repositorio principal en
+/// lugar de duplicarlo. El resultado se ordena por `git_dir`.
+pub fn discover_repositories(root: &Path, max_depth: usize) -> Vec<DiscoveredRepository> {
+    let mut found = Vec::new();
+    let mut pending = vec![(root.to_path_buf(), 0)];
+
+    while let Some((dir, depth)) = pending.pop() {
+        if let Ok(repo) = Repository::open_ext(&dir, RepositoryOpenFlags::NO_SEARCH, &[] as &[&str])
+        {
+            found.push(DiscoveredRepository {
+                git_dir: fs::canonicalize(repo.path())
+                    .unwrap_or_else(|_| repo.path().to_path_buf()),
+                identity: repository_identity(&repo),
+            });
+            continue;
+        }
+
+        if depth == max_depth {
+            continue;
+        }
+        let Ok(entries) = fs::read_dir(&dir) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            let is_dir = entry.file_type().is_ok_and(|kind| kind.is_dir());
+            let hidden = entry.file_name().to_string_lossy().starts_with('.');
+            if is_dir && !hidden {
+                pending.push((entry.path(), depth + 1));
+            }
+        }
+    }
+
+    found.sort_by(|a, b| a.git_dir.cmp(&b.git_dir));
+    found
+}
+
+#[cfg(test)]
+mod tests {
+    use std::process::Command;
+
+    use super::*;
+    use crate::services::git::test_support::{commit_files, init_repo};
+
+    #[test]
+    fn test_linked_worktree_shares_identity() {
+        let (dir, repo) = init_repo();
+        commit_files(&repo, &[("a.txt", b"a")], "first");
+        let main = repository_identity(&repo);
+        assert!(!main.linked_worktree);
+
+        let path = dir.path().join("wt");
+        let worktree = repo.worktree("wt", &path, None).unwrap();
+        let linked = Repository::open_from_worktree(&worktree).unwrap();
+        let identity = repository_identity(&linked);
+
+        assert!(identity.linked_worktree);
+        assert_eq!(identity.common_dir, main.common_dir);
+    }
+
+    #[test]
+    fn test_discover_repositories_flags_linked_worktrees() {
+        let root = tempfile::TempDir::new().unwrap();
+        let app = root.path().join("app");
+        let repo = Repository::init(&app).unwrap();
+        commit_files(&repo, &[("a.txt", b"a")], "first");
+        Repository::init(root.path().join("team").join("lib")).unwrap();
+        fs::create_dir(root.path().join("notes")).unwrap();
+
+        let status = Command::new("git")
+            .arg("-C")
+            .arg(&app)
+            .args(["worktree", "add", "-q", "-b", "feature"])
+            .arg(root.path().join("app-feature"))
+            .status()
+            .unwrap();
+        assert!(status.success());
+
+        let found = discover_repositories(root.path(), 2);
+        let names: Vec<_> = found
+            .iter()
+            .map(|r| {
+                r.git_dir
+                    .strip_prefix(fs::canonicalize(root.path()).unwrap())
+                    .unwrap()
+            })
+            .collect();
+        assert_eq!(
+            names,
+            [
+                Path::new("app/.git"),
+                Path::new("app/.git/worktrees/app-feature"),
+                Path::new("team/lib/.git"),
+            ]
+        );
+
+        assert!(!found[0].identity.linked_worktree);
+        assert!(found[1].identity.linked_worktree);
+        assert_eq!(found[1].identity.common_dir, found[0].identity.common_dir);
+        assert!(!found[2].identity.linked_worktree);
+
+        assert_eq!(discover_repositories(root.path(), 1).len(), 2);
+    }
+}
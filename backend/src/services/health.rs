@@ -0,0 +1,433 @@
+//! Revisiones de salud de los repositorios
+//!
+//! Los repositorios monitoreados se deterioran con el uso: objetos sueltos
+//! y colgando que nadie limpia, packs que crecen sin control, working
+//! copies con cambios o conflictos olvidados y `HEAD` desacoplados. El
+//! [`HealthChecker`] revisa todos los proyectos al arrancar y después
+//! periódicamente; la API permite además lanzar una revisión a demanda.
+//! Cada revisión se guarda con sus métricas y la lista de problemas.
+//!
+//! Con más frecuencia se mira solo el estado de los working copies y se
+//! publica [`ProjectEvent::WorkingCopyDirty`] cuando uno pasa a tener
+//! cambios sin commitear (ver [`became_dirty`]). Esta vigilancia se activa
+//! por separado (`HEALTH_WORKING_COPY_ENABLED`), así que sigue avisando
+//! aunque las revisiones programadas estén desactivadas.
+
+use std::collections::HashMap;
+use std::time::Instant;
+
+use sqlx::PgPool;
+use tokio::task::JoinHandle;
+use tokio::time::MissedTickBehavior;
+
+use crate::config::HealthConfig;
+use crate::db;
+use crate::models::{
+    CreateRepositoryHealthCheck, HealthIssue, HealthIssueKind, HealthStatus, Project,
+    RepositoryHealthCheck,
+};
+use crate::services::events::{EventBus, ProjectEvent};
+use crate::services::git::{self, GitError};
+
+/// Servicio que revisa periódicamente la salud de los repositorios
+pub struct HealthChecker {
+    db: PgPool,
+    events: EventBus,
+    config: HealthConfig,
+    /// Último estado conocido de cada working copy (`true` si tenía cambios)
+    dirty: HashMap<i32, bool>,
+}
+
+impl HealthChecker {
+    pub fn new(db: PgPool, events: EventBus, config: HealthConfig) -> Self {
+        Self {
+            db,
+            events,
+            config,
+            dirty: HashMap::new(),
+        }
+    }
+
+    /// Arranca las revisiones en una tarea en segundo plano
+    pub fn spawn(self) -> JoinHandle<()> {
+        tokio::spawn(self.run())
+    }
+
+    async fn run(mut self) {
+        let mut sweep = tokio::time::interval(self.config.interval);
+        sweep.set_missed_tick_behavior(MissedTickBehavior::Delay);
+        let mut working_copies = tokio::time::interval(self.config.working_copy_interval);
+        working_copies.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+        tracing::info!(
+            "Revisiones de salud activas (intervalo {:?}, working copies cada {:?})",
+            self.config.interval,
+            self.config.working_copy_interval
+        );
+
+        loop {
+            tokio::select! {
+                _ = sweep.tick(), if self.config.enabled => self.check_all().await,
+                _ = working_copies.tick(), if self.config.working_copy_enabled => {
+                    self.watch_working_copies().await
+                }
+            }
+        }
+    }
+
+    async fn check_all(&self) {
+        let projects = match db::projects::list_projects(&self.db).await {
+            Ok(projects) => projects,
+            Err(e) => {
+                tracing::error!("No se pudieron leer los proyectos a revisar: {}", e);
+                return;
+            }
+        };
+
+        for project in &projects {
+            match check_project(&self.db, &self.config, project, None).await {
+                Ok(check) if check.status == HealthStatus::Healthy => {}
+                Ok(check) => tracing::warn!(
+                    "Proyecto {} con estado {:?}: {} problema(s)",
+                    project.id,
+                    check.status,
+                    check.issues.len()
+                ),
+                Err(e) => tracing::warn!("No se pudo revisar el proyecto {}: {}", project.id, e),
+            }
+        }
+    }
+
+    /// Avisa de los working copies que han pasado a tener cambios sin commitear
+    ///
+    /// La primera vez que se ve un proyecto se compara con su última
+    /// revisión guardada; sin revisión previa se da por limpio, de modo que
+    /// un working copy que ya tiene cambios al arrancar también avisa.
+    async fn watch_working_copies(&mut self) {
+        let projects = match db::projects::list_projects(&self.db).await {
+            Ok(projects) => projects,
+            Err(e) => {
+                tracing::error!("No se pudieron leer los proyectos a vigilar: {}", e);
+                return;
+            }
+        };
+
+        for project in &projects {
+            let path = project.repository_path.clone();
+            let changes = tokio::task::spawn_blocking(move || {
+                let repo = git::open_repository(&path)?;
+                git::working_copy_status(&repo)
+            })
+            .await;
+            let changes = match changes {
+                Ok(Ok(changes)) => changes,
+                Ok(Err(GitError::BareRepository)) => continue,
+                Ok(Err(e)) => {
+                    tracing::debug!(
+                        "No se pudo leer el working copy del proyecto {}: {}",
+                        project.id,
+                        e
+                    );
+                    continue;
+                }
+                Err(e) => {
+                    tracing::warn!(
+                        "Tarea de estado del proyecto {} abortada: {}",
+                        project.id,
+                        e
+                    );
+                    continue;
+                }
+            };
+
+            let dirty = !changes.is_clean();
+            let previous = match self.dirty.insert(project.id, dirty) {
+                Some(previous) => Some(previous),
+                None => db::health::latest_health_check(&self.db, project.id)
+                    .await
+                    .ok()
+                    .flatten()
+                    .map(|check| check.dirty_files > 0),
+            };
+
+            if became_dirty(previous, dirty) {
+                let summary = changes.summary();
+                tracing::warn!(
+                    "El working copy del proyecto {} tiene cambios sin commitear: {:?}",
+                    project.id,
+                    summary
+                );
+                self.events.publish(ProjectEvent::WorkingCopyDirty {
+                    project_id: project.id,
+                    summary,
+                });
+            }
+        }
+    }
+}
+
+/// Indica si hay que avisar de un working copy con cambios
+///
+/// `previous` es el último estado conocido (`true` si tenía cambios). Un
+/// estado desconocido cuenta como limpio: la primera vez que se ve un
+/// working copy con cambios también se avisa, pero no se repite el aviso
+/// mientras siga con cambios.
+fn became_dirty(previous: Option<bool>, dirty: bool) -> bool {
+    dirty && previous != Some(true)
+}
+
+/// Errores de la revisión de un proyecto
+///
+/// Los fallos de Git no son errores: quedan registrados en la revisión.
+#[derive(Debug, thiserror::Error)]
+pub enum HealthError {
+    #[error(transparent)]
+    Database(#[from] sqlx::Error),
+
+    #[error("Tarea de revisión abortada: {0}")]
+    Join(#[from] tokio::task::JoinError),
+}
+
+/// Revisa un proyecto y guarda el resultado
+///
+/// Si el repositorio no se puede abrir o leer se guarda una revisión
+/// `failing` con las métricas a cero. `triggered_by` es el usuario que la
+/// pidió, o `None` en las programadas.
+///
+/// # Errors
+/// - `HealthError::Database` si falla el guardado
+pub async fn check_project(
+    pool: &PgPool,
+    config: &HealthConfig,
+    project: &Project,
+    triggered_by: Option<i32>,
+) -> Result<RepositoryHealthCheck, HealthError> {
+    let path = project.repository_path.clone();
+    let max_objects = config.max_objects;
+    let started = Instant::now();
+
+    let result = tokio::task::spawn_blocking(move || {
+        let repo = git::open_repository(&path)?;
+        git::check_repository(&repo, max_objects)
+    })
+    .await?;
+
+    let int = |n: u64| i32::try_from(n).unwrap_or(i32::MAX);
+    let bigint = |n: u64| i64::try_from(n).unwrap_or(i64::MAX);
+    let mut check = CreateRepositoryHealthCheck {
+        project_id: project.id,
+        status: HealthStatus::Healthy,
+        integrity_ok: false,
+        integrity_complete: false,
+        objects_checked: 0,
+        unreachable_objects: None,
+        loose_objects: 0,
+        loose_size: 0,
+        pack_count: 0,
+        pack_size: 0,
+        is_bare: false,
+        dirty_files: 0,
+        detached_head: false,
+        unmerged_files: Vec::new(),
+        issues: Vec::new(),
+        triggered_by,
+        duration_ms: 0,
+    };
+
+    match result {
+        Ok(health) => {
+            check.issues = evaluate(&health, config);
+            check.integrity_ok = health.integrity_errors.is_empty();
+            check.integrity_complete = health.integrity_complete;
+            check.objects_checked = int(health.objects_checked);
+            check.unreachable_objects = health.unreachable_objects.map(int);
+            check.loose_objects = int(health.storage.loose_objects);
+            check.loose_size = bigint(health.storage.loose_size);
+            check.pack_count = int(health.storage.pack_count);
+            check.pack_size = bigint(health.storage.pack_size);
+            check.is_bare = health.bare;
+            check.dirty_files = int(health.dirty_files);
+            check.detached_head = health.detached_head;
+            check.unmerged_files = health.unmerged_files;
+        }
+        Err(e) => {
+            check.issues.push(HealthIssue {
+                kind: HealthIssueKind::RepositoryError,
+                severity: HealthStatus::Failing,
+                message: e.to_string(),
+            });
+        }
+    }
+    check.status = check
+        .issues
+        .iter()
+        .map(|issue| issue.severity)
+        .max()
+        .unwrap_or(HealthStatus::Healthy);
+    check.duration_ms = i32::try_from(started.elapsed().as_millis()).unwrap_or(i32::MAX);
+
+    Ok(db::health::insert_health_check(pool, check).await?)
+}
+
+/// Problemas de un repositorio según los umbrales configurados
+///
+/// Las referencias rotas, los objetos ausentes y los conflictos sin resolver
+/// son `failing`; el resto de problemas, `warning`.
+pub fn evaluate(health: &git::RepositoryHealth, config: &HealthConfig) -> Vec<HealthIssue> {
+    let mut issues: Vec<HealthIssue> = health
+        .integrity_errors
+        .iter()
+        .map(|error| HealthIssue {
+            kind: HealthIssueKind::Integrity,
+            severity: HealthStatus::Failing,
+            message: error.clone(),
+        })
+        .collect();
+    let mut warn = |kind, message: String| {
+        issues.push(HealthIssue {
+            kind,
+            severity: HealthStatus::Warning,
+            message,
+        })
+    };
+
+    if let Some(unreachable) = health.unreachable_objects {
+        if unreachable > config.max_unreachable_objects {
+            warn(
+                HealthIssueKind::UnreachableObjects,
+                format!("{} objetos colgando; ejecuta `git gc --prune`", unreachable),
+            );
+        }
+    }
+    if health.storage.loose_objects > config.max_loose_objects {
+        warn(
+            HealthIssueKind::LooseObjects,
+            format!(
+                "{} objetos sueltos sin empaquetar; ejecuta `git gc`",
+                health.storage.loose_objects
+            ),
+        );
+    }
+    if health.storage.pack_count > config.max_packs {
+        warn(
+            HealthIssueKind::PackCount,
+            format!(
+                "{} packs; ejecuta `git gc` para consolidarlos",
+                health.storage.pack_count
+            ),
+        );
+    }
+    if health.storage.pack_size > config.max_pack_size {
+        warn(
+            HealthIssueKind::PackSize,
+            format!(
+                "Los packs ocupan {} MiB",
+                health.storage.pack_size / (1024 * 1024)
+            ),
+        );
+    }
+    if health.dirty_files > 0 {
+        warn(
+            HealthIssueKind::DirtyWorkingCopy,
+            format!(
+                "{} archivo(s) con cambios sin commitear en el working copy",
+                health.dirty_files
+            ),
+        );
+    }
+    if health.detached_head {
+        warn(
+            HealthIssueKind::DetachedHead,
+            "HEAD no apunta a ninguna rama".to_string(),
+        );
+    }
+
+    if !health.unmerged_files.is_empty() {
+        issues.push(HealthIssue {
+            kind: HealthIssueKind::UnmergedFiles,
+            severity: HealthStatus::Failing,
+            message: format!(
+                "{} archivo(s) con conflictos sin resolver",
+                health.unmerged_files.len()
+            ),
+        });
+    }
+
+    issues
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::git::{RepositoryHealth, StorageStats};
+
+    #[test]
+    fn test_became_dirty() {
+        // Sin estado previo: el primer working copy con cambios avisa
+        assert!(became_dirty(None, true));
+        assert!(!became_dirty(None, false));
+        assert!(became_dirty(Some(false), true));
+        assert!(!became_dirty(Some(true), true));
+        assert!(!became_dirty(Some(true), false));
+
+        // Limpio, con cambios, sigue con cambios, limpio y de nuevo con cambios
+        let mut previous = None;
+        let alerts: Vec<bool> = [false, true, true, false, true]
+            .into_iter()
+            .map(|dirty| became_dirty(previous.replace(dirty), dirty))
+            .collect();
+        assert_eq!(alerts, vec![false, true, false, false, true]);
+    }
+
+    #[test]
+    fn test_evaluate() {
+        let config = HealthConfig::default();
+        let healthy = RepositoryHealth {
+            integrity_complete: true,
+            unreachable_objects: Some(3),
+            storage: StorageStats {
+                loose_objects: 12,
+                pack_count: 1,
+                ..StorageStats::default()
+            },
+            ..RepositoryHealth::default()
+        };
+        assert!(evaluate(&healthy, &config).is_empty());
+
+        let neglected = RepositoryHealth {
+            unreachable_objects: Some(config.max_unreachable_objects + 1),
+            storage: StorageStats {
+                loose_objects: config.max_loose_objects + 1,
+                pack_count: config.max_packs + 1,
+                pack_size: config.max_pack_size + 1,
+                ..StorageStats::default()
+            },
+            dirty_files: 2,
+            detached_head: true,
+            ..healthy.clone()
+        };
+        let issues = evaluate(&neglected, &config);
+        let kinds: Vec<HealthIssueKind> = issues.iter().map(|i| i.kind).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                HealthIssueKind::UnreachableObjects,
+                HealthIssueKind::LooseObjects,
+                HealthIssueKind::PackCount,
+                HealthIssueKind::PackSize,
+                HealthIssueKind::DirtyWorkingCopy,
+                HealthIssueKind::DetachedHead,
+            ]
+        );
+        assert!(issues.iter().all(|i| i.severity == HealthStatus::Warning));
+
+        let broken = RepositoryHealth {
+            integrity_errors: vec!["Blob 1234 ausente".to_string()],
+            unmerged_files: vec!["src/main.rs".to_string()],
+            ..healthy
+        };
+        let issues = evaluate(&broken, &config);
+        assert_eq!(issues.len(), 2);
+        assert!(issues.iter().all(|i| i.severity == HealthStatus::Failing));
+    }
+}
@@ -0,0 +1,86 @@
+//! Resaltado de sintaxis en el servidor con `syntect`
+
+use std::sync::OnceLock;
+
+use syntect::highlighting::{Theme, ThemeSet};
+use syntect::html::highlighted_html_for_string;
+use syntect::parsing::{SyntaxReference, SyntaxSet};
+
+/// Tema usado para el HTML resaltado (estilos inline, fondo claro)
+const THEME_NAME: &str = "InspiredGitHub";
+
+fn syntax_set() -> &'static SyntaxSet {
+    static SYNTAXES: OnceLock<SyntaxSet> = OnceLock::new();
+    SYNTAXES.get_or_init(SyntaxSet::load_defaults_newlines)
+}
+
+fn theme() -> &'static Theme {
+    static THEME: OnceLock<Theme> = OnceLock::new();
+    THEME.get_or_init(|| {
+        let mut themes = ThemeSet::load_defaults();
+        themes
+            .themes
+            .remove(THEME_NAME)
+            .expect("el tema por defecto de syntect debe existir")
+    })
+}
+
+/// Busca la sintaxis de un archivo por nombre, extensión y, en su defecto,
+/// por la primera línea (shebang, modelines como `-*- mode -*-`, `<?xml`)
+fn find_syntax(path: &str, content: &str) -> Option<&'static SyntaxReference> {
+    let syntaxes = syntax_set();
+    let name = path.rsplit('/').next().unwrap_or(path);
+
+    syntaxes
+        .find_syntax_by_extension(name)
+        .or_else(|| {
+            name.rsplit_once('.')
+                .and_then(|(_, ext)| syntaxes.find_syntax_by_extension(ext))
+        })
+        .or_else(|| {
+            let first_line = content.lines().next().unwrap_or_default();
+            syntaxes.find_syntax_by_first_line(first_line)
+        })
+}
+
+/// Genera HTML resaltado para el contenido de un archivo
+///
+/// El resultado es un `<pre>` con estilos inline, seguro de insertar tal
+/// cual: `syntect` escapa todo el texto del archivo. Si no se reconoce la
+/// sintaxis se usa texto plano.
+pub fn highlight_html(path: &str, content: &str) -> Option<String> {
+    let syntaxes = syntax_set();
+    let syntax = find_syntax(path, content).unwrap_or_else(|| syntaxes.find_syntax_plain_text());
+
+    match highlighted_html_for_string(content, syntaxes, syntax, theme()) {
+        Ok(html) => Some(html),
+        Err(e) => {
+            tracing::warn!("No se pudo resaltar {}: {}", path, e);
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_syntax_by_extension_and_shebang() {
+        assert_eq!(find_syntax("src/main.rs", "").unwrap().name, "Rust");
+        assert_eq!(find_syntax("Makefile", "").unwrap().name, "Makefile");
+        assert_eq!(
+            find_syntax("scripts/deploy", "#!/usr/bin/env python3\nprint(1)").unwrap().name,
+            "Python"
+        );
+        assert!(find_syntax("LICENSE", "MIT License").is_none());
+    }
+
+    #[test]
+    fn test_highlight_html_escapes_content() {
+        let html = highlight_html("index.html", "<script>alert('x')</script>").unwrap();
+        assert!(html.starts_with("<pre"));
+        assert!(!html.contains("<script>"));
+        assert!(html.contains("&lt;"));
+    }
+}
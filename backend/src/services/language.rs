@@ -0,0 +1,208 @@
+//! Detección de lenguaje de programación a partir de la ruta de un archivo
+
+/// Nombres de archivo con lenguaje conocido independientemente de su extensión
+const FILENAMES: &[(&str, &str)] = &[
+    ("Dockerfile", "Dockerfile"),
+    ("Containerfile", "Dockerfile"),
+    ("Makefile", "Makefile"),
+    ("GNUmakefile", "Makefile"),
+    ("CMakeLists.txt", "CMake"),
+    ("Cargo.lock", "TOML"),
+    ("Gemfile", "Ruby"),
+    ("Rakefile", "Ruby"),
+    ("Jenkinsfile", "Groovy"),
+    ("Vagrantfile", "Ruby"),
+    (".bashrc", "Shell"),
+    (".profile", "Shell"),
+    (".zshrc", "Shell"),
+];
+
+/// Extensiones (en minúsculas, sin punto) y su lenguaje
+const EXTENSIONS: &[(&str, &str)] = &[
+    ("rs", "Rust"),
+    ("ts", "TypeScript"),
+    ("tsx", "TSX"),
+    ("mts", "TypeScript"),
+    ("cts", "TypeScript"),
+    ("js", "JavaScript"),
+    ("jsx", "JavaScript"),
+    ("mjs", "JavaScript"),
+    ("cjs", "JavaScript"),
+    ("vue", "Vue"),
+    ("svelte", "Svelte"),
+    ("py", "Python"),
+    ("pyi", "Python"),
+    ("rb", "Ruby"),
+    ("go", "Go"),
+    ("java", "Java"),
+    ("kt", "Kotlin"),
+    ("kts", "Kotlin"),
+    ("scala", "Scala"),
+    ("groovy", "Groovy"),
+    ("gradle", "Groovy"),
+    ("c", "C"),
+    ("h", "C"),
+    ("cc", "C++"),
+    ("cpp", "C++"),
+    ("cxx", "C++"),
+    ("hpp", "C++"),
+    ("hh", "C++"),
+    ("cs", "C#"),
+    ("fs", "F#"),
+    ("swift", "Swift"),
+    ("m", "Objective-C"),
+    ("php", "PHP"),
+    ("pl", "Perl"),
+    ("pm", "Perl"),
+    ("lua", "Lua"),
+    ("r", "R"),
+    ("dart", "Dart"),
+    ("ex", "Elixir"),
+    ("exs", "Elixir"),
+    ("erl", "Erlang"),
+    ("hs", "Haskell"),
+    ("ml", "OCaml"),
+    ("clj", "Clojure"),
+    ("zig", "Zig"),
+    ("nim", "Nim"),
+    ("sh", "Shell"),
+    ("bash", "Shell"),
+    ("zsh", "Shell"),
+    ("fish", "Fish"),
+    ("ps1", "PowerShell"),
+    ("bat", "Batchfile"),
+    ("cmd", "Batchfile"),
+    ("sql", "SQL"),
+    ("html", "HTML"),
+    ("htm", "HTML"),
+    ("css", "CSS"),
+    ("scss", "SCSS"),
+    ("sass", "Sass"),
+    ("less", "Less"),
+    ("json", "JSON"),
+    ("jsonc", "JSON"),
+    ("yaml", "YAML"),
+    ("yml", "YAML"),
+    ("toml", "TOML"),
+    ("ini", "INI"),
+    ("xml", "XML"),
+    ("svg", "SVG"),
+    ("md", "Markdown"),
+    ("markdown", "Markdown"),
+    ("rst", "reStructuredText"),
+    ("tex", "TeX"),
+    ("proto", "Protocol Buffers"),
+    ("graphql", "GraphQL"),
+    ("gql", "GraphQL"),
+    ("tf", "HCL"),
+    ("hcl", "HCL"),
+    ("nix", "Nix"),
+    ("dockerfile", "Dockerfile"),
+    ("mk", "Makefile"),
+    ("cmake", "CMake"),
+    ("diff", "Diff"),
+    ("patch", "Diff"),
+    ("txt", "Text"),
+];
+
+/// Detecta el lenguaje de un archivo por su nombre o extensión
+///
+/// Retorna `None` si no se reconoce.
+pub fn detect_language(path: &str) -> Option<&'static str> {
+    let name = path.rsplit('/').next().unwrap_or(path);
+
+    if let Some((_, language)) = FILENAMES.iter().find(|(n, _)| *n == name) {
+        return Some(language);
+    }
+
+    let (stem, extension) = name.rsplit_once('.')?;
+    if stem.is_empty() {
+        return None;
+    }
+
+    let extension = extension.to_ascii_lowercase();
+    EXTENSIONS
+        .iter()
+        .find(|(ext, _)| *ext == extension)
+        .map(|(_, language)| *language)
+}
+
+/// Intérpretes de shebang y su lenguaje
+const INTERPRETERS: &[(&str, &str)] = &[
+    ("sh", "Shell"),
+    ("bash", "Shell"),
+    ("zsh", "Shell"),
+    ("dash", "Shell"),
+    ("ksh", "Shell"),
+    ("fish", "Fish"),
+    ("python", "Python"),
+    ("python2", "Python"),
+    ("python3", "Python"),
+    ("node", "JavaScript"),
+    ("deno", "TypeScript"),
+    ("bun", "TypeScript"),
+    ("ruby", "Ruby"),
+    ("perl", "Perl"),
+    ("php", "PHP"),
+    ("lua", "Lua"),
+    ("Rscript", "R"),
+];
+
+/// Detecta el lenguaje por nombre o extensión y, en su defecto, por el shebang
+pub fn detect_language_with_content(path: &str, content: &[u8]) -> Option<&'static str> {
+    detect_language(path).or_else(|| detect_shebang(content))
+}
+
+/// Detecta el lenguaje a partir de un shebang (`#!/usr/bin/env python3`, `#!/bin/bash`)
+fn detect_shebang(content: &[u8]) -> Option<&'static str> {
+    let rest = content.strip_prefix(b"#!")?;
+    let line_end = rest.iter().position(|&b| b == b'\n').unwrap_or(rest.len());
+    let line = std::str::from_utf8(&rest[..line_end]).ok()?;
+
+    let mut parts = line.split_whitespace();
+    let mut program = parts.next()?.rsplit('/').next()?;
+    if program == "env" {
+        program = parts.find(|arg| !arg.starts_with('-'))?;
+    }
+
+    INTERPRETERS
+        .iter()
+        .find(|(name, _)| *name == program)
+        .map(|(_, language)| *language)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detect_language_by_extension() {
+        assert_eq!(detect_language("src/main.rs"), Some("Rust"));
+        assert_eq!(detect_language("frontend/src/App.vue"), Some("Vue"));
+        assert_eq!(detect_language("README.MD"), Some("Markdown"));
+        assert_eq!(detect_language("archive.tar.gz"), None);
+    }
+
+    #[test]
+    fn test_detect_language_by_filename() {
+        assert_eq!(detect_language("Dockerfile"), Some("Dockerfile"));
+        assert_eq!(detect_language("build/Makefile"), Some("Makefile"));
+        assert_eq!(detect_language(".gitignore"), None);
+        assert_eq!(detect_language("LICENSE"), None);
+    }
+
+    #[test]
+    fn test_detect_language_by_shebang() {
+        assert_eq!(
+            detect_language_with_content("bin/deploy", b"#!/usr/bin/env python3\nimport os"),
+            Some("Python")
+        );
+        assert_eq!(detect_language_with_content("run", b"#!/bin/bash -e\n"), Some("Shell"));
+        assert_eq!(
+            detect_language_with_content("tool", b"#!/usr/bin/env -S deno run\n"),
+            Some("TypeScript")
+        );
+        assert_eq!(detect_language_with_content("main.rs", b"#!/bin/sh"), Some("Rust"));
+        assert_eq!(detect_language_with_content("notes", b"plain text"), None);
+    }
+}
@@ -0,0 +1,536 @@
+//! Renderizado de Markdown a HTML saneado
+
+use ammonia::UrlRelative;
+use std::ops::Range;
+
+use pulldown_cmark::{html, CowStr, Event, LinkType, Options, Parser, Tag, TagEnd};
+
+use crate::utils::url::{blob_url, percent_decode, raw_url, tree_url};
+
+/// Documento del repositorio cuyos enlaces relativos deben resolverse
+#[derive(Debug, Clone, Copy)]
+pub struct RepositoryLinks<'a> {
+    pub project_id: i32,
+    /// Commit fijo al que apuntan las URLs generadas
+    pub commit: &'a str,
+    /// Directorio del documento, relativo a la raíz del repositorio
+    pub base_dir: &'a str,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum LinkKind {
+    Link,
+    Image,
+}
+
+/// Renderiza Markdown (CommonMark + tablas, tachado, listas de tareas y notas al pie) a HTML saneado
+///
+/// Si se indica `links`, los enlaces relativos se resuelven contra el
+/// repositorio: las imágenes apuntan a `/raw` y los enlaces a `/blob`
+/// (o a `/tree` si terminan en `/`). El HTML crudo embebido en el
+/// documento pasa por la lista blanca de `ammonia`, que elimina scripts,
+/// manejadores de eventos y esquemas peligrosos como `javascript:`.
+pub fn render_markdown(source: &str, links: Option<&RepositoryLinks>) -> String {
+    let options = Options::ENABLE_TABLES
+        | Options::ENABLE_STRIKETHROUGH
+        | Options::ENABLE_TASKLISTS
+        | Options::ENABLE_FOOTNOTES;
+
+    let parser = Parser::new_ext(source, options).map(|event| {
+        let Some(links) = links else {
+            return event;
+        };
+
+        match event {
+            Event::Start(Tag::Link {
+                link_type,
+                dest_url,
+                title,
+                id,
+            }) => Event::Start(Tag::Link {
+                link_type,
+                dest_url: resolve_url(dest_url, links, LinkKind::Link),
+                title,
+                id,
+            }),
+            Event::Start(Tag::Image {
+                link_type,
+                dest_url,
+                title,
+                id,
+            }) => Event::Start(Tag::Image {
+                link_type,
+                dest_url: resolve_url(dest_url, links, LinkKind::Image),
+                title,
+                id,
+            }),
+            other => other,
+        }
+    });
+
+    let mut output = String::new();
+    html::push_html(&mut output, parser);
+    sanitize_html(&output)
+}
+
+/// Sanea HTML con la lista blanca por defecto de `ammonia`
+///
+/// Además permite las casillas de las listas de tareas (solo
+/// `type="checkbox"`) y la clase de lenguaje en bloques de código, y añade
+/// `rel="noopener noreferrer nofollow"` a todos los enlaces.
+pub fn sanitize_html(html: &str) -> String {
+    ammonia::Builder::default()
+        .url_relative(UrlRelative::PassThrough)
+        .link_rel(Some("noopener noreferrer nofollow"))
+        .add_tags(["input"])
+        .add_tag_attributes("input", ["type", "checked", "disabled"])
+        .add_tag_attributes("code", ["class"])
+        .attribute_filter(|element, attribute, value| match (element, attribute) {
+            ("input", "type") if value != "checkbox" => None,
+            ("code", "class") if !value.starts_with("language-") => None,
+            _ => Some(value.into()),
+        })
+        .clean(html)
+        .to_string()
+}
+
+/// Resuelve una URL relativa del documento contra el repositorio
+///
+/// Las URLs absolutas, protocolo-relativas, anclas y las que intentan salir
+/// de la raíz del repositorio se devuelven sin cambios.
+fn resolve_url<'a>(url: CowStr<'a>, links: &RepositoryLinks, kind: LinkKind) -> CowStr<'a> {
+    if url.is_empty() || url.starts_with('#') || url.starts_with("//") || has_scheme(&url) {
+        return url;
+    }
+
+    let (without_fragment, fragment) = match url.split_once('#') {
+        Some((path, fragment)) => (path, Some(fragment)),
+        None => (url.as_ref(), None),
+    };
+    let path_part = without_fragment.split('?').next().unwrap_or_default();
+    let is_directory = path_part.ends_with('/');
+    let decoded = percent_decode(path_part);
+
+    let joined = if let Some(absolute) = decoded.strip_prefix('/') {
+        absolute.to_string()
+    } else if links.base_dir.is_empty() {
+        decoded
+    } else {
+        format!("{}/{}", links.base_dir, decoded)
+    };
+
+    let Some(path) = normalize_relative(&joined) else {
+        return url;
+    };
+
+    let mut resolved = match (kind, is_directory || path.is_empty()) {
+        (LinkKind::Image, _) => raw_url(links.project_id, links.commit, &path),
+        (LinkKind::Link, true) => tree_url(links.project_id, links.commit, &path),
+        (LinkKind::Link, false) => blob_url(links.project_id, links.commit, &path),
+    };
+    if let Some(fragment) = fragment {
+        resolved.push('#');
+        resolved.push_str(fragment);
+    }
+
+    CowStr::from(resolved)
+}
+
+/// Indica si la URL empieza por un esquema (`https:`, `mailto:`, `javascript:`...)
+fn has_scheme(url: &str) -> bool {
+    let Some((scheme, _)) = url.split_once(':') else {
+        return false;
+    };
+
+    let mut chars = scheme.chars();
+    chars.next().is_some_and(|c| c.is_ascii_alphabetic())
+        && chars.all(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '-' | '.'))
+}
+
+/// Resuelve `.` y `..` en una ruta relativa; `None` si sale de la raíz
+fn normalize_relative(path: &str) -> Option<String> {
+    let mut components: Vec<&str> = Vec::new();
+    for component in path.split('/') {
+        match component {
+            "" | "." => {}
+            ".." => {
+                components.pop()?;
+            }
+            c => components.push(c),
+        }
+    }
+    Some(components.join("/"))
+}
+
+/// Nombres de README reconocidos, en orden de preferencia
+const README_NAMES: &[&str] = &[
+    "readme.md",
+    "readme.markdown",
+    "readme.mdown",
+    "readme.mkd",
+    "readme",
+    "readme.txt",
+    "readme.rst",
+];
+
+/// Elige el README de un listado de nombres de archivo
+///
+/// La comparación ignora mayúsculas; si hay varios se prefiere Markdown.
+pub fn pick_readme<'a>(names: impl IntoIterator<Item = &'a str>) -> Option<&'a str> {
+    let names: Vec<&str> = names.into_iter().collect();
+
+    README_NAMES.iter().find_map(|candidate| {
+        names
+            .iter()
+            .find(|name| name.eq_ignore_ascii_case(candidate))
+            .copied()
+    })
+}
+
+/// Indica si un archivo debe renderizarse como Markdown
+pub fn is_markdown(path: &str) -> bool {
+    let lower = path.to_ascii_lowercase();
+    [".md", ".markdown", ".mdown", ".mkd"]
+        .iter()
+        .any(|ext| lower.ends_with(ext))
+}
+
+/// Menciones distintas que se extraen como máximo de un texto
+const MAX_MENTIONS: usize = 50;
+
+/// Extrae las menciones `@usuario` de un texto Markdown
+///
+/// Se ignoran las que están en código (inline o en bloques), en HTML
+/// embebido y las precedidas por una letra o dígito, como en un email.
+/// Un nombre de usuario admite letras, dígitos, `_`, `-` y `.`, sin
+/// terminar en `.` ni `-` (fin de frase). Retorna los nombres sin `@`, sin
+/// repetir (sin distinguir mayúsculas) y en orden de aparición.
+pub fn extract_mentions(source: &str) -> Vec<String> {
+    let mut mentions: Vec<String> = Vec::new();
+    let mut text = String::new();
+    let mut in_code_block = false;
+
+    let flush = |text: &mut String, mentions: &mut Vec<String>| {
+        for mention in mentions_in_text(text) {
+            if mentions.len() < MAX_MENTIONS
+                && !mentions.iter().any(|m| m.eq_ignore_ascii_case(&mention))
+            {
+                mentions.push(mention);
+            }
+        }
+        text.clear();
+    };
+
+    for event in Parser::new_ext(source, Options::ENABLE_STRIKETHROUGH) {
+        match event {
+            Event::Start(Tag::CodeBlock(_)) => in_code_block = true,
+            Event::End(TagEnd::CodeBlock) => in_code_block = false,
+            // Los eventos de texto llegan partidos en `_`, `*`, etc.
+            Event::Text(chunk) if !in_code_block => text.push_str(&chunk),
+            _ => flush(&mut text, &mut mentions),
+        }
+    }
+    flush(&mut text, &mut mentions);
+
+    mentions
+}
+
+/// Menciones de un fragmento de texto plano
+fn mentions_in_text(text: &str) -> Vec<String> {
+    let is_name_char = |c: char| c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.');
+    let mut mentions = Vec::new();
+    let mut previous: Option<char> = None;
+    let mut chars = text.char_indices().peekable();
+
+    while let Some((i, c)) = chars.next() {
+        if c == '@' && !previous.is_some_and(|p| p.is_alphanumeric() || p == '_') {
+            let rest = &text[i + 1..];
+            let len = rest.find(|c: char| !is_name_char(c)).unwrap_or(rest.len());
+            let name = rest[..len].trim_end_matches(['.', '-']);
+            if !name.is_empty() && name.len() <= 50 {
+                mentions.push(name.to_string());
+            }
+            // Saltar el nombre para no tratar un `@` interior como otra mención
+            while chars.peek().is_some_and(|&(j, _)| j <= i + len) {
+                chars.next();
+            }
+            previous = rest[..len].chars().last().or(Some('@'));
+            continue;
+        }
+        previous = Some(c);
+    }
+
+    mentions
+}
+
+/// Referencias distintas que se extraen como máximo de un texto
+const MAX_REFERENCES: usize = 100;
+
+/// Referencia del texto de un comentario que puede enlazarse
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Reference {
+    /// Hash de un commit, completo o abreviado (de 7 caracteres o más), en minúsculas
+    Commit(String),
+    /// `#123`: otro comentario del proyecto
+    Comment(i32),
+}
+
+/// Renderiza el Markdown de un comentario a HTML saneado
+///
+/// Admite lo básico (énfasis, enlaces, listas, citas, código, tablas y
+/// tachado). El HTML embebido no se interpreta: se muestra como texto. Los
+/// hashes de commit y las referencias `#123` que `resolve` convierte en
+/// URL se enlazan, salvo dentro de código o de otro enlace. El resultado
+/// pasa además por [`sanitize_html`], que descarta esquemas como
+/// `javascript:` en los enlaces escritos a mano.
+pub fn render_comment(source: &str, resolve: impl Fn(&Reference) -> Option<String>) -> String {
+    let mut events: Vec<Event> = Vec::new();
+    let mut in_code_block = false;
+    let mut link_depth = 0usize;
+
+    for event in comment_events(source) {
+        match event {
+            Event::Start(Tag::CodeBlock(_)) => in_code_block = true,
+            Event::End(TagEnd::CodeBlock) => in_code_block = false,
+            Event::Start(Tag::Link { .. }) => link_depth += 1,
+            Event::End(TagEnd::Link) => link_depth = link_depth.saturating_sub(1),
+            Event::Text(ref text) if !in_code_block && link_depth == 0 => {
+                let mut last = 0;
+                for (range, reference) in scan_references(text) {
+                    let Some(url) = resolve(&reference) else {
+                        continue;
+                    };
+                    if range.start > last {
+                        events.push(Event::Text(text[last..range.start].to_string().into()));
+                    }
+                    events.push(Event::Start(Tag::Link {
+                        link_type: LinkType::Inline,
+                        dest_url: url.into(),
+                        title: CowStr::Borrowed(""),
+                        id: CowStr::Borrowed(""),
+                    }));
+                    events.push(Event::Text(text[range.clone()].to_string().into()));
+                    events.push(Event::End(TagEnd::Link));
+                    last = range.end;
+                }
+                if last > 0 {
+                    if last < text.len() {
+                        events.push(Event::Text(text[last..].to_string().into()));
+                    }
+                    continue;
+                }
+            }
+            _ => {}
+        }
+        events.push(event);
+    }
+
+    let mut output = String::new();
+    html::push_html(&mut output, events.into_iter());
+    sanitize_html(&output)
+}
+
+/// Referencias enlazables del Markdown de un comentario
+///
+/// Se ignoran las que están en código o dentro de un enlace. Retorna cada
+/// referencia una sola vez, en orden de aparición.
+pub fn find_references(source: &str) -> Vec<Reference> {
+    let mut references: Vec<Reference> = Vec::new();
+    let mut in_code_block = false;
+    let mut link_depth = 0usize;
+
+    for event in comment_events(source) {
+        match event {
+            Event::Start(Tag::CodeBlock(_)) => in_code_block = true,
+            Event::End(TagEnd::CodeBlock) => in_code_block = false,
+            Event::Start(Tag::Link { .. }) => link_depth += 1,
+            Event::End(TagEnd::Link) => link_depth = link_depth.saturating_sub(1),
+            Event::Text(text) if !in_code_block && link_depth == 0 => {
+                for (_, reference) in scan_references(&text) {
+                    if references.len() < MAX_REFERENCES && !references.contains(&reference) {
+                        references.push(reference);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    references
+}
+
+/// Eventos del Markdown de un comentario, con el HTML convertido en texto
+/// y los fragmentos de texto consecutivos unidos
+fn comment_events(source: &str) -> Vec<Event<'_>> {
+    let options =
+        Options::ENABLE_TABLES | Options::ENABLE_STRIKETHROUGH | Options::ENABLE_TASKLISTS;
+    let mut events: Vec<Event> = Vec::new();
+
+    for event in Parser::new_ext(source, options) {
+        let event = match event {
+            Event::Html(html) | Event::InlineHtml(html) => Event::Text(html),
+            other => other,
+        };
+        // Los eventos de texto llegan partidos en `_`, `*`, etc.
+        if let (Event::Text(text), Some(Event::Text(previous))) = (&event, events.last_mut()) {
+            *previous = format!("{}{}", previous, text).into();
+            continue;
+        }
+        events.push(event);
+    }
+
+    events
+}
+
+/// Referencias de un fragmento de texto plano con su posición
+///
+/// Un hash es una palabra de 7 a 40 dígitos hexadecimales; una referencia a
+/// un comentario, `#` seguido de dígitos. Ambas deben ir separadas de
+/// letras y dígitos.
+fn scan_references(text: &str) -> Vec<(Range<usize>, Reference)> {
+    let bytes = text.as_bytes();
+    // Los bytes no ASCII son parte de letras en UTF-8
+    let is_word = |b: u8| b.is_ascii_alphanumeric() || b == b'_' || !b.is_ascii();
+    let mut found = Vec::new();
+    let mut i = 0;
+
+    while i < bytes.len() {
+        let after_word = i > 0 && is_word(bytes[i - 1]);
+        if bytes[i] == b'#' && !after_word {
+            let digits = bytes[i + 1..]
+                .iter()
+                .take_while(|b| b.is_ascii_digit())
+                .count();
+            let end = i + 1 + digits;
+            if digits > 0 && !bytes.get(end).is_some_and(|&b| is_word(b)) {
+                if let Ok(id) = text[i + 1..end].parse() {
+                    found.push((i..end, Reference::Comment(id)));
+                }
+            }
+            i = end.max(i + 1);
+        } else if bytes[i].is_ascii_alphanumeric() && !after_word {
+            let len = bytes[i..].iter().take_while(|&&b| is_word(b)).count();
+            let word = &bytes[i..i + len];
+            if (7..=40).contains(&len) && word.iter().all(u8::is_ascii_hexdigit) {
+                let hash = text[i..i + len].to_ascii_lowercase();
+                found.push((i..i + len, Reference::Commit(hash)));
+            }
+            i += len;
+        } else {
+            i += 1;
+        }
+    }
+
+    found
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LINKS: RepositoryLinks = RepositoryLinks {
+        project_id: 7,
+        commit: "abc123",
+        base_dir: "docs",
+    };
+
+    #[test]
+    fn test_render_markdown_basic() {
+        let html = render_markdown("# Título\n\n- [x] hecho\n\n| a |\n|---|\n| 1 |", None);
+        assert!(html.contains("<h1>Título</h1>"));
+        assert!(html.contains("<table>"));
+        assert!(html.contains("type=\"checkbox\""));
+    }
+
+    #[test]
+    fn test_render_markdown_sanitizes() {
+        let html = render_markdown(
+            "<script>alert(1)</script>\n\n<img src=x onerror=alert(1)>\n\n[x](javascript:alert(1))",
+            None,
+        );
+        assert!(!html.contains("<script"));
+        assert!(!html.contains("onerror"));
+        assert!(!html.contains("javascript:"));
+    }
+
+    #[test]
+    fn test_render_markdown_resolves_relative_links() {
+        let html = render_markdown(
+            "![logo](img/logo.png) [guía](../CONTRIBUTING.md#setup) [api](api/) [web](https://example.com)",
+            Some(&LINKS),
+        );
+        assert!(html.contains("src=\"/api/projects/7/raw?ref=abc123&amp;path=docs%2Fimg%2Flogo.png\""));
+        assert!(html.contains(
+            "href=\"/api/projects/7/blob?ref=abc123&amp;path=CONTRIBUTING.md#setup\""
+        ));
+        assert!(html.contains("href=\"/api/projects/7/tree?ref=abc123&amp;path=docs%2Fapi\""));
+        assert!(html.contains("href=\"https://example.com\""));
+        assert!(html.contains("rel=\"noopener noreferrer nofollow\""));
+    }
+
+    #[test]
+    fn test_resolve_url_keeps_escaping_links() {
+        let html = render_markdown("[fuera](../../etc/passwd) [ancla](#uso)", Some(&LINKS));
+        assert!(html.contains("href=\"../../etc/passwd\""));
+        assert!(html.contains("href=\"#uso\""));
+    }
+
+    #[test]
+    fn test_pick_readme() {
+        assert_eq!(pick_readme(["src", "README", "readme.md"]), Some("readme.md"));
+        assert_eq!(pick_readme(["LICENSE", "Readme.txt"]), Some("Readme.txt"));
+        assert_eq!(pick_readme(["LICENSE"]), None);
+    }
+
+    #[test]
+    fn test_extract_mentions() {
+        let source = "Hola @jane_doe y @Bob.\n\n\
+            `@code` no cuenta, ni jane@example.com, ni @bob otra vez.\n\n\
+            ```\n@block\n```\n\n\
+            > cc @ana-maria, @x.y\n";
+        assert_eq!(
+            extract_mentions(source),
+            vec!["jane_doe", "Bob", "ana-maria", "x.y"]
+        );
+        assert!(extract_mentions("@ sola y @@").is_empty());
+    }
+
+    #[test]
+    fn test_find_references() {
+        let source = "Arreglado en 9FCEB02 (ver #12 y #12).\n\n\
+            `abc1234` y [#13](https://example.com) no cuentan, ni cafe, ni x#14, \
+            ni #15a, ni deadbeef_1, ni 1234567890123456789012345678901234567890a.";
+        assert_eq!(
+            find_references(source),
+            vec![
+                Reference::Commit("9fceb02".to_string()),
+                Reference::Comment(12)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_render_comment() {
+        let resolve = |reference: &Reference| match reference {
+            Reference::Commit(hash) if hash == "9fceb02" => {
+                Some("/projects/1/commits?commit=9fceb02".to_string())
+            }
+            Reference::Comment(12) => Some("/projects/1#comment-12".to_string()),
+            _ => None,
+        };
+        let html = render_comment(
+            "**Ojo**: 9fceb02 rompe #12, no #13 ni `9fceb02`.\n\n\
+             <script>alert(1)</script> <b onclick=x>hola</b>\n\n[x](javascript:alert(1))",
+            resolve,
+        );
+        assert!(html.contains("<strong>Ojo</strong>"));
+        assert!(html.contains(
+            "<a href=\"/projects/1/commits?commit=9fceb02\" rel=\"noopener noreferrer nofollow\">9fceb02</a> rompe"
+        ));
+        assert!(html.contains("<a href=\"/projects/1#comment-12\""));
+        assert!(html.contains("no #13 ni <code>9fceb02</code>"));
+        assert!(html.contains("&lt;script&gt;alert(1)&lt;/script&gt;"));
+        assert!(html.contains("&lt;b onclick=x&gt;hola&lt;/b&gt;"));
+        assert!(!html.contains("javascript:"));
+    }
+}
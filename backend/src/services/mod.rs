@@ -0,0 +1,15 @@
+pub mod analyzer;
+pub mod auth;
+pub mod changelog;
+pub mod comments;
+pub mod conventional;
+pub mod events;
+pub mod git;
+pub mod health;
+pub mod highlight;
+pub mod language;
+pub mod markdown;
+pub mod notifications;
+pub mod signature;
+pub mod vault;
+pub mod watcher;
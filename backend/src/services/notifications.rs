@@ -0,0 +1,426 @@
+//! Notificaciones dentro de la aplicación
+//!
+//! Los demás módulos notifican a los usuarios con [`notify`] o
+//! [`notify_users`]. `link` es la ruta de la aplicación web a la que lleva
+//! la notificación (ver [`crate::utils::url`]); solo se admiten rutas
+//! internas, para que una notificación no pueda llevar a otro sitio.
+//!
+//! El [`NotificationDispatcher`] convierte los eventos del [`EventBus`] en
+//! notificaciones: los bloqueos, las solicitudes de bloqueo y los working
+//! copies que pasan a tener cambios sin commitear llegan a los
+//! administradores del proyecto, y la revisión de una solicitud, a quien la
+//! hizo. Nadie se notifica de lo que hizo él mismo.
+
+use sqlx::PgPool;
+use tokio::sync::broadcast::error::RecvError;
+use tokio::task::JoinHandle;
+
+use crate::db;
+use crate::db::projects::ProjectError;
+use crate::models::{
+    CreateNotification, LockAction, LockRequestStatus, Notification, NotificationType,
+    WorkingCopySummary,
+};
+use crate::services::events::{EventBus, ProjectEvent};
+
+/// Longitud máxima del título, en caracteres
+const MAX_TITLE_LENGTH: usize = 255;
+
+/// Longitud máxima del enlace, en caracteres
+const MAX_LINK_LENGTH: usize = 500;
+
+/// Caracteres de un texto que se incluyen en el mensaje con [`excerpt`]
+const EXCERPT_LENGTH: usize = 200;
+
+/// Servicio que notifica los eventos de los proyectos a los usuarios afectados
+pub struct NotificationDispatcher {
+    db: PgPool,
+    events: EventBus,
+}
+
+impl NotificationDispatcher {
+    pub fn new(db: PgPool, events: EventBus) -> Self {
+        Self { db, events }
+    }
+
+    /// Arranca el envío en una tarea en segundo plano
+    pub fn spawn(self) -> JoinHandle<()> {
+        tokio::spawn(self.run())
+    }
+
+    async fn run(self) {
+        let mut rx = self.events.subscribe();
+
+        loop {
+            match rx.recv().await {
+                Ok(event) => {
+                    if let Err(e) = self.dispatch(&event).await {
+                        tracing::warn!(
+                            "No se pudo notificar un evento del proyecto {}: {}",
+                            event.project_id(),
+                            e
+                        );
+                    }
+                }
+                Err(RecvError::Lagged(skipped)) => {
+                    tracing::warn!("{} eventos se descartaron sin notificar", skipped);
+                }
+                Err(RecvError::Closed) => break,
+            }
+        }
+    }
+
+    async fn dispatch(&self, event: &ProjectEvent) -> Result<(), ProjectError> {
+        if matches!(
+            event,
+            ProjectEvent::Pull { .. }
+                | ProjectEvent::ProjectCreated { .. }
+                | ProjectEvent::ProjectDeleted { .. }
+        ) {
+            return Ok(());
+        }
+
+        let project = db::projects::find_project_by_id(&self.db, event.project_id()).await?;
+        let Some(target) = event_notification(event, &project.name) else {
+            return Ok(());
+        };
+
+        let mut user_ids = target.users;
+        if target.admins {
+            user_ids.extend(db::projects::list_project_admin_ids(&self.db, project.id).await?);
+        }
+        user_ids.retain(|&id| Some(id) != target.actor_id);
+
+        notify_users(&self.db, &user_ids, &target.notification).await?;
+        Ok(())
+    }
+}
+
+/// Notificación que genera un evento y a quién va dirigida
+#[derive(Debug)]
+struct EventNotification {
+    notification: CreateNotification,
+    /// Si se notifica a los administradores del proyecto
+    admins: bool,
+    /// Usuarios a notificar además de los administradores
+    users: Vec<i32>,
+    /// Quien provocó el evento, que no se notifica
+    actor_id: Option<i32>,
+}
+
+/// Notificación de un evento; `None` si el evento no se notifica
+fn event_notification(event: &ProjectEvent, project_name: &str) -> Option<EventNotification> {
+    let project_id = event.project_id();
+    let notification = |kind, title: String, message: &str| CreateNotification {
+        project_id: Some(project_id),
+        kind,
+        title,
+        message: excerpt(message),
+        link: Some(format!("/projects/{}", project_id)),
+    };
+
+    match event {
+        ProjectEvent::LockChanged {
+            action,
+            status,
+            actor_id,
+        } => {
+            let title = match action {
+                LockAction::Locked => format!("Se bloqueó {}", project_name),
+                LockAction::Unlocked => format!("Se desbloqueó {}", project_name),
+            };
+            Some(EventNotification {
+                notification: notification(
+                    NotificationType::Lock,
+                    title,
+                    status.reason.as_deref().unwrap_or_default(),
+                ),
+                admins: true,
+                users: Vec::new(),
+                actor_id: Some(*actor_id),
+            })
+        }
+        ProjectEvent::LockRequested { request } => {
+            let requester = request
+                .requested_by_username
+                .as_deref()
+                .unwrap_or("Alguien");
+            Some(EventNotification {
+                notification: notification(
+                    NotificationType::Lock,
+                    format!("{} solicitó bloquear {}", requester, project_name),
+                    &request.reason,
+                ),
+                admins: true,
+                users: Vec::new(),
+                actor_id: request.requested_by,
+            })
+        }
+        ProjectEvent::LockRequestReviewed { request } => {
+            let title = match request.status {
+                LockRequestStatus::Approved => {
+                    format!("Se aprobó tu solicitud de bloqueo de {}", project_name)
+                }
+                LockRequestStatus::Rejected => {
+                    format!("Se rechazó tu solicitud de bloqueo de {}", project_name)
+                }
+                LockRequestStatus::Pending => return None,
+            };
+            Some(EventNotification {
+                notification: notification(
+                    NotificationType::Lock,
+                    title,
+                    request.review_comment.as_deref().unwrap_or_default(),
+                ),
+                admins: false,
+                users: request.requested_by.into_iter().collect(),
+                actor_id: request.reviewed_by,
+            })
+        }
+        ProjectEvent::WorkingCopyDirty { summary, .. } => Some(EventNotification {
+            notification: notification(
+                NotificationType::WorkingCopy,
+                format!(
+                    "El working copy de {} tiene cambios sin commitear",
+                    project_name
+                ),
+                &describe_changes(summary),
+            ),
+            admins: true,
+            users: Vec::new(),
+            actor_id: None,
+        }),
+        ProjectEvent::Pull { .. }
+        | ProjectEvent::ProjectCreated { .. }
+        | ProjectEvent::ProjectDeleted { .. } => None,
+    }
+}
+
+/// Resumen legible de los cambios de un working copy (`2 preparados, 1 sin seguimiento`)
+fn describe_changes(summary: &WorkingCopySummary) -> String {
+    [
+        (summary.staged, "preparados"),
+        (summary.unstaged, "sin preparar"),
+        (summary.untracked, "sin seguimiento"),
+        (summary.conflicted, "en conflicto"),
+    ]
+    .into_iter()
+    .filter(|(count, _)| *count > 0)
+    .map(|(count, label)| format!("{} {}", count, label))
+    .collect::<Vec<_>>()
+    .join(", ")
+}
+
+/// Notifica a un usuario
+///
+/// # Errors
+/// Retorna error si falla la inserción
+pub async fn notify(
+    pool: &PgPool,
+    user_id: i32,
+    notification: &CreateNotification,
+) -> Result<Notification, sqlx::Error> {
+    notify_users(pool, &[user_id], notification)
+        .await?
+        .into_iter()
+        .next()
+        .ok_or(sqlx::Error::RowNotFound)
+}
+
+/// Envía la misma notificación a varios usuarios
+///
+/// Los usuarios repetidos se notifican una sola vez. El título se recorta
+/// si es demasiado largo y el enlace se descarta si no es una ruta interna
+/// o no cabe.
+///
+/// # Errors
+/// Retorna error si falla la inserción
+pub async fn notify_users(
+    pool: &PgPool,
+    user_ids: &[i32],
+    notification: &CreateNotification,
+) -> Result<Vec<Notification>, sqlx::Error> {
+    let mut user_ids = user_ids.to_vec();
+    user_ids.sort_unstable();
+    user_ids.dedup();
+    if user_ids.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let notification = normalize(notification);
+    db::notifications::insert_notifications(pool, &user_ids, &notification).await
+}
+
+/// Primeros caracteres de un texto, en una sola línea, para el mensaje de una notificación
+pub fn excerpt(content: &str) -> String {
+    let flat = content.split_whitespace().collect::<Vec<_>>().join(" ");
+    if flat.chars().count() <= EXCERPT_LENGTH {
+        return flat;
+    }
+    let cut: String = flat.chars().take(EXCERPT_LENGTH).collect();
+    format!("{}…", cut.trim_end())
+}
+
+/// Ajusta una notificación a lo que admite la tabla
+fn normalize(notification: &CreateNotification) -> CreateNotification {
+    let mut notification = notification.clone();
+
+    if notification.title.chars().count() > MAX_TITLE_LENGTH {
+        let cut: String = notification
+            .title
+            .chars()
+            .take(MAX_TITLE_LENGTH - 1)
+            .collect();
+        notification.title = format!("{}…", cut.trim_end());
+    }
+
+    notification.link = notification.link.filter(|link| {
+        let valid = is_internal_link(link) && link.chars().count() <= MAX_LINK_LENGTH;
+        if !valid {
+            tracing::warn!("Enlace de notificación descartado: {}", link);
+        }
+        valid
+    });
+
+    notification
+}
+
+/// Indica si un enlace es una ruta de la propia aplicación (`/projects/1`, no `//host` ni `https:`)
+fn is_internal_link(link: &str) -> bool {
+    link.starts_with('/') && !link.starts_with("//") && !link.contains('\\')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{LockRequest, LockStatus};
+
+    #[test]
+    fn test_excerpt() {
+        assert_eq!(excerpt("Hola\n\n  @ana,   mira"), "Hola @ana, mira");
+        let long = "ñ".repeat(EXCERPT_LENGTH + 5);
+        let cut = excerpt(&long);
+        assert_eq!(cut.chars().count(), EXCERPT_LENGTH + 1);
+        assert!(cut.ends_with('…'));
+    }
+
+    #[test]
+    fn test_normalize() {
+        let notification = CreateNotification {
+            project_id: Some(1),
+            kind: NotificationType::Comment,
+            title: "á".repeat(MAX_TITLE_LENGTH + 10),
+            message: "Mensaje".to_string(),
+            link: Some("/projects/1#comment-2".to_string()),
+        };
+        let normalized = normalize(&notification);
+        assert_eq!(normalized.title.chars().count(), MAX_TITLE_LENGTH);
+        assert_eq!(normalized.link.as_deref(), Some("/projects/1#comment-2"));
+
+        for link in [
+            "https://evil.example",
+            "//evil.example",
+            "/\\evil.example",
+            "projects/1",
+        ] {
+            let notification = CreateNotification {
+                link: Some(link.to_string()),
+                ..notification.clone()
+            };
+            assert_eq!(normalize(&notification).link, None, "{link}");
+        }
+    }
+
+    fn lock_request(status: LockRequestStatus) -> LockRequest {
+        LockRequest {
+            id: 3,
+            project_id: 7,
+            requested_by: Some(4),
+            requested_by_username: Some("dave".to_string()),
+            reason: "Migración".to_string(),
+            status,
+            reviewed_by: Some(2),
+            review_comment: Some("Ahora no".to_string()),
+            created_at: chrono::NaiveDateTime::default(),
+            reviewed_at: None,
+        }
+    }
+
+    #[test]
+    fn test_lock_event_notifications() {
+        let requested = ProjectEvent::LockRequested {
+            request: lock_request(LockRequestStatus::Pending),
+        };
+        let target = event_notification(&requested, "lumastack").unwrap();
+        assert!(target.admins);
+        assert_eq!(target.actor_id, Some(4));
+        assert_eq!(target.notification.kind, NotificationType::Lock);
+        assert_eq!(target.notification.project_id, Some(7));
+        assert_eq!(
+            target.notification.title,
+            "dave solicitó bloquear lumastack"
+        );
+        assert_eq!(target.notification.message, "Migración");
+        assert_eq!(target.notification.link.as_deref(), Some("/projects/7"));
+
+        // La revisión llega a quien pidió el bloqueo, no a los administradores
+        let reviewed = ProjectEvent::LockRequestReviewed {
+            request: lock_request(LockRequestStatus::Rejected),
+        };
+        let target = event_notification(&reviewed, "lumastack").unwrap();
+        assert!(!target.admins);
+        assert_eq!(target.users, vec![4]);
+        assert_eq!(target.actor_id, Some(2));
+        assert_eq!(
+            target.notification.title,
+            "Se rechazó tu solicitud de bloqueo de lumastack"
+        );
+        assert_eq!(target.notification.message, "Ahora no");
+
+        let unlocked = ProjectEvent::LockChanged {
+            action: LockAction::Unlocked,
+            status: LockStatus {
+                project_id: 7,
+                is_blocked: false,
+                reason: None,
+                locked_by: None,
+                locked_by_username: None,
+                locked_at: None,
+            },
+            actor_id: 1,
+        };
+        let target = event_notification(&unlocked, "lumastack").unwrap();
+        assert!(target.admins);
+        assert_eq!(target.actor_id, Some(1));
+        assert_eq!(target.notification.title, "Se desbloqueó lumastack");
+        assert_eq!(target.notification.message, "");
+    }
+
+    #[test]
+    fn test_working_copy_notification() {
+        let dirty = ProjectEvent::WorkingCopyDirty {
+            project_id: 7,
+            summary: WorkingCopySummary {
+                staged: 2,
+                unstaged: 0,
+                untracked: 3,
+                conflicted: 0,
+            },
+        };
+        let target = event_notification(&dirty, "lumastack").unwrap();
+        assert!(target.admins);
+        assert!(target.users.is_empty());
+        assert_eq!(target.actor_id, None);
+        assert_eq!(target.notification.kind, NotificationType::WorkingCopy);
+        assert_eq!(target.notification.project_id, Some(7));
+        assert_eq!(
+            target.notification.title,
+            "El working copy de lumastack tiene cambios sin commitear"
+        );
+        assert_eq!(
+            target.notification.message,
+            "2 preparados, 3 sin seguimiento"
+        );
+        assert_eq!(target.notification.link.as_deref(), Some("/projects/7"));
+    }
+}
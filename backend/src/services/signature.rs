@@ -0,0 +1,752 @@
+//! Verificación de las firmas GPG y SSH de los commits
+//!
+//! Las claves de confianza las gestionan los administradores en la tabla
+//! `signing_keys`. Las firmas SSH se verifican en proceso contra esa lista,
+//! con la misma semántica que el archivo `allowed_signers` de Git (espacio
+//! de nombres `git`). Las GPG se delegan al programa `gpg`, como hace Git,
+//! usando un keyring propio que se reconstruye al cambiar las claves.
+//!
+//! Una firma solo se considera verificada si la clave es de confianza y
+//! está autorizada para el email del committer.
+
+use std::collections::HashMap;
+use std::num::NonZeroUsize;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::sync::{Arc, Mutex, RwLock};
+
+use git2::{Commit, Oid, Repository};
+use lru::LruCache;
+use sqlx::PgPool;
+use ssh_key::{HashAlg, PublicKey, SshSig};
+
+use crate::config::SignatureConfig;
+use crate::db::signing_keys::list_signing_keys;
+use crate::models::{
+    CommitSignature, SignatureKind, SignatureStatus, SigningKey, UnverifiedReason,
+};
+use crate::services::git;
+
+/// Espacio de nombres con el que Git firma los commits por SSH
+const SSH_NAMESPACE: &str = "git";
+
+/// Archivos del keyring que se borran al reconstruirlo
+const KEYRING_FILES: &[&str] = &["pubring.kbx", "pubring.kbx~", "pubring.gpg", "trustdb.gpg"];
+
+/// Errores al gestionar las claves de confianza
+#[derive(Debug, thiserror::Error)]
+pub enum SignatureError {
+    #[error("Clave inválida: {0}")]
+    InvalidKey(String),
+
+    #[error("La verificación GPG no está disponible: no se encontró el programa gpg")]
+    GpgUnavailable,
+
+    #[error("Error al ejecutar gpg: {0}")]
+    Gpg(String),
+
+    #[error("Error de E/S: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("Error de base de datos: {0}")]
+    Database(#[from] sqlx::Error),
+}
+
+pub type Result<T> = std::result::Result<T, SignatureError>;
+
+/// Clave GPG leída de una armadura, antes de guardarla
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GpgKeyInfo {
+    /// Huella de la clave primaria, en mayúsculas
+    pub fingerprint: String,
+    /// Emails de los user IDs
+    pub emails: Vec<String>,
+}
+
+/// Firmante SSH autorizado
+struct SshSigner {
+    key: PublicKey,
+    principals: Vec<String>,
+}
+
+/// Claves de confianza cargadas en memoria
+#[derive(Default)]
+struct TrustedKeys {
+    ssh: Vec<SshSigner>,
+    /// Huella de la clave primaria → emails autorizados
+    gpg: HashMap<String, Vec<String>>,
+}
+
+/// Verifica firmas de commits y cachea los resultados por hash
+///
+/// Los commits son inmutables, así que el resultado solo cambia cuando
+/// cambian las claves de confianza; `reload` vacía la caché.
+pub struct SignatureVerifier {
+    /// Programa `gpg`; `None` si no está disponible
+    gpg_program: Option<String>,
+    gpg_home: PathBuf,
+    trusted: RwLock<TrustedKeys>,
+    cache: Option<Mutex<LruCache<Oid, CommitSignature>>>,
+}
+
+impl SignatureVerifier {
+    pub fn new(config: &SignatureConfig) -> Self {
+        let available = Command::new(&config.gpg_program)
+            .arg("--version")
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .status()
+            .is_ok_and(|status| status.success());
+        if !available {
+            tracing::warn!(
+                "No se encontró {}: las firmas GPG no se podrán verificar",
+                config.gpg_program
+            );
+        }
+
+        Self {
+            gpg_program: available.then(|| config.gpg_program.clone()),
+            gpg_home: config.gpg_home.clone(),
+            trusted: RwLock::new(TrustedKeys::default()),
+            cache: NonZeroUsize::new(config.cache_size).map(|cap| Mutex::new(LruCache::new(cap))),
+        }
+    }
+
+    /// Recarga las claves de confianza desde la base de datos
+    ///
+    /// # Errors
+    /// - `SignatureError::Database` si falla la consulta
+    /// - `SignatureError::Gpg` / `Io` si no se puede reconstruir el keyring
+    pub async fn reload(self: &Arc<Self>, pool: &PgPool) -> Result<()> {
+        let keys = list_signing_keys(pool, None).await?;
+        let verifier = Arc::clone(self);
+
+        tokio::task::spawn_blocking(move || verifier.load(&keys))
+            .await
+            .map_err(|e| SignatureError::Gpg(e.to_string()))?
+    }
+
+    /// Sustituye las claves de confianza y reconstruye el keyring GPG
+    fn load(&self, keys: &[SigningKey]) -> Result<()> {
+        // El bloqueo de escritura impide verificar mientras el keyring está a medias
+        let mut current = self.trusted.write().unwrap_or_else(|e| e.into_inner());
+        let mut trusted = TrustedKeys::default();
+        let mut armored = String::new();
+
+        for key in keys {
+            match key.kind {
+                SignatureKind::Ssh => match PublicKey::from_openssh(&key.public_key) {
+                    Ok(public_key) => trusted.ssh.push(SshSigner {
+                        key: public_key,
+                        principals: key.principals.clone(),
+                    }),
+                    Err(e) => tracing::warn!("Clave SSH {} ignorada: {}", key.id, e),
+                },
+                SignatureKind::Gpg => {
+                    trusted
+                        .gpg
+                        .insert(key.fingerprint.clone(), key.principals.clone());
+                    armored.push_str(&key.public_key);
+                    armored.push('\n');
+                }
+                SignatureKind::X509 => {}
+            }
+        }
+
+        if let Some(program) = &self.gpg_program {
+            prepare_gpg_home(&self.gpg_home)?;
+            if !armored.is_empty() {
+                run_gpg(program, &self.gpg_home, &["--import"], armored.as_bytes())?;
+            }
+        }
+
+        tracing::info!(
+            "Claves de confianza cargadas: {} SSH, {} GPG",
+            trusted.ssh.len(),
+            trusted.gpg.len()
+        );
+
+        *current = trusted;
+        if let Some(cache) = &self.cache {
+            cache.lock().unwrap_or_else(|e| e.into_inner()).clear();
+        }
+
+        Ok(())
+    }
+
+    /// Verifica la firma de un commit
+    ///
+    /// # Errors
+    /// - `GitError::Git` si no se puede leer el objeto del commit
+    pub fn verify(&self, repo: &Repository, commit: &Commit) -> git::Result<CommitSignature> {
+        if let Some(cached) = self.cached(commit.id()) {
+            return Ok(cached);
+        }
+
+        // Se mantiene hasta guardar el resultado para no cachear uno previo a `load`
+        let trusted = self.trusted.read().unwrap_or_else(|e| e.into_inner());
+        let result = match git::commit_signature(repo, commit.id())? {
+            None => CommitSignature::unsigned(),
+            Some(signed) => {
+                let email = String::from_utf8_lossy(commit.committer().email_bytes()).into_owned();
+                match signed.kind() {
+                    SignatureKind::Ssh => verify_ssh(&trusted, &signed, &email),
+                    SignatureKind::Gpg => self.verify_gpg(&trusted, &signed, &email),
+                    SignatureKind::X509 => CommitSignature::unverified(
+                        SignatureKind::X509,
+                        UnverifiedReason::Unsupported,
+                    ),
+                }
+            }
+        };
+
+        if let Some(cache) = &self.cache {
+            cache
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .put(commit.id(), result.clone());
+        }
+
+        Ok(result)
+    }
+
+    fn cached(&self, oid: Oid) -> Option<CommitSignature> {
+        let cache = self.cache.as_ref()?;
+        let mut cache = cache.lock().unwrap_or_else(|e| e.into_inner());
+        cache.get(&oid).cloned()
+    }
+
+    fn verify_gpg(
+        &self,
+        trusted: &TrustedKeys,
+        signed: &git::SignedCommit,
+        email: &str,
+    ) -> CommitSignature {
+        let Some(program) = &self.gpg_program else {
+            return CommitSignature::unverified(SignatureKind::Gpg, UnverifiedReason::Unsupported);
+        };
+
+        let status = match gpg_verify(program, &self.gpg_home, signed) {
+            Ok(status) => status,
+            Err(e) => {
+                tracing::warn!("No se pudo verificar una firma GPG: {}", e);
+                return CommitSignature::unverified(
+                    SignatureKind::Gpg,
+                    UnverifiedReason::Unsupported,
+                );
+            }
+        };
+
+        let mut result = CommitSignature::unverified(
+            SignatureKind::Gpg,
+            status.reason.unwrap_or(UnverifiedReason::Malformed),
+        );
+        result.key_fingerprint = status.fingerprint.clone();
+        result.signer = status.uid;
+
+        if status.reason.is_none() {
+            let authorized = status
+                .fingerprint
+                .as_ref()
+                .and_then(|fingerprint| trusted.gpg.get(fingerprint));
+            result.reason = match authorized {
+                None => Some(UnverifiedReason::UnknownKey),
+                Some(emails) if emails.iter().any(|e| e.eq_ignore_ascii_case(email)) => None,
+                Some(_) => Some(UnverifiedReason::IdentityMismatch),
+            };
+            if result.reason.is_none() {
+                result.status = SignatureStatus::Verified;
+            }
+        }
+
+        result
+    }
+
+    /// Lee la huella y los emails de una clave GPG sin importarla
+    ///
+    /// # Errors
+    /// - `SignatureError::GpgUnavailable` si no hay programa `gpg`
+    /// - `SignatureError::InvalidKey` si el texto no contiene exactamente una clave pública
+    pub fn inspect_gpg_key(&self, armored: &str) -> Result<GpgKeyInfo> {
+        let program = self
+            .gpg_program
+            .as_ref()
+            .ok_or(SignatureError::GpgUnavailable)?;
+        if !armored.contains("-----BEGIN PGP PUBLIC KEY BLOCK-----") {
+            return Err(SignatureError::InvalidKey(
+                "se esperaba una clave pública GPG con armadura ASCII".to_string(),
+            ));
+        }
+
+        ensure_gpg_home(&self.gpg_home)?;
+        let output = run_gpg(
+            program,
+            &self.gpg_home,
+            &["--with-colons", "--import-options", "show-only", "--import"],
+            armored.as_bytes(),
+        )
+        .map_err(|_| SignatureError::InvalidKey("gpg no pudo leer la clave".to_string()))?;
+
+        parse_gpg_key_listing(&output)
+    }
+}
+
+/// Normaliza una clave SSH y calcula su huella `SHA256:...`
+///
+/// # Errors
+/// - `SignatureError::InvalidKey` si no es una clave pública en formato OpenSSH
+pub fn parse_ssh_key(text: &str) -> Result<(PublicKey, String)> {
+    let key = PublicKey::from_openssh(text.trim())
+        .map_err(|e| SignatureError::InvalidKey(e.to_string()))?;
+    let fingerprint = key.fingerprint(HashAlg::Sha256).to_string();
+    Ok((key, fingerprint))
+}
+
+fn verify_ssh(trusted: &TrustedKeys, signed: &git::SignedCommit, email: &str) -> CommitSignature {
+    let Ok(signature) = SshSig::from_pem(signed.signature.trim()) else {
+        return CommitSignature::unverified(SignatureKind::Ssh, UnverifiedReason::Malformed);
+    };
+
+    let signing_key = PublicKey::from(signature.public_key().clone());
+    let mut result = CommitSignature::unverified(SignatureKind::Ssh, UnverifiedReason::UnknownKey);
+    result.key_fingerprint = Some(signing_key.fingerprint(HashAlg::Sha256).to_string());
+
+    let signers: Vec<&SshSigner> = trusted
+        .ssh
+        .iter()
+        .filter(|s| s.key.key_data() == signature.public_key())
+        .collect();
+    if signers.is_empty() {
+        return result;
+    }
+
+    if signing_key
+        .verify(SSH_NAMESPACE, &signed.signed_data, &signature)
+        .is_err()
+    {
+        result.reason = Some(UnverifiedReason::BadSignature);
+        return result;
+    }
+
+    let authorized = signers
+        .iter()
+        .flat_map(|s| &s.principals)
+        .any(|pattern| principal_matches(pattern, email));
+    if authorized {
+        result.status = SignatureStatus::Verified;
+        result.signer = Some(email.to_string());
+        result.reason = None;
+    } else {
+        result.signer = signers[0].principals.first().cloned();
+        result.reason = Some(UnverifiedReason::IdentityMismatch);
+    }
+
+    result
+}
+
+/// Compara un principal de `allowed_signers` con un email
+///
+/// Admite los comodines `*` y `?` de OpenSSH; sin distinguir mayúsculas.
+fn principal_matches(pattern: &str, email: &str) -> bool {
+    fn matches(pattern: &[u8], text: &[u8]) -> bool {
+        match (pattern.first(), text.first()) {
+            (None, None) => true,
+            (Some(b'*'), _) => {
+                matches(&pattern[1..], text) || (!text.is_empty() && matches(pattern, &text[1..]))
+            }
+            (Some(b'?'), Some(_)) => matches(&pattern[1..], &text[1..]),
+            (Some(p), Some(t)) if p.eq_ignore_ascii_case(t) => matches(&pattern[1..], &text[1..]),
+            _ => false,
+        }
+    }
+
+    !email.is_empty() && matches(pattern.trim().as_bytes(), email.as_bytes())
+}
+
+/// Resultado de `gpg --verify` leído de `--status-fd`
+#[derive(Debug, Default, PartialEq, Eq)]
+struct GpgStatus {
+    /// Huella de la clave primaria que firmó
+    fingerprint: Option<String>,
+    /// User ID de la firma válida
+    uid: Option<String>,
+    /// `None` si la firma es criptográficamente válida
+    reason: Option<UnverifiedReason>,
+}
+
+/// Interpreta las líneas `[GNUPG:]` de una verificación
+fn parse_gpg_status(output: &str) -> GpgStatus {
+    let mut status = GpgStatus {
+        reason: Some(UnverifiedReason::Malformed),
+        ..GpgStatus::default()
+    };
+    let mut good = false;
+
+    for line in output.lines() {
+        let Some(rest) = line.strip_prefix("[GNUPG:] ") else {
+            continue;
+        };
+        let (keyword, args) = rest.split_once(' ').unwrap_or((rest, ""));
+        let fields: Vec<&str> = args.split(' ').collect();
+
+        let reason = match keyword {
+            "GOODSIG" => {
+                good = true;
+                status.uid = args.split_once(' ').map(|(_, uid)| uid.to_string());
+                continue;
+            }
+            // La huella de la clave primaria es el último campo
+            "VALIDSIG" => {
+                status.fingerprint = fields
+                    .get(9)
+                    .or(fields.first())
+                    .map(|f| f.to_ascii_uppercase());
+                continue;
+            }
+            "BADSIG" => UnverifiedReason::BadSignature,
+            "EXPSIG" | "EXPKEYSIG" => UnverifiedReason::ExpiredKey,
+            "REVKEYSIG" => UnverifiedReason::RevokedKey,
+            "NO_PUBKEY" => UnverifiedReason::UnknownKey,
+            "ERRSIG" => {
+                // La huella completa solo aparece si la firma la incluye
+                if let Some(fpr) = fields.get(6).filter(|f| **f != "-") {
+                    status.fingerprint = Some(fpr.to_ascii_uppercase());
+                }
+                match fields.get(5) {
+                    Some(&"9") => UnverifiedReason::UnknownKey,
+                    Some(&"4") => UnverifiedReason::Unsupported,
+                    _ => UnverifiedReason::Malformed,
+                }
+            }
+            _ => continue,
+        };
+
+        // El primer error encontrado es el más específico
+        if good || status.reason == Some(UnverifiedReason::Malformed) {
+            status.reason = Some(reason);
+            good = false;
+        }
+    }
+
+    if good && status.fingerprint.is_some() {
+        status.reason = None;
+    }
+    status
+}
+
+/// Interpreta el listado `--with-colons` de una clave importada con `show-only`
+fn parse_gpg_key_listing(output: &str) -> Result<GpgKeyInfo> {
+    let mut primaries = 0;
+    let mut fingerprint = None;
+    let mut expect_primary_fpr = false;
+    let mut emails: Vec<String> = Vec::new();
+
+    for line in output.lines() {
+        let fields: Vec<&str> = line.split(':').collect();
+        match fields.first() {
+            Some(&"pub") => {
+                primaries += 1;
+                expect_primary_fpr = true;
+            }
+            Some(&"fpr") if expect_primary_fpr => {
+                fingerprint = fields.get(9).map(|f| f.to_ascii_uppercase());
+                expect_primary_fpr = false;
+            }
+            Some(&"uid") => {
+                let uid = unescape_colons(fields.get(9).copied().unwrap_or_default());
+                let email = match (uid.rfind('<'), uid.rfind('>')) {
+                    (Some(start), Some(end)) if start < end => &uid[start + 1..end],
+                    _ => uid.as_str(),
+                };
+                if email.contains('@') && !emails.iter().any(|e| e.eq_ignore_ascii_case(email)) {
+                    emails.push(email.to_string());
+                }
+            }
+            _ => {}
+        }
+    }
+
+    match (primaries, fingerprint) {
+        (1, Some(fingerprint)) => Ok(GpgKeyInfo {
+            fingerprint,
+            emails,
+        }),
+        (0, _) | (_, None) => Err(SignatureError::InvalidKey(
+            "no contiene ninguna clave pública".to_string(),
+        )),
+        _ => Err(SignatureError::InvalidKey(
+            "contiene más de una clave pública".to_string(),
+        )),
+    }
+}
+
+/// Deshace el escapado `\xNN` de los campos de `--with-colons`
+fn unescape_colons(field: &str) -> String {
+    let mut bytes = Vec::with_capacity(field.len());
+    let raw = field.as_bytes();
+    let mut i = 0;
+    while i < raw.len() {
+        if raw[i] == b'\\' && raw.get(i + 1) == Some(&b'x') {
+            if let Some(byte) = field
+                .get(i + 2..i + 4)
+                .and_then(|hex| u8::from_str_radix(hex, 16).ok())
+            {
+                bytes.push(byte);
+                i += 4;
+                continue;
+            }
+        }
+        bytes.push(raw[i]);
+        i += 1;
+    }
+    String::from_utf8_lossy(&bytes).into_owned()
+}
+
+/// Crea el directorio del keyring con permisos solo para el propietario
+fn ensure_gpg_home(home: &Path) -> std::io::Result<()> {
+    std::fs::create_dir_all(home)?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        std::fs::set_permissions(home, std::fs::Permissions::from_mode(0o700))?;
+    }
+    Ok(())
+}
+
+/// Crea el directorio del keyring y elimina las claves anteriores
+fn prepare_gpg_home(home: &Path) -> std::io::Result<()> {
+    ensure_gpg_home(home)?;
+    for file in KEYRING_FILES {
+        match std::fs::remove_file(home.join(file)) {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => return Err(e),
+            _ => {}
+        }
+    }
+    Ok(())
+}
+
+/// Ejecuta `gpg --verify` sobre una firma separada
+fn gpg_verify(program: &str, home: &Path, signed: &git::SignedCommit) -> Result<GpgStatus> {
+    let id = uuid::Uuid::new_v4();
+    let signature_path = home.join(format!("verify-{}.sig", id));
+    let data_path = home.join(format!("verify-{}.dat", id));
+
+    let result = std::fs::write(&signature_path, &signed.signature)
+        .and_then(|_| std::fs::write(&data_path, &signed.signed_data))
+        .map_err(SignatureError::from)
+        .and_then(|_| {
+            let output = gpg_command(program, home)
+                .args(["--status-fd", "1", "--verify"])
+                .arg(&signature_path)
+                .arg(&data_path)
+                .stdin(Stdio::null())
+                .stderr(Stdio::null())
+                .output()?;
+            Ok(parse_gpg_status(&String::from_utf8_lossy(&output.stdout)))
+        });
+
+    let _ = std::fs::remove_file(&signature_path);
+    let _ = std::fs::remove_file(&data_path);
+    result
+}
+
+/// Ejecuta gpg pasando `input` por stdin y retorna su stdout
+fn run_gpg(program: &str, home: &Path, args: &[&str], input: &[u8]) -> Result<String> {
+    use std::io::Write;
+
+    let mut child = gpg_command(program, home)
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()?;
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin.write_all(input)?;
+    }
+    let output = child.wait_with_output()?;
+
+    if !output.status.success() {
+        return Err(SignatureError::Gpg(
+            String::from_utf8_lossy(&output.stderr).trim().to_string(),
+        ));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+fn gpg_command(program: &str, home: &Path) -> Command {
+    let mut command = Command::new(program);
+    command.arg("--homedir").arg(home).args([
+        "--batch",
+        "--no-tty",
+        "--trust-model",
+        "always",
+        "--no-auto-key-retrieve",
+    ]);
+    command
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::git::test_support::{commit_files, init_repo};
+    use aes_gcm::aead::OsRng;
+    use ssh_key::{Algorithm, LineEnding, PrivateKey};
+
+    fn verifier() -> SignatureVerifier {
+        SignatureVerifier {
+            gpg_program: None,
+            gpg_home: PathBuf::new(),
+            trusted: RwLock::new(TrustedKeys::default()),
+            cache: None,
+        }
+    }
+
+    fn ssh_key(principals: &[&str], public_key: &PublicKey) -> SigningKey {
+        SigningKey {
+            id: 1,
+            kind: SignatureKind::Ssh,
+            fingerprint: public_key.fingerprint(HashAlg::Sha256).to_string(),
+            principals: principals.iter().map(|p| p.to_string()).collect(),
+            public_key: public_key.to_openssh().unwrap(),
+            comment: None,
+            created_by: None,
+            created_at: chrono::Utc::now().naive_utc(),
+        }
+    }
+
+    /// Crea un commit firmado con `key` encima de `HEAD` (sin mover `HEAD`)
+    fn sign_commit(repo: &Repository, key: &PrivateKey, namespace: &str) -> Oid {
+        let head = repo.head().unwrap().peel_to_commit().unwrap();
+        let buffer = repo
+            .commit_create_buffer(
+                &head.author(),
+                &head.committer(),
+                "signed",
+                &head.tree().unwrap(),
+                &[&head],
+            )
+            .unwrap();
+        let signature = key
+            .sign(namespace, HashAlg::Sha512, &buffer)
+            .unwrap()
+            .to_pem(LineEnding::LF)
+            .unwrap();
+        repo.commit_signed(buffer.as_str().unwrap(), &signature, None)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_verify_ssh_signatures() {
+        let (_dir, repo) = init_repo();
+        let unsigned = commit_files(&repo, &[("a", b"1")], "unsigned");
+        let key = PrivateKey::random(&mut OsRng, Algorithm::Ed25519).unwrap();
+        let stranger = PrivateKey::random(&mut OsRng, Algorithm::Ed25519).unwrap();
+        let signed = sign_commit(&repo, &key, "git");
+        let wrong_namespace = sign_commit(&repo, &key, "file");
+        let unknown = sign_commit(&repo, &stranger, "git");
+
+        let verifier = verifier();
+        let verify = |oid| {
+            verifier
+                .verify(&repo, &repo.find_commit(oid).unwrap())
+                .unwrap()
+        };
+
+        assert_eq!(verify(unsigned).status, SignatureStatus::Unsigned);
+        assert_eq!(verify(signed).reason, Some(UnverifiedReason::UnknownKey));
+
+        verifier
+            .load(&[ssh_key(&["*@example.com"], key.public_key())])
+            .unwrap();
+        let result = verify(signed);
+        assert_eq!(result.status, SignatureStatus::Verified);
+        assert_eq!(result.kind, Some(SignatureKind::Ssh));
+        assert_eq!(result.signer.as_deref(), Some("test@example.com"));
+        assert_eq!(
+            result.key_fingerprint,
+            Some(key.public_key().fingerprint(HashAlg::Sha256).to_string())
+        );
+        assert_eq!(
+            verify(wrong_namespace).reason,
+            Some(UnverifiedReason::BadSignature)
+        );
+        assert_eq!(verify(unknown).reason, Some(UnverifiedReason::UnknownKey));
+
+        verifier
+            .load(&[ssh_key(&["ada@example.com"], key.public_key())])
+            .unwrap();
+        let result = verify(signed);
+        assert_eq!(result.status, SignatureStatus::Unverified);
+        assert_eq!(result.reason, Some(UnverifiedReason::IdentityMismatch));
+        assert_eq!(result.signer.as_deref(), Some("ada@example.com"));
+    }
+
+    #[test]
+    fn test_principal_matches() {
+        assert!(principal_matches("ada@example.com", "Ada@Example.com"));
+        assert!(principal_matches("*@example.com", "ada@example.com"));
+        assert!(principal_matches("ad?@*", "ada@example.com"));
+        assert!(!principal_matches("*@example.com", "ada@example.org"));
+        assert!(!principal_matches("*", ""));
+    }
+
+    #[test]
+    fn test_parse_gpg_status() {
+        let good = "[GNUPG:] NEWSIG\n\
+            [GNUPG:] GOODSIG DDC2E9898857A71A Ada <ada@example.com>\n\
+            [GNUPG:] VALIDSIG 1111 2026-10-19 1792375738 0 4 0 22 8 00 8866fe2fc85787a035c99649ddc2e9898857a71a\n";
+        assert_eq!(
+            parse_gpg_status(good),
+            GpgStatus {
+                fingerprint: Some("8866FE2FC85787A035C99649DDC2E9898857A71A".to_string()),
+                uid: Some("Ada <ada@example.com>".to_string()),
+                reason: None,
+            }
+        );
+
+        let missing = "[GNUPG:] ERRSIG DDC2E9898857A71A 22 8 00 1792375738 9 8866fe2f\n\
+            [GNUPG:] NO_PUBKEY DDC2E9898857A71A\n";
+        let status = parse_gpg_status(missing);
+        assert_eq!(status.reason, Some(UnverifiedReason::UnknownKey));
+        assert_eq!(status.fingerprint.as_deref(), Some("8866FE2F"));
+
+        let bad = "[GNUPG:] BADSIG DDC2E9898857A71A Ada <ada@example.com>\n";
+        assert_eq!(
+            parse_gpg_status(bad).reason,
+            Some(UnverifiedReason::BadSignature)
+        );
+
+        let expired = "[GNUPG:] EXPKEYSIG DDC2E9898857A71A Ada <ada@example.com>\n\
+            [GNUPG:] VALIDSIG 8866 2026-10-19 1792375738 0 4 0 22 8 00 8866\n";
+        assert_eq!(
+            parse_gpg_status(expired).reason,
+            Some(UnverifiedReason::ExpiredKey)
+        );
+        assert_eq!(
+            parse_gpg_status("").reason,
+            Some(UnverifiedReason::Malformed)
+        );
+    }
+
+    #[test]
+    fn test_parse_gpg_key_listing() {
+        let listing = "pub:-:255:22:DDC2E9898857A71A:1792375738:::-:::scSC:::::ed25519:::0:\n\
+            fpr:::::::::8866FE2FC85787A035C99649DDC2E9898857A71A:\n\
+            uid:-::::1792375738::262C::Ada <ada@example.com>::::::::::0:\n\
+            uid:-::::1792375738::262D::Ada\\x3a work <ADA@example.com>::::::::::0:\n\
+            uid:-::::1792375738::262E::bob@example.com::::::::::0:\n\
+            sub:-:255:18:1234:1792375738::::::e:::::cv25519::\n\
+            fpr:::::::::AAAA:\n";
+        assert_eq!(
+            parse_gpg_key_listing(listing).unwrap(),
+            GpgKeyInfo {
+                fingerprint: "8866FE2FC85787A035C99649DDC2E9898857A71A".to_string(),
+                emails: vec!["ada@example.com".to_string(), "bob@example.com".to_string()],
+            }
+        );
+
+        let two = format!("{}{}", listing, listing);
+        assert!(parse_gpg_key_listing(&two).is_err());
+        assert!(parse_gpg_key_listing("").is_err());
+    }
+}
@@ -0,0 +1,161 @@
+//! Cifrado en reposo de las credenciales de los remotos
+//!
+//! Usa AES-256-GCM con un nonce aleatorio por secreto. El resultado guardado
+//! es `nonce (12 bytes) || texto cifrado + tag`. El dato asociado liga cada
+//! secreto a su proyecto, de modo que copiar la fila cifrada a otro
+//! proyecto no permite descifrarla.
+
+use aes_gcm::{
+    aead::{Aead, AeadCore, KeyInit, OsRng, Payload},
+    Aes256Gcm, Key, Nonce,
+};
+
+use crate::config::VaultConfig;
+
+const NONCE_LEN: usize = 12;
+
+/// Errores del almacén de credenciales
+#[derive(Debug, thiserror::Error)]
+pub enum VaultError {
+    #[error("El almacén de credenciales no está configurado (CREDENTIALS_KEY)")]
+    NotConfigured,
+
+    #[error("No se pudo cifrar la credencial")]
+    Encryption,
+
+    #[error("No se pudo descifrar la credencial: la clave cambió o los datos están dañados")]
+    Decryption,
+}
+
+pub type Result<T> = std::result::Result<T, VaultError>;
+
+/// Cifra y descifra secretos con la clave de `CREDENTIALS_KEY`
+#[derive(Clone)]
+pub struct CredentialVault {
+    cipher: Option<Aes256Gcm>,
+}
+
+impl CredentialVault {
+    pub fn new(config: &VaultConfig) -> Self {
+        Self {
+            cipher: config
+                .key
+                .map(|key| Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(&key))),
+        }
+    }
+
+    pub fn is_configured(&self) -> bool {
+        self.cipher.is_some()
+    }
+
+    /// Cifra `plaintext` ligándolo a `context`
+    ///
+    /// # Errors
+    /// - `VaultError::NotConfigured` si no hay clave configurada
+    pub fn seal(&self, plaintext: &[u8], context: &[u8]) -> Result<Vec<u8>> {
+        let cipher = self.cipher.as_ref().ok_or(VaultError::NotConfigured)?;
+        let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+        let ciphertext = cipher
+            .encrypt(
+                &nonce,
+                Payload {
+                    msg: plaintext,
+                    aad: context,
+                },
+            )
+            .map_err(|_| VaultError::Encryption)?;
+
+        let mut sealed = Vec::with_capacity(NONCE_LEN + ciphertext.len());
+        sealed.extend_from_slice(&nonce);
+        sealed.extend_from_slice(&ciphertext);
+        Ok(sealed)
+    }
+
+    /// Descifra un secreto producido por [`CredentialVault::seal`] con el mismo `context`
+    ///
+    /// # Errors
+    /// - `VaultError::NotConfigured` si no hay clave configurada
+    /// - `VaultError::Decryption` si la clave o el contexto no coinciden o los datos están dañados
+    pub fn open(&self, sealed: &[u8], context: &[u8]) -> Result<Vec<u8>> {
+        let cipher = self.cipher.as_ref().ok_or(VaultError::NotConfigured)?;
+        if sealed.len() <= NONCE_LEN {
+            return Err(VaultError::Decryption);
+        }
+        let (nonce, ciphertext) = sealed.split_at(NONCE_LEN);
+
+        cipher
+            .decrypt(
+                Nonce::from_slice(nonce),
+                Payload {
+                    msg: ciphertext,
+                    aad: context,
+                },
+            )
+            .map_err(|_| VaultError::Decryption)
+    }
+}
+
+impl std::fmt::Debug for CredentialVault {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CredentialVault")
+            .field("configured", &self.is_configured())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn vault(key: u8) -> CredentialVault {
+        CredentialVault::new(&VaultConfig {
+            key: Some([key; 32]),
+        })
+    }
+
+    #[test]
+    fn test_seal_and_open() {
+        let vault = vault(7);
+        let sealed = vault.seal(b"ghp_secret", b"project:1").unwrap();
+        assert!(!sealed.windows(10).any(|w| w == b"ghp_secret"));
+        assert_eq!(vault.open(&sealed, b"project:1").unwrap(), b"ghp_secret");
+
+        // Cada cifrado usa un nonce distinto
+        assert_ne!(sealed, vault.seal(b"ghp_secret", b"project:1").unwrap());
+    }
+
+    #[test]
+    fn test_open_rejects_wrong_context_key_or_tampering() {
+        let sealed = vault(7).seal(b"ghp_secret", b"project:1").unwrap();
+
+        assert!(matches!(
+            vault(7).open(&sealed, b"project:2"),
+            Err(VaultError::Decryption)
+        ));
+        assert!(matches!(
+            vault(8).open(&sealed, b"project:1"),
+            Err(VaultError::Decryption)
+        ));
+
+        let mut tampered = sealed.clone();
+        *tampered.last_mut().unwrap() ^= 1;
+        assert!(matches!(
+            vault(7).open(&tampered, b"project:1"),
+            Err(VaultError::Decryption)
+        ));
+        assert!(matches!(
+            vault(7).open(&sealed[..5], b"project:1"),
+            Err(VaultError::Decryption)
+        ));
+    }
+
+    #[test]
+    fn test_unconfigured_vault() {
+        let vault = CredentialVault::new(&VaultConfig::default());
+        assert!(!vault.is_configured());
+        assert!(matches!(
+            vault.seal(b"x", b"project:1"),
+            Err(VaultError::NotConfigured)
+        ));
+    }
+}
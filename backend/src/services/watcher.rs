@@ -0,0 +1,514 @@
+//! Detección de pulls en los repositorios monitoreados (RF-009)
+//!
+//! Vigila `HEAD`, `packed-refs` y `refs/` dentro del directorio Git de cada
+//! proyecto. Los cambios se agrupan (debounce) y, pasado el intervalo sin
+//! actividad, se comparan las puntas de las ramas locales con el último
+//! snapshot. Por cada rama creada o movida se registra un `pull_event`, se
+//! guardan los commits nuevos en la caché, se actualiza `last_pull_at` y se
+//! publica un [`ProjectEvent::Pull`] en el bus de eventos.
+//!
+//! Los repositorios que no se pueden observar con eventos del sistema de
+//! archivos (o todos, con `WATCHER_MODE=poll`) se revisan periódicamente.
+//! La lista de proyectos y el snapshot inicial se leen al arrancar, por lo
+//! que los cambios ocurridos con el servidor detenido no generan eventos. La
+//! lista se vuelve a leer cuando se registra o elimina un proyecto
+//! ([`ProjectEvent::ProjectCreated`], [`ProjectEvent::ProjectDeleted`]).
+//!
+//! Los worktrees enlazados comparten las referencias del repositorio
+//! principal: si varios proyectos apuntan al mismo directorio Git común
+//! solo se vigila uno, para no registrar cada pull dos veces.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use notify::{RecommendedWatcher, RecursiveMode, Watcher};
+use sqlx::PgPool;
+use tokio::sync::broadcast::error::{RecvError, TryRecvError};
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+use tokio::time::{Instant, MissedTickBehavior};
+
+use crate::config::{WatchMode, WatcherConfig};
+use crate::db;
+use crate::db::projects::ProjectError;
+use crate::models::{CreatePullEvent, Project};
+use crate::services::events::{EventBus, ProjectEvent};
+use crate::services::git::{self, RefSnapshot, RefUpdate, RepositoryIdentity};
+
+/// Commits nuevos leídos como máximo por rama en cada pull
+const MAX_PULL_COMMITS: usize = 500;
+
+/// Estado de un proyecto vigilado
+struct WatchedProject {
+    repository_path: String,
+    snapshot: RefSnapshot,
+}
+
+/// Proyectos vigilados y cómo se vigilan
+struct Watching {
+    watched: HashMap<i32, WatchedProject>,
+    /// Proyectos que se revisan por polling
+    polled: Vec<i32>,
+    /// Debe mantenerse vivo mientras se vigilen estos proyectos
+    _fs_watcher: Option<RecommendedWatcher>,
+}
+
+/// Servicio que detecta pulls en todos los proyectos
+pub struct PullWatcher {
+    db: PgPool,
+    events: EventBus,
+    config: WatcherConfig,
+}
+
+impl PullWatcher {
+    pub fn new(db: PgPool, events: EventBus, config: WatcherConfig) -> Self {
+        Self { db, events, config }
+    }
+
+    /// Arranca el watcher en una tarea en segundo plano
+    pub fn spawn(self) -> JoinHandle<()> {
+        tokio::spawn(self.run())
+    }
+
+    async fn run(self) {
+        // Suscribirse antes de leer los proyectos para no perder altas
+        let mut events = self.events.subscribe();
+        let (tx, mut rx) = mpsc::unbounded_channel::<i32>();
+
+        let mut current = match self.load_projects(HashMap::new(), &tx).await {
+            Ok(watching) => watching,
+            Err(e) => {
+                tracing::error!(
+                    "Watcher de pulls desactivado: no se pudieron leer los proyectos: {}",
+                    e
+                );
+                return;
+            }
+        };
+
+        let mut debouncer = Debouncer::new(self.config.debounce);
+        let mut poll = tokio::time::interval(self.config.poll_interval);
+        poll.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+        loop {
+            let deadline = debouncer.next_deadline();
+
+            tokio::select! {
+                Some(project_id) = rx.recv() => debouncer.touch(project_id, Instant::now()),
+                _ = sleep_until(deadline), if deadline.is_some() => {
+                    for project_id in debouncer.take_due(Instant::now()) {
+                        self.check_project(project_id, &mut current.watched).await;
+                    }
+                }
+                _ = poll.tick(), if !current.polled.is_empty() => {
+                    for &project_id in &current.polled {
+                        self.check_project(project_id, &mut current.watched).await;
+                    }
+                }
+                event = events.recv() => match event {
+                    Ok(ProjectEvent::ProjectCreated { .. } | ProjectEvent::ProjectDeleted { .. })
+                    | Err(RecvError::Lagged(_)) => {
+                        // Un escaneo publica un evento por proyecto; basta con recargar una vez
+                        while !matches!(
+                            events.try_recv(),
+                            Err(TryRecvError::Empty | TryRecvError::Closed)
+                        ) {}
+
+                        let previous = std::mem::take(&mut current.watched);
+                        match self.load_projects(previous, &tx).await {
+                            Ok(watching) => {
+                                // Lo ocurrido mientras se recargaba se detecta
+                                // comparando con los snapshots conservados
+                                for &project_id in watching.watched.keys() {
+                                    debouncer.touch(project_id, Instant::now());
+                                }
+                                current = watching;
+                            }
+                            Err(e) => tracing::error!(
+                                "No se pudo recargar la lista de proyectos vigilados: {}",
+                                e
+                            ),
+                        }
+                    }
+                    Ok(_) => {}
+                    Err(RecvError::Closed) => break,
+                },
+            }
+        }
+    }
+
+    /// Lee los proyectos y empieza a vigilar sus repositorios
+    ///
+    /// Los proyectos de `previous` que siguen existiendo con la misma ruta
+    /// conservan su snapshot, para no perder los pulls ocurridos desde la
+    /// última revisión.
+    async fn load_projects(
+        &self,
+        mut previous: HashMap<i32, WatchedProject>,
+        tx: &mpsc::UnboundedSender<i32>,
+    ) -> Result<Watching, ProjectError> {
+        let projects = db::projects::list_projects(&self.db).await?;
+
+        let mut states = Vec::new();
+        for project in projects {
+            match initial_state(&project).await {
+                Ok((identity, mut state)) => {
+                    if let Some(old) = previous.remove(&project.id) {
+                        if old.repository_path == state.repository_path {
+                            state.snapshot = old.snapshot;
+                        }
+                    }
+                    states.push((project.id, identity, state));
+                }
+                Err(e) => {
+                    tracing::warn!("No se vigilará el proyecto {}: {}", project.id, e);
+                }
+            }
+        }
+
+        // Con varios proyectos sobre el mismo repositorio se vigila el
+        // checkout principal y no sus worktrees enlazados
+        states.sort_by_key(|(id, identity, _)| (identity.linked_worktree, *id));
+
+        let mut watched = HashMap::new();
+        let mut roots = Vec::new();
+        for (project_id, identity, state) in states {
+            let common_dir = identity.common_dir;
+            if let Some((_, owner)) = roots.iter().find(|(dir, _)| *dir == common_dir) {
+                tracing::info!(
+                    "El proyecto {} es un worktree del repositorio del proyecto {}; sus ramas se vigilan allí",
+                    project_id,
+                    owner
+                );
+                continue;
+            }
+            roots.push((common_dir, project_id));
+            watched.insert(project_id, state);
+        }
+
+        let (fs_watcher, mut polled) = match self.config.mode {
+            WatchMode::Poll => (None, roots.iter().map(|(_, id)| *id).collect()),
+            WatchMode::Auto => watch_filesystem(roots, tx.clone()),
+        };
+        polled.sort_unstable();
+
+        tracing::info!(
+            "Watcher de pulls activo: {} proyectos ({} por polling)",
+            watched.len(),
+            polled.len()
+        );
+
+        Ok(Watching {
+            watched,
+            polled,
+            _fs_watcher: fs_watcher,
+        })
+    }
+
+    /// Compara las ramas de un proyecto con su último snapshot y registra los cambios
+    async fn check_project(&self, project_id: i32, watched: &mut HashMap<i32, WatchedProject>) {
+        let Some(state) = watched.get_mut(&project_id) else {
+            return;
+        };
+
+        let path = state.repository_path.clone();
+        let before = state.snapshot.clone();
+        let result = tokio::task::spawn_blocking(move || -> git::Result<_> {
+            let repo = git::open_repository(&path)?;
+            let after = git::snapshot_branches(&repo)?;
+            let updates = git::detect_updates(&repo, &before, &after, MAX_PULL_COMMITS)?;
+            Ok((after, updates))
+        })
+        .await;
+
+        let updates = match result {
+            Ok(Ok((after, updates))) => {
+                state.snapshot = after;
+                updates
+            }
+            Ok(Err(e)) => {
+                tracing::warn!(
+                    "No se pudieron leer las ramas del proyecto {}: {}",
+                    project_id,
+                    e
+                );
+                return;
+            }
+            Err(e) => {
+                tracing::error!(
+                    "Tarea de revisión del proyecto {} abortada: {}",
+                    project_id,
+                    e
+                );
+                return;
+            }
+        };
+
+        if updates.is_empty() {
+            return;
+        }
+
+        for update in updates {
+            if let Err(e) = self.record_update(project_id, update).await {
+                tracing::error!(
+                    "No se pudo registrar el pull del proyecto {}: {}",
+                    project_id,
+                    e
+                );
+            }
+        }
+
+        if let Err(e) = db::projects::update_last_pull_at(&self.db, project_id).await {
+            tracing::error!(
+                "No se pudo actualizar last_pull_at del proyecto {}: {}",
+                project_id,
+                e
+            );
+        }
+    }
+
+    async fn record_update(&self, project_id: i32, update: RefUpdate) -> Result<(), sqlx::Error> {
+        let inserted = db::commits::insert_commits(&self.db, project_id, &update.commits).await?;
+
+        let event = db::pulls::create_pull_event(
+            &self.db,
+            CreatePullEvent {
+                project_id,
+                ref_name: update.ref_name,
+                old_commit: update.old.map(|oid| oid.to_string()),
+                new_commit: update.new.to_string(),
+                commit_count: i32::try_from(update.commits.len()).unwrap_or(i32::MAX),
+            },
+        )
+        .await?;
+
+        tracing::info!(
+            "Pull detectado en el proyecto {}: {} {} -> {} ({} commits, {} nuevos en caché)",
+            project_id,
+            event.ref_name,
+            event.old_commit.as_deref().unwrap_or("(nueva)"),
+            event.new_commit,
+            event.commit_count,
+            inserted
+        );
+
+        self.events.publish(ProjectEvent::Pull {
+            event,
+            commits: update.commits,
+        });
+
+        Ok(())
+    }
+}
+
+/// Abre el repositorio de un proyecto y toma el snapshot inicial de sus ramas
+///
+/// Retorna también la identidad del repositorio: su directorio Git común,
+/// que es el que se vigila porque ahí viven las referencias incluso en los
+/// worktrees enlazados.
+async fn initial_state(project: &Project) -> git::Result<(RepositoryIdentity, WatchedProject)> {
+    let path = project.repository_path.clone();
+
+    let result = tokio::task::spawn_blocking(move || -> git::Result<_> {
+        let repo = git::open_repository(&path)?;
+        let identity = git::repository_identity(&repo);
+        let snapshot = git::snapshot_branches(&repo)?;
+        Ok((
+            identity,
+            WatchedProject {
+                repository_path: path,
+                snapshot,
+            },
+        ))
+    })
+    .await;
+
+    result.unwrap_or(Err(git::GitError::RepositoryNotFound))
+}
+
+/// Registra los directorios Git en un watcher del sistema de archivos
+///
+/// Retorna el watcher (que debe mantenerse vivo) y los proyectos que no se
+/// pudieron registrar y deben revisarse por polling.
+fn watch_filesystem(
+    roots: Vec<(PathBuf, i32)>,
+    tx: mpsc::UnboundedSender<i32>,
+) -> (Option<RecommendedWatcher>, Vec<i32>) {
+    let roots = Arc::new(roots);
+    let callback_roots = Arc::clone(&roots);
+
+    let handler = move |result: notify::Result<notify::Event>| {
+        let Ok(event) = result else {
+            return;
+        };
+        for path in &event.paths {
+            let project = callback_roots.iter().find_map(|(git_dir, id)| {
+                let relative = path.strip_prefix(git_dir).ok()?;
+                is_ref_path(relative).then_some(*id)
+            });
+            if let Some(project_id) = project {
+                let _ = tx.send(project_id);
+            }
+        }
+    };
+
+    let mut watcher = match notify::recommended_watcher(handler) {
+        Ok(watcher) => watcher,
+        Err(e) => {
+            tracing::warn!(
+                "Eventos del sistema de archivos no disponibles, se usará polling: {}",
+                e
+            );
+            return (None, roots.iter().map(|(_, id)| *id).collect());
+        }
+    };
+
+    let mut polled = Vec::new();
+    for (git_dir, project_id) in roots.iter() {
+        let registered = watcher
+            .watch(git_dir, RecursiveMode::NonRecursive)
+            .and_then(|_| watcher.watch(&git_dir.join("refs"), RecursiveMode::Recursive));
+        if let Err(e) = registered {
+            tracing::warn!(
+                "No se pudo vigilar {} (proyecto {}), se usará polling: {}",
+                git_dir.display(),
+                project_id,
+                e
+            );
+            polled.push(*project_id);
+        }
+    }
+
+    (Some(watcher), polled)
+}
+
+/// Indica si una ruta relativa al directorio Git afecta a las referencias
+///
+/// Se ignoran los archivos `.lock` que Git usa mientras escribe: el cambio
+/// real llega con el `rename` final sobre el archivo definitivo.
+fn is_ref_path(relative: &Path) -> bool {
+    if relative.extension().is_some_and(|ext| ext == "lock") {
+        return false;
+    }
+
+    relative == Path::new("HEAD")
+        || relative == Path::new("packed-refs")
+        || relative.starts_with("refs")
+}
+
+async fn sleep_until(deadline: Option<Instant>) {
+    if let Some(deadline) = deadline {
+        tokio::time::sleep_until(deadline).await;
+    }
+}
+
+/// Agrupa cambios seguidos de un mismo proyecto
+///
+/// Cada cambio pospone la revisión del proyecto hasta que pase `delay` sin
+/// nuevos cambios, de modo que un `git pull` (que reescribe varias
+/// referencias) produce una única revisión.
+struct Debouncer {
+    delay: std::time::Duration,
+    pending: HashMap<i32, Instant>,
+}
+
+impl Debouncer {
+    fn new(delay: std::time::Duration) -> Self {
+        Self {
+            delay,
+            pending: HashMap::new(),
+        }
+    }
+
+    fn touch(&mut self, project_id: i32, now: Instant) {
+        self.pending.insert(project_id, now + self.delay);
+    }
+
+    fn next_deadline(&self) -> Option<Instant> {
+        self.pending.values().min().copied()
+    }
+
+    fn take_due(&mut self, now: Instant) -> Vec<i32> {
+        let mut due: Vec<i32> = self
+            .pending
+            .iter()
+            .filter(|(_, deadline)| **deadline <= now)
+            .map(|(id, _)| *id)
+            .collect();
+        due.sort_unstable();
+
+        for id in &due {
+            self.pending.remove(id);
+        }
+
+        due
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn test_is_ref_path() {
+        assert!(is_ref_path(Path::new("HEAD")));
+        assert!(is_ref_path(Path::new("packed-refs")));
+        assert!(is_ref_path(Path::new("refs/heads/main")));
+        assert!(is_ref_path(Path::new("refs/heads/feature/login")));
+
+        assert!(!is_ref_path(Path::new("HEAD.lock")));
+        assert!(!is_ref_path(Path::new("refs/heads/main.lock")));
+        assert!(!is_ref_path(Path::new("FETCH_HEAD")));
+        assert!(!is_ref_path(Path::new("ORIG_HEAD")));
+        assert!(!is_ref_path(Path::new("index")));
+        assert!(!is_ref_path(Path::new("objects/ab/cdef")));
+    }
+
+    #[test]
+    fn test_debouncer_waits_for_quiet_period() {
+        let start = Instant::now();
+        let mut debouncer = Debouncer::new(Duration::from_millis(100));
+        assert_eq!(debouncer.next_deadline(), None);
+
+        debouncer.touch(1, start);
+        debouncer.touch(1, start + Duration::from_millis(50));
+        debouncer.touch(2, start + Duration::from_millis(20));
+        assert_eq!(
+            debouncer.next_deadline(),
+            Some(start + Duration::from_millis(120))
+        );
+
+        assert!(debouncer
+            .take_due(start + Duration::from_millis(110))
+            .is_empty());
+        assert_eq!(
+            debouncer.take_due(start + Duration::from_millis(130)),
+            vec![2]
+        );
+        assert_eq!(
+            debouncer.take_due(start + Duration::from_millis(150)),
+            vec![1]
+        );
+        assert_eq!(debouncer.next_deadline(), None);
+    }
+
+    #[tokio::test]
+    async fn test_filesystem_events_reach_project() {
+        let (dir, repo) = git::test_support::init_repo();
+        git::test_support::commit_files(&repo, &[("a.txt", b"a")], "first");
+        let git_dir = std::fs::canonicalize(repo.path()).unwrap();
+
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        let (watcher, polled) = watch_filesystem(vec![(git_dir, 42)], tx);
+        assert!(watcher.is_some());
+        assert!(polled.is_empty());
+
+        git::test_support::commit_files(&repo, &[("a.txt", b"b")], "second");
+
+        let received = tokio::time::timeout(Duration::from_secs(5), rx.recv()).await;
+        assert_eq!(received.unwrap(), Some(42));
+        drop(dir);
+    }
+}
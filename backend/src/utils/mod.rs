@@ -0,0 +1,2 @@
+pub mod stream;
+pub mod url;
@@ -0,0 +1,159 @@
+//! Adaptadores para enviar como cuerpo de respuesta lo que genera código bloqueante
+
+use std::io::{self, Read, Seek, SeekFrom, Write};
+
+use tokio::sync::mpsc;
+
+/// Bloque enviado por el canal; un `Err` corta la respuesta HTTP
+pub type Chunk = Result<Vec<u8>, io::Error>;
+
+/// Tamaño de los bloques enviados en modo streaming
+const CHUNK_SIZE: usize = 64 * 1024;
+
+/// `Write` que envía lo escrito por un canal, para usarse desde `spawn_blocking`
+///
+/// El receptor se convierte en el cuerpo de la respuesta con
+/// `Body::from_stream(ReceiverStream::new(rx))`. Si el cliente se
+/// desconecta, la siguiente escritura falla con `BrokenPipe` y el
+/// generador se detiene.
+///
+/// También implementa `Seek`, pero solo dentro de lo que aún no se ha
+/// enviado: los formatos que completan cabeceras a posteriori (zip)
+/// funcionan mientras vacíen el writer (`flush`) cuando ya no van a volver atrás.
+pub struct ChannelWriter {
+    tx: mpsc::Sender<Chunk>,
+    /// Bytes pendientes de enviar
+    buffer: Vec<u8>,
+    /// Posición absoluta del primer byte de `buffer`
+    base: u64,
+    /// Posición de escritura dentro de `buffer`
+    cursor: usize,
+    /// Envía cada `CHUNK_SIZE` bytes sin esperar a `flush`
+    streaming: bool,
+}
+
+impl ChannelWriter {
+    /// Writer secuencial que envía bloques de `CHUNK_SIZE` bytes
+    pub fn streaming(tx: mpsc::Sender<Chunk>) -> Self {
+        Self::new(tx, true)
+    }
+
+    /// Writer que solo envía al llamar a `flush`; permite `Seek` hacia atrás
+    /// hasta el último `flush`
+    pub fn spooled(tx: mpsc::Sender<Chunk>) -> Self {
+        Self::new(tx, false)
+    }
+
+    fn new(tx: mpsc::Sender<Chunk>, streaming: bool) -> Self {
+        Self {
+            tx,
+            buffer: Vec::new(),
+            base: 0,
+            cursor: 0,
+            streaming,
+        }
+    }
+}
+
+impl Write for ChannelWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let overlap = (self.buffer.len() - self.cursor).min(buf.len());
+        self.buffer[self.cursor..self.cursor + overlap].copy_from_slice(&buf[..overlap]);
+        self.buffer.extend_from_slice(&buf[overlap..]);
+        self.cursor += buf.len();
+
+        if self.streaming && self.buffer.len() >= CHUNK_SIZE {
+            self.flush()?;
+        }
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        if self.buffer.is_empty() {
+            return Ok(());
+        }
+
+        let chunk = std::mem::take(&mut self.buffer);
+        self.base += chunk.len() as u64;
+        self.cursor = 0;
+        self.tx
+            .blocking_send(Ok(chunk))
+            .map_err(|_| io::Error::new(io::ErrorKind::BrokenPipe, "El cliente se desconectó"))
+    }
+}
+
+impl Seek for ChannelWriter {
+    fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+        let end = self.base + self.buffer.len() as u64;
+        let target = match pos {
+            SeekFrom::Start(offset) => Some(offset),
+            SeekFrom::Current(delta) => (self.base + self.cursor as u64).checked_add_signed(delta),
+            SeekFrom::End(delta) => end.checked_add_signed(delta),
+        };
+
+        match target {
+            Some(target) if (self.base..=end).contains(&target) => {
+                self.cursor = (target - self.base) as usize;
+                Ok(target)
+            }
+            _ => Err(io::Error::new(
+                io::ErrorKind::Unsupported,
+                "No se puede volver a datos ya enviados",
+            )),
+        }
+    }
+}
+
+// `zip` solo permite vaciar el writer tras cada archivo si también es
+// `Read` (para copiar entradas ya escritas, que aquí no se usa)
+impl Read for ChannelWriter {
+    fn read(&mut self, _buf: &mut [u8]) -> io::Result<usize> {
+        Err(io::Error::new(
+            io::ErrorKind::Unsupported,
+            "No se pueden leer datos ya enviados",
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn collect(mut rx: mpsc::Receiver<Chunk>) -> Vec<u8> {
+        let mut data = Vec::new();
+        while let Some(chunk) = rx.blocking_recv() {
+            data.extend(chunk.unwrap());
+        }
+        data
+    }
+
+    #[test]
+    fn test_spooled_writer_seeks_until_flush() {
+        let (tx, rx) = mpsc::channel(16);
+        let mut writer = ChannelWriter::spooled(tx);
+
+        writer.write_all(b"head????body").unwrap();
+        writer.seek(SeekFrom::Start(4)).unwrap();
+        writer.write_all(b"1234").unwrap();
+        assert_eq!(writer.seek(SeekFrom::End(0)).unwrap(), 12);
+        writer.flush().unwrap();
+
+        // Lo enviado ya no se puede reescribir
+        assert!(writer.seek(SeekFrom::Start(0)).is_err());
+        writer.write_all(b"!").unwrap();
+        writer.flush().unwrap();
+        drop(writer);
+
+        assert_eq!(collect(rx), b"head1234body!");
+    }
+
+    #[test]
+    fn test_writer_detects_disconnect() {
+        let (tx, rx) = mpsc::channel(16);
+        let mut writer = ChannelWriter::streaming(tx);
+        drop(rx);
+
+        let error = writer.write_all(&vec![0; CHUNK_SIZE]).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::BrokenPipe);
+    }
+}
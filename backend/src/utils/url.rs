@@ -0,0 +1,142 @@
+//! Utilidades para construir URLs de la API
+
+/// Codifica un valor para URLs: solo se dejan sin codificar los caracteres no reservados
+pub fn percent_encode(value: &str) -> String {
+    value
+        .bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => (b as char).to_string(),
+            _ => format!("%{:02X}", b),
+        })
+        .collect()
+}
+
+/// Decodifica secuencias `%XX`; las secuencias inválidas se conservan tal cual
+pub fn percent_decode(value: &str) -> String {
+    let bytes = value.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+
+    while i < bytes.len() {
+        if bytes[i] == b'%' && i + 2 < bytes.len() {
+            let high = (bytes[i + 1] as char).to_digit(16);
+            let low = (bytes[i + 2] as char).to_digit(16);
+            if let (Some(high), Some(low)) = (high, low) {
+                decoded.push((high * 16 + low) as u8);
+                i += 3;
+                continue;
+            }
+        }
+        decoded.push(bytes[i]);
+        i += 1;
+    }
+
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+/// URL de `/blob` para un archivo en un commit
+pub fn blob_url(project_id: i32, commit: &str, path: &str) -> String {
+    format!(
+        "/api/projects/{}/blob?ref={}&path={}",
+        project_id,
+        percent_encode(commit),
+        percent_encode(path)
+    )
+}
+
+/// URL de `/raw` para un archivo en un commit
+pub fn raw_url(project_id: i32, commit: &str, path: &str) -> String {
+    format!(
+        "/api/projects/{}/raw?ref={}&path={}",
+        project_id,
+        percent_encode(commit),
+        percent_encode(path)
+    )
+}
+
+/// URL de `/tree` para un directorio en un commit
+pub fn tree_url(project_id: i32, commit: &str, path: &str) -> String {
+    format!(
+        "/api/projects/{}/tree?ref={}&path={}",
+        project_id,
+        percent_encode(commit),
+        percent_encode(path)
+    )
+}
+
+/// Ruta de la aplicación web en la que se muestra un commit
+pub fn commit_link(project_id: i32, commit: &str) -> String {
+    format!(
+        "/projects/{}/commits?commit={}",
+        project_id,
+        percent_encode(commit)
+    )
+}
+
+/// Ruta de la aplicación web en la que se muestra un comentario
+///
+/// Los comentarios de archivo llevan a la vista del archivo, los de un
+/// commit al historial de commits y el resto a la página del proyecto.
+pub fn comment_link(
+    project_id: i32,
+    comment_id: i32,
+    commit: Option<&str>,
+    path: Option<&str>,
+) -> String {
+    match (path, commit) {
+        (Some(path), _) => {
+            let segments: Vec<String> = path.split('/').map(percent_encode).collect();
+            format!(
+                "/projects/{}/tree/{}#comment-{}",
+                project_id,
+                segments.join("/"),
+                comment_id
+            )
+        }
+        (None, Some(commit)) => {
+            format!("{}#comment-{}", commit_link(project_id, commit), comment_id)
+        }
+        (None, None) => format!("/projects/{}#comment-{}", project_id, comment_id),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_percent_encode_roundtrip() {
+        let original = "docs/guía de uso.md";
+        let encoded = percent_encode(original);
+        assert_eq!(encoded, "docs%2Fgu%C3%ADa%20de%20uso.md");
+        assert_eq!(percent_decode(&encoded), original);
+    }
+
+    #[test]
+    fn test_percent_decode_keeps_invalid_sequences() {
+        assert_eq!(percent_decode("100%"), "100%");
+        assert_eq!(percent_decode("%zz%41"), "%zzA");
+        assert_eq!(percent_decode("%é"), "%é");
+    }
+
+    #[test]
+    fn test_raw_url() {
+        assert_eq!(
+            raw_url(3, "abc123", "img/logo.png"),
+            "/api/projects/3/raw?ref=abc123&path=img%2Flogo.png"
+        );
+    }
+
+    #[test]
+    fn test_comment_link() {
+        assert_eq!(comment_link(3, 12, None, None), "/projects/3#comment-12");
+        assert_eq!(
+            comment_link(3, 12, Some("abc123"), None),
+            "/projects/3/commits?commit=abc123#comment-12"
+        );
+        assert_eq!(
+            comment_link(3, 12, Some("abc123"), Some("docs/guía.md")),
+            "/projects/3/tree/docs/gu%C3%ADa.md#comment-12"
+        );
+    }
+}
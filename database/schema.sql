@@ -36,6 +36,15 @@ CREATE TABLE projects (
     description TEXT,
     is_public BOOLEAN DEFAULT FALSE,
     last_scanned_at TIMESTAMP,
+    last_pull_at TIMESTAMP,
+    is_blocked BOOLEAN NOT NULL DEFAULT FALSE,
+    lock_reason TEXT,
+    locked_by INTEGER REFERENCES users(id) ON DELETE SET NULL,
+    locked_at TIMESTAMP,
+    search_vector tsvector GENERATED ALWAYS AS (
+        setweight(to_tsvector('simple', coalesce(name, '')), 'A') ||
+        setweight(to_tsvector('simple', coalesce(description, '')), 'B')
+    ) STORED,
     created_at TIMESTAMP DEFAULT NOW(),
     updated_at TIMESTAMP DEFAULT NOW()
 );
@@ -44,11 +53,14 @@ CREATE TABLE projects (
 CREATE INDEX idx_projects_path ON projects(repository_path);
 CREATE INDEX idx_projects_public ON projects(is_public) WHERE is_public = TRUE;
 CREATE INDEX idx_projects_name ON projects(name);
+CREATE INDEX idx_projects_search ON projects USING GIN (search_vector);
 
 COMMENT ON TABLE projects IS 'Git repositories detected and tracked by the system';
 COMMENT ON COLUMN projects.repository_path IS 'Absolute path to the .git directory';
 COMMENT ON COLUMN projects.is_public IS 'If true, all users can view (no need to be a member)';
 COMMENT ON COLUMN projects.last_scanned_at IS 'Last time repository was scanned for commits';
+COMMENT ON COLUMN projects.is_blocked IS 'If true, pulls into the repository are refused by the installed Git hook';
+COMMENT ON COLUMN projects.last_pull_at IS 'Last time a branch tip moved in the repository (pull detected by the watcher)';
 
 -- Project members (many-to-many: users <-> projects)
 CREATE TABLE project_members (
@@ -70,22 +82,243 @@ COMMENT ON COLUMN project_members.role IS 'viewer: read-only, contributor: can c
 CREATE TABLE commits (
     id SERIAL PRIMARY KEY,
     project_id INTEGER REFERENCES projects(id) ON DELETE CASCADE,
-    commit_hash VARCHAR(40) UNIQUE NOT NULL,
+    commit_hash VARCHAR(40) NOT NULL,
     author_name VARCHAR(255) NOT NULL,
     author_email VARCHAR(255),
     message TEXT NOT NULL,
     committed_at TIMESTAMP NOT NULL,
-    created_at TIMESTAMP DEFAULT NOW()
+    additions INTEGER,                       -- NULL for merges
+    deletions INTEGER,
+    commit_type VARCHAR(50),                 -- Conventional Commits type, NULL if not conventional
+    commit_scope VARCHAR(100),
+    is_breaking BOOLEAN NOT NULL DEFAULT FALSE,
+    trailers JSONB NOT NULL DEFAULT '[]',    -- [{"key", "value"}]
+    issue_refs TEXT[] NOT NULL DEFAULT '{}',
+    search_vector tsvector GENERATED ALWAYS AS (
+        setweight(to_tsvector('simple', coalesce(message, '')), 'A') ||
+        setweight(to_tsvector('simple', coalesce(author_name, '') || ' ' || coalesce(author_email, '')), 'B')
+    ) STORED,
+    created_at TIMESTAMP DEFAULT NOW(),
+    UNIQUE (project_id, commit_hash)
 );
 
 -- Indexes for commits
 CREATE INDEX idx_commits_project_date ON commits(project_id, committed_at DESC);
 CREATE INDEX idx_commits_hash ON commits(commit_hash);
 CREATE INDEX idx_commits_author ON commits(author_email) WHERE author_email IS NOT NULL;
+CREATE INDEX idx_commits_search ON commits USING GIN (search_vector);
+CREATE INDEX idx_commits_type ON commits(project_id, commit_type) WHERE commit_type IS NOT NULL;
 
 COMMENT ON TABLE commits IS 'Cached Git commits for faster queries and historical tracking';
-COMMENT ON COLUMN commits.commit_hash IS 'Git SHA-1 hash (40 hex characters)';
+COMMENT ON COLUMN commits.commit_hash IS 'Git SHA-1 hash (40 hex characters), unique per project (forks share history)';
 COMMENT ON COLUMN commits.committed_at IS 'Commit timestamp from Git metadata';
+COMMENT ON COLUMN commits.additions IS 'Lines added vs. the first parent; NULL for merges and commits cached before churn tracking';
+COMMENT ON COLUMN commits.commit_type IS 'Conventional Commits type in lowercase (feat, fix...); NULL if the header does not follow the format';
+COMMENT ON COLUMN commits.is_breaking IS 'True if the header has "!" or the footer a BREAKING CHANGE trailer';
+COMMENT ON COLUMN commits.trailers IS 'Footer trailers as [{"key", "value"}]; empty for commits cached before parsing was added';
+COMMENT ON COLUMN commits.search_vector IS 'Full-text index: message (A) + author name/email (B); simple config, no stemming';
+
+-- Pull events (audit log of branch updates detected by the watcher)
+CREATE TABLE pull_events (
+    id SERIAL PRIMARY KEY,
+    project_id INTEGER NOT NULL REFERENCES projects(id) ON DELETE CASCADE,
+    ref_name VARCHAR(255) NOT NULL,
+    old_commit VARCHAR(40),                  -- NULL if the branch was created
+    new_commit VARCHAR(40) NOT NULL,
+    commit_count INTEGER NOT NULL DEFAULT 0,
+    detected_at TIMESTAMP DEFAULT NOW()
+);
+
+-- Indexes for pull_events
+CREATE INDEX idx_pull_events_project_date ON pull_events(project_id, detected_at DESC);
+
+COMMENT ON TABLE pull_events IS 'Branch updates detected in monitored repositories (RF-009)';
+COMMENT ON COLUMN pull_events.commit_count IS 'Number of new commits (capped by the watcher)';
+
+-- Lock requests from regular users, pending admin approval (RF-010)
+CREATE TABLE project_lock_requests (
+    id SERIAL PRIMARY KEY,
+    project_id INTEGER NOT NULL REFERENCES projects(id) ON DELETE CASCADE,
+    requested_by INTEGER REFERENCES users(id) ON DELETE SET NULL,
+    reason TEXT NOT NULL,
+    status VARCHAR(20) NOT NULL DEFAULT 'pending', -- 'pending' | 'approved' | 'rejected'
+    reviewed_by INTEGER REFERENCES users(id) ON DELETE SET NULL,
+    review_comment TEXT,
+    created_at TIMESTAMP DEFAULT NOW(),
+    reviewed_at TIMESTAMP,
+    CONSTRAINT chk_lock_request_status CHECK (status IN ('pending', 'approved', 'rejected'))
+);
+
+-- Indexes for project_lock_requests
+CREATE INDEX idx_lock_requests_project ON project_lock_requests(project_id, created_at DESC);
+CREATE UNIQUE INDEX idx_lock_requests_pending ON project_lock_requests(project_id, requested_by)
+    WHERE status = 'pending';
+
+-- Lock history (every lock/unlock)
+CREATE TABLE project_lock_history (
+    id SERIAL PRIMARY KEY,
+    project_id INTEGER NOT NULL REFERENCES projects(id) ON DELETE CASCADE,
+    action VARCHAR(20) NOT NULL, -- 'locked' | 'unlocked'
+    reason TEXT,
+    actor_id INTEGER REFERENCES users(id) ON DELETE SET NULL,
+    request_id INTEGER REFERENCES project_lock_requests(id) ON DELETE SET NULL,
+    created_at TIMESTAMP DEFAULT NOW(),
+    CONSTRAINT chk_lock_history_action CHECK (action IN ('locked', 'unlocked'))
+);
+
+-- Indexes for project_lock_history
+CREATE INDEX idx_lock_history_project ON project_lock_history(project_id, created_at DESC);
+
+COMMENT ON TABLE project_lock_history IS 'Audit log of repository lock state changes';
+
+-- Remote credentials per project, encrypted at rest with CREDENTIALS_KEY
+CREATE TABLE project_credentials (
+    project_id INTEGER PRIMARY KEY REFERENCES projects(id) ON DELETE CASCADE,
+    kind VARCHAR(20) NOT NULL, -- 'ssh_key' | 'token'
+    username VARCHAR(255),
+    secret_encrypted BYTEA NOT NULL,
+    created_by INTEGER REFERENCES users(id) ON DELETE SET NULL,
+    created_at TIMESTAMP DEFAULT NOW(),
+    updated_at TIMESTAMP DEFAULT NOW(),
+    CONSTRAINT chk_credential_kind CHECK (kind IN ('ssh_key', 'token'))
+);
+
+COMMENT ON TABLE project_credentials IS 'Credentials used to fetch from the project remote';
+COMMENT ON COLUMN project_credentials.secret_encrypted IS 'AES-256-GCM: nonce || ciphertext, bound to the project id';
+
+-- Server-initiated fetch + fast-forward operations
+CREATE TABLE sync_operations (
+    id SERIAL PRIMARY KEY,
+    project_id INTEGER NOT NULL REFERENCES projects(id) ON DELETE CASCADE,
+    triggered_by INTEGER REFERENCES users(id) ON DELETE SET NULL,
+    remote VARCHAR(255),
+    branch VARCHAR(255),
+    status VARCHAR(20) NOT NULL, -- 'up_to_date' | 'fast_forwarded' | 'failed'
+    old_commit VARCHAR(40),
+    new_commit VARCHAR(40),
+    message TEXT,
+    duration_ms INTEGER NOT NULL DEFAULT 0,
+    created_at TIMESTAMP DEFAULT NOW(),
+    CONSTRAINT chk_sync_status CHECK (status IN ('up_to_date', 'fast_forwarded', 'failed'))
+);
+
+-- Indexes for sync_operations
+CREATE INDEX idx_sync_operations_project ON sync_operations(project_id, created_at DESC);
+
+COMMENT ON TABLE sync_operations IS 'Outcome of fetch + fast-forward operations triggered from the API';
+
+-- General audit log of user actions on projects
+CREATE TABLE audit_log (
+    id SERIAL PRIMARY KEY,
+    project_id INTEGER REFERENCES projects(id) ON DELETE CASCADE,
+    user_id INTEGER REFERENCES users(id) ON DELETE SET NULL, -- NULL for anonymous access
+    action VARCHAR(50) NOT NULL, -- 'archive_download' | ...
+    details JSONB NOT NULL DEFAULT '{}',
+    created_at TIMESTAMP DEFAULT NOW()
+);
+
+-- Indexes for audit_log
+CREATE INDEX idx_audit_log_project ON audit_log(project_id, created_at DESC);
+CREATE INDEX idx_audit_log_user ON audit_log(user_id, created_at DESC);
+
+COMMENT ON TABLE audit_log IS 'Audit log of user actions (downloads, ...); the action set grows with the API';
+
+-- Repository composition at the default branch tip (language mix over time)
+CREATE TABLE repository_snapshots (
+    id SERIAL PRIMARY KEY,
+    project_id INTEGER NOT NULL REFERENCES projects(id) ON DELETE CASCADE,
+    commit_hash VARCHAR(40) NOT NULL,
+    branch VARCHAR(255) NOT NULL,
+    committed_at TIMESTAMP NOT NULL,
+    total_files INTEGER NOT NULL,
+    total_bytes BIGINT NOT NULL,             -- Excluding vendored/generated/documentation
+    excluded_files INTEGER NOT NULL,
+    disk_size BIGINT NOT NULL,               -- Git directory size on disk
+    pack_count INTEGER NOT NULL,
+    pack_size BIGINT NOT NULL,
+    loose_objects INTEGER NOT NULL,
+    loose_size BIGINT NOT NULL,
+    analyzed_at TIMESTAMP DEFAULT NOW(),
+    UNIQUE (project_id, commit_hash)
+);
+
+CREATE TABLE repository_languages (
+    snapshot_id INTEGER NOT NULL REFERENCES repository_snapshots(id) ON DELETE CASCADE,
+    language VARCHAR(50) NOT NULL,
+    files INTEGER NOT NULL,
+    bytes BIGINT NOT NULL,
+    PRIMARY KEY (snapshot_id, language)
+);
+
+-- Indexes for repository_snapshots
+CREATE INDEX idx_repository_snapshots_project ON repository_snapshots(project_id, committed_at DESC);
+
+COMMENT ON TABLE repository_snapshots IS 'Composition of the default branch tip, one row per analyzed commit (charted over time)';
+COMMENT ON TABLE repository_languages IS 'Bytes and files per detected language for a repository snapshot';
+
+-- Trusted keys for commit signature verification (SSH allowed signers and GPG keyring)
+CREATE TABLE signing_keys (
+    id SERIAL PRIMARY KEY,
+    kind VARCHAR(10) NOT NULL CHECK (kind IN ('ssh', 'gpg')),
+    fingerprint VARCHAR(100) NOT NULL UNIQUE, -- SHA256:... (ssh) or primary key fingerprint (gpg)
+    principals TEXT[] NOT NULL DEFAULT '{}',  -- Allowed principals (ssh) or user ID emails (gpg)
+    public_key TEXT NOT NULL,                -- OpenSSH public key or ASCII-armored GPG key
+    comment VARCHAR(255),
+    created_by INTEGER REFERENCES users(id) ON DELETE SET NULL,
+    created_at TIMESTAMP NOT NULL DEFAULT NOW()
+);
+
+-- Indexes for signing_keys
+CREATE INDEX idx_signing_keys_kind ON signing_keys(kind);
+
+COMMENT ON TABLE signing_keys IS 'Admin-managed keys trusted to sign commits';
+COMMENT ON COLUMN signing_keys.principals IS 'SSH: allowed-signers principals (emails, * and ? wildcards); GPG: emails of the key user IDs';
+
+-- CODEOWNERS owner mappings
+CREATE TABLE owner_mappings (
+    id SERIAL PRIMARY KEY,
+    owner VARCHAR(255) NOT NULL,             -- @username, @org/team or email as written in CODEOWNERS
+    user_id INTEGER REFERENCES users(id) ON DELETE CASCADE,
+    telegram_handle VARCHAR(64),             -- Telegram username without the leading @
+    created_by INTEGER REFERENCES users(id) ON DELETE SET NULL,
+    created_at TIMESTAMP NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMP NOT NULL DEFAULT NOW(),
+    CONSTRAINT owner_mappings_target CHECK (user_id IS NOT NULL OR telegram_handle IS NOT NULL)
+);
+
+-- Indexes for owner_mappings
+CREATE UNIQUE INDEX idx_owner_mappings_owner ON owner_mappings(LOWER(owner));
+
+COMMENT ON TABLE owner_mappings IS 'Admin-managed mapping from CODEOWNERS entries to users and Telegram handles';
+
+-- Repository health checks
+CREATE TABLE repository_health_checks (
+    id SERIAL PRIMARY KEY,
+    project_id INTEGER NOT NULL REFERENCES projects(id) ON DELETE CASCADE,
+    status VARCHAR(20) NOT NULL,             -- healthy, warning, failing
+    integrity_ok BOOLEAN NOT NULL,
+    integrity_complete BOOLEAN NOT NULL,     -- false if the walk hit the object limit
+    objects_checked INTEGER NOT NULL,
+    unreachable_objects INTEGER,             -- dangling objects; NULL if the walk was incomplete
+    loose_objects INTEGER NOT NULL,
+    loose_size BIGINT NOT NULL,
+    pack_count INTEGER NOT NULL,
+    pack_size BIGINT NOT NULL,
+    is_bare BOOLEAN NOT NULL,
+    dirty_files INTEGER NOT NULL,
+    detached_head BOOLEAN NOT NULL,
+    unmerged_files TEXT[] NOT NULL DEFAULT '{}',
+    issues JSONB NOT NULL DEFAULT '[]',      -- [{kind, severity, message}]
+    triggered_by INTEGER REFERENCES users(id) ON DELETE SET NULL, -- NULL for scheduled checks
+    duration_ms INTEGER NOT NULL DEFAULT 0,
+    checked_at TIMESTAMP NOT NULL DEFAULT NOW(),
+    CONSTRAINT chk_health_status CHECK (status IN ('healthy', 'warning', 'failing'))
+);
+
+-- Indexes for repository_health_checks
+CREATE INDEX idx_repository_health_checks_project ON repository_health_checks(project_id, checked_at DESC);
+
+COMMENT ON TABLE repository_health_checks IS 'Integrity, storage and working-copy checks of each repository (latest rows kept per project)';
 
 -- ============================================
 -- FASE 2 - COLLABORATION TABLES
@@ -94,45 +327,115 @@ COMMENT ON COLUMN commits.committed_at IS 'Commit timestamp from Git metadata';
 -- Comments (added in Phase 2)
 CREATE TABLE comments (
     id SERIAL PRIMARY KEY,
-    project_id INTEGER REFERENCES projects(id) ON DELETE CASCADE,
+    project_id INTEGER NOT NULL REFERENCES projects(id) ON DELETE CASCADE,
     user_id INTEGER REFERENCES users(id) ON DELETE SET NULL,
     commit_hash VARCHAR(40),
     content TEXT NOT NULL,
     parent_comment_id INTEGER REFERENCES comments(id) ON DELETE CASCADE,
-    created_at TIMESTAMP DEFAULT NOW(),
-    updated_at TIMESTAMP DEFAULT NOW()
+    created_at TIMESTAMP NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMP NOT NULL DEFAULT NOW(),
+    deleted_at TIMESTAMP,
+    file_path TEXT,
+    line_start INTEGER,
+    line_end INTEGER,
+    anchor_commit VARCHAR(40),
+    anchor_path TEXT,
+    anchor_line_start INTEGER,
+    anchor_line_end INTEGER,
+    is_outdated BOOLEAN NOT NULL DEFAULT FALSE,
+    resolved_at TIMESTAMP,
+    resolved_by INTEGER REFERENCES users(id) ON DELETE SET NULL,
+    CONSTRAINT chk_comments_file CHECK (file_path IS NULL OR commit_hash IS NOT NULL),
+    CONSTRAINT chk_comments_lines CHECK (
+        (line_start IS NULL AND line_end IS NULL)
+        OR (file_path IS NOT NULL AND line_start >= 1 AND line_end >= line_start)
+    ),
+    CONSTRAINT chk_comments_resolved CHECK (resolved_at IS NULL OR parent_comment_id IS NULL)
 );
 
 -- Indexes for comments
 CREATE INDEX idx_comments_project ON comments(project_id, created_at DESC);
 CREATE INDEX idx_comments_parent ON comments(parent_comment_id) WHERE parent_comment_id IS NOT NULL;
-CREATE INDEX idx_comments_commit ON comments(commit_hash) WHERE commit_hash IS NOT NULL;
+CREATE INDEX idx_comments_commit ON comments(project_id, commit_hash) WHERE commit_hash IS NOT NULL;
+CREATE INDEX idx_comments_anchor ON comments(project_id, anchor_path) WHERE anchor_path IS NOT NULL;
+CREATE INDEX idx_comments_tracked ON comments(project_id)
+    WHERE anchor_commit IS NOT NULL AND is_outdated = FALSE AND deleted_at IS NULL;
+CREATE INDEX idx_comments_resolved ON comments(project_id, resolved_at) WHERE parent_comment_id IS NULL;
 
 COMMENT ON TABLE comments IS 'User comments on projects or specific commits';
 COMMENT ON COLUMN comments.commit_hash IS 'If set, comment is specific to this commit';
 COMMENT ON COLUMN comments.parent_comment_id IS 'For threaded comments (1 level deep)';
+COMMENT ON COLUMN comments.deleted_at IS 'Soft delete: content is hidden but replies stay in the thread';
+COMMENT ON COLUMN comments.file_path IS 'If set, comment is on this file (and lines) as of commit_hash';
+COMMENT ON COLUMN comments.anchor_commit IS 'Commit where anchor_path/anchor_line_* were last computed';
+COMMENT ON COLUMN comments.is_outdated IS 'True once the commented lines changed or the file was deleted; anchor_* keep the last known position';
+COMMENT ON COLUMN comments.resolved_at IS 'When the thread was resolved; NULL while open (top-level comments only)';
+COMMENT ON COLUMN comments.resolved_by IS 'User who resolved the thread';
 
 -- Notifications (added in Phase 2)
 CREATE TABLE notifications (
     id SERIAL PRIMARY KEY,
-    user_id INTEGER REFERENCES users(id) ON DELETE CASCADE,
+    user_id INTEGER NOT NULL REFERENCES users(id) ON DELETE CASCADE,
     project_id INTEGER REFERENCES projects(id) ON DELETE CASCADE,
     type VARCHAR(50) NOT NULL, -- 'commit' | 'comment' | 'mention'
     title VARCHAR(255) NOT NULL,
     message TEXT NOT NULL,
     link VARCHAR(500),
-    is_read BOOLEAN DEFAULT FALSE,
-    created_at TIMESTAMP DEFAULT NOW()
+    is_read BOOLEAN NOT NULL DEFAULT FALSE,
+    created_at TIMESTAMP NOT NULL DEFAULT NOW(),
+    CONSTRAINT chk_notifications_type
+        CHECK (type IN ('commit', 'comment', 'mention', 'lock', 'working_copy'))
 );
 
 -- Indexes for notifications
 CREATE INDEX idx_notifications_user_unread ON notifications(user_id, is_read, created_at DESC);
 CREATE INDEX idx_notifications_type ON notifications(type);
+CREATE INDEX idx_notifications_user_id ON notifications(user_id, id DESC);
 
 COMMENT ON TABLE notifications IS 'In-app notifications for users';
 COMMENT ON COLUMN notifications.type IS 'Notification category for filtering and icons';
 COMMENT ON COLUMN notifications.link IS 'Deep link to relevant content in the app';
 
+-- Users mentioned with @username in a comment
+CREATE TABLE comment_mentions (
+    comment_id INTEGER NOT NULL REFERENCES comments(id) ON DELETE CASCADE,
+    user_id INTEGER NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    created_at TIMESTAMP NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (comment_id, user_id)
+);
+
+CREATE INDEX idx_comment_mentions_user ON comment_mentions(user_id);
+
+COMMENT ON TABLE comment_mentions IS 'Resolved @mentions; only users who can see the project are recorded';
+
+-- Edit history of comments: one row per replaced version
+CREATE TABLE comment_revisions (
+    id SERIAL PRIMARY KEY,
+    comment_id INTEGER NOT NULL REFERENCES comments(id) ON DELETE CASCADE,
+    content TEXT NOT NULL,
+    edited_by INTEGER REFERENCES users(id) ON DELETE SET NULL,
+    edited_at TIMESTAMP NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_comment_revisions_comment ON comment_revisions(comment_id, edited_at);
+
+COMMENT ON TABLE comment_revisions IS 'Previous versions of edited comments, for auditing';
+COMMENT ON COLUMN comment_revisions.content IS 'Content before the edit';
+COMMENT ON COLUMN comment_revisions.edited_at IS 'When this version was replaced';
+
+-- Emoji reactions on comments: one row per user and emoji
+CREATE TABLE comment_reactions (
+    comment_id INTEGER NOT NULL REFERENCES comments(id) ON DELETE CASCADE,
+    user_id INTEGER NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    emoji VARCHAR(32) NOT NULL,
+    created_at TIMESTAMP NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (comment_id, user_id, emoji)
+);
+
+CREATE INDEX idx_comment_reactions_comment ON comment_reactions(comment_id, emoji);
+
+COMMENT ON TABLE comment_reactions IS 'Emoji reactions; counts are aggregated per comment and emoji';
+
 -- ============================================
 -- FASE 3 - TELEGRAM INTEGRATION
 -- ============================================
@@ -209,6 +512,12 @@ CREATE TRIGGER update_users_updated_at BEFORE UPDATE ON users
 CREATE TRIGGER update_projects_updated_at BEFORE UPDATE ON projects
     FOR EACH ROW EXECUTE FUNCTION update_updated_at_column();
 
+CREATE TRIGGER update_project_credentials_updated_at BEFORE UPDATE ON project_credentials
+    FOR EACH ROW EXECUTE FUNCTION update_updated_at_column();
+
+CREATE TRIGGER update_owner_mappings_updated_at BEFORE UPDATE ON owner_mappings
+    FOR EACH ROW EXECUTE FUNCTION update_updated_at_column();
+
 CREATE TRIGGER update_comments_updated_at BEFORE UPDATE ON comments
     FOR EACH ROW EXECUTE FUNCTION update_updated_at_column();
 
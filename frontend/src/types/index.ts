@@ -32,17 +32,365 @@ export interface Project {
   path: string
   description?: string
   is_blocked: boolean
+  lock_reason?: string
+  locked_by?: number
+  locked_at?: string
   last_commit_hash?: string
+  default_branch?: string
   last_pull_at?: string
   created_at: string
   updated_at: string
+  readme?: ProjectReadme
+  health?: RepositoryHealthCheck
+}
+
+export type ProjectRole = 'viewer' | 'contributor' | 'admin'
+
+export interface ProjectMember {
+  user_id: number
+  username: string
+  role: ProjectRole
+}
+
+export interface ProjectReadme {
+  path: string
+  html: string
+}
+
+export type HealthStatus = 'healthy' | 'warning' | 'failing'
+
+export interface HealthIssue {
+  kind:
+    | 'repository_error'
+    | 'integrity'
+    | 'unreachable_objects'
+    | 'loose_objects'
+    | 'pack_count'
+    | 'pack_size'
+    | 'dirty_working_copy'
+    | 'detached_head'
+    | 'unmerged_files'
+  severity: HealthStatus
+  message: string
+}
+
+export interface RepositoryHealthCheck {
+  id: number
+  project_id: number
+  status: HealthStatus
+  integrity_ok: boolean
+  integrity_complete: boolean
+  objects_checked: number
+  unreachable_objects?: number
+  loose_objects: number
+  loose_size: number
+  pack_count: number
+  pack_size: number
+  is_bare: boolean
+  dirty_files: number
+  detached_head: boolean
+  unmerged_files: string[]
+  issues: HealthIssue[]
+  triggered_by?: number
+  triggered_by_username?: string
+  duration_ms: number
+  checked_at: string
 }
 
 export interface ProjectFile {
   name: string
   path: string
-  type: 'file' | 'directory'
+  type: 'file' | 'directory' | 'symlink' | 'submodule'
   size?: number
+  mode: string
+  oid: string
+  symlink_target?: string
+  submodule?: SubmoduleInfo
+  lfs?: LfsInfo
+  last_commit?: CommitSummary
+}
+
+export interface SubmoduleInfo {
+  url: string | null
+  commit: string
+  project_id: number | null // Monitored project for the same repository, if visible
+}
+
+export interface LfsInfo {
+  oid: string
+  size: number
+  available: boolean // Object present in the server's LFS store
+}
+
+export interface CommitSummary {
+  hash: string
+  summary: string
+  author_name: string
+  author_email: string
+  authored_at: string
+  signature?: CommitSignature // Only in responses that verify signatures (compare)
+}
+
+export type SignatureStatus = 'verified' | 'unverified' | 'unsigned'
+
+export type SignatureKind = 'gpg' | 'ssh' | 'x509'
+
+export type UnverifiedReason =
+  | 'unknown_key'
+  | 'bad_signature'
+  | 'identity_mismatch'
+  | 'expired_key'
+  | 'revoked_key'
+  | 'malformed'
+  | 'unsupported'
+
+export interface CommitSignature {
+  status: SignatureStatus
+  kind: SignatureKind | null
+  key_fingerprint: string | null
+  signer: string | null
+  reason: UnverifiedReason | null
+}
+
+export interface SigningKey {
+  id: number
+  kind: 'ssh' | 'gpg'
+  fingerprint: string
+  principals: string[]
+  public_key: string
+  comment: string | null
+  created_by: number | null
+  created_at: string
+}
+
+export interface CodeOwner {
+  owner: string
+  user: { id: number; username: string } | null
+  telegram_handle: string | null
+}
+
+export interface PathOwners {
+  commit: string
+  source: string | null
+  path: string
+  rule: { line: number; pattern: string } | null
+  owners: CodeOwner[]
+}
+
+export interface OwnerMapping {
+  id: number
+  owner: string
+  user_id: number | null
+  username: string | null
+  telegram_handle: string | null
+  created_by: number | null
+  created_at: string
+  updated_at: string
+}
+
+export interface ProjectFileContent {
+  ref: string
+  commit: string
+  path: string
+  name: string
+  oid: string
+  size: number
+  mode: string
+  mime_type: string
+  binary: boolean
+  is_symlink: boolean
+  encoding?: string
+  language?: string
+  content?: string
+  truncated: boolean
+  raw_url: string
+  lfs?: LfsInfo
+  highlighted_html?: string
+  rendered_html?: string
+}
+
+export interface ProjectTree {
+  ref: string
+  commit: string
+  path: string
+  entries: ProjectFile[]
+}
+
+export interface BlameRange {
+  start_line: number
+  end_line: number
+  commit: CommitSummary
+  original_path?: string
+  original_start_line: number
+}
+
+export interface ProjectBlame {
+  ref: string
+  commit: string
+  path: string
+  oid: string
+  ranges: BlameRange[]
+}
+
+export interface UpstreamStatus {
+  name: string
+  ahead_by: number
+  behind_by: number
+}
+
+export interface Branch {
+  name: string
+  is_default: boolean
+  commit: CommitSummary
+  last_commit_at: string
+  ahead_by: number | null
+  behind_by: number | null
+  upstream: UpstreamStatus | null
+}
+
+export interface ProjectBranches {
+  default_branch: string | null
+  branches: Branch[]
+}
+
+export interface Tag {
+  name: string
+  commit: CommitSummary
+  annotated: boolean
+  message: string | null
+  tagger_name: string | null
+  tagger_email: string | null
+  tagged_at: string
+}
+
+export interface PullEvent {
+  id: number
+  project_id: number
+  ref_name: string
+  old_commit: string | null
+  new_commit: string
+  commit_count: number
+  detected_at: string
+}
+
+export interface LockStatus {
+  project_id: number
+  is_blocked: boolean
+  reason: string | null
+  locked_by: number | null
+  locked_by_username: string | null
+  locked_at: string | null
+}
+
+export interface LockHistoryEntry {
+  id: number
+  project_id: number
+  action: 'locked' | 'unlocked'
+  reason: string | null
+  actor_id: number | null
+  actor_username: string | null
+  request_id: number | null
+  created_at: string
+}
+
+export interface LockRequest {
+  id: number
+  project_id: number
+  requested_by: number | null
+  requested_by_username: string | null
+  reason: string
+  status: 'pending' | 'approved' | 'rejected'
+  reviewed_by: number | null
+  review_comment: string | null
+  created_at: string
+  reviewed_at: string | null
+}
+
+export interface LockHookStatus {
+  path: string
+  installed: boolean
+  foreign_hook: boolean
+}
+
+export interface SyncOperation {
+  id: number
+  project_id: number
+  triggered_by: number | null
+  triggered_by_username: string | null
+  remote: string | null
+  branch: string | null
+  status: 'up_to_date' | 'fast_forwarded' | 'failed'
+  old_commit: string | null
+  new_commit: string | null
+  message: string | null
+  duration_ms: number
+  created_at: string
+}
+
+export interface ProjectCredential {
+  project_id: number
+  kind: 'ssh_key' | 'token'
+  username: string | null
+  created_by: number | null
+  created_at: string
+  updated_at: string
+}
+
+export interface ProjectSearchHit {
+  id: number
+  name: string
+  description: string | null
+  is_public: boolean
+  rank: number
+}
+
+export interface CommitSearchHit {
+  project_id: number
+  project_name: string
+  hash: string
+  author_name: string
+  author_email: string | null
+  message: string
+  committed_at: string
+  rank: number
+}
+
+export interface SearchResponse {
+  query: string
+  projects: ProjectSearchHit[]
+  commits: CommitSearchHit[]
+}
+
+export interface CodeMatch {
+  path: string
+  line_number: number
+  line: string
+  ranges: { start: number; end: number }[]
+}
+
+export interface CodeSearchSummary {
+  files_scanned: number
+  files_matched: number
+  matches: number
+  truncated: boolean
+  timed_out: boolean
+  elapsed_ms: number
+}
+
+export type CodeSearchEvent =
+  | { type: 'start'; ref: string; commit: string }
+  | ({ type: 'match' } & CodeMatch)
+  | ({ type: 'summary' } & CodeSearchSummary)
+  | { type: 'error'; error: string }
+
+export interface AuditEntry {
+  id: number
+  project_id: number | null
+  user_id: number | null
+  username: string | null
+  action: 'archive_download'
+  details: Record<string, unknown>
+  created_at: string
 }
 
 export interface Commit {
@@ -52,6 +400,208 @@ export interface Commit {
   message: string
 }
 
+export type FileStatus = 'added' | 'modified' | 'deleted' | 'renamed' | 'copied' | 'type_change'
+
+export interface DiffLine {
+  origin: 'context' | 'addition' | 'deletion'
+  old_lineno: number | null
+  new_lineno: number | null
+  content: string
+}
+
+export interface DiffHunk {
+  header: string
+  old_start: number
+  old_lines: number
+  new_start: number
+  new_lines: number
+  lines: DiffLine[]
+}
+
+export interface FileDiff {
+  path: string
+  old_path: string | null
+  status: FileStatus
+  old_mode: string | null
+  new_mode: string | null
+  binary: boolean
+  additions: number
+  deletions: number
+  truncated: boolean
+  hunks?: DiffHunk[]
+  patch?: string
+}
+
+export interface DiffStats {
+  files_changed: number
+  additions: number
+  deletions: number
+}
+
+export interface DiffResult {
+  stats: DiffStats
+  files: FileDiff[]
+  truncated: boolean
+}
+
+export interface CommitDetail extends DiffResult {
+  hash: string
+  tree: string
+  parents: string[]
+  summary: string
+  message: string
+  author_name: string
+  author_email: string
+  authored_at: string
+  committer_name: string
+  committer_email: string
+  committed_at: string
+  signature: CommitSignature
+  owners: CodeOwner[]
+}
+
+export interface WorkingCopyFile {
+  path: string
+  old_path: string | null
+  status: FileStatus
+}
+
+export interface WorkingCopyStatus extends DiffResult {
+  branch: string | null
+  head: string | null
+  is_clean: boolean
+  staged: WorkingCopyFile[]
+  unstaged: WorkingCopyFile[]
+  untracked: string[]
+  conflicted: string[]
+  status_truncated: boolean
+}
+
+export interface CompareResult extends DiffResult {
+  base: string
+  head: string
+  merge_base: string | null
+  ahead_by: number
+  behind_by: number
+  commits: CommitSummary[]
+}
+
+export interface ChangelogEntry {
+  hash: string
+  scope: string | null
+  description: string
+  breaking: boolean
+  breaking_note: string | null
+  author_name: string
+  authored_at: string
+  co_authors: string[]
+  issues: string[]
+}
+
+export interface ChangelogSection {
+  kind: string // 'breaking' | 'feat' | 'fix' | ... | 'other'
+  title: string
+  entries: ChangelogEntry[]
+}
+
+export interface Changelog {
+  from: string | null
+  from_commit: string | null
+  to: string
+  to_commit: string
+  date: string
+  total_commits: number
+  truncated: boolean
+  sections: ChangelogSection[]
+}
+
+export interface ActivityPoint {
+  period: string
+  commits: number
+  additions: number
+  deletions: number
+}
+
+export interface ActivityStats {
+  interval: 'day' | 'week'
+  since: string
+  until: string
+  points: ActivityPoint[]
+}
+
+export interface AuthorStats {
+  name: string
+  email: string
+  commits: number
+  additions: number
+  deletions: number
+  first_commit_at: string
+  last_commit_at: string
+}
+
+export interface AuthorStatsResponse {
+  total_authors: number
+  authors: AuthorStats[]
+}
+
+export interface CommitHeatmap {
+  tz_offset: number
+  total: number
+  // 7 filas (lunes a domingo) x 24 horas
+  cells: number[][]
+}
+
+export interface DashboardSummary {
+  since: string
+  until: string
+  commits: number
+  active_projects: number
+  active_authors: number
+  additions: number
+  deletions: number
+  daily: ActivityPoint[]
+  top_projects: { project_id: number; project_name: string; commits: number }[]
+  top_authors: { name: string; email: string; commits: number }[]
+}
+
+export interface LanguageShare {
+  language: string
+  files: number
+  bytes: number
+  percentage: number
+}
+
+export interface RepositorySnapshot {
+  id: number
+  project_id: number
+  commit_hash: string
+  branch: string
+  committed_at: string
+  total_files: number
+  total_bytes: number
+  excluded_files: number
+  disk_size: number
+  pack_count: number
+  pack_size: number
+  loose_objects: number
+  loose_size: number
+  analyzed_at: string | null
+}
+
+export interface RepositoryComposition {
+  snapshot: RepositorySnapshot
+  languages: LanguageShare[]
+}
+
+export interface CompositionPoint {
+  commit_hash: string
+  committed_at: string
+  total_files: number
+  total_bytes: number
+  disk_size: number
+  languages: Omit<LanguageShare, 'percentage'>[]
+}
+
 // Script types
 export interface Script {
   id: number
@@ -81,13 +631,21 @@ export interface Notification {
   id: number
   user_id: number
   project_id?: number
-  type: 'commit' | 'comment' | 'mention' | 'script_execution'
+  type: 'commit' | 'comment' | 'mention' | 'lock' | 'working_copy' | 'script_execution'
   title: string
   message: string
+  // Frontend path to the notified item, e.g. `/projects/1#comment-12`
+  link?: string | null
   is_read: boolean
   created_at: string
 }
 
+export interface NotificationsResponse {
+  notifications: Notification[]
+  // Pass as `cursor` to fetch the next page; null on the last page
+  next_cursor: number | null
+}
+
 export interface NotificationSubscription {
   id: number
   user_id: number
@@ -102,11 +660,65 @@ export interface NotificationSubscription {
 export interface Comment {
   id: number
   project_id: number
-  user_id: number
+  user_id: number | null
+  commit_hash: string | null
+  // Raw Markdown; render `content_html` instead (sanitized server-side)
   content: string
+  content_html: string
+  file_path: string | null
+  line_start: number | null
+  line_end: number | null
+  // Current position at the default branch tip; last known one if outdated
+  position: CommentPosition | null
+  is_outdated: boolean
+  // Usernames mentioned with `@username` who can see the project
+  mentions: string[]
+  parent_comment_id: number | null
+  // Number of edits; previous versions at `/comments/:id/revisions`
+  revision_count: number
+  reactions: ReactionCount[]
+  // Set on resolved top-level threads only
+  resolved_at: string | null
+  resolved_by: number | null
+  resolved_by_username: string | null
   created_at: string
   updated_at: string
-  user?: User
+  deleted_at: string | null
+  user?: User | null
+}
+
+export interface CommentPosition {
+  commit: string
+  path: string
+  line_start: number | null
+  line_end: number | null
+}
+
+export interface ReactionCount {
+  emoji: string
+  count: number
+  // Usernames in reaction order
+  users: string[]
+}
+
+export interface CommentRevision {
+  id: number
+  comment_id: number
+  // Content before the edit
+  content: string
+  content_html: string
+  edited_by: number | null
+  edited_by_username: string | null
+  edited_at: string
+}
+
+export interface CreateCommentPayload {
+  content: string
+  commit_hash?: string
+  file_path?: string
+  line_start?: number
+  line_end?: number
+  parent_comment_id?: number
 }
 
 // API Response types